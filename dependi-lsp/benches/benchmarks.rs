@@ -347,6 +347,13 @@ fn generate_gemfile(dep_count: usize) -> String {
     content
 }
 
+fn current_timestamp() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
 fn create_version_info() -> VersionInfo {
     VersionInfo {
         latest: Some("1.0.0".to_string()),
@@ -367,6 +374,7 @@ fn create_version_info() -> VersionInfo {
         yanked: false,
         yanked_versions: vec!["0.1.0".to_string(), "0.2.0".to_string()],
         release_dates: Default::default(),
+        platforms: Default::default(),
     }
 }
 
@@ -517,6 +525,22 @@ fn bench_memory_cache(c: &mut Criterion) {
                 });
             },
         );
+
+        group.bench_with_input(
+            BenchmarkId::new("insert_with_ttl", entry_count),
+            &cache,
+            |b, cache| {
+                let mut i = entry_count;
+                b.iter(|| {
+                    cache.insert_with_ttl(
+                        format!("ttl_package_{}", i),
+                        create_version_info(),
+                        std::time::Duration::from_secs(900),
+                    );
+                    i += 1;
+                });
+            },
+        );
     }
 
     group.finish();
@@ -580,6 +604,32 @@ fn bench_sqlite_cache(c: &mut Criterion) {
                 });
             },
         );
+
+        let mut ttl_insert_counter = entry_count;
+        group.bench_with_input(
+            BenchmarkId::new("insert_with_ttl", entry_count),
+            &entry_count,
+            |b, _| {
+                b.iter(|| {
+                    cache.insert_with_ttl(
+                        format!("ttl_package_{}", ttl_insert_counter),
+                        create_version_info(),
+                        std::time::Duration::from_secs(900),
+                    );
+                    ttl_insert_counter += 1;
+                });
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("refresh_stale", entry_count),
+            &entry_count,
+            |b, _| {
+                b.iter(|| {
+                    black_box(cache.refresh_stale(current_timestamp() + 10_000, 50));
+                });
+            },
+        );
     }
 
     group.finish();
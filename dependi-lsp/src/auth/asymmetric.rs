@@ -0,0 +1,231 @@
+//! Asymmetric PASETO v3.public token provider for Cargo-style registry auth.
+//!
+//! crates.io and compatible alternate registries can advertise asymmetric
+//! auth instead of a static bearer token: rather than sending the same
+//! secret on every request, the client signs a fresh `v3.public` PASETO
+//! token per request with a private key (see Cargo's unstable
+//! `registry-auth` protocol). Because each token embeds the target URL and
+//! a short-lived timestamp, [`AsymmetricTokenProvider`] mints one on every
+//! [`TokenProvider::get_auth_headers`] call instead of caching anything.
+
+use pasetors::keys::{AsymmetricPublicKey, AsymmetricSecretKey};
+use pasetors::paserk::{FormatAsPaserk, Id};
+use pasetors::version3::{PublicToken, V3};
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
+use serde::Serialize;
+
+use super::TokenProvider;
+
+/// Which mutation a [`WriteOperation`] authorizes.
+#[derive(Debug, Clone, Copy)]
+pub enum Mutation {
+    Publish,
+    Yank,
+    Unyank,
+    Owners,
+}
+
+impl Mutation {
+    fn as_str(self) -> &'static str {
+        match self {
+            Mutation::Publish => "publish",
+            Mutation::Yank => "yank",
+            Mutation::Unyank => "unyank",
+            Mutation::Owners => "owners",
+        }
+    }
+}
+
+/// The write a mutating request performs, carried in the token's claims so
+/// the registry can authorize it. Read requests carry none of this, and the
+/// resulting claims omit these fields entirely.
+#[derive(Debug, Clone)]
+pub struct WriteOperation {
+    pub mutation: Mutation,
+    pub name: String,
+    pub vers: Option<String>,
+    pub cksum: Option<String>,
+}
+
+/// PASETO v3.public token provider backed by a PASERK secret key (a
+/// `k3.secret...` string, as loaded from [`super::cargo_credentials`]).
+///
+/// # Security
+/// The secret key is held in memory only for the lifetime of this provider
+/// and is never logged; neither is any token it mints, since each one is
+/// sensitive for as long as it remains valid.
+pub struct AsymmetricTokenProvider {
+    secret_key: AsymmetricSecretKey<V3>,
+    key_id: String,
+    operation: Option<WriteOperation>,
+}
+
+impl AsymmetricTokenProvider {
+    /// Parses `secret_key_paserk` and derives the PASERK key ID that goes
+    /// into every minted token's footer.
+    pub fn new(secret_key_paserk: &str) -> anyhow::Result<Self> {
+        let secret_key = AsymmetricSecretKey::<V3>::try_from(secret_key_paserk)
+            .map_err(|err| anyhow::anyhow!("invalid PASERK secret key: {err:?}"))?;
+        let public_key = AsymmetricPublicKey::<V3>::try_from(&secret_key)
+            .map_err(|err| anyhow::anyhow!("failed to derive public key from secret key: {err:?}"))?;
+
+        let mut key_id = String::new();
+        Id::from(&public_key)
+            .fmt(&mut key_id)
+            .map_err(|_| anyhow::anyhow!("failed to format PASERK key id"))?;
+
+        Ok(Self {
+            secret_key,
+            key_id,
+            operation: None,
+        })
+    }
+
+    /// Returns a provider that signs tokens authorizing `operation` instead
+    /// of a bare read. Since the operation is baked into every token this
+    /// mints, a provider configured this way should be used for the one
+    /// mutating request it was built for and then discarded.
+    pub fn for_write(mut self, operation: WriteOperation) -> Self {
+        self.operation = Some(operation);
+        self
+    }
+}
+
+#[derive(Serialize)]
+struct Claims<'a> {
+    iat: String,
+    v: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mutation: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    vers: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cksum: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct Footer<'a> {
+    url: &'a str,
+    kip: &'a str,
+}
+
+impl TokenProvider for AsymmetricTokenProvider {
+    fn get_auth_headers(&self, url: &str) -> Option<HeaderMap> {
+        let claims = Claims {
+            iat: chrono::Utc::now().to_rfc3339(),
+            v: 1,
+            mutation: self.operation.as_ref().map(|op| op.mutation.as_str()),
+            name: self.operation.as_ref().map(|op| op.name.as_str()),
+            vers: self.operation.as_ref().and_then(|op| op.vers.as_deref()),
+            cksum: self.operation.as_ref().and_then(|op| op.cksum.as_deref()),
+        };
+        let claims_json = serde_json::to_vec(&claims).ok()?;
+
+        // crates.io identifies itself as the literal string "crates-io" in
+        // this footer rather than its URL - alternate registries use theirs.
+        let footer_url = if is_crates_io(url) { "crates-io" } else { url };
+        let footer = Footer {
+            url: footer_url,
+            kip: &self.key_id,
+        };
+        let footer_json = serde_json::to_vec(&footer).ok()?;
+
+        let token = PublicToken::sign(&self.secret_key, &claims_json, Some(&footer_json), None).ok()?;
+
+        let mut headers = HeaderMap::new();
+        let value = HeaderValue::from_str(&token).ok()?;
+        headers.insert(AUTHORIZATION, value);
+        Some(headers)
+    }
+}
+
+fn is_crates_io(url: &str) -> bool {
+    url.starts_with("https://crates.io/")
+}
+
+#[cfg(test)]
+mod tests {
+    use pasetors::keys::{AsymmetricKeyPair, Generate};
+    use pasetors::token::{Public, UntrustedToken};
+
+    use super::*;
+
+    fn generate_paserk() -> (String, AsymmetricPublicKey<V3>) {
+        let pair = AsymmetricKeyPair::<V3>::generate().unwrap();
+        let mut paserk = String::new();
+        pair.secret.fmt(&mut paserk).unwrap();
+        (paserk, pair.public)
+    }
+
+    #[test]
+    fn test_get_auth_headers_produces_verifiable_token() {
+        let (paserk, public_key) = generate_paserk();
+        let provider = AsymmetricTokenProvider::new(&paserk).unwrap();
+
+        let headers = provider
+            .get_auth_headers("https://example-registry.internal/api/v1/crates")
+            .unwrap();
+        let token = headers.get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert!(token.starts_with("v3.public."));
+        assert!(!token.contains("Bearer"));
+
+        let untrusted = UntrustedToken::<Public, V3>::try_from(token).unwrap();
+        let footer: serde_json::Value = serde_json::from_slice(untrusted.untrusted_footer()).unwrap();
+        assert_eq!(footer["url"], "https://example-registry.internal/api/v1/crates");
+
+        let mut key_id = String::new();
+        Id::from(&public_key).fmt(&mut key_id).unwrap();
+        assert_eq!(footer["kip"], key_id);
+
+        let trusted = PublicToken::verify(&public_key, &untrusted, Some(untrusted.untrusted_footer()), None).unwrap();
+        let payload: serde_json::Value = serde_json::from_str(trusted.payload()).unwrap();
+        assert_eq!(payload["v"], 1);
+        assert!(payload.get("mutation").is_none());
+    }
+
+    #[test]
+    fn test_get_auth_headers_uses_crates_io_literal_in_footer() {
+        let (paserk, public_key) = generate_paserk();
+        let provider = AsymmetricTokenProvider::new(&paserk).unwrap();
+
+        let headers = provider
+            .get_auth_headers("https://crates.io/api/v1/crates/serde/1.0.0/download")
+            .unwrap();
+        let token = headers.get(AUTHORIZATION).unwrap().to_str().unwrap();
+
+        let untrusted = UntrustedToken::<Public, V3>::try_from(token).unwrap();
+        let footer: serde_json::Value = serde_json::from_slice(untrusted.untrusted_footer()).unwrap();
+        assert_eq!(footer["url"], "crates-io");
+
+        PublicToken::verify(&public_key, &untrusted, Some(untrusted.untrusted_footer()), None).unwrap();
+    }
+
+    #[test]
+    fn test_for_write_includes_mutation_claims() {
+        let (paserk, public_key) = generate_paserk();
+        let provider = AsymmetricTokenProvider::new(&paserk).unwrap().for_write(WriteOperation {
+            mutation: Mutation::Publish,
+            name: "my-crate".to_string(),
+            vers: Some("1.2.3".to_string()),
+            cksum: Some("deadbeef".to_string()),
+        });
+
+        let headers = provider.get_auth_headers("https://crates.io/api/v1/crates/new").unwrap();
+        let token = headers.get(AUTHORIZATION).unwrap().to_str().unwrap();
+
+        let untrusted = UntrustedToken::<Public, V3>::try_from(token).unwrap();
+        let trusted = PublicToken::verify(&public_key, &untrusted, Some(untrusted.untrusted_footer()), None).unwrap();
+        let payload: serde_json::Value = serde_json::from_str(trusted.payload()).unwrap();
+        assert_eq!(payload["mutation"], "publish");
+        assert_eq!(payload["name"], "my-crate");
+        assert_eq!(payload["vers"], "1.2.3");
+        assert_eq!(payload["cksum"], "deadbeef");
+    }
+
+    #[test]
+    fn test_new_rejects_malformed_paserk() {
+        assert!(AsymmetricTokenProvider::new("not-a-valid-key").is_err());
+    }
+}
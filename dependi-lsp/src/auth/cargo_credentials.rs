@@ -1,10 +1,16 @@
 //! Parser for Cargo credentials files.
 //!
 //! Parses `~/.cargo/credentials.toml` or `$CARGO_HOME/credentials.toml`
-//! to extract authentication tokens for alternative Cargo registries.
+//! to extract authentication tokens for alternative Cargo registries, then
+//! layers on the other sources real Cargo resolves registry auth from -
+//! per-registry `CARGO_REGISTRIES_<NAME>_TOKEN`/`CARGO_REGISTRY_TOKEN`
+//! environment variables (env always wins over the file, since it's usually
+//! a deliberately-set override like CI injecting a short-lived token) and
+//! `credential-process` provider commands.
 
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::process::Command;
 
 use serde::Deserialize;
 use tokio::fs;
@@ -18,31 +24,104 @@ struct CargoCredentials {
 #[derive(Debug, Deserialize)]
 struct RegistryCredential {
     token: Option<String>,
+    #[serde(rename = "credential-process")]
+    credential_process: Option<String>,
+    #[serde(rename = "secret-key")]
+    secret_key: Option<String>,
 }
 
-/// Parse `.cargo/credentials.toml` file for registry tokens.
+/// Parse Cargo's registry auth into a map of registry name to token,
+/// merging every source Cargo itself consults:
 ///
-/// Looks in `$CARGO_HOME/credentials.toml` or `~/.cargo/credentials.toml`.
+/// 1. `.cargo/credentials.toml`'s `[registries.<name>]` - a literal `token`,
+///    or a `credential-process` command invoked to obtain one.
+/// 2. `CARGO_REGISTRIES_<NAME>_TOKEN` / `CARGO_REGISTRY_TOKEN` environment
+///    variables, which take precedence over whatever the file provided.
 ///
 /// # Returns
 /// A map of registry name to token string.
 pub async fn parse_cargo_credentials() -> HashMap<String, String> {
-    let credentials_path = get_credentials_path();
-
-    let Some(path) = credentials_path else {
-        return HashMap::new();
+    let mut tokens = match get_credentials_path() {
+        Some(path) if path.exists() => match fs::read_to_string(&path).await {
+            Ok(content) => parse_credentials_content(&content),
+            Err(_) => HashMap::new(),
+        },
+        _ => HashMap::new(),
     };
 
-    if !path.exists() {
-        return HashMap::new();
+    apply_env_overrides(&mut tokens);
+    tokens
+}
+
+/// Cargo resolves a registry's token from its environment variable first -
+/// `CARGO_REGISTRIES_<NAME>_TOKEN` (name uppercased, `-`/`.` replaced with
+/// `_`) for named registries, `CARGO_REGISTRY_TOKEN` for crates.io - before
+/// falling back to `credentials.toml`. Only overrides registries already
+/// known from the file, since without a discovered registry name there's no
+/// key to check an env var for.
+fn apply_env_overrides(tokens: &mut HashMap<String, String>) {
+    if let Ok(token) = std::env::var("CARGO_REGISTRY_TOKEN") {
+        tokens.insert("crates-io".to_string(), token);
     }
 
-    let content = match fs::read_to_string(&path).await {
-        Ok(c) => c,
-        Err(_) => return HashMap::new(),
-    };
+    for name in tokens.keys().cloned().collect::<Vec<_>>() {
+        let env_name = format!(
+            "CARGO_REGISTRIES_{}_TOKEN",
+            name.to_uppercase().replace(['-', '.'], "_")
+        );
+        if let Ok(token) = std::env::var(&env_name) {
+            tokens.insert(name, token);
+        }
+    }
+}
+
+/// Resolve a single registry's token the same way [`parse_cargo_credentials`]
+/// resolves the whole set, without paying for a full credentials.toml parse
+/// of every other registry when a caller only needs this one:
+/// `CARGO_REGISTRIES_<NAME>_TOKEN` (`CARGO_REGISTRY_TOKEN` for `crates-io`)
+/// first, then `credentials.toml`'s literal `token` or `credential-process`
+/// command for `name`.
+pub async fn cargo_registry_token(name: &str) -> Option<String> {
+    if name == "crates-io"
+        && let Ok(token) = std::env::var("CARGO_REGISTRY_TOKEN")
+    {
+        return Some(token);
+    }
 
-    parse_credentials_content(&content)
+    let env_name = format!(
+        "CARGO_REGISTRIES_{}_TOKEN",
+        name.to_uppercase().replace(['-', '.'], "_")
+    );
+    if let Ok(token) = std::env::var(&env_name) {
+        return Some(token);
+    }
+
+    let path = get_credentials_path()?;
+    let content = fs::read_to_string(&path).await.ok()?;
+    let credentials: CargoCredentials = toml::from_str(&content).ok()?;
+    let cred = credentials.registries.get(name)?;
+    cred.token
+        .clone()
+        .or_else(|| cred.credential_process.as_deref().and_then(invoke_credential_process))
+}
+
+/// Invokes a configured `credential-process` command and returns the token
+/// it printed to stdout. Cargo's real credential-provider protocol is a
+/// JSON action/response exchange over stdin/stdout; this crate only ever
+/// needs a read-only token for outgoing requests, so a single best-effort
+/// invocation that trims stdout is enough - any failure (missing binary,
+/// non-zero exit, empty output) just means no token, same as a
+/// `credentials.toml` entry with no token set at all.
+fn invoke_credential_process(command: &str) -> Option<String> {
+    let mut parts = command.split_whitespace();
+    let program = parts.next()?;
+    let output = Command::new(program).args(parts).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let token = String::from_utf8(output.stdout).ok()?;
+    let token = token.trim();
+    (!token.is_empty()).then(|| token.to_string())
 }
 
 fn get_credentials_path() -> Option<PathBuf> {
@@ -86,13 +165,48 @@ fn parse_credentials_content(content: &str) -> HashMap<String, String> {
     credentials
         .registries
         .into_iter()
-        .filter_map(|(name, cred)| cred.token.map(|t| (name, t)))
+        .filter_map(|(name, cred)| {
+            let token = cred
+                .token
+                .or_else(|| cred.credential_process.as_deref().and_then(invoke_credential_process))?;
+            Some((name, token))
+        })
+        .collect()
+}
+
+/// Reads `.cargo/credentials.toml` and returns a map of registry name to
+/// PASERK secret key (`secret-key = "k3.secret...."`), for registries
+/// configured for [`super::asymmetric::AsymmetricTokenProvider`] instead of
+/// a bearer token. A registry entry sets either `token` or `secret-key`,
+/// never both, so unlike [`parse_cargo_credentials`] there are no
+/// environment-variable overrides to layer on here.
+pub async fn parse_cargo_secret_keys() -> HashMap<String, String> {
+    match get_credentials_path() {
+        Some(path) if path.exists() => match fs::read_to_string(&path).await {
+            Ok(content) => parse_secret_keys_content(&content),
+            Err(_) => HashMap::new(),
+        },
+        _ => HashMap::new(),
+    }
+}
+
+fn parse_secret_keys_content(content: &str) -> HashMap<String, String> {
+    let credentials: CargoCredentials = match toml::from_str(content) {
+        Ok(c) => c,
+        Err(_) => return HashMap::new(),
+    };
+
+    credentials
+        .registries
+        .into_iter()
+        .filter_map(|(name, cred)| Some((name, cred.secret_key?)))
         .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serial_test::serial;
 
     #[test]
     fn test_parse_credentials() {
@@ -157,4 +271,146 @@ token = "has_token"
         assert_eq!(result.len(), 1);
         assert_eq!(result.get("with-token"), Some(&"has_token".to_string()));
     }
+
+    #[test]
+    fn test_parse_credential_process_invokes_command_for_token() {
+        let content = r#"
+[registries.my-registry]
+credential-process = "echo process-issued-token"
+"#;
+        let result = parse_credentials_content(content);
+        assert_eq!(
+            result.get("my-registry"),
+            Some(&"process-issued-token".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_credential_process_failure_is_dropped_not_fatal() {
+        let content = r#"
+[registries.my-registry]
+credential-process = "this-binary-does-not-exist-anywhere"
+"#;
+        let result = parse_credentials_content(content);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_env_overrides_take_precedence_over_file_token() {
+        // SAFETY: serial_test ensures this test runs exclusively, preventing race conditions
+        unsafe {
+            std::env::set_var("CARGO_REGISTRIES_MY_REGISTRY_TOKEN", "env_token");
+        }
+        let mut tokens = HashMap::from([("my-registry".to_string(), "file_token".to_string())]);
+        apply_env_overrides(&mut tokens);
+        assert_eq!(tokens.get("my-registry"), Some(&"env_token".to_string()));
+        // SAFETY: serial_test ensures this test runs exclusively, preventing race conditions
+        unsafe {
+            std::env::remove_var("CARGO_REGISTRIES_MY_REGISTRY_TOKEN");
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_env_override_name_transformation_uppercases_and_replaces_dashes() {
+        // SAFETY: serial_test ensures this test runs exclusively, preventing race conditions
+        unsafe {
+            std::env::set_var("CARGO_REGISTRIES_ACME_CORP_TOKEN", "acme_env_token");
+        }
+        let mut tokens = HashMap::from([("acme-corp".to_string(), "stale".to_string())]);
+        apply_env_overrides(&mut tokens);
+        assert_eq!(tokens.get("acme-corp"), Some(&"acme_env_token".to_string()));
+        // SAFETY: serial_test ensures this test runs exclusively, preventing race conditions
+        unsafe {
+            std::env::remove_var("CARGO_REGISTRIES_ACME_CORP_TOKEN");
+        }
+    }
+
+    #[test]
+    fn test_parse_secret_keys_content() {
+        let content = r#"
+[registries.my-registry]
+secret-key = "k3.secret.xuwOfmquIlM58m-oItF_0N87Sg8RjVZ_2rRQMT7U8gDShJhCrjv6ntnHWKUEM6r_"
+
+[registries.bearer-registry]
+token = "plain_token"
+"#;
+        let result = parse_secret_keys_content(content);
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result.get("my-registry"),
+            Some(&"k3.secret.xuwOfmquIlM58m-oItF_0N87Sg8RjVZ_2rRQMT7U8gDShJhCrjv6ntnHWKUEM6r_".to_string())
+        );
+        assert!(!result.contains_key("bearer-registry"));
+    }
+
+    #[test]
+    fn test_parse_secret_keys_content_empty_without_any() {
+        let content = r#"
+[registries.my-registry]
+token = "plain_token"
+"#;
+        assert!(parse_secret_keys_content(content).is_empty());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_cargo_registry_token_prefers_named_env_var() {
+        // SAFETY: serial_test ensures this test runs exclusively, preventing race conditions
+        unsafe {
+            std::env::set_var("CARGO_REGISTRIES_MY_REGISTRY_TOKEN", "env_token");
+        }
+        assert_eq!(
+            cargo_registry_token("my-registry").await,
+            Some("env_token".to_string())
+        );
+        // SAFETY: serial_test ensures this test runs exclusively, preventing race conditions
+        unsafe {
+            std::env::remove_var("CARGO_REGISTRIES_MY_REGISTRY_TOKEN");
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_cargo_registry_token_falls_back_to_global_env_var_for_crates_io() {
+        // SAFETY: serial_test ensures this test runs exclusively, preventing race conditions
+        unsafe {
+            std::env::set_var("CARGO_REGISTRY_TOKEN", "crates_io_token");
+        }
+        assert_eq!(
+            cargo_registry_token("crates-io").await,
+            Some("crates_io_token".to_string())
+        );
+        // SAFETY: serial_test ensures this test runs exclusively, preventing race conditions
+        unsafe {
+            std::env::remove_var("CARGO_REGISTRY_TOKEN");
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_cargo_registry_token_none_without_env_or_file() {
+        // SAFETY: serial_test ensures this test runs exclusively, preventing race conditions
+        unsafe {
+            std::env::remove_var("CARGO_HOME");
+        }
+        assert_eq!(cargo_registry_token("no-such-registry").await, None);
+    }
+
+    #[test]
+    #[serial]
+    fn test_global_registry_token_env_var_maps_to_crates_io() {
+        // SAFETY: serial_test ensures this test runs exclusively, preventing race conditions
+        unsafe {
+            std::env::set_var("CARGO_REGISTRY_TOKEN", "crates_io_token");
+        }
+        let mut tokens = HashMap::new();
+        apply_env_overrides(&mut tokens);
+        assert_eq!(tokens.get("crates-io"), Some(&"crates_io_token".to_string()));
+        // SAFETY: serial_test ensures this test runs exclusively, preventing race conditions
+        unsafe {
+            std::env::remove_var("CARGO_REGISTRY_TOKEN");
+        }
+    }
 }
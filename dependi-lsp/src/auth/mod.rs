@@ -15,21 +15,26 @@
 //! The authentication system is built around the [`TokenProvider`] trait:
 //!
 //! - [`TokenProvider`]: Trait for getting auth headers for requests
-//! - [`EnvTokenProvider`]: Reads token from environment variable
+//! - [`EnvTokenProvider`]: Reads token from environment variable, emits Bearer
+//! - [`BasicAuthProvider`]: Username/password, emits HTTP Basic
+//! - [`CustomHeaderProvider`]: Arbitrary header (e.g. `PRIVATE-TOKEN`)
 //! - [`TokenProviderManager`]: Associates tokens with registry URL prefixes
 //!
 //! ## Submodules
 //!
 //! - [`cargo_credentials`]: Parser for Cargo credentials files (`~/.cargo/credentials.toml`)
 //! - [`npmrc`]: Parser for npm configuration files (`.npmrc`)
+//! - [`asymmetric`]: Cargo-style asymmetric PASETO token provider
 
+pub mod asymmetric;
 pub mod cargo_credentials;
 pub mod npmrc;
 
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
+use base64::Engine;
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderName, HeaderValue};
 use tokio::sync::RwLock;
 
 /// Token provider trait for registry authentication.
@@ -75,6 +80,87 @@ impl TokenProvider for EnvTokenProvider {
     }
 }
 
+/// HTTP Basic authentication provider.
+///
+/// Emits `Authorization: Basic base64(username:password)`, for registries
+/// like Artifactory/Nexus or PyPI uploads that don't accept Bearer tokens.
+/// PyPI conventionally pairs this with the literal username `__token__` and
+/// an API token as the password.
+pub struct BasicAuthProvider {
+    username: String,
+    password: String,
+}
+
+impl BasicAuthProvider {
+    /// Create a new provider from a username and password.
+    ///
+    /// # Security
+    /// The password is stored in memory. Ensure it is not logged.
+    pub fn new(username: String, password: String) -> Self {
+        Self { username, password }
+    }
+
+    /// Create a provider from an already-base64-encoded `username:password`
+    /// pair, as stored in an `.npmrc`'s `_auth` setting. Returns `None` if
+    /// `encoded` isn't valid base64 or doesn't decode to a `user:pass` pair.
+    pub fn from_encoded(encoded: &str) -> Option<Self> {
+        let decoded = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .ok()?;
+        let decoded = String::from_utf8(decoded).ok()?;
+        let (username, password) = decoded.split_once(':')?;
+        Some(Self::new(username.to_string(), password.to_string()))
+    }
+}
+
+impl TokenProvider for BasicAuthProvider {
+    fn get_auth_headers(&self, _url: &str) -> Option<HeaderMap> {
+        let credentials = format!("{}:{}", self.username, self.password);
+        let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+        let auth_value = format!("Basic {}", encoded);
+        let mut headers = HeaderMap::new();
+        if let Ok(value) = HeaderValue::from_str(&auth_value) {
+            headers.insert(AUTHORIZATION, value);
+            Some(headers)
+        } else {
+            None
+        }
+    }
+}
+
+/// Custom-header authentication provider.
+///
+/// Emits a single configurable header instead of `Authorization`, for
+/// registries that authenticate via their own header name (e.g. GitLab's
+/// `PRIVATE-TOKEN`).
+pub struct CustomHeaderProvider {
+    header_name: HeaderName,
+    value: String,
+}
+
+impl CustomHeaderProvider {
+    /// Create a new provider, returning `None` if `header_name` isn't a
+    /// valid HTTP header name.
+    ///
+    /// # Security
+    /// The header value is stored in memory. Ensure it is not logged.
+    pub fn new(header_name: &str, value: String) -> Option<Self> {
+        Some(Self {
+            header_name: HeaderName::from_bytes(header_name.as_bytes()).ok()?,
+            value,
+        })
+    }
+}
+
+impl TokenProvider for CustomHeaderProvider {
+    fn get_auth_headers(&self, _url: &str) -> Option<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        let value = HeaderValue::from_str(&self.value).ok()?;
+        headers.insert(self.header_name.clone(), value);
+        Some(headers)
+    }
+}
+
 /// No-op provider for public registries.
 ///
 /// Always returns `None`, indicating no authentication is needed.
@@ -159,7 +245,6 @@ impl TokenProviderManager {
     ///
     /// # Matching
     /// Uses longest-prefix matching to support nested registries.
-    #[cfg(test)]
     pub async fn get_auth_headers(&self, url: &str) -> HeaderMap {
         let providers = self.providers.read().await;
 
@@ -204,6 +289,73 @@ impl TokenProviderManager {
         let providers = self.providers.read().await;
         providers.len()
     }
+
+    /// Parses one environment variable into a manager pre-populated with an
+    /// [`EnvTokenProvider`] per entry, for users with several private
+    /// registries who'd rather set a single bundle than call [`Self::register`]
+    /// once per registry.
+    ///
+    /// The variable's value is a `;`-separated list of `token@host` entries
+    /// (e.g. `abc123@npm.company.com;def456@nuget.internal`), mirroring the
+    /// ergonomics of Deno's `DENO_AUTH_TOKENS`. Each entry is registered
+    /// under `https://{host}` so the existing longest-prefix matching in
+    /// [`Self::get_auth_headers`] keeps working unchanged. An entry missing
+    /// `@`, or whose host implies a non-HTTPS URL, is skipped with a warning
+    /// rather than failing the whole variable - the token itself is never
+    /// logged, only its [`redact_token`] form.
+    pub async fn from_env_var(var_name: &str) -> Self {
+        let manager = Self::new();
+
+        let Ok(value) = std::env::var(var_name) else {
+            return manager;
+        };
+
+        for entry in value.split(';') {
+            let entry = entry.trim();
+            if entry.is_empty() {
+                continue;
+            }
+
+            let Some((token, host)) = entry.split_once('@') else {
+                tracing::warn!("Skipping malformed entry in {var_name} (missing '@'): no host found");
+                continue;
+            };
+
+            if host.is_empty() || host.starts_with("http://") {
+                tracing::warn!(
+                    "Skipping entry for token {} in {var_name}: host implies a non-HTTPS URL",
+                    redact_token(token)
+                );
+                continue;
+            }
+
+            let url_prefix = if host.starts_with("https://") {
+                host.to_string()
+            } else {
+                format!("https://{host}")
+            };
+
+            let provider = Arc::new(EnvTokenProvider::new(token.to_string()));
+            manager.register(url_prefix, provider).await;
+        }
+
+        manager
+    }
+
+    /// Parses `.npmrc` content into a manager pre-populated with one
+    /// provider per registry-scoped credential line, choosing between
+    /// [`EnvTokenProvider`] (`_authToken`, Bearer) and [`BasicAuthProvider`]
+    /// (`_auth`, Basic) the same way npm itself does - see
+    /// [`npmrc::parse_npmrc_providers`] for the line format this expects.
+    pub async fn from_npmrc(content: &str) -> Self {
+        let manager = Self::new();
+
+        for (url_prefix, provider) in npmrc::parse_npmrc_providers(content) {
+            manager.register(url_prefix, provider).await;
+        }
+
+        manager
+    }
 }
 
 /// Redact a token for safe logging.
@@ -224,6 +376,8 @@ pub fn redact_token(token: &str) -> String {
 
 #[cfg(test)]
 mod tests {
+    use serial_test::serial;
+
     use super::*;
 
     #[test]
@@ -255,6 +409,54 @@ mod tests {
         assert_eq!(auth_value, "Bearer test_token");
     }
 
+    #[test]
+    fn test_basic_auth_provider_new() {
+        let provider = BasicAuthProvider::new("__token__".to_string(), "pypi-secret".to_string());
+        let headers = provider.get_auth_headers("https://upload.pypi.org").unwrap();
+        let auth_value = headers.get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert_eq!(
+            auth_value,
+            format!(
+                "Basic {}",
+                base64::engine::general_purpose::STANDARD.encode("__token__:pypi-secret")
+            )
+        );
+    }
+
+    #[test]
+    fn test_basic_auth_provider_from_encoded_round_trips() {
+        let encoded = base64::engine::general_purpose::STANDARD.encode("alice:hunter2");
+        let provider = BasicAuthProvider::from_encoded(&encoded).unwrap();
+        let headers = provider.get_auth_headers("https://registry.internal").unwrap();
+        let auth_value = headers.get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert_eq!(auth_value, format!("Basic {encoded}"));
+    }
+
+    #[test]
+    fn test_basic_auth_provider_from_encoded_rejects_non_base64() {
+        assert!(BasicAuthProvider::from_encoded("not valid base64!!").is_none());
+    }
+
+    #[test]
+    fn test_basic_auth_provider_from_encoded_rejects_missing_colon() {
+        let encoded = base64::engine::general_purpose::STANDARD.encode("no-colon-here");
+        assert!(BasicAuthProvider::from_encoded(&encoded).is_none());
+    }
+
+    #[test]
+    fn test_custom_header_provider() {
+        let provider = CustomHeaderProvider::new("PRIVATE-TOKEN", "glpat-secret".to_string()).unwrap();
+        let headers = provider.get_auth_headers("https://gitlab.company.com").unwrap();
+        let value = headers.get("PRIVATE-TOKEN").unwrap().to_str().unwrap();
+        assert_eq!(value, "glpat-secret");
+        assert!(!headers.contains_key(AUTHORIZATION));
+    }
+
+    #[test]
+    fn test_custom_header_provider_rejects_invalid_header_name() {
+        assert!(CustomHeaderProvider::new("not a header\n", "value".to_string()).is_none());
+    }
+
     #[test]
     fn test_no_auth_provider() {
         let provider = NoAuthProvider;
@@ -343,4 +545,105 @@ mod tests {
         let auth = headers.get(AUTHORIZATION).unwrap().to_str().unwrap();
         assert_eq!(auth, "Bearer general_token");
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_from_env_var_parses_multiple_entries() {
+        // SAFETY: serial_test ensures this test runs exclusively, preventing race conditions
+        unsafe {
+            std::env::set_var(
+                "TEST_DEPENDI_AUTH_TOKENS",
+                "abc123@npm.company.com;def456@nuget.internal",
+            );
+        }
+
+        let manager = TokenProviderManager::from_env_var("TEST_DEPENDI_AUTH_TOKENS").await;
+
+        assert_eq!(manager.provider_count().await, 2);
+        assert!(manager.has_provider("https://npm.company.com").await);
+        assert!(manager.has_provider("https://nuget.internal").await);
+
+        let headers = manager
+            .get_auth_headers("https://npm.company.com/@company/utils")
+            .await;
+        let auth = headers.get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert_eq!(auth, "Bearer abc123");
+
+        // SAFETY: serial_test ensures this test runs exclusively, preventing race conditions
+        unsafe {
+            std::env::remove_var("TEST_DEPENDI_AUTH_TOKENS");
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_from_env_var_skips_malformed_and_insecure_entries() {
+        // SAFETY: serial_test ensures this test runs exclusively, preventing race conditions
+        unsafe {
+            std::env::set_var(
+                "TEST_DEPENDI_AUTH_TOKENS_BAD",
+                "no_at_sign_here;abc123@http://insecure.com;good@registry.internal",
+            );
+        }
+
+        let manager = TokenProviderManager::from_env_var("TEST_DEPENDI_AUTH_TOKENS_BAD").await;
+
+        assert_eq!(manager.provider_count().await, 1);
+        assert!(manager.has_provider("https://registry.internal").await);
+
+        // SAFETY: serial_test ensures this test runs exclusively, preventing race conditions
+        unsafe {
+            std::env::remove_var("TEST_DEPENDI_AUTH_TOKENS_BAD");
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_from_env_var_missing_var_returns_empty_manager() {
+        // SAFETY: serial_test ensures this test runs exclusively, preventing race conditions
+        unsafe {
+            std::env::remove_var("TEST_DEPENDI_AUTH_TOKENS_MISSING");
+        }
+
+        let manager = TokenProviderManager::from_env_var("TEST_DEPENDI_AUTH_TOKENS_MISSING").await;
+
+        assert_eq!(manager.provider_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_from_npmrc_registers_bearer_and_basic_providers() {
+        let encoded = base64::engine::general_purpose::STANDARD.encode("alice:hunter2");
+        let content = format!(
+            "//npm.company.com/:_authToken=abc123\n//registry.internal/:_auth={encoded}\n"
+        );
+
+        let manager = TokenProviderManager::from_npmrc(&content).await;
+
+        assert_eq!(manager.provider_count().await, 2);
+        assert!(manager.has_provider("https://npm.company.com").await);
+        assert!(manager.has_provider("https://registry.internal").await);
+
+        let headers = manager
+            .get_auth_headers("https://npm.company.com/@company/utils")
+            .await;
+        assert_eq!(
+            headers.get(AUTHORIZATION).unwrap().to_str().unwrap(),
+            "Bearer abc123"
+        );
+
+        let headers = manager
+            .get_auth_headers("https://registry.internal/pkg")
+            .await;
+        assert_eq!(
+            headers.get(AUTHORIZATION).unwrap().to_str().unwrap(),
+            format!("Basic {encoded}")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_from_npmrc_empty_without_scoped_entries() {
+        let content = "registry=https://registry.npmjs.org\n_authToken=unscoped-token\n";
+        let manager = TokenProviderManager::from_npmrc(content).await;
+        assert_eq!(manager.provider_count().await, 0);
+    }
 }
@@ -2,10 +2,52 @@
 //!
 //! Parses npm configuration to extract authentication tokens and registry URLs.
 //! Supports environment variable substitution (`${VAR}` syntax).
-//!
-//! Note: This module provides parsing utilities for .npmrc files.
-//! The parsing logic is tested; file I/O integration will be added when
-//! this is wired into the main auth flow.
+
+use std::sync::Arc;
+
+use super::{BasicAuthProvider, EnvTokenProvider, TokenProvider};
+
+/// Parses `.npmrc` content into `(registry URL prefix, provider)` pairs, one
+/// per registry-scoped credential line - `//host/:_authToken=...` (Bearer,
+/// via [`EnvTokenProvider`]) or `//host/:_auth=...` (Basic, already
+/// `base64(user:pass)` per npm's own format, via
+/// [`BasicAuthProvider::from_encoded`]). Bare, unscoped `_authToken`/`_auth`
+/// lines name no host to register a prefix under, so they're left for a
+/// caller that already knows which registry they apply to.
+pub(crate) fn parse_npmrc_providers(content: &str) -> Vec<(String, Arc<dyn TokenProvider>)> {
+    let mut providers = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('#') || line.starts_with(';') || !line.starts_with("//") {
+            continue;
+        }
+
+        if let Some(idx) = line.find(":_authToken=") {
+            let host = line[2..idx].trim_end_matches('/');
+            let token_part = line[idx + ":_authToken=".len()..].trim();
+            if let Some(token) = resolve_env_var(token_part) {
+                providers.push((
+                    format!("https://{host}"),
+                    Arc::new(EnvTokenProvider::new(token)) as Arc<dyn TokenProvider>,
+                ));
+            }
+        } else if let Some(idx) = line.find(":_auth=") {
+            let host = line[2..idx].trim_end_matches('/');
+            let auth_part = line[idx + ":_auth=".len()..].trim();
+            if let Some(encoded) = resolve_env_var(auth_part)
+                && let Some(provider) = BasicAuthProvider::from_encoded(&encoded)
+            {
+                providers.push((
+                    format!("https://{host}"),
+                    Arc::new(provider) as Arc<dyn TokenProvider>,
+                ));
+            }
+        }
+    }
+
+    providers
+}
 
 #[cfg(test)]
 fn parse_token_from_content(content: &str) -> Option<String> {
@@ -66,7 +108,6 @@ fn extract_auth_token(line: &str) -> Option<&str> {
     None
 }
 
-#[cfg(test)]
 fn resolve_env_var(value: &str) -> Option<String> {
     // Handle ${VAR} syntax
     if let Some(inner) = value.strip_prefix("${").and_then(|v| v.strip_suffix('}')) {
@@ -194,4 +235,62 @@ save-exact=true
         let content = "_authToken=${NONEXISTENT_VAR_12345}";
         assert_eq!(parse_token_from_content(content), None);
     }
+
+    #[test]
+    fn test_parse_npmrc_providers_authtoken_emits_bearer() {
+        let content = "//npm.company.com/:_authToken=npm_abc123\n";
+        let providers = parse_npmrc_providers(content);
+
+        assert_eq!(providers.len(), 1);
+        assert_eq!(providers[0].0, "https://npm.company.com");
+        let headers = providers[0].1.get_auth_headers("https://npm.company.com/pkg").unwrap();
+        assert_eq!(
+            headers.get(reqwest::header::AUTHORIZATION).unwrap().to_str().unwrap(),
+            "Bearer npm_abc123"
+        );
+    }
+
+    #[test]
+    fn test_parse_npmrc_providers_auth_emits_basic() {
+        use base64::Engine;
+        let encoded = base64::engine::general_purpose::STANDARD.encode("alice:hunter2");
+        let content = format!("//registry.internal/:_auth={encoded}\n");
+        let providers = parse_npmrc_providers(&content);
+
+        assert_eq!(providers.len(), 1);
+        assert_eq!(providers[0].0, "https://registry.internal");
+        let headers = providers[0].1.get_auth_headers("https://registry.internal/pkg").unwrap();
+        assert_eq!(
+            headers.get(reqwest::header::AUTHORIZATION).unwrap().to_str().unwrap(),
+            format!("Basic {encoded}")
+        );
+    }
+
+    #[test]
+    fn test_parse_npmrc_providers_ignores_unscoped_entries() {
+        let content = "registry=https://registry.npmjs.org\n_authToken=unscoped\n";
+        assert!(parse_npmrc_providers(content).is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_parse_npmrc_providers_resolves_env_var_in_scoped_token() {
+        // SAFETY: serial_test ensures this test runs exclusively, preventing race conditions
+        unsafe {
+            std::env::set_var("TEST_NPMRC_SCOPED_TOKEN", "resolved_token");
+        }
+        let content = "//npm.company.com/:_authToken=${TEST_NPMRC_SCOPED_TOKEN}\n";
+        let providers = parse_npmrc_providers(content);
+
+        assert_eq!(providers.len(), 1);
+        let headers = providers[0].1.get_auth_headers("https://npm.company.com").unwrap();
+        assert_eq!(
+            headers.get(reqwest::header::AUTHORIZATION).unwrap().to_str().unwrap(),
+            "Bearer resolved_token"
+        );
+        // SAFETY: serial_test ensures this test runs exclusively, preventing race conditions
+        unsafe {
+            std::env::remove_var("TEST_NPMRC_SCOPED_TOKEN");
+        }
+    }
 }
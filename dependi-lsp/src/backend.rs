@@ -1,27 +1,103 @@
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, RwLock};
 
 use dashmap::DashMap;
 use tower_lsp::jsonrpc::Result;
 use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer};
 
-use crate::cache::MemoryCache;
+use crate::auth::asymmetric::AsymmetricTokenProvider;
+use crate::auth::npmrc::parse_npmrc_providers;
+use crate::auth::{EnvTokenProvider, TokenProvider, TokenProviderManager};
+use crate::cache::HybridCache;
+use crate::config::{CacheMode, Config, HttpConfig, SecurityConfig, VersionPreference};
+use crate::ignore::IgnoreMatcher;
+use crate::lockfiles::cargo::CargoLockParser;
+use crate::lockfiles::go::GoSumParser;
+use crate::lockfiles::npm::{PackageLockParser, YarnLockParser};
+use crate::lockfiles::php::ComposerLockParser;
+use crate::lockfiles::python::{PoetryLockParser, UvLockParser};
+use crate::lockfiles::ruby::GemfileLockParser;
+use crate::lockfiles::{self, LockedPackage, LockfileParser};
 use crate::parsers::cargo::CargoParser;
+use crate::parsers::csharp::CsharpParser;
+use crate::parsers::dart::DartParser;
 use crate::parsers::go::GoParser;
 use crate::parsers::npm::NpmParser;
 use crate::parsers::php::PhpParser;
 use crate::parsers::python::PythonParser;
+use crate::parsers::ruby::RubyParser;
 use crate::parsers::{Dependency, Parser};
-use crate::providers::code_actions::create_code_actions;
+use crate::providers::code_actions::{self, create_code_actions};
 use crate::providers::completion::get_completions;
 use crate::providers::diagnostics::create_diagnostics;
-use crate::providers::inlay_hints::create_inlay_hint;
+use crate::providers::inlay_hints::{CooldownWindow, create_inlay_hint, satisfies_requirement};
+use crate::registries::cargo_sparse::CargoSparseRegistry;
 use crate::registries::crates_io::CratesIoRegistry;
+use crate::registries::github_releases::GithubReleasesRegistry;
 use crate::registries::go_proxy::GoProxyRegistry;
+use crate::registries::http_client::HttpClientProvider;
 use crate::registries::npm::NpmRegistry;
+use crate::registries::nuget::NuGetRegistry;
 use crate::registries::packagist::PackagistRegistry;
+use crate::registries::pub_dev::PubDevRegistry;
 use crate::registries::pypi::PyPiRegistry;
-use crate::registries::{Registry, VersionInfo};
+use crate::registries::registry_config::DiscoveredRegistries;
+use crate::registries::rubygems::RubyGemsRegistry;
+use crate::registries::version_scheme::VersionScheme;
+use crate::registries::{Registry, VersionInfo, VulnerabilitySeverity};
+use crate::reports::{self, VulnerabilityReportEntry};
+
+/// LSP `workspace/executeCommand` name for flushing the version cache
+const CLEAR_CACHE_COMMAND: &str = "dependi/clearCache";
+
+/// LSP `workspace/executeCommand` name for generating a SARIF 2.1.0
+/// vulnerability report for an open document's dependencies, so CI
+/// pipelines can ingest findings with standard SARIF tooling. Takes the
+/// document URI (as a string) as its single argument and returns the
+/// report as a JSON string.
+const GENERATE_SARIF_REPORT_COMMAND: &str = "dependi/generateSarifReport";
+
+/// LSP `workspace/executeCommand` name for a dry-run "upgrade all" preview:
+/// returns the edits `source.fixAll` would apply for an open document's
+/// eligible dependencies (see [`plan_upgrades`]) without applying them.
+/// Takes the document URI (as a string) as its single argument and returns
+/// the plan as a JSON array of [`PlannedUpgrade`] entries.
+const PLAN_UPGRADES_COMMAND: &str = "dependi/planUpgrades";
+
+/// Map a vulnerability's severity to the lowercase string
+/// `VulnerabilityReportEntry::severity` expects.
+fn severity_label(severity: VulnerabilitySeverity) -> &'static str {
+    match severity {
+        VulnerabilitySeverity::Critical => "critical",
+        VulnerabilitySeverity::High => "high",
+        VulnerabilitySeverity::Medium => "medium",
+        VulnerabilitySeverity::Low => "low",
+    }
+}
+
+/// Build the fallback auth provider for a resolved Cargo registry: a
+/// `secret_key` (PASERK, `credentials.toml`'s `secret-key`) takes precedence
+/// and resolves to an [`AsymmetricTokenProvider`] that signs each request
+/// fresh, since a registry is configured for one scheme or the other, never
+/// both; otherwise falls back to a plain bearer `token`, if any.
+fn cargo_fallback_provider(
+    token: Option<String>,
+    secret_key: Option<String>,
+) -> Option<Arc<dyn TokenProvider>> {
+    if let Some(secret_key) = secret_key {
+        return match AsymmetricTokenProvider::new(&secret_key) {
+            Ok(provider) => Some(Arc::new(provider)),
+            Err(e) => {
+                tracing::warn!("Failed to build asymmetric token provider: {}", e);
+                None
+            }
+        };
+    }
+
+    token.map(|token| Arc::new(EnvTokenProvider::new(token)) as Arc<dyn TokenProvider>)
+}
 
 /// File type for determining which parser/registry to use
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -31,6 +107,9 @@ pub enum FileType {
     Python,
     Go,
     Php,
+    Ruby,
+    Csharp,
+    Dart,
 }
 
 /// Document state with parsed dependencies
@@ -39,26 +118,58 @@ struct DocumentState {
     dependencies: Vec<Dependency>,
     /// Type of dependency file
     file_type: FileType,
+    /// Resolved packages from the companion lockfile, if one was found next
+    /// to the manifest, keyed by package name
+    locked: HashMap<String, LockedPackage>,
 }
 
 pub struct DependiBackend {
     client: Client,
     /// Cache for documents and their parsed state
     documents: DashMap<Url, DocumentState>,
-    /// Cache for version information (keyed by "registry:package")
-    version_cache: Arc<MemoryCache>,
+    /// Cache for version information (keyed by "registry:package"), backed
+    /// by memory for fast access and SQLite for persistence across restarts
+    version_cache: Arc<HybridCache>,
     /// Parsers
     cargo_parser: CargoParser,
     npm_parser: NpmParser,
     python_parser: PythonParser,
     go_parser: GoParser,
     php_parser: PhpParser,
-    /// Registry clients
-    crates_io: Arc<CratesIoRegistry>,
-    npm_registry: Arc<NpmRegistry>,
-    pypi: Arc<PyPiRegistry>,
-    go_proxy: Arc<GoProxyRegistry>,
-    packagist: Arc<PackagistRegistry>,
+    ruby_parser: RubyParser,
+    csharp_parser: CsharpParser,
+    dart_parser: DartParser,
+    /// Registry clients, rebuilt with a freshly configured HTTP client
+    /// whenever `initialize` parses the user's `http` settings
+    crates_io: RwLock<Arc<CratesIoRegistry>>,
+    npm_registry: RwLock<Arc<NpmRegistry>>,
+    pypi: RwLock<Arc<PyPiRegistry>>,
+    go_proxy: RwLock<Arc<GoProxyRegistry>>,
+    packagist: RwLock<Arc<PackagistRegistry>>,
+    rubygems: RwLock<Arc<RubyGemsRegistry>>,
+    nuget: RwLock<Arc<NuGetRegistry>>,
+    pub_dev: RwLock<Arc<PubDevRegistry>>,
+    /// User configuration, populated from `initializationOptions` on `initialize`
+    config: RwLock<Config>,
+    /// Builds the shared HTTP client from the user's proxy/CA/timeout/offline
+    /// settings; every registry client is rebuilt from it on `initialize`
+    http_provider: RwLock<HttpClientProvider>,
+    /// Sparse-index clients for alternative Cargo registries, keyed by
+    /// registry name (lazily built from `config.registries`)
+    sparse_registries: DashMap<String, Arc<CargoSparseRegistry>>,
+    /// GitHub-releases-backed registry clients, keyed by package/module name
+    /// (lazily built from `config.github_releases`)
+    github_registries: DashMap<String, Arc<GithubReleasesRegistry>>,
+    /// Compiled `config.ignore` glob patterns, rebuilt whenever the config
+    /// changes rather than recompiled per package
+    ignore_matcher: RwLock<Arc<IgnoreMatcher>>,
+    /// Alternate registry sources auto-discovered from the workspace's own
+    /// toolchain config files (`.cargo/config.toml`, `.npmrc`, ...), set
+    /// once on `initialize`
+    registry_config: RwLock<Arc<DiscoveredRegistries>>,
+    /// npm registry clients for a scope resolved from `registry_config`,
+    /// keyed by scope (without the leading `@`)
+    npm_registries: DashMap<String, Arc<NpmRegistry>>,
 }
 
 impl DependiBackend {
@@ -66,17 +177,298 @@ impl DependiBackend {
         Self {
             client,
             documents: DashMap::new(),
-            version_cache: Arc::new(MemoryCache::new()),
+            version_cache: Arc::new(HybridCache::new()),
             cargo_parser: CargoParser::new(),
             npm_parser: NpmParser::new(),
             python_parser: PythonParser::new(),
             go_parser: GoParser::new(),
             php_parser: PhpParser::new(),
-            crates_io: Arc::new(CratesIoRegistry::default()),
-            npm_registry: Arc::new(NpmRegistry::default()),
-            pypi: Arc::new(PyPiRegistry::default()),
-            go_proxy: Arc::new(GoProxyRegistry::default()),
-            packagist: Arc::new(PackagistRegistry::default()),
+            ruby_parser: RubyParser::new(),
+            csharp_parser: CsharpParser::new(),
+            dart_parser: DartParser::new(),
+            crates_io: RwLock::new(Arc::new(CratesIoRegistry::default())),
+            npm_registry: RwLock::new(Arc::new(NpmRegistry::default())),
+            pypi: RwLock::new(Arc::new(PyPiRegistry::default())),
+            go_proxy: RwLock::new(Arc::new(GoProxyRegistry::default())),
+            packagist: RwLock::new(Arc::new(PackagistRegistry::default())),
+            rubygems: RwLock::new(Arc::new(RubyGemsRegistry::default())),
+            nuget: RwLock::new(Arc::new(NuGetRegistry::default())),
+            pub_dev: RwLock::new(Arc::new(PubDevRegistry::default())),
+            config: RwLock::new(Config::default()),
+            http_provider: RwLock::new(HttpClientProvider::default()),
+            sparse_registries: DashMap::new(),
+            github_registries: DashMap::new(),
+            ignore_matcher: RwLock::new(Arc::new(IgnoreMatcher::default())),
+            registry_config: RwLock::new(Arc::new(DiscoveredRegistries::default())),
+            npm_registries: DashMap::new(),
+        }
+    }
+
+    /// Get (or lazily create) the sparse-index client for a configured
+    /// alternative Cargo registry: a user-declared `config.registries` entry
+    /// takes precedence, falling back to a registry auto-discovered from
+    /// `.cargo/config.toml`'s `[registries]`.
+    fn sparse_registry_for(&self, registry_name: &str) -> Option<Arc<CargoSparseRegistry>> {
+        if let Some(client) = self.sparse_registries.get(registry_name) {
+            return Some(Arc::clone(&client));
+        }
+
+        let config = self.config.read().ok()?;
+        let configured = config.registries.get(registry_name).cloned();
+        drop(config);
+
+        let (index, token, secret_key) = match configured {
+            Some(registry_config) => (registry_config.index, registry_config.token, None),
+            None => {
+                let registry_config = self.registry_config.read().ok()?;
+                let resolved = registry_config.resolve_cargo(Some(registry_name))?.clone();
+                (resolved.url, resolved.token, resolved.secret_key)
+            }
+        };
+
+        let http_provider = self.http_provider.read().ok()?;
+        let http_client = http_provider.client();
+        let token_manager = http_provider.token_manager();
+        drop(http_provider);
+        let client = Arc::new(CargoSparseRegistry::with_client_fallback_and_manager(
+            http_client,
+            index,
+            cargo_fallback_provider(token, secret_key),
+            token_manager,
+        ));
+        self.sparse_registries
+            .insert(registry_name.to_string(), Arc::clone(&client));
+        Some(client)
+    }
+
+    /// Get (or lazily create) the sparse-index client crates.io itself has
+    /// been replaced with, via `.cargo/config.toml`'s `[source.crates-io]`
+    /// `replace-with` chain. `None` when no such replacement is configured,
+    /// in which case callers should fall back to the public crates.io API.
+    fn default_cargo_registry(&self) -> Option<Arc<CargoSparseRegistry>> {
+        const SENTINEL: &str = "crates-io"; // reserved by Cargo, can't collide with a real registry name
+
+        if let Some(client) = self.sparse_registries.get(SENTINEL) {
+            return Some(Arc::clone(&client));
+        }
+
+        let registry_config = self.registry_config.read().ok()?;
+        let resolved = registry_config.resolve_cargo(None)?.clone();
+        drop(registry_config);
+
+        let http_provider = self.http_provider.read().ok()?;
+        let http_client = http_provider.client();
+        let token_manager = http_provider.token_manager();
+        drop(http_provider);
+        let client = Arc::new(CargoSparseRegistry::with_client_fallback_and_manager(
+            http_client,
+            resolved.url,
+            cargo_fallback_provider(resolved.token, resolved.secret_key),
+            token_manager,
+        ));
+        self.sparse_registries
+            .insert(SENTINEL.to_string(), Arc::clone(&client));
+        Some(client)
+    }
+
+    /// Get (or lazily create) the npm registry client for `package_name`'s
+    /// scope, resolved from `.npmrc`'s `@scope:registry=`/default `registry=`
+    /// entries. Returns `None` for unscoped packages with no `.npmrc`
+    /// default, so callers fall back to the shared public `npm_registry`.
+    fn npm_registry_for(&self, package_name: &str) -> Option<Arc<NpmRegistry>> {
+        let scope = package_name
+            .strip_prefix('@')
+            .and_then(|rest| rest.split_once('/'))
+            .map(|(scope, _)| scope)
+            .unwrap_or("");
+
+        if let Some(client) = self.npm_registries.get(scope) {
+            return Some(Arc::clone(&client));
+        }
+
+        let registry_config = self.registry_config.read().ok()?;
+        let resolved = registry_config.resolve_npm(package_name)?.clone();
+        drop(registry_config);
+
+        let http_provider = self.http_provider.read().ok()?;
+        let http_client = http_provider.client();
+        let token_manager = http_provider.token_manager();
+        drop(http_provider);
+        let client = Arc::new(NpmRegistry::with_client_config_and_manager(
+            http_client,
+            resolved.url,
+            resolved.token,
+            token_manager,
+        ));
+        self.npm_registries
+            .insert(scope.to_string(), Arc::clone(&client));
+        Some(client)
+    }
+
+    /// Get (or lazily create) the GitHub releases client for a configured
+    /// `github_releases` source, keyed by package/module name (e.g. a Go
+    /// module path like `"github.com/example/tool"`).
+    fn github_registry_for(&self, package_name: &str) -> Option<Arc<GithubReleasesRegistry>> {
+        if let Some(client) = self.github_registries.get(package_name) {
+            return Some(Arc::clone(&client));
+        }
+
+        let config = self.config.read().ok()?;
+        let source = config.github_releases.get(package_name)?.clone();
+        drop(config);
+
+        let http_client = self.http_provider.read().ok()?.client();
+        let client = Arc::new(GithubReleasesRegistry::with_client_and_config(
+            http_client,
+            source.repo,
+            source.select_search,
+            source.select_replace,
+            source.semantic_only,
+        ));
+        self.github_registries
+            .insert(package_name.to_string(), Arc::clone(&client));
+        Some(client)
+    }
+
+    /// Ecosystem tag used to qualify `Config.ignore` patterns written as
+    /// `"<ecosystem>:<name>"` - the same prefix `cache_key` uses, minus the
+    /// package name.
+    fn ecosystem_tag(file_type: FileType) -> &'static str {
+        match file_type {
+            FileType::Cargo => "crates",
+            FileType::Npm => "npm",
+            FileType::Python => "pypi",
+            FileType::Go => "go",
+            FileType::Php => "packagist",
+            FileType::Ruby => "rubygems",
+            FileType::Csharp => "nuget",
+            FileType::Dart => "pub",
+        }
+    }
+
+    /// The version ordering/requirement-matching rules for `file_type`'s
+    /// ecosystem, delegated to its parser's [`Parser::version_scheme`].
+    fn version_scheme_for(&self, file_type: FileType) -> &'static dyn VersionScheme {
+        match file_type {
+            FileType::Cargo => self.cargo_parser.version_scheme(),
+            FileType::Npm => self.npm_parser.version_scheme(),
+            FileType::Python => self.python_parser.version_scheme(),
+            FileType::Go => self.go_parser.version_scheme(),
+            FileType::Php => self.php_parser.version_scheme(),
+            FileType::Ruby => self.ruby_parser.version_scheme(),
+            FileType::Csharp => self.csharp_parser.version_scheme(),
+            FileType::Dart => self.dart_parser.version_scheme(),
+        }
+    }
+
+    /// Current compiled ignore matcher from the user's configuration
+    fn ignore_matcher(&self) -> Arc<IgnoreMatcher> {
+        self.ignore_matcher
+            .read()
+            .map(|guard| Arc::clone(&guard))
+            .unwrap_or_default()
+    }
+
+    /// Current cache mode from the user's configuration
+    fn cache_mode(&self) -> CacheMode {
+        self.config
+            .read()
+            .map(|config| config.cache.mode)
+            .unwrap_or(CacheMode::RespectHeaders)
+    }
+
+    /// Current cooldown/maturity gating window from the user's configuration
+    fn cooldown_window(&self) -> CooldownWindow {
+        self.config
+            .read()
+            .map(|config| CooldownWindow::from_config(&config.cooldown))
+            .unwrap_or_else(|_| CooldownWindow::disabled())
+    }
+
+    /// Current vulnerability-display policy (severity band, ignore list)
+    /// from the user's configuration
+    fn security_config(&self) -> SecurityConfig {
+        self.config
+            .read()
+            .map(|config| config.security.clone())
+            .unwrap_or_default()
+    }
+
+    /// Whether inlay hint tooltips should note a newer pre-release, from the
+    /// user's configuration
+    fn show_prereleases(&self) -> bool {
+        self.config
+            .read()
+            .map(|config| config.inlay_hints.show_prereleases)
+            .unwrap_or(false)
+    }
+
+    /// Whether to recommend the highest available version or the lowest one
+    /// still satisfying the declared requirement, from the user's configuration
+    fn version_preference(&self) -> VersionPreference {
+        self.config
+            .read()
+            .map(|config| config.inlay_hints.version_preference)
+            .unwrap_or_default()
+    }
+
+    /// Whether the HTTP client provider's hard offline toggle is set
+    fn is_offline(&self) -> bool {
+        self.http_provider
+            .read()
+            .map(|provider| provider.is_offline())
+            .unwrap_or(false)
+    }
+
+    /// Rebuild the shared HTTP client and every registry client from the
+    /// user's `http` configuration (proxy, extra CA certificates, timeouts),
+    /// carrying over `token_manager` so registries keep authenticating
+    /// through the same `DEPENDI_AUTH_TOKENS`-derived providers.
+    fn rebuild_http_client(&self, config: &HttpConfig, token_manager: Arc<TokenProviderManager>) {
+        let provider = match HttpClientProvider::with_token_manager(config, token_manager) {
+            Ok(provider) => provider,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to build configured HTTP client, keeping previous one: {}",
+                    e
+                );
+                return;
+            }
+        };
+        let client = provider.client();
+
+        if let Ok(mut guard) = self.crates_io.write() {
+            *guard = Arc::new(CratesIoRegistry::with_client(Arc::clone(&client)));
+        }
+        if let Ok(mut guard) = self.npm_registry.write() {
+            *guard = Arc::new(NpmRegistry::with_client(Arc::clone(&client)));
+        }
+        if let Ok(mut guard) = self.pypi.write() {
+            *guard = Arc::new(PyPiRegistry::with_client(Arc::clone(&client)));
+        }
+        if let Ok(mut guard) = self.go_proxy.write() {
+            *guard = Arc::new(GoProxyRegistry::with_client(Arc::clone(&client)));
+        }
+        if let Ok(mut guard) = self.packagist.write() {
+            *guard = Arc::new(PackagistRegistry::with_client(Arc::clone(&client)));
+        }
+        if let Ok(mut guard) = self.rubygems.write() {
+            *guard = Arc::new(RubyGemsRegistry::with_client(Arc::clone(&client)));
+        }
+        if let Ok(mut guard) = self.nuget.write() {
+            *guard = Arc::new(NuGetRegistry::with_client(Arc::clone(&client)));
+        }
+        if let Ok(mut guard) = self.pub_dev.write() {
+            *guard = Arc::new(PubDevRegistry::with_client(Arc::clone(&client)));
+        }
+        // Alternative Cargo registries, npm scopes, and GitHub releases
+        // sources are rebuilt lazily from this provider on their next lookup.
+        self.sparse_registries.clear();
+        self.github_registries.clear();
+        self.npm_registries.clear();
+
+        if let Ok(mut guard) = self.http_provider.write() {
+            *guard = provider;
         }
     }
 
@@ -97,6 +489,12 @@ impl DependiBackend {
             Some(FileType::Go)
         } else if path.ends_with("composer.json") {
             Some(FileType::Php)
+        } else if path.ends_with("Gemfile") || path.ends_with(".gemspec") {
+            Some(FileType::Ruby)
+        } else if path.ends_with(".csproj") || path.ends_with("packages.config") {
+            Some(FileType::Csharp)
+        } else if path.ends_with("pubspec.yaml") {
+            Some(FileType::Dart)
         } else {
             None
         }
@@ -110,47 +508,271 @@ impl DependiBackend {
     /// Parse a document and extract dependencies
     fn parse_document(&self, uri: &Url, content: &str) -> Vec<Dependency> {
         match Self::detect_file_type(uri) {
-            Some(FileType::Cargo) => self.cargo_parser.parse(content),
+            Some(FileType::Cargo) => {
+                let workspace_versions = Self::load_workspace_dependencies(uri);
+                self.cargo_parser
+                    .parse_with_workspace_versions(content, &workspace_versions)
+            }
             Some(FileType::Npm) => self.npm_parser.parse(content),
             Some(FileType::Python) => self.python_parser.parse(content),
             Some(FileType::Go) => self.go_parser.parse(content),
             Some(FileType::Php) => self.php_parser.parse(content),
+            Some(FileType::Ruby) => self.ruby_parser.parse(content),
+            Some(FileType::Csharp) => {
+                let central_versions = Self::load_central_package_versions(uri);
+                self.csharp_parser
+                    .parse_with_central_versions(content, &central_versions)
+            }
+            Some(FileType::Dart) => self.dart_parser.parse(content),
             None => vec![],
         }
     }
 
-    /// Get cache key for a package (includes registry prefix)
-    fn cache_key(file_type: FileType, package_name: &str) -> String {
+    /// Locate and parse a sibling `Directory.Packages.props`, if one exists,
+    /// into the name→version map NuGet Central Package Management uses to
+    /// resolve version-less `PackageReference` entries.
+    fn load_central_package_versions(uri: &Url) -> HashMap<String, String> {
+        let Ok(manifest_path) = uri.to_file_path() else {
+            return HashMap::new();
+        };
+        let Some(dir) = manifest_path.parent() else {
+            return HashMap::new();
+        };
+
+        std::fs::read_to_string(dir.join("Directory.Packages.props"))
+            .ok()
+            .map(|content| CsharpParser::parse_central_package_versions(&content))
+            .unwrap_or_default()
+    }
+
+    /// Locate the workspace root by walking up from the member crate's
+    /// directory looking for a `Cargo.toml` that declares
+    /// `[workspace.dependencies]`, and parse it into a name→version map for
+    /// resolving `workspace = true` entries. Unlike
+    /// [`Self::load_central_package_versions`], the workspace root is rarely
+    /// in the same directory as the member crate, so this walks up the tree
+    /// rather than checking a single sibling path.
+    fn load_workspace_dependencies(uri: &Url) -> HashMap<String, String> {
+        let Ok(manifest_path) = uri.to_file_path() else {
+            return HashMap::new();
+        };
+
+        let mut dir = manifest_path.parent();
+        while let Some(d) = dir {
+            if let Ok(content) = std::fs::read_to_string(d.join("Cargo.toml"))
+                && content.contains("[workspace.dependencies]")
+            {
+                return CargoParser::parse_workspace_dependencies(&content);
+            }
+            dir = d.parent();
+        }
+
+        HashMap::new()
+    }
+
+    /// Read and index a companion lockfile from `dir`, if present
+    fn read_lockfile(
+        dir: &Path,
+        filename: &str,
+        parser: &dyn LockfileParser,
+    ) -> Option<HashMap<String, LockedPackage>> {
+        let content = std::fs::read_to_string(dir.join(filename)).ok()?;
+        Some(lockfiles::index_by_name(parser, &content))
+    }
+
+    /// Locate and parse the lockfile next to a manifest, if one exists
+    /// (`Cargo.lock`, `package-lock.json`/`yarn.lock`, `poetry.lock`,
+    /// `go.sum`, `composer.lock`, `Gemfile.lock`), indexed by package name.
+    fn load_lockfile(uri: &Url, file_type: FileType) -> HashMap<String, LockedPackage> {
+        let Ok(manifest_path) = uri.to_file_path() else {
+            return HashMap::new();
+        };
+        let Some(dir) = manifest_path.parent() else {
+            return HashMap::new();
+        };
+
         match file_type {
-            FileType::Cargo => format!("crates:{}", package_name),
-            FileType::Npm => format!("npm:{}", package_name),
-            FileType::Python => format!("pypi:{}", package_name),
-            FileType::Go => format!("go:{}", package_name),
-            FileType::Php => format!("packagist:{}", package_name),
+            FileType::Cargo => Self::read_lockfile(dir, "Cargo.lock", &CargoLockParser::new()),
+            FileType::Npm => Self::read_lockfile(dir, "package-lock.json", &PackageLockParser::new())
+                .or_else(|| Self::read_lockfile(dir, "yarn.lock", &YarnLockParser::new())),
+            FileType::Python => Self::read_lockfile(dir, "poetry.lock", &PoetryLockParser::new())
+                .or_else(|| Self::read_lockfile(dir, "uv.lock", &UvLockParser::new())),
+            FileType::Go => Self::read_lockfile(dir, "go.sum", &GoSumParser::new()),
+            FileType::Php => Self::read_lockfile(dir, "composer.lock", &ComposerLockParser::new()),
+            FileType::Ruby => Self::read_lockfile(dir, "Gemfile.lock", &GemfileLockParser::new()),
+            // No lockfile parser yet for these ecosystems (packages.lock.json,
+            // pubspec.lock).
+            FileType::Csharp | FileType::Dart => None,
+        }
+        .unwrap_or_default()
+    }
+
+    /// Get cache key for a package (includes registry prefix)
+    ///
+    /// For Cargo dependencies pinned to an alternative registry via
+    /// `registry = "..."`, the key is namespaced by registry name so a
+    /// package available on both crates.io and a private registry doesn't
+    /// collide in the cache.
+    fn cache_key(file_type: FileType, package_name: &str, registry: Option<&str>) -> String {
+        match (file_type, registry) {
+            (FileType::Cargo, Some(registry)) => format!("crates:{}:{}", registry, package_name),
+            (FileType::Cargo, None) => format!("crates:{}", package_name),
+            (FileType::Npm, _) => format!("npm:{}", package_name),
+            (FileType::Python, _) => format!("pypi:{}", package_name),
+            (FileType::Go, _) => format!("go:{}", package_name),
+            (FileType::Php, _) => format!("packagist:{}", package_name),
+            (FileType::Ruby, _) => format!("rubygems:{}", package_name),
+            (FileType::Csharp, _) => format!("nuget:{}", package_name),
+            (FileType::Dart, _) => format!("pub:{}", package_name),
+        }
+    }
+
+    /// Handle `dependi/generateSarifReport`: build a SARIF report from an
+    /// open document's cached vulnerability data. `arguments` is expected to
+    /// hold the document URI as its first element; anything else (missing
+    /// argument, unknown/unopened document) yields `None` rather than an
+    /// error, matching `execute_command`'s existing best-effort handling.
+    fn generate_sarif_report(&self, arguments: &[serde_json::Value]) -> Option<serde_json::Value> {
+        let uri = arguments.first().and_then(|arg| arg.as_str())?;
+        let uri = Url::parse(uri).ok()?;
+        let doc = self.documents.get(&uri)?;
+
+        let mut entries = Vec::new();
+        for dep in &doc.dependencies {
+            let cache_key = Self::cache_key(doc.file_type, &dep.name, dep.registry.as_deref());
+            let Some(version_info) = self.version_cache.get(&cache_key) else {
+                continue;
+            };
+            for vuln in &version_info.vulnerabilities {
+                entries.push(VulnerabilityReportEntry {
+                    package: dep.name.clone(),
+                    version: dep.version.clone(),
+                    id: vuln.id.clone(),
+                    severity: severity_label(vuln.severity).to_string(),
+                    description: vuln.description.clone(),
+                    url: vuln.url.clone(),
+                    line: Some(dep.line),
+                    version_start: Some(dep.version_start),
+                    version_end: Some(dep.version_end),
+                });
+            }
         }
+        drop(doc);
+
+        Some(serde_json::Value::String(reports::generate_sarif_report(
+            &uri, &entries,
+        )))
+    }
+
+    /// Handle `dependi/planUpgrades`: the dry-run equivalent of the
+    /// `source.fixAll` code action - the same compatible-bump edits, without
+    /// applying them. `arguments` is expected to hold the document URI as
+    /// its first element; anything else (missing argument, unknown/unopened
+    /// document) yields `None`, matching `execute_command`'s existing
+    /// best-effort handling. Dev/optional dependencies are excluded, the
+    /// same as the `source.fixAll` aggregate offered through code actions.
+    fn plan_upgrades(&self, arguments: &[serde_json::Value]) -> Option<serde_json::Value> {
+        let uri = arguments.first().and_then(|arg| arg.as_str())?;
+        let uri = Url::parse(uri).ok()?;
+        let doc = self.documents.get(&uri)?;
+        let file_type = doc.file_type;
+
+        let plan = code_actions::plan_upgrades(
+            &doc.dependencies,
+            &self.version_cache,
+            file_type,
+            self.version_scheme_for(file_type),
+            |dep| Self::cache_key(file_type, &dep.name, dep.registry.as_deref()),
+            &self.cooldown_window(),
+            self.version_preference(),
+            |dep| !dep.dev && !dep.optional,
+            false,
+        );
+        drop(doc);
+
+        serde_json::to_value(plan).ok()
     }
 
     /// Fetch version info for a package (with caching)
-    async fn get_version_info(&self, file_type: FileType, package_name: &str) -> Option<VersionInfo> {
-        let cache_key = Self::cache_key(file_type, package_name);
+    async fn get_version_info(
+        &self,
+        file_type: FileType,
+        package_name: &str,
+        registry: Option<&str>,
+    ) -> Option<VersionInfo> {
+        let cache_key = Self::cache_key(file_type, package_name, registry);
+        let mode = self.cache_mode();
+        let offline = mode == CacheMode::Offline || self.is_offline();
 
-        // Check cache first
-        if let Some(cached) = self.version_cache.get(&cache_key) {
+        // Check cache first, unless the user asked to always refresh
+        if mode != CacheMode::RefreshAll
+            && let Some(cached) = self.version_cache.get(&cache_key)
+        {
             return Some(cached);
         }
 
+        if offline {
+            // Offline mode never hits the network, even on a cache miss
+            return None;
+        }
+
         // Fetch from appropriate registry
-        let result = match file_type {
-            FileType::Cargo => self.crates_io.get_version_info(package_name).await,
-            FileType::Npm => self.npm_registry.get_version_info(package_name).await,
-            FileType::Python => self.pypi.get_version_info(package_name).await,
-            FileType::Go => self.go_proxy.get_version_info(package_name).await,
-            FileType::Php => self.packagist.get_version_info(package_name).await,
+        let result = match (file_type, registry) {
+            (FileType::Cargo, Some(registry)) => match self.sparse_registry_for(registry) {
+                Some(client) => client.get_version_info(package_name).await,
+                None => {
+                    tracing::warn!("Unknown Cargo registry {} for {}", registry, package_name);
+                    return None;
+                }
+            },
+            (FileType::Cargo, None) => match self.default_cargo_registry() {
+                Some(client) => client.get_version_info(package_name).await,
+                None => {
+                    let crates_io = Arc::clone(&self.crates_io.read().ok()?);
+                    crates_io.get_version_info(package_name).await
+                }
+            },
+            (FileType::Npm, _) => match self.npm_registry_for(package_name) {
+                Some(npm_registry) => npm_registry.get_version_info(package_name).await,
+                None => {
+                    let npm_registry = Arc::clone(&self.npm_registry.read().ok()?);
+                    npm_registry.get_version_info(package_name).await
+                }
+            },
+            (FileType::Python, _) => {
+                let pypi = Arc::clone(&self.pypi.read().ok()?);
+                pypi.get_version_info(package_name).await
+            }
+            (FileType::Go, _) => match self.github_registry_for(package_name) {
+                Some(github) => github.get_version_info(package_name).await,
+                None => {
+                    let go_proxy = Arc::clone(&self.go_proxy.read().ok()?);
+                    go_proxy.get_version_info(package_name).await
+                }
+            },
+            (FileType::Php, _) => {
+                let packagist = Arc::clone(&self.packagist.read().ok()?);
+                packagist.get_version_info(package_name).await
+            }
+            (FileType::Ruby, _) => {
+                let rubygems = Arc::clone(&self.rubygems.read().ok()?);
+                rubygems.get_version_info(package_name).await
+            }
+            (FileType::Csharp, _) => {
+                let nuget = Arc::clone(&self.nuget.read().ok()?);
+                nuget.get_version_info(package_name).await
+            }
+            (FileType::Dart, _) => {
+                let pub_dev = Arc::clone(&self.pub_dev.read().ok()?);
+                pub_dev.get_version_info(package_name).await
+            }
         };
 
         match result {
             Ok(info) => {
-                self.version_cache.insert(cache_key, info.clone());
+                let ttl = crate::cache::staleness_ttl(&info);
+                self.version_cache
+                    .insert_with_ttl(cache_key, info.clone(), ttl);
                 Some(info)
             }
             Err(e) => {
@@ -166,48 +788,158 @@ impl DependiBackend {
             return;
         };
 
-        let dependencies = self.parse_document(uri, content);
+        let ignore_matcher = self.ignore_matcher();
+        let ecosystem = Self::ecosystem_tag(file_type);
+        let mut dependencies = self.parse_document(uri, content);
+        dependencies.retain(|dep| !ignore_matcher.is_ignored_in(ecosystem, &dep.name));
+        let locked = Self::load_lockfile(uri, file_type);
 
         tracing::info!(
-            "Parsed {} dependencies from {}",
+            "Parsed {} dependencies from {} (ignore patterns excluded the rest)",
             dependencies.len(),
             uri.path()
         );
 
         // Clone Arc references for async tasks
-        let crates_io = Arc::clone(&self.crates_io);
-        let npm_registry = Arc::clone(&self.npm_registry);
-        let pypi = Arc::clone(&self.pypi);
-        let go_proxy = Arc::clone(&self.go_proxy);
-        let packagist = Arc::clone(&self.packagist);
+        let Ok(crates_io_guard) = self.crates_io.read() else {
+            return;
+        };
+        let crates_io = Arc::clone(&crates_io_guard);
+        drop(crates_io_guard);
+        let Ok(npm_registry_guard) = self.npm_registry.read() else {
+            return;
+        };
+        let npm_registry = Arc::clone(&npm_registry_guard);
+        drop(npm_registry_guard);
+        let Ok(pypi_guard) = self.pypi.read() else {
+            return;
+        };
+        let pypi = Arc::clone(&pypi_guard);
+        drop(pypi_guard);
+        let Ok(go_proxy_guard) = self.go_proxy.read() else {
+            return;
+        };
+        let go_proxy = Arc::clone(&go_proxy_guard);
+        drop(go_proxy_guard);
+        let Ok(packagist_guard) = self.packagist.read() else {
+            return;
+        };
+        let packagist = Arc::clone(&packagist_guard);
+        drop(packagist_guard);
+        let Ok(rubygems_guard) = self.rubygems.read() else {
+            return;
+        };
+        let rubygems = Arc::clone(&rubygems_guard);
+        drop(rubygems_guard);
+        let Ok(nuget_guard) = self.nuget.read() else {
+            return;
+        };
+        let nuget = Arc::clone(&nuget_guard);
+        drop(nuget_guard);
+        let Ok(pub_dev_guard) = self.pub_dev.read() else {
+            return;
+        };
+        let pub_dev = Arc::clone(&pub_dev_guard);
+        drop(pub_dev_guard);
         let cache = Arc::clone(&self.version_cache);
+        let mode = self.cache_mode();
+        let offline = mode == CacheMode::Offline || self.is_offline();
+
+        // Scan the full resolved dependency tree, not just the manifest's
+        // direct dependencies - a vulnerability a few levels down is just as
+        // real as one in a top-level crate. Transitive-only packages have no
+        // registry override or external source of their own, so they're
+        // fetched as plain `(name, registry: None)` lookups.
+        let direct_names: HashSet<&str> =
+            dependencies.iter().map(|dep| dep.name.as_str()).collect();
+        let transitive_names: Vec<String> = locked
+            .values()
+            .filter(|pkg| !direct_names.contains(pkg.name.as_str()))
+            .map(|pkg| pkg.name.clone())
+            .collect();
 
         let fetch_tasks: Vec<_> = dependencies
             .iter()
-            .map(|dep| {
-                let name = dep.name.clone();
-                let cache_key = Self::cache_key(file_type, &name);
+            .map(|dep| (dep.name.clone(), dep.registry.clone(), dep.source.is_some()))
+            .chain(
+                transitive_names
+                    .into_iter()
+                    .map(|name| (name, None, false)),
+            )
+            .map(|(name, registry, sourced_externally)| {
+                let cache_key = Self::cache_key(file_type, &name, registry.as_deref());
+                // Resolve the alternative-registry client up front (cheap DashMap lookup)
+                // since it isn't `'static` friendly to resolve inside the spawned task.
+                let sparse_client = registry
+                    .as_deref()
+                    .and_then(|registry| self.sparse_registry_for(registry));
+                // Resolved up front for the same reason as `sparse_client`; only
+                // relevant when there's no explicit `registry = "..."` to route
+                // on, since that case always wins above.
+                let default_cargo_client = (file_type == FileType::Cargo && registry.is_none())
+                    .then(|| self.default_cargo_registry())
+                    .flatten();
+                // Resolved up front for the same reason as `sparse_client`.
+                let github_client = (file_type == FileType::Go)
+                    .then(|| self.github_registry_for(&name))
+                    .flatten();
+                // Resolved up front for the same reason as `sparse_client`.
+                let npm_client = (file_type == FileType::Npm)
+                    .then(|| self.npm_registry_for(&name))
+                    .flatten();
                 let crates_io = Arc::clone(&crates_io);
                 let npm_registry = Arc::clone(&npm_registry);
                 let pypi = Arc::clone(&pypi);
                 let go_proxy = Arc::clone(&go_proxy);
                 let packagist = Arc::clone(&packagist);
+                let rubygems = Arc::clone(&rubygems);
+                let nuget = Arc::clone(&nuget);
+                let pub_dev = Arc::clone(&pub_dev);
                 let cache = Arc::clone(&cache);
                 async move {
-                    // Check cache first
-                    if cache.get(&cache_key).is_some() {
+                    if sourced_externally {
+                        return;
+                    }
+                    // Check cache first, unless the user asked to always refresh.
+                    // `is_cached` also covers a live negative entry, so a
+                    // recent "not found" / failed lookup isn't retried on
+                    // every keystroke.
+                    if mode != CacheMode::RefreshAll && cache.is_cached(&cache_key) {
+                        return;
+                    }
+                    if offline {
+                        // Offline mode never hits the network, even on a cache miss
                         return;
                     }
                     // Fetch from appropriate registry
-                    let result = match file_type {
-                        FileType::Cargo => crates_io.get_version_info(&name).await,
-                        FileType::Npm => npm_registry.get_version_info(&name).await,
-                        FileType::Python => pypi.get_version_info(&name).await,
-                        FileType::Go => go_proxy.get_version_info(&name).await,
-                        FileType::Php => packagist.get_version_info(&name).await,
+                    let result = match (file_type, sparse_client) {
+                        (FileType::Cargo, Some(sparse)) => {
+                            sparse.get_version_info(&name).await
+                        }
+                        (FileType::Cargo, None) => match &default_cargo_client {
+                            Some(client) => client.get_version_info(&name).await,
+                            None => crates_io.get_version_info(&name).await,
+                        },
+                        (FileType::Npm, _) => match &npm_client {
+                            Some(client) => client.get_version_info(&name).await,
+                            None => npm_registry.get_version_info(&name).await,
+                        },
+                        (FileType::Python, _) => pypi.get_version_info(&name).await,
+                        (FileType::Go, _) => match &github_client {
+                            Some(github) => github.get_version_info(&name).await,
+                            None => go_proxy.get_version_info(&name).await,
+                        },
+                        (FileType::Php, _) => packagist.get_version_info(&name).await,
+                        (FileType::Ruby, _) => rubygems.get_version_info(&name).await,
+                        (FileType::Csharp, _) => nuget.get_version_info(&name).await,
+                        (FileType::Dart, _) => pub_dev.get_version_info(&name).await,
                     };
-                    if let Ok(info) = result {
-                        cache.insert(cache_key, info);
+                    match result {
+                        Ok(info) => {
+                            let ttl = crate::cache::staleness_ttl(&info);
+                            cache.insert_with_ttl(cache_key, info, ttl);
+                        }
+                        Err(_) => cache.insert_negative(cache_key),
                     }
                 }
             })
@@ -237,13 +969,22 @@ impl DependiBackend {
             DocumentState {
                 dependencies: dependencies.clone(),
                 file_type,
+                locked: locked.clone(),
             },
         );
 
         // Publish diagnostics
-        let diagnostics = create_diagnostics(&dependencies, &self.version_cache, |name| {
-            Self::cache_key(file_type, name)
-        });
+        let diagnostics = create_diagnostics(
+            &uri,
+            &dependencies,
+            &self.version_cache,
+            |name, registry| Self::cache_key(file_type, name, registry),
+            &locked,
+            &self.cooldown_window(),
+            &self.security_config(),
+            self.version_scheme_for(file_type),
+            self.version_preference(),
+        );
 
         self.client
             .publish_diagnostics(uri.clone(), diagnostics, None)
@@ -259,7 +1000,53 @@ impl DependiBackend {
 
 #[tower_lsp::async_trait]
 impl LanguageServer for DependiBackend {
-    async fn initialize(&self, _: InitializeParams) -> Result<InitializeResult> {
+    async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+        // `root_uri` is deprecated in favor of `workspace_folders`, but older
+        // clients still only send the former.
+        #[allow(deprecated)]
+        let root_uri = params.root_uri.as_ref();
+        let workspace_root = params
+            .workspace_folders
+            .as_ref()
+            .and_then(|folders| folders.first())
+            .map(|folder| &folder.uri)
+            .or(root_uri)
+            .and_then(|uri| uri.to_file_path().ok());
+
+        // Editor-sent `initializationOptions` take precedence; otherwise
+        // fall back to a `dependi.toml` checked into the workspace root, if
+        // one exists there.
+        let config = match params.initialization_options {
+            Some(options) => Config::from_init_options(Some(options)),
+            None => workspace_root
+                .as_deref()
+                .and_then(Config::from_dependi_toml)
+                .unwrap_or_default(),
+        };
+        let token_manager = TokenProviderManager::from_env_var("DEPENDI_AUTH_TOKENS").await;
+        if let Some(root) = &workspace_root
+            && let Ok(npmrc) = std::fs::read_to_string(root.join(".npmrc"))
+        {
+            for (url_prefix, provider) in parse_npmrc_providers(&npmrc) {
+                token_manager.register(url_prefix, provider).await;
+            }
+        }
+        self.rebuild_http_client(&config.http, Arc::new(token_manager));
+        if let Ok(mut guard) = self.ignore_matcher.write() {
+            *guard = Arc::new(IgnoreMatcher::new(&config.ignore));
+        }
+
+        if let Some(root) = &workspace_root {
+            let discovered = DiscoveredRegistries::discover(root).await;
+            if let Ok(mut guard) = self.registry_config.write() {
+                *guard = Arc::new(discovered);
+            }
+        }
+
+        if let Ok(mut guard) = self.config.write() {
+            *guard = config;
+        }
+
         Ok(InitializeResult {
             server_info: Some(ServerInfo {
                 name: "dependi-lsp".to_string(),
@@ -276,6 +1063,14 @@ impl LanguageServer for DependiBackend {
                     trigger_characters: Some(vec!["\"".to_string(), "=".to_string()]),
                     ..Default::default()
                 }),
+                execute_command_provider: Some(ExecuteCommandOptions {
+                    commands: vec![
+                        CLEAR_CACHE_COMMAND.to_string(),
+                        GENERATE_SARIF_REPORT_COMMAND.to_string(),
+                        PLAN_UPGRADES_COMMAND.to_string(),
+                    ],
+                    ..Default::default()
+                }),
                 ..Default::default()
             },
             ..Default::default()
@@ -325,7 +1120,15 @@ impl LanguageServer for DependiBackend {
     async fn did_close(&self, params: DidCloseTextDocumentParams) {
         let uri = params.text_document.uri;
         tracing::debug!("Document closed: {}", uri);
-        self.documents.remove(&uri);
+
+        // Flush cached version info for this document's dependencies so a
+        // future reopen re-fetches rather than serving stale entries.
+        if let Some((_, doc)) = self.documents.remove(&uri) {
+            for dep in &doc.dependencies {
+                let cache_key = Self::cache_key(doc.file_type, &dep.name, dep.registry.as_deref());
+                self.version_cache.remove(&cache_key);
+            }
+        }
 
         // Clear diagnostics for this document
         self.client
@@ -333,6 +1136,29 @@ impl LanguageServer for DependiBackend {
             .await;
     }
 
+    async fn execute_command(
+        &self,
+        params: ExecuteCommandParams,
+    ) -> Result<Option<serde_json::Value>> {
+        if params.command == CLEAR_CACHE_COMMAND {
+            self.version_cache.clear();
+            if let Err(e) = crate::vulnerabilities::cache::clear_cache() {
+                tracing::warn!("Failed to clear vulnerability cache: {}", e);
+            }
+            self.client
+                .log_message(MessageType::INFO, "Dependi cache cleared")
+                .await;
+            Ok(None)
+        } else if params.command == GENERATE_SARIF_REPORT_COMMAND {
+            Ok(self.generate_sarif_report(&params.arguments))
+        } else if params.command == PLAN_UPGRADES_COMMAND {
+            Ok(self.plan_upgrades(&params.arguments))
+        } else {
+            tracing::warn!("Unknown command: {}", params.command);
+            Ok(None)
+        }
+    }
+
     async fn inlay_hint(&self, params: InlayHintParams) -> Result<Option<Vec<InlayHint>>> {
         let uri = &params.text_document.uri;
 
@@ -341,6 +1167,11 @@ impl LanguageServer for DependiBackend {
         };
 
         let file_type = doc.file_type;
+        let cooldown = self.cooldown_window();
+        let security = self.security_config();
+        let show_prereleases = self.show_prereleases();
+        let preference = self.version_preference();
+        let scheme = self.version_scheme_for(file_type);
         let hints: Vec<InlayHint> = doc
             .dependencies
             .iter()
@@ -350,9 +1181,20 @@ impl LanguageServer for DependiBackend {
                 line >= params.range.start.line && line <= params.range.end.line
             })
             .map(|dep| {
-                let cache_key = Self::cache_key(file_type, &dep.name);
+                let cache_key = Self::cache_key(file_type, &dep.name, dep.registry.as_deref());
                 let version_info = self.version_cache.get(&cache_key);
-                create_inlay_hint(dep, version_info.as_ref())
+                let locked = doc.locked.get(&dep.name);
+                create_inlay_hint(
+                    dep,
+                    version_info.as_ref(),
+                    locked,
+                    &cooldown,
+                    &security,
+                    scheme,
+                    file_type,
+                    show_prereleases,
+                    preference,
+                )
             })
             .collect();
 
@@ -380,12 +1222,15 @@ impl LanguageServer for DependiBackend {
         let Some(dep) = dep.cloned() else {
             return Ok(None);
         };
+        let locked = doc.locked.get(&dep.name).cloned();
 
         // Drop the lock before async call
         drop(doc);
 
         // Get version info
-        let version_info = self.get_version_info(file_type, &dep.name).await;
+        let version_info = self
+            .get_version_info(file_type, &dep.name, dep.registry.as_deref())
+            .await;
 
         let content = match version_info {
             Some(info) => {
@@ -412,6 +1257,19 @@ impl LanguageServer for DependiBackend {
                     parts.push(format!("[Homepage]({})", homepage));
                 }
 
+                if let Some(locked) = &locked {
+                    parts.push(format!("**Locked:** {}", locked.version));
+                    if !satisfies_requirement(&dep.version, &locked.version) {
+                        parts.push(format!(
+                            "⚠ Locked version no longer satisfies `{}`",
+                            dep.version
+                        ));
+                    }
+                    if locked.checksum.is_none() {
+                        parts.push("⚠ Lockfile entry has no integrity checksum".to_string());
+                    }
+                }
+
                 parts.join("\n")
             }
             None => format!("## {}\n\nCould not fetch package information.", dep.name),
@@ -449,7 +1307,11 @@ impl LanguageServer for DependiBackend {
             uri,
             params.range,
             file_type,
-            |name| Self::cache_key(file_type, name),
+            self.version_scheme_for(file_type),
+            |dep| Self::cache_key(file_type, &dep.name, dep.registry.as_deref()),
+            &self.cooldown_window(),
+            self.version_preference(),
+            |dep| !dep.dev && !dep.optional,
         );
 
         Ok(Some(actions))
@@ -468,7 +1330,7 @@ impl LanguageServer for DependiBackend {
             &doc.dependencies,
             position,
             &self.version_cache,
-            |name| Self::cache_key(file_type, name),
+            |dep| Self::cache_key(file_type, &dep.name, dep.registry.as_deref()),
         );
 
         match completions {
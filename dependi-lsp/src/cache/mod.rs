@@ -1,7 +1,8 @@
 //! Cache layer for package version information
 
+use chrono::Utc;
 use dashmap::DashMap;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 
 use crate::registries::VersionInfo;
@@ -10,26 +11,129 @@ pub mod sqlite;
 
 pub use sqlite::SqliteCache;
 
-/// Trait for cache implementations
-pub trait Cache: Send + Sync {
+/// Trait for read access to a cache
+pub trait ReadCache: Send + Sync {
     /// Get a value from the cache
     fn get(&self, key: &str) -> Option<VersionInfo>;
+
+    /// Whether `key` has a live entry, including a negative ("not found")
+    /// marker - `true` here while [`Self::get`] still returns `None` means
+    /// the lookup was already tried and came back empty, so there's no need
+    /// to hit the registry again.
+    fn is_cached(&self, key: &str) -> bool;
 }
 
-// Implement Cache for Arc<T> where T: Cache
-impl<T: Cache> Cache for Arc<T> {
+/// Trait for write access to a cache
+pub trait WriteCache: Send + Sync {
+    /// Insert a value into the cache
+    fn insert(&self, key: String, value: VersionInfo);
+
+    /// Insert a value under an explicit TTL instead of the cache's default -
+    /// see [`staleness_ttl`] for the policy that picks it.
+    fn insert_with_ttl(&self, key: String, value: VersionInfo, ttl: Duration);
+
+    /// Record that `key`'s lookup came back empty or failed, under a much
+    /// shorter TTL than a normal entry (see [`NEGATIVE_TTL`]) - long enough
+    /// that a genuinely-missing package isn't retried on every keystroke,
+    /// short enough that a transient registry hiccup self-heals quickly.
+    fn insert_negative(&self, key: String);
+
+    /// Remove a single value from the cache
+    fn remove(&self, key: &str);
+
+    /// Remove every entry from the cache
+    fn clear(&self);
+}
+
+/// Full read/write cache implementation
+pub trait Cache: ReadCache + WriteCache {}
+
+impl<T: ReadCache + WriteCache> Cache for T {}
+
+// Implement ReadCache/WriteCache for Arc<T> so callers can hold a shared
+// cache behind an Arc and still satisfy `impl Cache` bounds.
+impl<T: ReadCache> ReadCache for Arc<T> {
     fn get(&self, key: &str) -> Option<VersionInfo> {
         (**self).get(key)
     }
+
+    fn is_cached(&self, key: &str) -> bool {
+        (**self).is_cached(key)
+    }
+}
+
+impl<T: WriteCache> WriteCache for Arc<T> {
+    fn insert(&self, key: String, value: VersionInfo) {
+        (**self).insert(key, value);
+    }
+
+    fn insert_with_ttl(&self, key: String, value: VersionInfo, ttl: Duration) {
+        (**self).insert_with_ttl(key, value, ttl);
+    }
+
+    fn insert_negative(&self, key: String) {
+        (**self).insert_negative(key);
+    }
+
+    fn remove(&self, key: &str) {
+        (**self).remove(key);
+    }
+
+    fn clear(&self) {
+        (**self).clear();
+    }
 }
 
 /// Default TTL for cache entries (1 hour)
 const DEFAULT_TTL: Duration = Duration::from_secs(3600);
 
+/// TTL for negative ("not found" / lookup failed) entries - much shorter
+/// than [`DEFAULT_TTL`] so a transient registry miss isn't stuck for an hour.
+const NEGATIVE_TTL: Duration = Duration::from_secs(300);
+
+/// A package whose newest known release falls within this many days of "now"
+/// is considered actively maintained for [`staleness_ttl`]'s purposes.
+const RECENT_RELEASE_WINDOW_DAYS: i64 = 30;
+
+/// TTL for actively-maintained packages - short, so a fast-moving dependency
+/// doesn't sit on a stale "latest" for long between checks.
+const ACTIVE_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// TTL for long-dormant packages - long, since there's little point
+/// re-querying a registry for something that hasn't published in years.
+const DORMANT_TTL: Duration = Duration::from_secs(7 * 24 * 3600);
+
+/// Pick a cache TTL for `info` based on how recently it was last published.
+///
+/// Uses the newest date in [`VersionInfo::release_dates`] to distinguish
+/// actively-maintained packages (short TTL, [`ACTIVE_TTL`]) from dormant ones
+/// (long TTL, [`DORMANT_TTL`]), falling back to [`DEFAULT_TTL`] when no
+/// release dates are known at all.
+pub fn staleness_ttl(info: &VersionInfo) -> Duration {
+    let Some(newest) = info.release_dates.values().max() else {
+        return DEFAULT_TTL;
+    };
+
+    let age_days = (Utc::now() - *newest).num_days();
+    if age_days <= RECENT_RELEASE_WINDOW_DAYS {
+        ACTIVE_TTL
+    } else {
+        DORMANT_TTL
+    }
+}
+
+/// What a [`CacheEntry`] holds: either the fetched version info, or a marker
+/// recording that the lookup came back empty or failed.
+#[derive(Debug, Clone)]
+enum CacheData {
+    Found(VersionInfo),
+    NotFound,
+}
+
 /// Cache entry with expiration
 #[derive(Debug, Clone)]
 struct CacheEntry {
-    data: VersionInfo,
+    data: CacheData,
     inserted_at: Instant,
     ttl: Duration,
 }
@@ -66,36 +170,104 @@ impl MemoryCache {
     pub fn get(&self, key: &str) -> Option<VersionInfo> {
         self.entries.get(key).and_then(|entry| {
             if entry.is_expired() {
-                None
-            } else {
-                Some(entry.data.clone())
+                return None;
+            }
+            match &entry.data {
+                CacheData::Found(info) => Some(info.clone()),
+                CacheData::NotFound => None,
             }
         })
     }
 
+    /// Whether `key` has a live entry, found or negative.
+    pub fn is_cached(&self, key: &str) -> bool {
+        self.entries.get(key).is_some_and(|entry| !entry.is_expired())
+    }
+
     /// Insert a value into the cache
     pub fn insert(&self, key: String, value: VersionInfo) {
+        self.insert_with_ttl(key, value, self.ttl);
+    }
+
+    /// Insert a value under an explicit TTL instead of the cache's default.
+    pub fn insert_with_ttl(&self, key: String, value: VersionInfo, ttl: Duration) {
         self.entries.insert(
             key,
             CacheEntry {
-                data: value,
+                data: CacheData::Found(value),
                 inserted_at: Instant::now(),
-                ttl: self.ttl,
+                ttl,
             },
         );
     }
+
+    /// Record that `key`'s lookup came back empty or failed.
+    pub fn insert_negative(&self, key: String) {
+        self.entries.insert(
+            key,
+            CacheEntry {
+                data: CacheData::NotFound,
+                inserted_at: Instant::now(),
+                ttl: NEGATIVE_TTL,
+            },
+        );
+    }
+
+    /// Remove a single value from the cache
+    pub fn remove(&self, key: &str) {
+        self.entries.remove(key);
+    }
+
+    /// Remove every entry from the cache
+    pub fn clear(&self) {
+        self.entries.clear();
+    }
 }
 
-impl Cache for MemoryCache {
+impl ReadCache for MemoryCache {
     fn get(&self, key: &str) -> Option<VersionInfo> {
         self.get(key)
     }
+
+    fn is_cached(&self, key: &str) -> bool {
+        self.is_cached(key)
+    }
+}
+
+impl WriteCache for MemoryCache {
+    fn insert(&self, key: String, value: VersionInfo) {
+        self.insert(key, value);
+    }
+
+    fn insert_with_ttl(&self, key: String, value: VersionInfo, ttl: Duration) {
+        self.insert_with_ttl(key, value, ttl);
+    }
+
+    fn insert_negative(&self, key: String) {
+        self.insert_negative(key);
+    }
+
+    fn remove(&self, key: &str) {
+        self.remove(key);
+    }
+
+    fn clear(&self) {
+        self.clear();
+    }
 }
 
 /// Hybrid cache that uses memory for fast access and SQLite for persistence
+///
+/// SQLite initialization (directory creation, WAL mode switch, expiry sweep)
+/// touches disk and can take long enough to stall editor startup if done
+/// synchronously in [`Self::new`]. Instead `sqlite` starts out `None` and is
+/// filled in by a background blocking task once [`SqliteCache::new`]
+/// finishes; until then (and forever, if it fails) every SQLite-backed
+/// method here sees `None` and behaves exactly like [`CacheFailure::Blackhole`](sqlite::CacheFailure::Blackhole) -
+/// reads miss, writes are dropped - falling back to the memory cache alone.
 pub struct HybridCache {
     memory: MemoryCache,
-    sqlite: Option<Arc<SqliteCache>>,
+    sqlite: Arc<RwLock<Option<Arc<SqliteCache>>>>,
 }
 
 impl Default for HybridCache {
@@ -106,20 +278,29 @@ impl Default for HybridCache {
 
 impl HybridCache {
     /// Create a new hybrid cache
+    ///
+    /// Returns immediately; the SQLite-backed half of the cache finishes
+    /// initializing on a background blocking thread (see the struct docs).
     pub fn new() -> Self {
-        let sqlite = match SqliteCache::new() {
-            Ok(cache) => {
-                tracing::info!("SQLite cache initialized");
-                Some(Arc::new(cache))
-            }
-            Err(e) => {
-                tracing::warn!(
-                    "Failed to initialize SQLite cache, using memory only: {}",
-                    e
-                );
-                None
+        let sqlite = Arc::new(RwLock::new(None));
+        let ready = Arc::clone(&sqlite);
+        tokio::spawn(async move {
+            match tokio::task::spawn_blocking(SqliteCache::new).await {
+                Ok(Ok(cache)) => {
+                    tracing::info!("SQLite cache initialized");
+                    *ready.write().unwrap() = Some(Arc::new(cache));
+                }
+                Ok(Err(e)) => {
+                    tracing::warn!(
+                        "Failed to initialize SQLite cache, using memory only: {}",
+                        e
+                    );
+                }
+                Err(e) => {
+                    tracing::warn!("SQLite cache initialization task panicked: {}", e);
+                }
             }
-        };
+        });
 
         Self {
             memory: MemoryCache::new(),
@@ -127,6 +308,12 @@ impl HybridCache {
         }
     }
 
+    /// The SQLite cache, if its background initialization has completed
+    /// successfully.
+    fn sqlite(&self) -> Option<Arc<SqliteCache>> {
+        self.sqlite.read().unwrap().clone()
+    }
+
     /// Get a value from the cache (memory first, then SQLite)
     pub fn get(&self, key: &str) -> Option<VersionInfo> {
         // Fast path: check memory cache first
@@ -134,32 +321,252 @@ impl HybridCache {
             return Some(value);
         }
 
+        // A live negative entry in memory means this key was already looked
+        // up and came back empty - no need to fall through to SQLite.
+        if self.memory.is_cached(key) {
+            return None;
+        }
+
         // Slow path: check SQLite cache
-        if let Some(ref sqlite) = self.sqlite
-            && let Some(value) = sqlite.get(key)
-        {
-            // Populate memory cache for future fast access
-            self.memory.insert(key.to_string(), value.clone());
-            return Some(value);
+        if let Some(sqlite) = self.sqlite() {
+            if let Some(value) = sqlite.get(key) {
+                // Populate memory cache for future fast access
+                self.memory.insert(key.to_string(), value.clone());
+                return Some(value);
+            }
+            if sqlite.is_cached(key) {
+                // Mirror the negative entry into memory so the next lookup
+                // doesn't need to hit SQLite again either.
+                self.memory.insert_negative(key.to_string());
+            }
         }
 
         None
     }
 
+    /// Whether `key` has a live entry in either cache, found or negative.
+    pub fn is_cached(&self, key: &str) -> bool {
+        self.memory.is_cached(key) || self.sqlite().is_some_and(|sqlite| sqlite.is_cached(key))
+    }
+
     /// Insert a value into both caches
     pub fn insert(&self, key: String, value: VersionInfo) {
         // Insert into memory cache
         self.memory.insert(key.clone(), value.clone());
 
         // Insert into SQLite cache
-        if let Some(ref sqlite) = self.sqlite {
+        if let Some(sqlite) = self.sqlite() {
             sqlite.insert(key, value);
         }
     }
+
+    /// Insert a value under an explicit TTL into both caches - see
+    /// [`staleness_ttl`].
+    pub fn insert_with_ttl(&self, key: String, value: VersionInfo, ttl: Duration) {
+        self.memory
+            .insert_with_ttl(key.clone(), value.clone(), ttl);
+        if let Some(sqlite) = self.sqlite() {
+            sqlite.insert_with_ttl(key, value, ttl);
+        }
+    }
+
+    /// Return the set of cache keys whose entries are due for a proactive
+    /// registry re-query, per the staleness policy tracked via SQLite's
+    /// `fetched_at` column. Memory-only mode (no SQLite backing, including
+    /// while SQLite is still initializing in the background) has no durable
+    /// way to track fetch history across restarts, so it reports no stale
+    /// keys.
+    pub fn refresh_stale(&self, now: i64, limit: usize) -> Vec<String> {
+        self.sqlite()
+            .and_then(|sqlite| sqlite.refresh_stale(now, limit).ok())
+            .unwrap_or_default()
+    }
+
+    /// Record that `key`'s lookup came back empty or failed, in both caches.
+    pub fn insert_negative(&self, key: String) {
+        self.memory.insert_negative(key.clone());
+        if let Some(sqlite) = self.sqlite() {
+            sqlite.insert_negative(key);
+        }
+    }
+
+    /// Remove a single value from both caches
+    pub fn remove(&self, key: &str) {
+        self.memory.remove(key);
+        if let Some(sqlite) = self.sqlite() {
+            sqlite.remove(key);
+        }
+    }
+
+    /// Remove every entry from both caches
+    pub fn clear(&self) {
+        self.memory.clear();
+        if let Some(sqlite) = self.sqlite() {
+            sqlite.clear();
+        }
+    }
 }
 
-impl Cache for HybridCache {
+impl ReadCache for HybridCache {
     fn get(&self, key: &str) -> Option<VersionInfo> {
         self.get(key)
     }
+
+    fn is_cached(&self, key: &str) -> bool {
+        self.is_cached(key)
+    }
+}
+
+impl WriteCache for HybridCache {
+    fn insert(&self, key: String, value: VersionInfo) {
+        self.insert(key, value);
+    }
+
+    fn insert_with_ttl(&self, key: String, value: VersionInfo, ttl: Duration) {
+        self.insert_with_ttl(key, value, ttl);
+    }
+
+    fn insert_negative(&self, key: String) {
+        self.insert_negative(key);
+    }
+
+    fn remove(&self, key: &str) {
+        self.remove(key);
+    }
+
+    fn clear(&self) {
+        self.clear();
+    }
+}
+
+/// Proactively re-fetch cache entries that [`HybridCache::refresh_stale`]
+/// reports as due, so a fast-moving dependency's "latest" doesn't sit stale
+/// for a full TTL window between document edits. `fetch` resolves a cache
+/// key back to a fresh [`VersionInfo`] (or `None` on lookup failure, in which
+/// case the entry is left alone and picked up again on the next pass); it
+/// mirrors the per-ecosystem dispatch in `DependiBackend::get_version_info`.
+/// Bounds concurrent re-fetches to `concurrency`, the same pattern used for
+/// document-triggered fetches. Callers are expected to invoke this
+/// periodically (e.g. on a timer) rather than only in response to edits.
+pub async fn refresh_stale_entries<F, Fut>(cache: Arc<HybridCache>, concurrency: usize, fetch: F)
+where
+    F: Fn(String) -> Fut + Send + Sync + 'static,
+    Fut: std::future::Future<Output = Option<VersionInfo>> + Send + 'static,
+{
+    let now = Utc::now().timestamp();
+    let keys = cache.refresh_stale(now, concurrency);
+    if keys.is_empty() {
+        return;
+    }
+
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(concurrency.max(1)));
+    let fetch = Arc::new(fetch);
+    let handles: Vec<_> = keys
+        .into_iter()
+        .map(|key| {
+            let permit = Arc::clone(&semaphore);
+            let cache = Arc::clone(&cache);
+            let fetch = Arc::clone(&fetch);
+            tokio::spawn(async move {
+                let _permit = permit.acquire().await;
+                if let Some(info) = fetch(key.clone()).await {
+                    let ttl = staleness_ttl(&info);
+                    cache.insert_with_ttl(key, info, ttl);
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        let _ = handle.await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_cache_remove_and_clear() {
+        let cache = MemoryCache::new();
+        cache.insert("a".to_string(), VersionInfo::default());
+        cache.insert("b".to_string(), VersionInfo::default());
+
+        cache.remove("a");
+        assert!(cache.get("a").is_none());
+        assert!(cache.get("b").is_some());
+
+        cache.clear();
+        assert!(cache.get("b").is_none());
+    }
+
+    #[test]
+    fn test_memory_cache_negative_entry_is_cached_but_absent() {
+        let cache = MemoryCache::new();
+        assert!(!cache.is_cached("missing"));
+
+        cache.insert_negative("missing".to_string());
+        assert!(cache.get("missing").is_none());
+        assert!(cache.is_cached("missing"));
+
+        // A later successful lookup overwrites the negative marker.
+        cache.insert("missing".to_string(), VersionInfo::default());
+        assert!(cache.get("missing").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_hybrid_cache_negative_lookup_skips_sqlite_on_repeat() {
+        let cache = HybridCache::new();
+        cache.insert_negative("pkg".to_string());
+
+        assert!(cache.get("pkg").is_none());
+        assert!(cache.is_cached("pkg"));
+    }
+
+    #[tokio::test]
+    async fn test_hybrid_cache_works_before_sqlite_background_init_completes() {
+        // `HybridCache::new` returns before its background SQLite
+        // initialization task has had a chance to run, so this exercises the
+        // memory-only fallback path deliberately, not just incidentally.
+        let cache = HybridCache::new();
+        cache.insert("pkg".to_string(), VersionInfo::default());
+        assert!(cache.get("pkg").is_some());
+    }
+
+    #[test]
+    fn test_staleness_ttl_no_release_dates_falls_back_to_default() {
+        let info = VersionInfo::default();
+        assert_eq!(staleness_ttl(&info), DEFAULT_TTL);
+    }
+
+    #[test]
+    fn test_staleness_ttl_recent_release_is_active() {
+        let mut info = VersionInfo::default();
+        info.release_dates
+            .insert("1.0.0".to_string(), Utc::now() - chrono::Duration::days(1));
+        assert_eq!(staleness_ttl(&info), ACTIVE_TTL);
+    }
+
+    #[test]
+    fn test_staleness_ttl_dormant_release_is_long() {
+        let mut info = VersionInfo::default();
+        info.release_dates.insert(
+            "1.0.0".to_string(),
+            Utc::now() - chrono::Duration::days(365),
+        );
+        assert_eq!(staleness_ttl(&info), DORMANT_TTL);
+    }
+
+    #[test]
+    fn test_memory_cache_insert_with_ttl_overrides_default() {
+        let cache = MemoryCache::new();
+        cache.insert_with_ttl(
+            "a".to_string(),
+            VersionInfo::default(),
+            Duration::from_secs(0),
+        );
+        // A zero-second TTL is immediately expired.
+        assert!(cache.get("a").is_none());
+        assert!(!cache.is_cached("a"));
+    }
 }
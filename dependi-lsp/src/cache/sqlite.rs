@@ -1,6 +1,6 @@
 //! SQLite persistent cache for package version information with connection pooling
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 #[cfg(test)]
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -16,6 +16,18 @@ use crate::registries::VersionInfo;
 /// Default TTL for cache entries (1 hour)
 const DEFAULT_TTL_SECS: i64 = 3600;
 
+/// TTL for negative ("not found" / lookup failed) entries - much shorter
+/// than [`DEFAULT_TTL_SECS`], mirroring the in-memory cache's negative TTL.
+const NEGATIVE_TTL_SECS: i64 = 300;
+
+/// Compiled-in cache data format version, bumped whenever a change to
+/// `VersionInfo`'s shape would make previously-cached rows fail to
+/// deserialize. Compared against the stored `PRAGMA user_version` on every
+/// open (see [`SqliteCache::apply_format_version`]) so stale rows are wiped
+/// up front instead of `get` silently returning `None` for every one of them
+/// forever.
+const CACHE_FORMAT_VERSION: i64 = 1;
+
 #[cfg(test)]
 static TEST_DB_COUNTER: AtomicU64 = AtomicU64::new(0);
 
@@ -34,6 +46,31 @@ pub struct SqliteCacheConfig {
     pub cache_size_kb: i64,
     /// Time-to-live for cache entries in seconds
     pub ttl_secs: i64,
+    /// Per-connection prepared-statement caching strategy, used by
+    /// [`Connection::prepare_cached`] so the four hot statements (get's
+    /// SELECT, insert's INSERT OR REPLACE, remove/clear's DELETEs) are
+    /// compiled once per connection instead of on every call. See
+    /// [`CacheStrategy`].
+    pub statement_cache_strategy: CacheStrategy,
+    /// What to do if the on-disk database still can't be opened after
+    /// `open_connection`'s retry-then-recreate recovery. See [`CacheFailure`].
+    pub on_open_failure: CacheFailure,
+    /// Cache data format version, stored in `PRAGMA user_version` and
+    /// compared on open. Defaults to the version compiled into this binary
+    /// ([`CACHE_FORMAT_VERSION`]) - overriding it is mainly for tests that
+    /// need to simulate a version bump.
+    pub format_version: i64,
+    /// SQL run when the stored format version doesn't match
+    /// `format_version`, before the version row is rewritten. Defaults to
+    /// wiping `packages`, since a serialization format change makes every
+    /// existing row undecodable.
+    pub on_version_change_sql: &'static str,
+    /// Statements to `prepare_cached` on every connection as it's created,
+    /// so the prepare cost is paid during pool initialization rather than
+    /// on the first `get`/`insert` after editor startup. Defaults to the
+    /// hot statements used by [`SqliteCache::get`], [`SqliteCache::insert_with_ttl`]
+    /// and [`SqliteCache::cleanup_expired`].
+    pub preheat_queries: Vec<&'static str>,
 }
 
 impl Default for SqliteCacheConfig {
@@ -45,14 +82,74 @@ impl Default for SqliteCacheConfig {
             busy_timeout_ms: 5000,
             cache_size_kb: 64000,
             ttl_secs: DEFAULT_TTL_SECS,
+            statement_cache_strategy: CacheStrategy::Bounded(16),
+            on_open_failure: CacheFailure::Error,
+            format_version: CACHE_FORMAT_VERSION,
+            on_version_change_sql: "DELETE FROM packages",
+            preheat_queries: vec![
+                "SELECT data, inserted_at, ttl_secs, found FROM packages WHERE key = ?",
+                "INSERT OR REPLACE INTO packages (key, data, inserted_at, ttl_secs, found, fetched_at) VALUES (?, ?, ?, ?, 1, ?)",
+                "DELETE FROM packages WHERE inserted_at + ttl_secs < ?",
+            ],
         }
     }
 }
 
+/// Per-connection prepared-statement caching strategy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheStrategy {
+    /// Let the statement cache grow to hold every distinct statement this
+    /// connection prepares, with no eviction.
+    Unbounded,
+    /// Cap the statement cache at `n` entries; rusqlite's LRU evicts the
+    /// rest.
+    Bounded(usize),
+    /// Skip statement caching entirely - every `prepare_cached` call
+    /// recompiles, same as a plain `prepare`. Useful on memory-constrained
+    /// runners, or to rule out a caching bug.
+    Disabled,
+}
+
+impl CacheStrategy {
+    /// The capacity to hand to
+    /// [`Connection::set_prepared_statement_cache_capacity`].
+    fn capacity(self) -> usize {
+        match self {
+            CacheStrategy::Unbounded => usize::MAX,
+            CacheStrategy::Bounded(n) => n,
+            CacheStrategy::Disabled => 0,
+        }
+    }
+}
+
+/// What the cache does when its on-disk database can't be opened or
+/// recovered - see [`SqliteCache::open_connection`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheFailure {
+    /// Propagate the error, failing cache construction entirely.
+    Error,
+    /// Fall back to a transient, process-local shared in-memory database -
+    /// caching still works for the life of the process, just without
+    /// persistence across restarts.
+    InMemory,
+    /// Disable the cache entirely: reads always miss and writes are
+    /// silently dropped, so dependency resolution keeps working uncached
+    /// rather than failing.
+    Blackhole,
+}
+
 /// SQLite-based persistent cache with connection pooling
+///
+/// `pool` is `None` in [`CacheFailure::Blackhole`] mode: every method that
+/// goes through [`Self::get_conn`] then sees no connection and takes the
+/// existing "pool unavailable" path, so reads miss and writes are dropped
+/// without a separate no-op code path to keep in sync.
 pub struct SqliteCache {
-    pool: Arc<Pool<SqliteConnectionManager>>,
+    pool: Option<Arc<Pool<SqliteConnectionManager>>>,
     ttl_secs: i64,
+    format_version: i64,
+    on_version_change_sql: &'static str,
+    statement_cache_strategy: CacheStrategy,
 }
 
 impl SqliteCache {
@@ -73,8 +170,21 @@ impl SqliteCache {
     pub fn with_path_and_config(path: PathBuf, config: SqliteCacheConfig) -> anyhow::Result<Self> {
         let busy_timeout_ms = config.busy_timeout_ms;
         let cache_size_kb = config.cache_size_kb;
+        let statement_cache_capacity = config.statement_cache_strategy.capacity();
+        let preheat_queries = config.preheat_queries.clone();
+
+        let Some(effective_path) = Self::open_connection(&path, config.on_open_failure)? else {
+            tracing::warn!("SQLite cache disabled (blackhole fallback)");
+            return Ok(Self {
+                pool: None,
+                ttl_secs: config.ttl_secs,
+                format_version: config.format_version,
+                on_version_change_sql: config.on_version_change_sql,
+                statement_cache_strategy: config.statement_cache_strategy,
+            });
+        };
 
-        let manager = SqliteConnectionManager::file(&path).with_init(move |conn| {
+        let manager = SqliteConnectionManager::file(&effective_path).with_init(move |conn| {
             let pragmas = format!(
                 "PRAGMA busy_timeout={};
                  PRAGMA synchronous=NORMAL;
@@ -82,6 +192,12 @@ impl SqliteCache {
                 busy_timeout_ms, cache_size_kb
             );
             conn.execute_batch(&pragmas)?;
+            conn.set_prepared_statement_cache_capacity(statement_cache_capacity);
+            for query in &preheat_queries {
+                // Preparing warms the statement cache; any failure here just
+                // means the first real call pays the prepare cost instead.
+                let _ = conn.prepare_cached(query);
+            }
             Ok(())
         });
 
@@ -94,8 +210,11 @@ impl SqliteCache {
             .build(manager)?;
 
         let cache = Self {
-            pool: Arc::new(pool),
+            pool: Some(Arc::new(pool)),
             ttl_secs: config.ttl_secs,
+            format_version: config.format_version,
+            on_version_change_sql: config.on_version_change_sql,
+            statement_cache_strategy: config.statement_cache_strategy,
         };
 
         cache.init_schema()?;
@@ -111,6 +230,65 @@ impl SqliteCache {
         Ok(cache)
     }
 
+    /// Open (or recover) the on-disk cache database at `path`, returning the
+    /// effective path the connection pool should use, or `None` for
+    /// [`CacheFailure::Blackhole`].
+    ///
+    /// Tries opening `path` directly, up to twice (a transient lock/busy
+    /// error on the first attempt often clears by itself). If it still won't
+    /// open cleanly, the file is assumed corrupt: it and its WAL/SHM
+    /// sidecars are deleted so SQLite recreates it from scratch. Only if
+    /// that recovery attempt also fails does `on_failure` decide what
+    /// happens next, so a corrupted cache degrades gracefully instead of
+    /// taking dependency resolution down with it.
+    fn open_connection(path: &Path, on_failure: CacheFailure) -> anyhow::Result<Option<PathBuf>> {
+        for _ in 0..2 {
+            if Self::probe(path).is_ok() {
+                return Ok(Some(path.to_path_buf()));
+            }
+        }
+
+        tracing::warn!(
+            path = %path.display(),
+            "cache database failed to open cleanly, recreating it"
+        );
+        let _ = std::fs::remove_file(path);
+        let _ = std::fs::remove_file(path.with_extension("db-wal"));
+        let _ = std::fs::remove_file(path.with_extension("db-shm"));
+
+        if Self::probe(path).is_ok() {
+            return Ok(Some(path.to_path_buf()));
+        }
+
+        match on_failure {
+            CacheFailure::Error => anyhow::bail!(
+                "cache database at {} could not be opened or recreated",
+                path.display()
+            ),
+            CacheFailure::InMemory => {
+                tracing::warn!(
+                    "cache database unrecoverable, falling back to a transient in-memory cache"
+                );
+                Ok(Some(PathBuf::from(format!(
+                    "file:dependi-cache-fallback-{}?mode=memory&cache=shared",
+                    std::process::id()
+                ))))
+            }
+            CacheFailure::Blackhole => {
+                tracing::warn!("cache database unrecoverable, disabling the cache");
+                Ok(None)
+            }
+        }
+    }
+
+    /// Check that `path` opens as a valid SQLite database.
+    fn probe(path: &Path) -> anyhow::Result<()> {
+        let conn = rusqlite::Connection::open(path)?;
+        let check: String = conn.query_row("PRAGMA quick_check", [], |row| row.get(0))?;
+        anyhow::ensure!(check == "ok", "quick_check reported: {check}");
+        Ok(())
+    }
+
     /// Create an in-memory cache (for testing)
     ///
     /// Uses a shared in-memory database URI so all pooled connections access
@@ -118,24 +296,35 @@ impl SqliteCache {
     /// conflicts between tests.
     #[cfg(test)]
     pub fn in_memory() -> anyhow::Result<Self> {
+        Self::in_memory_with_config(SqliteCacheConfig::default())
+    }
+
+    /// Like [`Self::in_memory`], but with a caller-supplied config - for
+    /// tests exercising non-default settings (e.g. a format version bump).
+    #[cfg(test)]
+    pub fn in_memory_with_config(config: SqliteCacheConfig) -> anyhow::Result<Self> {
         let db_id = TEST_DB_COUNTER.fetch_add(1, Ordering::SeqCst);
         let uri = format!("file:memdb{}?mode=memory&cache=shared", db_id);
-        let config = SqliteCacheConfig::default();
 
-        let manager = SqliteConnectionManager::file(&uri).with_init(|conn| {
+        let statement_cache_capacity = config.statement_cache_strategy.capacity();
+        let manager = SqliteConnectionManager::file(&uri).with_init(move |conn| {
             conn.execute_batch(
                 "PRAGMA busy_timeout=5000;
                  PRAGMA synchronous=NORMAL;
                  PRAGMA cache_size=-64000;",
             )?;
+            conn.set_prepared_statement_cache_capacity(statement_cache_capacity);
             Ok(())
         });
 
         let pool = Pool::builder().max_size(5).build(manager)?;
 
         let cache = Self {
-            pool: Arc::new(pool),
+            pool: Some(Arc::new(pool)),
             ttl_secs: config.ttl_secs,
+            format_version: config.format_version,
+            on_version_change_sql: config.on_version_change_sql,
+            statement_cache_strategy: config.statement_cache_strategy,
         };
 
         cache.init_schema_memory()?;
@@ -145,14 +334,16 @@ impl SqliteCache {
     /// Initialize schema for in-memory database (no WAL mode)
     #[cfg(test)]
     fn init_schema_memory(&self) -> anyhow::Result<()> {
-        let conn = self.pool.get()?;
+        let conn = self.get_conn_or_err()?;
 
         conn.execute(
             "CREATE TABLE IF NOT EXISTS packages (
                 key TEXT PRIMARY KEY,
                 data TEXT NOT NULL,
                 inserted_at INTEGER NOT NULL,
-                ttl_secs INTEGER NOT NULL
+                ttl_secs INTEGER NOT NULL,
+                found INTEGER NOT NULL DEFAULT 1,
+                fetched_at INTEGER NOT NULL DEFAULT 0
             )",
             [],
         )?;
@@ -160,6 +351,7 @@ impl SqliteCache {
             "CREATE INDEX IF NOT EXISTS idx_expiry ON packages(inserted_at, ttl_secs)",
             [],
         )?;
+        self.apply_format_version(&conn)?;
         Ok(())
     }
 
@@ -170,9 +362,23 @@ impl SqliteCache {
         Ok(cache_dir.join("dependi"))
     }
 
-    /// Get a connection from the pool, returning None if unavailable
+    /// Get a connection from the pool, returning None if unavailable (either
+    /// the pool is disabled - see [`CacheFailure::Blackhole`] - or checkout
+    /// failed).
     fn get_conn(&self) -> Option<PooledConnection<SqliteConnectionManager>> {
-        self.pool.get().ok()
+        self.pool.as_ref()?.get().ok()
+    }
+
+    /// Like [`Self::get_conn`], but for call sites that return
+    /// `anyhow::Result` and want the underlying error instead of silently
+    /// no-oping - schema setup and the test-only batch helpers shouldn't
+    /// succeed silently against a blackholed cache.
+    fn get_conn_or_err(&self) -> anyhow::Result<PooledConnection<SqliteConnectionManager>> {
+        let pool = self
+            .pool
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("cache is disabled (blackhole fallback)"))?;
+        Ok(pool.get()?)
     }
 
     /// Initialize the database schema with WAL mode
@@ -181,7 +387,7 @@ impl SqliteCache {
     /// are applied via with_init() on every new connection from the pool.
     /// Only WAL mode (database-level) is set here.
     fn init_schema(&self) -> anyhow::Result<()> {
-        let conn = self.pool.get()?;
+        let conn = self.get_conn_or_err()?;
 
         conn.execute_batch("PRAGMA journal_mode=WAL;")?;
 
@@ -190,7 +396,9 @@ impl SqliteCache {
                 key TEXT PRIMARY KEY,
                 data TEXT NOT NULL,
                 inserted_at INTEGER NOT NULL,
-                ttl_secs INTEGER NOT NULL
+                ttl_secs INTEGER NOT NULL,
+                found INTEGER NOT NULL DEFAULT 1,
+                fetched_at INTEGER NOT NULL DEFAULT 0
             )",
             [],
         )?;
@@ -198,6 +406,46 @@ impl SqliteCache {
             "CREATE INDEX IF NOT EXISTS idx_expiry ON packages(inserted_at, ttl_secs)",
             [],
         )?;
+        // Pre-existing databases predate the `found` column; adding it is a
+        // no-op once it's already there, so ignore the error.
+        let _ = conn.execute(
+            "ALTER TABLE packages ADD COLUMN found INTEGER NOT NULL DEFAULT 1",
+            [],
+        );
+        // Same for `fetched_at`, added to support refresh_stale(); backfill
+        // rows written before this column existed from their inserted_at so
+        // they aren't all considered maximally stale.
+        let _ = conn.execute(
+            "ALTER TABLE packages ADD COLUMN fetched_at INTEGER NOT NULL DEFAULT 0",
+            [],
+        );
+        conn.execute(
+            "UPDATE packages SET fetched_at = inserted_at WHERE fetched_at = 0",
+            [],
+        )?;
+        self.apply_format_version(&conn)?;
+        Ok(())
+    }
+
+    /// Compare the stored `PRAGMA user_version` against `self.format_version`
+    /// and, on a mismatch, run `self.on_version_change_sql` before rewriting
+    /// the stored version - so a `VersionInfo` serialization change discards
+    /// now-undecodable rows instead of leaving `get` returning `None` for
+    /// them forever.
+    fn apply_format_version(&self, conn: &rusqlite::Connection) -> anyhow::Result<()> {
+        let stored: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        if stored == self.format_version {
+            return Ok(());
+        }
+        if stored != 0 {
+            tracing::info!(
+                stored,
+                current = self.format_version,
+                "cache format version changed, discarding stale entries"
+            );
+        }
+        conn.execute_batch(self.on_version_change_sql)?;
+        conn.execute_batch(&format!("PRAGMA user_version = {};", self.format_version))?;
         Ok(())
     }
 }
@@ -207,16 +455,21 @@ impl ReadCache for SqliteCache {
         let conn = self.get_conn()?;
         let now = current_timestamp();
 
-        let result: Result<(String, i64, i64), _> = conn.query_row(
-            "SELECT data, inserted_at, ttl_secs FROM packages WHERE key = ?",
-            [key],
-            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
-        );
+        let result: Result<(String, i64, i64, i64), _> = conn
+            .prepare_cached("SELECT data, inserted_at, ttl_secs, found FROM packages WHERE key = ?")
+            .ok()?
+            .query_row([key], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            });
 
         match result {
-            Ok((data, inserted_at, ttl_secs)) => {
+            Ok((data, inserted_at, ttl_secs, found)) => {
                 if now > inserted_at + ttl_secs {
-                    let _ = conn.execute("DELETE FROM packages WHERE key = ?", [key]);
+                    if let Ok(mut stmt) = conn.prepare_cached("DELETE FROM packages WHERE key = ?") {
+                        let _ = stmt.execute([key]);
+                    }
+                    None
+                } else if found == 0 {
                     None
                 } else {
                     serde_json::from_str(&data).ok()
@@ -225,10 +478,31 @@ impl ReadCache for SqliteCache {
             Err(_) => None,
         }
     }
+
+    fn is_cached(&self, key: &str) -> bool {
+        let Some(conn) = self.get_conn() else {
+            return false;
+        };
+        let now = current_timestamp();
+
+        let Ok(mut stmt) =
+            conn.prepare_cached("SELECT inserted_at, ttl_secs FROM packages WHERE key = ?")
+        else {
+            return false;
+        };
+        let result: Result<(i64, i64), _> =
+            stmt.query_row([key], |row| Ok((row.get(0)?, row.get(1)?)));
+
+        matches!(result, Ok((inserted_at, ttl_secs)) if now <= inserted_at + ttl_secs)
+    }
 }
 
 impl WriteCache for SqliteCache {
     fn insert(&self, key: String, value: VersionInfo) {
+        self.insert_with_ttl(key, value, Duration::from_secs(self.ttl_secs.max(0) as u64));
+    }
+
+    fn insert_with_ttl(&self, key: String, value: VersionInfo, ttl: Duration) {
         let Some(conn) = self.get_conn() else {
             return;
         };
@@ -237,25 +511,44 @@ impl WriteCache for SqliteCache {
             Ok(d) => d,
             Err(_) => return,
         };
+        let ttl_secs = ttl.as_secs() as i64;
 
-        let _ = conn.execute(
-            "INSERT OR REPLACE INTO packages (key, data, inserted_at, ttl_secs) VALUES (?, ?, ?, ?)",
-            params![key, data, now, self.ttl_secs],
-        );
+        if let Ok(mut stmt) = conn.prepare_cached(
+            "INSERT OR REPLACE INTO packages (key, data, inserted_at, ttl_secs, found, fetched_at) VALUES (?, ?, ?, ?, 1, ?)",
+        ) {
+            let _ = stmt.execute(params![key, data, now, ttl_secs, now]);
+        }
+    }
+
+    fn insert_negative(&self, key: String) {
+        let Some(conn) = self.get_conn() else {
+            return;
+        };
+        let now = current_timestamp();
+
+        if let Ok(mut stmt) = conn.prepare_cached(
+            "INSERT OR REPLACE INTO packages (key, data, inserted_at, ttl_secs, found, fetched_at) VALUES (?, '', ?, ?, 0, ?)",
+        ) {
+            let _ = stmt.execute(params![key, now, NEGATIVE_TTL_SECS, now]);
+        }
     }
 
     fn remove(&self, key: &str) {
         let Some(conn) = self.get_conn() else {
             return;
         };
-        let _ = conn.execute("DELETE FROM packages WHERE key = ?", [key]);
+        if let Ok(mut stmt) = conn.prepare_cached("DELETE FROM packages WHERE key = ?") {
+            let _ = stmt.execute([key]);
+        }
     }
 
     fn clear(&self) {
         let Some(conn) = self.get_conn() else {
             return;
         };
-        let _ = conn.execute("DELETE FROM packages", []);
+        if let Ok(mut stmt) = conn.prepare_cached("DELETE FROM packages") {
+            let _ = stmt.execute([]);
+        }
     }
 }
 
@@ -267,7 +560,7 @@ impl SqliteCache {
             return Ok(0);
         }
 
-        let mut conn = self.pool.get()?;
+        let mut conn = self.get_conn_or_err()?;
         let tx = conn.transaction()?;
         let now = current_timestamp();
         let mut count = 0;
@@ -299,14 +592,14 @@ impl SqliteCache {
     /// Clear all entries from the cache, returning the count
     #[cfg(test)]
     pub fn clear_with_count(&self) -> anyhow::Result<usize> {
-        let conn = self.pool.get()?;
+        let conn = self.get_conn_or_err()?;
         let rows = conn.execute("DELETE FROM packages", [])?;
         Ok(rows)
     }
 
     /// Remove expired entries from the cache
     pub fn cleanup_expired(&self) -> anyhow::Result<usize> {
-        let conn = self.pool.get()?;
+        let conn = self.get_conn_or_err()?;
         let now = current_timestamp();
         let rows = conn.execute(
             "DELETE FROM packages WHERE inserted_at + ttl_secs < ?",
@@ -315,12 +608,45 @@ impl SqliteCache {
         Ok(rows)
     }
 
-    /// Get pool statistics for monitoring
+    /// Return up to `limit` keys whose entries are due for a proactive
+    /// registry re-query, i.e. still live (`found = 1`, not yet expired) but
+    /// past their TTL measured from the last actual fetch (`fetched_at`)
+    /// rather than the last row write (`inserted_at`) - see
+    /// [`crate::cache::staleness_ttl`] for how that TTL is chosen. Keys are
+    /// returned oldest-fetched first so the most overdue entries refresh
+    /// first when `limit` caps how many run in a given pass.
+    pub fn refresh_stale(&self, now: i64, limit: usize) -> anyhow::Result<Vec<String>> {
+        let conn = self.get_conn_or_err()?;
+        let mut stmt = conn.prepare(
+            "SELECT key FROM packages
+             WHERE found = 1 AND fetched_at + ttl_secs < ?
+             ORDER BY fetched_at ASC
+             LIMIT ?",
+        )?;
+        let keys = stmt
+            .query_map(params![now, limit as i64], |row| row.get::<_, String>(0))?
+            .filter_map(Result::ok)
+            .collect();
+        Ok(keys)
+    }
+
+    /// Get pool statistics for monitoring. Reports all-zero when the cache
+    /// is disabled ([`CacheFailure::Blackhole`]) since there's no pool to report on.
     pub fn pool_state(&self) -> PoolState {
-        let state = self.pool.state();
-        PoolState {
-            connections: state.connections,
-            idle_connections: state.idle_connections,
+        match &self.pool {
+            Some(pool) => {
+                let state = pool.state();
+                PoolState {
+                    connections: state.connections,
+                    idle_connections: state.idle_connections,
+                    statement_cache_strategy: self.statement_cache_strategy,
+                }
+            }
+            None => PoolState {
+                connections: 0,
+                idle_connections: 0,
+                statement_cache_strategy: self.statement_cache_strategy,
+            },
         }
     }
 }
@@ -332,6 +658,8 @@ pub struct PoolState {
     pub connections: u32,
     /// Number of idle connections available
     pub idle_connections: u32,
+    /// Statement cache strategy configured for this cache
+    pub statement_cache_strategy: CacheStrategy,
 }
 
 /// Get current Unix timestamp
@@ -360,6 +688,7 @@ mod tests {
             yanked: false,
             yanked_versions: vec![],
             release_dates: Default::default(),
+            platforms: Default::default(),
         }
     }
 
@@ -411,6 +740,17 @@ mod tests {
         assert!(state.connections > 0);
     }
 
+    #[test]
+    fn test_pool_state_reports_configured_statement_cache_strategy() {
+        let config = SqliteCacheConfig {
+            statement_cache_strategy: CacheStrategy::Disabled,
+            ..SqliteCacheConfig::default()
+        };
+        let cache = SqliteCache::in_memory_with_config(config).unwrap();
+        let state = cache.pool_state();
+        assert_eq!(state.statement_cache_strategy, CacheStrategy::Disabled);
+    }
+
     #[test]
     fn test_remove() {
         let cache = SqliteCache::in_memory().unwrap();
@@ -427,6 +767,28 @@ mod tests {
         assert!(!removed_again);
     }
 
+    #[test]
+    fn test_negative_entry_is_cached_but_absent() {
+        let cache = SqliteCache::in_memory().unwrap();
+        assert!(!cache.is_cached("missing"));
+
+        cache.insert_negative("missing".to_string());
+        assert!(cache.get("missing").is_none());
+        assert!(cache.is_cached("missing"));
+    }
+
+    #[test]
+    fn test_insert_overwrites_negative_entry() {
+        let cache = SqliteCache::in_memory().unwrap();
+        let info = create_test_version_info();
+
+        cache.insert_negative("test:package".to_string());
+        assert!(cache.get("test:package").is_none());
+
+        cache.insert("test:package".to_string(), info.clone());
+        assert_eq!(cache.get("test:package").unwrap().latest, info.latest);
+    }
+
     #[test]
     fn test_clear() {
         let cache = SqliteCache::in_memory().unwrap();
@@ -585,6 +947,48 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_insert_with_ttl_overrides_default() {
+        let cache = SqliteCache::in_memory().unwrap();
+        let info = create_test_version_info();
+
+        cache.insert_with_ttl("test:package".to_string(), info, Duration::from_secs(0));
+        // A zero-second TTL means the entry is expired as soon as any time passes.
+        std::thread::sleep(Duration::from_millis(10));
+        assert!(cache.get("test:package").is_none());
+    }
+
+    #[test]
+    fn test_refresh_stale_returns_only_overdue_entries() {
+        let cache = SqliteCache::in_memory().unwrap();
+        let info = create_test_version_info();
+
+        // Fresh entry: not due for refresh.
+        cache.insert("fresh".to_string(), info.clone());
+        // Stale entry: fetched far enough in the past that its TTL has lapsed.
+        cache.insert_with_ttl("stale".to_string(), info, Duration::from_secs(60));
+
+        let far_future = current_timestamp() + 120;
+        let due = cache.refresh_stale(far_future, 10).unwrap();
+
+        assert!(due.contains(&"stale".to_string()));
+        assert!(!due.contains(&"fresh".to_string()));
+    }
+
+    #[test]
+    fn test_refresh_stale_respects_limit() {
+        let cache = SqliteCache::in_memory().unwrap();
+        let info = create_test_version_info();
+
+        for i in 0..5 {
+            cache.insert_with_ttl(format!("pkg{}", i), info.clone(), Duration::from_secs(60));
+        }
+
+        let far_future = current_timestamp() + 120;
+        let due = cache.refresh_stale(far_future, 2).unwrap();
+        assert_eq!(due.len(), 2);
+    }
+
     #[test]
     fn test_config_default() {
         let config = SqliteCacheConfig::default();
@@ -593,5 +997,172 @@ mod tests {
         assert_eq!(config.busy_timeout_ms, 5000);
         assert_eq!(config.cache_size_kb, 64000);
         assert_eq!(config.ttl_secs, DEFAULT_TTL_SECS);
+        assert_eq!(
+            config.statement_cache_strategy,
+            CacheStrategy::Bounded(16)
+        );
+        assert_eq!(config.on_open_failure, CacheFailure::Error);
+        assert_eq!(config.format_version, CACHE_FORMAT_VERSION);
+        assert_eq!(config.on_version_change_sql, "DELETE FROM packages");
+        assert_eq!(config.preheat_queries.len(), 3);
+    }
+
+    #[test]
+    fn test_with_path_and_config_preheats_without_breaking_reads_and_writes() {
+        let db_id = TEST_DB_COUNTER.fetch_add(1, Ordering::SeqCst);
+        let path = std::env::temp_dir().join(format!("dependi-preheat-test-{}.db", db_id));
+        let _ = std::fs::remove_file(&path);
+
+        let cache =
+            SqliteCache::with_path_and_config(path.clone(), SqliteCacheConfig::default()).unwrap();
+        let info = create_test_version_info();
+        cache.insert_with_ttl("pkg".to_string(), info.clone(), Duration::from_secs(60));
+        assert_eq!(cache.get("pkg").unwrap().latest, info.latest);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_format_version_change_discards_stale_entries() {
+        let db_id = TEST_DB_COUNTER.fetch_add(1, Ordering::SeqCst);
+        let path = std::env::temp_dir().join(format!("dependi-version-test-{}.db", db_id));
+        let _ = std::fs::remove_file(&path);
+
+        {
+            let config = SqliteCacheConfig {
+                format_version: 1,
+                ..SqliteCacheConfig::default()
+            };
+            let cache = SqliteCache::with_path_and_config(path.clone(), config).unwrap();
+            cache.insert("test:package".to_string(), create_test_version_info());
+            assert!(cache.get("test:package").is_some());
+        }
+
+        let config = SqliteCacheConfig {
+            format_version: 2,
+            ..SqliteCacheConfig::default()
+        };
+        let cache = SqliteCache::with_path_and_config(path.clone(), config).unwrap();
+        assert!(cache.get("test:package").is_none());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_format_version_unchanged_keeps_entries() {
+        let db_id = TEST_DB_COUNTER.fetch_add(1, Ordering::SeqCst);
+        let path = std::env::temp_dir().join(format!("dependi-version-test-{}.db", db_id));
+        let _ = std::fs::remove_file(&path);
+
+        let config = SqliteCacheConfig {
+            format_version: 5,
+            ..SqliteCacheConfig::default()
+        };
+        {
+            let cache = SqliteCache::with_path_and_config(path.clone(), config.clone()).unwrap();
+            cache.insert("test:package".to_string(), create_test_version_info());
+        }
+
+        let cache = SqliteCache::with_path_and_config(path.clone(), config).unwrap();
+        assert!(cache.get("test:package").is_some());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    /// Path to a fresh, pre-corrupted database file for the recovery test:
+    /// valid enough to exist, not valid enough to be a SQLite database, but
+    /// sitting in a directory recreation can write to - so recovery succeeds.
+    fn corrupted_db_path() -> PathBuf {
+        let db_id = TEST_DB_COUNTER.fetch_add(1, Ordering::SeqCst);
+        let path = std::env::temp_dir().join(format!("dependi-corrupt-test-{}.db", db_id));
+        std::fs::write(&path, b"this is not a sqlite database").unwrap();
+        path
+    }
+
+    /// A path recovery can never succeed at - its parent directory doesn't
+    /// exist, so both the initial open and the delete-and-recreate attempt
+    /// fail, forcing `on_open_failure` to actually decide the outcome.
+    fn unrecoverable_db_path() -> PathBuf {
+        PathBuf::from("/dependi-test-nonexistent-dir-xyz/cache.db")
+    }
+
+    #[test]
+    fn test_on_open_failure_error_propagates() {
+        let config = SqliteCacheConfig {
+            on_open_failure: CacheFailure::Error,
+            ..SqliteCacheConfig::default()
+        };
+
+        let result = SqliteCache::with_path_and_config(unrecoverable_db_path(), config);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_on_open_failure_in_memory_still_caches() {
+        let config = SqliteCacheConfig {
+            on_open_failure: CacheFailure::InMemory,
+            ..SqliteCacheConfig::default()
+        };
+
+        let cache = SqliteCache::with_path_and_config(unrecoverable_db_path(), config).unwrap();
+        let info = create_test_version_info();
+        cache.insert("test:package".to_string(), info.clone());
+        assert_eq!(cache.get("test:package").unwrap().latest, info.latest);
+    }
+
+    #[test]
+    fn test_on_open_failure_blackhole_is_a_no_op() {
+        let config = SqliteCacheConfig {
+            on_open_failure: CacheFailure::Blackhole,
+            ..SqliteCacheConfig::default()
+        };
+
+        let cache = SqliteCache::with_path_and_config(unrecoverable_db_path(), config).unwrap();
+        let info = create_test_version_info();
+        cache.insert("test:package".to_string(), info);
+        assert!(cache.get("test:package").is_none());
+        let state = cache.pool_state();
+        assert_eq!(state.connections, 0);
+    }
+
+    #[test]
+    fn test_recreating_corrupt_db_recovers_without_fallback() {
+        // A corrupt-but-recreatable file is deleted and recreated before any
+        // fallback policy is consulted - `Error` still succeeds here because
+        // recovery itself works, it's never reached.
+        let path = corrupted_db_path();
+        let config = SqliteCacheConfig {
+            on_open_failure: CacheFailure::Error,
+            ..SqliteCacheConfig::default()
+        };
+
+        let cache = SqliteCache::with_path_and_config(path.clone(), config).unwrap();
+        let info = create_test_version_info();
+        cache.insert("test:package".to_string(), info.clone());
+        assert_eq!(cache.get("test:package").unwrap().latest, info.latest);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_repeated_operations_reuse_cached_statements() {
+        // Exercises get/insert/remove/clear enough times that, if
+        // `prepare_cached` weren't wired up, each call would still work but
+        // a capacity regression (e.g. forgetting to raise the cache size for
+        // many distinct statements) would surface as a cache-miss-driven
+        // slowdown rather than a failure - so this mainly guards that the
+        // cached path returns the same results as the uncached one did.
+        let cache = SqliteCache::in_memory().unwrap();
+        let info = create_test_version_info();
+
+        for i in 0..50 {
+            cache.insert(format!("pkg{}", i), info.clone());
+        }
+        for i in 0..50 {
+            assert!(cache.get(&format!("pkg{}", i)).is_some());
+        }
+        cache.remove("pkg0");
+        assert!(cache.get("pkg0").is_none());
+        cache.clear();
+        assert!(cache.get("pkg1").is_none());
     }
 }
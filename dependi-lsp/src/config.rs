@@ -1,5 +1,7 @@
 //! Configuration management for Dependi LSP
 
+use std::collections::HashMap;
+
 use serde::Deserialize;
 
 /// Default cache TTL (1 hour)
@@ -8,6 +10,12 @@ const DEFAULT_CACHE_TTL_SECS: u64 = 3600;
 /// Default vulnerability cache TTL (6 hours)
 const DEFAULT_VULN_CACHE_TTL_SECS: u64 = 6 * 3600;
 
+/// Default HTTP request timeout (10 seconds)
+const DEFAULT_HTTP_TIMEOUT_SECS: u64 = 10;
+
+/// Default cooldown window before a new version is recommended (14 days)
+const DEFAULT_COOLDOWN_DAYS: u64 = 14;
+
 /// LSP configuration
 #[derive(Debug, Clone, Deserialize, Default)]
 #[serde(default)]
@@ -23,6 +31,60 @@ pub struct Config {
     /// Packages to ignore (glob patterns)
     #[serde(default)]
     pub ignore: Vec<String>,
+    /// Alternative/private Cargo registries, keyed by the name used in
+    /// a dependency's `registry = "..."` field (e.g. `.cargo/config.toml`'s
+    /// `[registries]` table).
+    #[serde(default)]
+    pub registries: HashMap<String, RegistryConfig>,
+    /// HTTP client configuration (proxy, TLS, timeouts, offline mode)
+    pub http: HttpConfig,
+    /// Cooldown/maturity gating for newly published versions
+    pub cooldown: CooldownConfig,
+    /// GitHub-releases-backed version sources, keyed by the package/module
+    /// name they apply to (e.g. a Go module path like
+    /// `"github.com/example/tool"`), for dependencies that aren't served by
+    /// a package registry at all.
+    #[serde(default)]
+    pub github_releases: HashMap<String, GithubReleaseConfig>,
+}
+
+/// HTTP client configuration shared by every registry client
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct HttpConfig {
+    /// HTTP(S) proxy URL, e.g. `"http://proxy.corp.example.com:8080"`
+    pub proxy: Option<String>,
+    /// Hosts that bypass the proxy even when one is configured
+    pub no_proxy: Vec<String>,
+    /// Paths to additional PEM-encoded root CA certificates to trust
+    /// (for registries behind a TLS-inspecting corporate proxy)
+    pub extra_ca_certs: Vec<String>,
+    /// Request timeout in seconds
+    pub timeout_secs: u64,
+    /// Never hit the network; only ever serve from cache
+    pub offline: bool,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            proxy: None,
+            no_proxy: Vec::new(),
+            extra_ca_certs: Vec::new(),
+            timeout_secs: DEFAULT_HTTP_TIMEOUT_SECS,
+            offline: false,
+        }
+    }
+}
+
+/// Configuration for a single alternative Cargo registry.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RegistryConfig {
+    /// Base URL of the registry's sparse index (without a trailing slash).
+    pub index: String,
+    /// Optional bearer token sent as `Authorization: Bearer <token>`.
+    #[serde(default)]
+    pub token: Option<String>,
 }
 
 /// Inlay hints configuration
@@ -33,6 +95,13 @@ pub struct InlayHintsConfig {
     pub enabled: bool,
     /// Show hints for up-to-date packages
     pub show_up_to_date: bool,
+    /// Append a "pre-release available" note to the tooltip when the
+    /// registry has a newer pre-release than the recommended stable update.
+    /// Off by default - a pre-release is never the primary recommendation
+    /// regardless of this setting, only an informational annotation.
+    pub show_prereleases: bool,
+    /// Which version in the declared requirement's range to recommend.
+    pub version_preference: VersionPreference,
 }
 
 impl Default for InlayHintsConfig {
@@ -40,10 +109,25 @@ impl Default for InlayHintsConfig {
         Self {
             enabled: true,
             show_up_to_date: true,
+            show_prereleases: false,
+            version_preference: VersionPreference::Highest,
         }
     }
 }
 
+/// Which version in a declared requirement's range to recommend, modeled on
+/// Cargo's `minimal-versions`/`direct-minimal-versions` resolver modes.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum VersionPreference {
+    /// Recommend the highest version available, breaking or not (default).
+    #[default]
+    Highest,
+    /// Recommend the lowest version that still satisfies the declared
+    /// requirement, for users prioritizing reproducibility over freshness.
+    LowestCompatible,
+}
+
 /// Diagnostics configuration
 #[derive(Debug, Clone, Deserialize)]
 #[serde(default)]
@@ -64,16 +148,31 @@ impl Default for DiagnosticsConfig {
 pub struct CacheConfig {
     /// Cache TTL in seconds
     pub ttl_secs: u64,
+    /// How aggressively cached version info is reused before hitting the registry
+    pub mode: CacheMode,
 }
 
 impl Default for CacheConfig {
     fn default() -> Self {
         Self {
             ttl_secs: DEFAULT_CACHE_TTL_SECS,
+            mode: CacheMode::RespectHeaders,
         }
     }
 }
 
+/// Cache reuse strategy for registry lookups
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum CacheMode {
+    /// Always refetch from the registry, overwriting any cached entry
+    RefreshAll,
+    /// Reuse a cached entry until its TTL expires (default)
+    RespectHeaders,
+    /// Never hit the network; only ever serve from cache
+    Offline,
+}
+
 /// Security/vulnerability scanning configuration
 #[derive(Debug, Clone, Deserialize)]
 #[serde(default)]
@@ -86,8 +185,32 @@ pub struct SecurityConfig {
     pub show_diagnostics: bool,
     /// Minimum severity level to display ("low", "medium", "high", "critical")
     pub min_severity: String,
+    /// Maximum severity level to display. `None` means no ceiling - useful
+    /// for teams that have already triaged and accepted the most severe
+    /// advisories and only want the editor to nag about the rest.
+    pub max_severity: Option<String>,
+    /// Advisory/CVE IDs to suppress regardless of severity (e.g. a
+    /// known-accepted `CVE-2021-23337`), matched against `Vulnerability::id`.
+    pub ignore_advisories: Vec<String>,
     /// Vulnerability cache TTL in seconds (default: 6 hours)
     pub cache_ttl_secs: u64,
+    /// Advisory policy schema version, modeled on cargo-deny's versioned
+    /// `[advisories]` policy. `1` (the default) keeps today's behavior:
+    /// `ignore_advisories` hides an advisory outright and every severity
+    /// maps to a fixed diagnostic level. `2` opts into `downgrade_ignored`
+    /// and `severity_levels` below, so existing configs keep working
+    /// unchanged until a team explicitly asks for the new behavior.
+    pub version: u32,
+    /// At `version = 2`, report an ignored advisory as a HINT diagnostic
+    /// instead of suppressing it entirely. Ignored at `version = 1`.
+    pub downgrade_ignored: bool,
+    /// At `version = 2`, per-severity diagnostic level overrides, e.g.
+    /// `{ "medium": "error" }` to treat medium-severity advisories as
+    /// errors. Keys are severity names ("low"/"medium"/"high"/"critical");
+    /// values are LSP diagnostic severities ("error", "warning",
+    /// "information", or "hint"), matched case-insensitively. Ignored at
+    /// `version = 1`.
+    pub severity_levels: HashMap<String, String>,
 }
 
 impl Default for SecurityConfig {
@@ -97,7 +220,12 @@ impl Default for SecurityConfig {
             show_in_hints: true,
             show_diagnostics: true,
             min_severity: "low".to_string(),
+            max_severity: None,
+            ignore_advisories: Vec::new(),
             cache_ttl_secs: DEFAULT_VULN_CACHE_TTL_SECS,
+            version: 1,
+            downgrade_ignored: false,
+            severity_levels: HashMap::new(),
         }
     }
 }
@@ -105,16 +233,123 @@ impl Default for SecurityConfig {
 impl SecurityConfig {
     /// Parse minimum severity level to VulnerabilitySeverity
     pub fn min_severity_level(&self) -> crate::registries::VulnerabilitySeverity {
+        parse_severity(&self.min_severity)
+    }
+
+    /// Parse maximum severity level to VulnerabilitySeverity. Defaults to
+    /// `Critical` (no ceiling) when unset.
+    pub fn max_severity_level(&self) -> crate::registries::VulnerabilitySeverity {
         use crate::registries::VulnerabilitySeverity;
-        match self.min_severity.to_lowercase().as_str() {
-            "critical" => VulnerabilitySeverity::Critical,
-            "high" => VulnerabilitySeverity::High,
-            "medium" => VulnerabilitySeverity::Medium,
-            _ => VulnerabilitySeverity::Low,
+        self.max_severity
+            .as_deref()
+            .map(parse_severity)
+            .unwrap_or(VulnerabilitySeverity::Critical)
+    }
+
+    /// Whether `id` is on the ignore list.
+    pub fn is_ignored_advisory(&self, id: &str) -> bool {
+        self.ignore_advisories.iter().any(|ignored| ignored == id)
+    }
+
+    /// Whether `vuln` should be surfaced in hints/diagnostics at all: within
+    /// the `[min_severity, max_severity]` band, and either not on the
+    /// ignore list or, at `version = 2` with `downgrade_ignored` set,
+    /// reported (as a HINT - see `severity_level_override`) rather than
+    /// hidden.
+    pub fn should_report(&self, vuln: &crate::registries::Vulnerability) -> bool {
+        if self.is_ignored_advisory(&vuln.id) && !(self.version >= 2 && self.downgrade_ignored) {
+            return false;
+        }
+        vuln.severity >= self.min_severity_level() && vuln.severity <= self.max_severity_level()
+    }
+
+    /// The configured diagnostic level override for `severity`, e.g.
+    /// `"error"`, if `severity_levels` sets one and `version = 2`. `None`
+    /// means the caller should fall back to its own default mapping.
+    pub fn severity_level_override(
+        &self,
+        severity: crate::registries::VulnerabilitySeverity,
+    ) -> Option<&str> {
+        if self.version < 2 {
+            return None;
+        }
+        self.severity_levels
+            .get(severity_name(severity))
+            .map(String::as_str)
+    }
+}
+
+/// Lowercase name for a `VulnerabilitySeverity`, used as the key into
+/// `SecurityConfig::severity_levels`.
+fn severity_name(severity: crate::registries::VulnerabilitySeverity) -> &'static str {
+    use crate::registries::VulnerabilitySeverity;
+    match severity {
+        VulnerabilitySeverity::Critical => "critical",
+        VulnerabilitySeverity::High => "high",
+        VulnerabilitySeverity::Medium => "medium",
+        VulnerabilitySeverity::Low => "low",
+    }
+}
+
+/// Parse a severity string ("low", "medium", "high", "critical") to
+/// `VulnerabilitySeverity`, falling back to `Low` for anything unrecognized.
+fn parse_severity(s: &str) -> crate::registries::VulnerabilitySeverity {
+    use crate::registries::VulnerabilitySeverity;
+    match s.to_lowercase().as_str() {
+        "critical" => VulnerabilitySeverity::Critical,
+        "high" => VulnerabilitySeverity::High,
+        "medium" => VulnerabilitySeverity::Medium,
+        _ => VulnerabilitySeverity::Low,
+    }
+}
+
+/// Maturity/cooldown gating configuration
+///
+/// When enabled, a newly published version isn't recommended as an update
+/// until it has been out for at least `days`. This avoids nudging users
+/// onto a release that gets yanked or patched within hours of publishing.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CooldownConfig {
+    /// Enable cooldown gating
+    pub enabled: bool,
+    /// Minimum age, in days, a version must have before it's recommended
+    pub days: u64,
+    /// How to treat a version with no known release date: `true` makes it
+    /// ineligible until a later, dated version clears the window; `false`
+    /// (default) recommends it anyway, since we can't confirm it's too new.
+    pub strict: bool,
+}
+
+impl Default for CooldownConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            days: DEFAULT_COOLDOWN_DAYS,
+            strict: false,
         }
     }
 }
 
+/// Configuration for a single GitHub-releases-backed version source.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GithubReleaseConfig {
+    /// `owner/repo` on GitHub to list tags/releases from.
+    pub repo: String,
+    /// Regex applied to each raw tag name to extract/normalize its version.
+    /// When unset, the tag name is used as-is.
+    #[serde(default)]
+    pub select_search: Option<String>,
+    /// Replacement template (`$1`, `$2`, ...) applied to a `select_search`
+    /// match to produce the normalized version. When unset, the whole match
+    /// is used unchanged.
+    #[serde(default)]
+    pub select_replace: Option<String>,
+    /// Drop tags that don't parse as valid semver after normalization.
+    #[serde(default)]
+    pub semantic_only: bool,
+}
+
 impl Config {
     /// Parse configuration from initialization options
     pub fn from_init_options(options: Option<serde_json::Value>) -> Self {
@@ -123,11 +358,21 @@ impl Config {
             None => Self::default(),
         }
     }
+
+    /// Load configuration from a `dependi.toml` file at the workspace root,
+    /// if one exists there. Returns `None` when the file is absent or fails
+    /// to parse, so callers can fall back to `initializationOptions` (or
+    /// defaults) without treating a missing file as an error.
+    pub fn from_dependi_toml(workspace_root: &std::path::Path) -> Option<Self> {
+        let content = std::fs::read_to_string(workspace_root.join("dependi.toml")).ok()?;
+        toml::from_str(&content).ok()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::registries::{Vulnerability, VulnerabilitySeverity};
     use serde_json::json;
 
     #[test]
@@ -135,6 +380,7 @@ mod tests {
         let config = Config::default();
         assert!(config.inlay_hints.enabled);
         assert!(config.inlay_hints.show_up_to_date);
+        assert!(!config.inlay_hints.show_prereleases);
         assert!(config.diagnostics.enabled);
         assert_eq!(config.cache.ttl_secs, DEFAULT_CACHE_TTL_SECS);
         assert!(config.ignore.is_empty());
@@ -164,6 +410,130 @@ mod tests {
         assert_eq!(config.ignore.len(), 2);
     }
 
+    #[test]
+    fn test_parse_registries() {
+        let json = json!({
+            "registries": {
+                "kellnr": {
+                    "index": "https://kellnr.example.com/api/v1/cargo",
+                    "token": "secret"
+                },
+                "internal": {
+                    "index": "https://crates.internal.example.com"
+                }
+            }
+        });
+
+        let config = Config::from_init_options(Some(json));
+        assert_eq!(config.registries.len(), 2);
+        let kellnr = &config.registries["kellnr"];
+        assert_eq!(kellnr.index, "https://kellnr.example.com/api/v1/cargo");
+        assert_eq!(kellnr.token.as_deref(), Some("secret"));
+        assert_eq!(config.registries["internal"].token, None);
+    }
+
+    #[test]
+    fn test_parse_cache_mode() {
+        let json = json!({
+            "cache": {
+                "mode": "offline"
+            }
+        });
+
+        let config = Config::from_init_options(Some(json));
+        assert_eq!(config.cache.mode, CacheMode::Offline);
+        assert_eq!(Config::default().cache.mode, CacheMode::RespectHeaders);
+    }
+
+    #[test]
+    fn test_parse_version_preference() {
+        let json = json!({
+            "inlay_hints": {
+                "version_preference": "lowest-compatible"
+            }
+        });
+
+        let config = Config::from_init_options(Some(json));
+        assert_eq!(
+            config.inlay_hints.version_preference,
+            VersionPreference::LowestCompatible
+        );
+        assert_eq!(
+            Config::default().inlay_hints.version_preference,
+            VersionPreference::Highest
+        );
+    }
+
+    #[test]
+    fn test_parse_http_config() {
+        let json = json!({
+            "http": {
+                "proxy": "http://proxy.example.com:8080",
+                "no_proxy": ["localhost", "*.internal.example.com"],
+                "extra_ca_certs": ["/etc/dependi/corp-ca.pem"],
+                "timeout_secs": 30,
+                "offline": true
+            }
+        });
+
+        let config = Config::from_init_options(Some(json));
+        assert_eq!(
+            config.http.proxy.as_deref(),
+            Some("http://proxy.example.com:8080")
+        );
+        assert_eq!(config.http.no_proxy.len(), 2);
+        assert_eq!(config.http.extra_ca_certs, vec!["/etc/dependi/corp-ca.pem"]);
+        assert_eq!(config.http.timeout_secs, 30);
+        assert!(config.http.offline);
+    }
+
+    #[test]
+    fn test_parse_cooldown_config() {
+        let json = json!({
+            "cooldown": {
+                "enabled": true,
+                "days": 30,
+                "strict": true
+            }
+        });
+
+        let config = Config::from_init_options(Some(json));
+        assert!(config.cooldown.enabled);
+        assert_eq!(config.cooldown.days, 30);
+        assert!(config.cooldown.strict);
+
+        let default = Config::default();
+        assert!(!default.cooldown.enabled);
+        assert_eq!(default.cooldown.days, DEFAULT_COOLDOWN_DAYS);
+        assert!(!default.cooldown.strict);
+    }
+
+    #[test]
+    fn test_parse_github_releases_config() {
+        let json = json!({
+            "github_releases": {
+                "github.com/example/tool": {
+                    "repo": "example/tool",
+                    "select_search": "^release-(\\d+\\.\\d+\\.\\d+)$",
+                    "select_replace": "$1",
+                    "semantic_only": true
+                }
+            }
+        });
+
+        let config = Config::from_init_options(Some(json));
+        assert_eq!(config.github_releases.len(), 1);
+        let source = &config.github_releases["github.com/example/tool"];
+        assert_eq!(source.repo, "example/tool");
+        assert_eq!(
+            source.select_search.as_deref(),
+            Some(r"^release-(\d+\.\d+\.\d+)$")
+        );
+        assert_eq!(source.select_replace.as_deref(), Some("$1"));
+        assert!(source.semantic_only);
+        assert!(Config::default().github_releases.is_empty());
+    }
+
     #[test]
     fn test_partial_config() {
         let json = json!({
@@ -178,4 +548,147 @@ mod tests {
         assert!(config.inlay_hints.show_up_to_date);
         assert!(config.diagnostics.enabled);
     }
+
+    #[test]
+    fn test_parse_security_config() {
+        let json = json!({
+            "security": {
+                "min_severity": "medium",
+                "max_severity": "high",
+                "ignore_advisories": ["CVE-2021-23337"]
+            }
+        });
+
+        let config = Config::from_init_options(Some(json));
+        assert_eq!(
+            config.security.min_severity_level(),
+            VulnerabilitySeverity::Medium
+        );
+        assert_eq!(
+            config.security.max_severity_level(),
+            VulnerabilitySeverity::High
+        );
+        assert_eq!(config.security.ignore_advisories, vec!["CVE-2021-23337"]);
+
+        let default = Config::default();
+        assert_eq!(
+            default.security.max_severity_level(),
+            VulnerabilitySeverity::Critical
+        );
+        assert!(default.security.ignore_advisories.is_empty());
+    }
+
+    fn vuln(id: &str, severity: VulnerabilitySeverity) -> Vulnerability {
+        Vulnerability {
+            id: id.to_string(),
+            severity,
+            description: String::new(),
+            url: None,
+            fixed_version: None,
+            ranges: vec![],
+            aliases: vec![],
+            related: vec![],
+        }
+    }
+
+    #[test]
+    fn test_should_report_respects_severity_band() {
+        let mut security = SecurityConfig {
+            min_severity: "medium".to_string(),
+            max_severity: Some("high".to_string()),
+            ..Default::default()
+        };
+
+        assert!(!security.should_report(&vuln("CVE-1", VulnerabilitySeverity::Low)));
+        assert!(security.should_report(&vuln("CVE-2", VulnerabilitySeverity::Medium)));
+        assert!(security.should_report(&vuln("CVE-3", VulnerabilitySeverity::High)));
+        assert!(!security.should_report(&vuln("CVE-4", VulnerabilitySeverity::Critical)));
+
+        security.ignore_advisories.push("CVE-2".to_string());
+        assert!(!security.should_report(&vuln("CVE-2", VulnerabilitySeverity::Medium)));
+    }
+
+    #[test]
+    fn test_downgrade_ignored_reports_instead_of_hiding_at_version_2() {
+        let v1 = SecurityConfig {
+            ignore_advisories: vec!["CVE-1".to_string()],
+            downgrade_ignored: true,
+            ..Default::default()
+        };
+        // `downgrade_ignored` is only honored at `version = 2`.
+        assert!(!v1.should_report(&vuln("CVE-1", VulnerabilitySeverity::High)));
+
+        let v2 = SecurityConfig {
+            ignore_advisories: vec!["CVE-1".to_string()],
+            version: 2,
+            downgrade_ignored: true,
+            ..Default::default()
+        };
+        assert!(v2.should_report(&vuln("CVE-1", VulnerabilitySeverity::High)));
+    }
+
+    #[test]
+    fn test_severity_level_override_requires_version_2() {
+        let mut security = SecurityConfig {
+            severity_levels: HashMap::from([("medium".to_string(), "error".to_string())]),
+            ..Default::default()
+        };
+        assert_eq!(
+            security.severity_level_override(VulnerabilitySeverity::Medium),
+            None
+        );
+
+        security.version = 2;
+        assert_eq!(
+            security.severity_level_override(VulnerabilitySeverity::Medium),
+            Some("error")
+        );
+        assert_eq!(
+            security.severity_level_override(VulnerabilitySeverity::High),
+            None
+        );
+    }
+
+    fn temp_workspace(name: &str) -> std::path::PathBuf {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let id = COUNTER.fetch_add(1, Ordering::SeqCst);
+        let dir = std::env::temp_dir().join(format!("dependi-config-test-{}-{}", name, id));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_from_dependi_toml_parses_advisory_policy() {
+        let dir = temp_workspace("dependi-toml");
+        std::fs::write(
+            dir.join("dependi.toml"),
+            r#"
+[security]
+version = 2
+min_severity = "medium"
+downgrade_ignored = true
+ignore_advisories = ["CVE-2021-23337"]
+
+[security.severity_levels]
+medium = "error"
+"#,
+        )
+        .unwrap();
+
+        let config = Config::from_dependi_toml(&dir).expect("dependi.toml should parse");
+        assert_eq!(config.security.version, 2);
+        assert_eq!(config.security.min_severity, "medium");
+        assert!(config.security.downgrade_ignored);
+        assert_eq!(
+            config.security.severity_levels.get("medium").map(String::as_str),
+            Some("error")
+        );
+    }
+
+    #[test]
+    fn test_from_dependi_toml_returns_none_when_file_is_absent() {
+        let dir = temp_workspace("dependi-toml-missing");
+        assert!(Config::from_dependi_toml(&dir).is_none());
+    }
 }
@@ -7,6 +7,17 @@ use tower_lsp::lsp_types::Url;
 
 use crate::vulnerabilities::Ecosystem;
 
+/// Whether a detected file is an ecosystem's manifest or its lockfile.
+///
+/// A manifest carries top-level declared ranges (what the project asked
+/// for); a lockfile carries the full pinned/transitive set actually
+/// resolved, which is where most CVE exposure lives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    Manifest,
+    Lockfile,
+}
+
 /// Supported dependency file types.
 ///
 /// Each variant corresponds to a specific package manager ecosystem
@@ -37,27 +48,54 @@ impl FileType {
     /// Returns `Some(FileType)` if the URI matches a known dependency file pattern,
     /// or `None` if the file type is not recognized.
     pub fn detect(uri: &Url) -> Option<Self> {
+        Self::detect_with_kind(uri).map(|(file_type, _)| file_type)
+    }
+
+    /// Detect the file type from a document URI, along with whether the
+    /// match is the ecosystem's manifest (top-level declared ranges) or its
+    /// lockfile (the full pinned/transitive dependency set). A lockfile
+    /// always maps to the same [`FileType`] as its manifest, since
+    /// [`Self::to_ecosystem`] and [`Self::cache_key`] only need to know the
+    /// ecosystem, not which of the two files was opened.
+    pub fn detect_with_kind(uri: &Url) -> Option<(Self, FileKind)> {
         let path = uri.path();
         let filename = path.rsplit('/').next().unwrap_or(path);
-        if path.ends_with("Cargo.toml") {
-            Some(FileType::Cargo)
+
+        if path.ends_with("Cargo.lock") {
+            Some((FileType::Cargo, FileKind::Lockfile))
+        } else if path.ends_with("package-lock.json") || path.ends_with("yarn.lock") || path.ends_with("pnpm-lock.yaml") {
+            Some((FileType::Npm, FileKind::Lockfile))
+        } else if path.ends_with("poetry.lock") {
+            Some((FileType::Python, FileKind::Lockfile))
+        } else if path.ends_with("Gemfile.lock") {
+            Some((FileType::Ruby, FileKind::Lockfile))
+        } else if path.ends_with("composer.lock") {
+            Some((FileType::Php, FileKind::Lockfile))
+        } else if path.ends_with("go.sum") {
+            Some((FileType::Go, FileKind::Lockfile))
+        } else if path.ends_with("pubspec.lock") {
+            Some((FileType::Dart, FileKind::Lockfile))
+        } else if path.ends_with("packages.lock.json") {
+            Some((FileType::Csharp, FileKind::Lockfile))
+        } else if path.ends_with("Cargo.toml") {
+            Some((FileType::Cargo, FileKind::Manifest))
         } else if path.ends_with("package.json") {
-            Some(FileType::Npm)
+            Some((FileType::Npm, FileKind::Manifest))
         } else if filename.ends_with(".txt")
             && (filename.contains("constraints") || filename.contains("requirements"))
             || path.ends_with("pyproject.toml")
         {
-            Some(FileType::Python)
+            Some((FileType::Python, FileKind::Manifest))
         } else if path.ends_with("go.mod") {
-            Some(FileType::Go)
+            Some((FileType::Go, FileKind::Manifest))
         } else if path.ends_with("composer.json") {
-            Some(FileType::Php)
+            Some((FileType::Php, FileKind::Manifest))
         } else if path.ends_with("pubspec.yaml") {
-            Some(FileType::Dart)
+            Some((FileType::Dart, FileKind::Manifest))
         } else if path.ends_with(".csproj") {
-            Some(FileType::Csharp)
+            Some((FileType::Csharp, FileKind::Manifest))
         } else if path.ends_with("Gemfile") {
-            Some(FileType::Ruby)
+            Some((FileType::Ruby, FileKind::Manifest))
         } else {
             None
         }
@@ -182,6 +220,50 @@ mod tests {
         assert_eq!(FileType::detect(&uri), Some(FileType::Ruby));
     }
 
+    #[test]
+    fn test_detect_with_kind_manifests() {
+        let uri = Url::parse("file:///project/Cargo.toml").unwrap();
+        assert_eq!(
+            FileType::detect_with_kind(&uri),
+            Some((FileType::Cargo, FileKind::Manifest))
+        );
+    }
+
+    #[test]
+    fn test_detect_with_kind_lockfiles() {
+        let cases = [
+            ("file:///project/Cargo.lock", FileType::Cargo),
+            ("file:///project/package-lock.json", FileType::Npm),
+            ("file:///project/yarn.lock", FileType::Npm),
+            ("file:///project/pnpm-lock.yaml", FileType::Npm),
+            ("file:///project/poetry.lock", FileType::Python),
+            ("file:///project/Gemfile.lock", FileType::Ruby),
+            ("file:///project/composer.lock", FileType::Php),
+            ("file:///project/go.sum", FileType::Go),
+            ("file:///project/pubspec.lock", FileType::Dart),
+            ("file:///project/packages.lock.json", FileType::Csharp),
+        ];
+
+        for (path, expected_file_type) in cases {
+            let uri = Url::parse(path).unwrap();
+            assert_eq!(
+                FileType::detect_with_kind(&uri),
+                Some((expected_file_type, FileKind::Lockfile)),
+                "expected {path} to detect as {expected_file_type:?} lockfile"
+            );
+        }
+    }
+
+    #[test]
+    fn test_detect_with_kind_lockfile_shares_ecosystem_with_manifest() {
+        let lockfile = Url::parse("file:///project/Cargo.lock").unwrap();
+        let manifest = Url::parse("file:///project/Cargo.toml").unwrap();
+        assert_eq!(
+            FileType::detect(&lockfile).map(FileType::to_ecosystem),
+            FileType::detect(&manifest).map(FileType::to_ecosystem)
+        );
+    }
+
     #[test]
     fn test_detect_unknown() {
         let uri = Url::parse("file:///project/unknown.txt").unwrap();
@@ -0,0 +1,239 @@
+//! Glob-based matching for `Config.ignore`
+//!
+//! `Config.ignore` holds glob patterns (e.g. `"test-*"`, `"internal-pkg"`)
+//! naming packages that should be skipped entirely - no version lookup, no
+//! inlay hint, no diagnostic. A pattern may also be qualified as
+//! `"<ecosystem>:<name>"` (e.g. `"npm:left-pad"`) to target a single
+//! ecosystem instead of matching same-named packages everywhere.
+
+/// One compiled glob pattern, supporting `*`, `?`, and `[...]` character
+/// classes (with `[!...]`/`[^...]` negation and `a-z` ranges).
+#[derive(Debug, Clone)]
+struct Glob {
+    parts: Vec<GlobPart>,
+}
+
+#[derive(Debug, Clone)]
+enum GlobPart {
+    Literal(char),
+    /// `*` - any run of characters, including none
+    AnySequence,
+    /// `?` - exactly one character
+    AnyChar,
+    /// `[...]` - one character from (or, if negated, outside) the ranges
+    Class { ranges: Vec<(char, char)>, negated: bool },
+}
+
+impl Glob {
+    fn compile(pattern: &str) -> Self {
+        let chars: Vec<char> = pattern.chars().collect();
+        let mut parts = Vec::with_capacity(chars.len());
+        let mut i = 0;
+
+        while i < chars.len() {
+            match chars[i] {
+                '*' => {
+                    parts.push(GlobPart::AnySequence);
+                    i += 1;
+                }
+                '?' => {
+                    parts.push(GlobPart::AnyChar);
+                    i += 1;
+                }
+                '[' => match Self::compile_class(&chars, i) {
+                    Some((part, next)) => {
+                        parts.push(part);
+                        i = next;
+                    }
+                    // Unterminated class - treat the '[' as a literal
+                    None => {
+                        parts.push(GlobPart::Literal('['));
+                        i += 1;
+                    }
+                },
+                c => {
+                    parts.push(GlobPart::Literal(c));
+                    i += 1;
+                }
+            }
+        }
+
+        Self { parts }
+    }
+
+    /// Parse a `[...]` class starting at `chars[start]` (the `[`), returning
+    /// the compiled part and the index just past the closing `]`.
+    fn compile_class(chars: &[char], start: usize) -> Option<(GlobPart, usize)> {
+        let mut i = start + 1;
+        let negated = matches!(chars.get(i), Some('!') | Some('^'));
+        if negated {
+            i += 1;
+        }
+        let body_start = i;
+        while i < chars.len() && chars[i] != ']' {
+            i += 1;
+        }
+        if i >= chars.len() {
+            return None;
+        }
+
+        let body = &chars[body_start..i];
+        let mut ranges = Vec::new();
+        let mut j = 0;
+        while j < body.len() {
+            if j + 2 < body.len() && body[j + 1] == '-' {
+                ranges.push((body[j], body[j + 2]));
+                j += 3;
+            } else {
+                ranges.push((body[j], body[j]));
+                j += 1;
+            }
+        }
+
+        Some((GlobPart::Class { ranges, negated }, i + 1))
+    }
+
+    fn matches(&self, input: &str) -> bool {
+        let input: Vec<char> = input.chars().collect();
+        Self::matches_parts(&self.parts, &input)
+    }
+
+    fn matches_parts(parts: &[GlobPart], input: &[char]) -> bool {
+        let Some((part, rest_parts)) = parts.split_first() else {
+            return input.is_empty();
+        };
+
+        match part {
+            GlobPart::AnySequence => (0..=input.len())
+                .any(|split| Self::matches_parts(rest_parts, &input[split..])),
+            GlobPart::AnyChar => {
+                !input.is_empty() && Self::matches_parts(rest_parts, &input[1..])
+            }
+            GlobPart::Class { ranges, negated } => match input.split_first() {
+                Some((c, rest_input)) => {
+                    let in_class = ranges.iter().any(|(lo, hi)| *lo <= *c && *c <= *hi);
+                    in_class != *negated && Self::matches_parts(rest_parts, rest_input)
+                }
+                None => false,
+            },
+            GlobPart::Literal(literal) => match input.split_first() {
+                Some((c, rest_input)) => {
+                    c == literal && Self::matches_parts(rest_parts, rest_input)
+                }
+                None => false,
+            },
+        }
+    }
+}
+
+/// Compiled form of `Config.ignore`, built once and reused for every
+/// dependency instead of recompiling the glob list per package.
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreMatcher {
+    globs: Vec<Glob>,
+}
+
+impl IgnoreMatcher {
+    /// Compile a list of glob patterns (as found in `Config.ignore`)
+    pub fn new(patterns: &[String]) -> Self {
+        Self {
+            globs: patterns.iter().map(|p| Glob::compile(p)).collect(),
+        }
+    }
+
+    /// Whether `name` matches any configured pattern on its own.
+    pub fn is_ignored(&self, name: &str) -> bool {
+        self.globs.iter().any(|glob| glob.matches(name))
+    }
+
+    /// Like `is_ignored`, but also matches patterns written as
+    /// `"<ecosystem>:<name>"` (e.g. `"npm:left-pad"` or `"npm:*"`), so a
+    /// pattern can target a single ecosystem without matching a
+    /// same-named package everywhere else.
+    pub fn is_ignored_in(&self, ecosystem: &str, name: &str) -> bool {
+        if self.is_ignored(name) {
+            return true;
+        }
+        let qualified = format!("{}:{}", ecosystem, name);
+        self.globs.iter().any(|glob| glob.matches(&qualified))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_match() {
+        let matcher = IgnoreMatcher::new(&["internal-pkg".to_string()]);
+        assert!(matcher.is_ignored("internal-pkg"));
+        assert!(!matcher.is_ignored("internal-pkg2"));
+    }
+
+    #[test]
+    fn test_star_wildcard() {
+        let matcher = IgnoreMatcher::new(&["test-*".to_string()]);
+        assert!(matcher.is_ignored("test-"));
+        assert!(matcher.is_ignored("test-utils"));
+        assert!(!matcher.is_ignored("my-test-utils"));
+    }
+
+    #[test]
+    fn test_question_mark_wildcard() {
+        let matcher = IgnoreMatcher::new(&["pkg-?".to_string()]);
+        assert!(matcher.is_ignored("pkg-a"));
+        assert!(!matcher.is_ignored("pkg-ab"));
+        assert!(!matcher.is_ignored("pkg-"));
+    }
+
+    #[test]
+    fn test_character_class() {
+        let matcher = IgnoreMatcher::new(&["pkg-[a-c]".to_string()]);
+        assert!(matcher.is_ignored("pkg-a"));
+        assert!(matcher.is_ignored("pkg-c"));
+        assert!(!matcher.is_ignored("pkg-d"));
+    }
+
+    #[test]
+    fn test_negated_character_class() {
+        let matcher = IgnoreMatcher::new(&["pkg-[!a-c]".to_string()]);
+        assert!(!matcher.is_ignored("pkg-a"));
+        assert!(matcher.is_ignored("pkg-z"));
+    }
+
+    #[test]
+    fn test_ecosystem_qualified_pattern() {
+        let matcher = IgnoreMatcher::new(&["npm:left-pad".to_string()]);
+        assert!(matcher.is_ignored_in("npm", "left-pad"));
+        assert!(!matcher.is_ignored_in("pypi", "left-pad"));
+        // The unqualified form alone shouldn't match a qualified pattern
+        assert!(!matcher.is_ignored("left-pad"));
+    }
+
+    #[test]
+    fn test_ecosystem_wildcard_pattern() {
+        let matcher = IgnoreMatcher::new(&["npm:*".to_string()]);
+        assert!(matcher.is_ignored_in("npm", "anything"));
+        assert!(!matcher.is_ignored_in("pypi", "anything"));
+    }
+
+    #[test]
+    fn test_unqualified_pattern_matches_any_ecosystem() {
+        let matcher = IgnoreMatcher::new(&["test-*".to_string()]);
+        assert!(matcher.is_ignored_in("npm", "test-utils"));
+        assert!(matcher.is_ignored_in("pypi", "test-utils"));
+    }
+
+    #[test]
+    fn test_empty_pattern_list_ignores_nothing() {
+        let matcher = IgnoreMatcher::new(&[]);
+        assert!(!matcher.is_ignored("anything"));
+    }
+
+    #[test]
+    fn test_unterminated_class_is_literal() {
+        let matcher = IgnoreMatcher::new(&["pkg-[abc".to_string()]);
+        assert!(matcher.is_ignored("pkg-[abc"));
+        assert!(!matcher.is_ignored("pkg-a"));
+    }
+}
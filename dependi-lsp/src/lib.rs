@@ -6,7 +6,10 @@
 pub mod backend;
 pub mod cache;
 pub mod config;
+pub mod ignore;
+pub mod lockfiles;
 pub mod parsers;
 pub mod providers;
 pub mod registries;
+pub mod reports;
 pub mod vulnerabilities;
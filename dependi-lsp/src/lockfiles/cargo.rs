@@ -0,0 +1,224 @@
+//! Parser for Cargo.lock files
+
+use super::{LockedPackage, LockfileParser};
+
+/// Parser for Rust Cargo.lock files
+#[derive(Debug, Default)]
+pub struct CargoLockParser;
+
+impl CargoLockParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Accumulator for the `[[package]]` table currently being parsed
+#[derive(Default)]
+struct PendingPackage {
+    name: Option<String>,
+    version: Option<String>,
+    checksum: Option<String>,
+    source: Option<String>,
+    dependencies: Vec<String>,
+    in_dependencies: bool,
+}
+
+impl LockfileParser for CargoLockParser {
+    fn parse(&self, content: &str) -> Vec<LockedPackage> {
+        let mut packages = Vec::new();
+        let mut current: Option<PendingPackage> = None;
+
+        for line in content.lines() {
+            let trimmed = line.trim();
+
+            if trimmed == "[[package]]" {
+                if let Some(package) = finalize(current.take()) {
+                    packages.push(package);
+                }
+                current = Some(PendingPackage::default());
+                continue;
+            }
+
+            let Some(pending) = current.as_mut() else {
+                continue;
+            };
+
+            if pending.in_dependencies {
+                if trimmed == "]" {
+                    pending.in_dependencies = false;
+                } else if let Some(dep) = parse_dependency_entry(trimmed) {
+                    pending.dependencies.push(dep);
+                }
+                continue;
+            }
+
+            if let Some(value) = parse_key(trimmed, "name") {
+                pending.name = Some(value);
+            } else if let Some(value) = parse_key(trimmed, "version") {
+                pending.version = Some(value);
+            } else if let Some(value) = parse_key(trimmed, "checksum") {
+                pending.checksum = Some(value);
+            } else if let Some(value) = parse_key(trimmed, "source") {
+                pending.source = Some(value);
+            } else if trimmed.starts_with("dependencies") {
+                // Either `dependencies = []` (no deps) or the opening line of
+                // a multi-line array; only the latter needs further lines.
+                pending.in_dependencies = !trimmed.trim_end().ends_with(']');
+            }
+        }
+
+        if let Some(package) = finalize(current.take()) {
+            packages.push(package);
+        }
+
+        packages
+    }
+}
+
+/// Extract the package name from a `dependencies` array entry, which is
+/// `"name"`, or `"name version"`/`"name version (source)"` when the name
+/// alone is ambiguous (multiple versions of the same crate resolved).
+fn parse_dependency_entry(line: &str) -> Option<String> {
+    let quoted = line.trim().trim_end_matches(',');
+    let inner = quoted.strip_prefix('"')?.strip_suffix('"')?;
+    Some(
+        inner
+            .split_whitespace()
+            .next()
+            .unwrap_or(inner)
+            .to_string(),
+    )
+}
+
+/// Extract a quoted value for `key = "value"`, trimmed of surrounding quotes
+fn parse_key(line: &str, key: &str) -> Option<String> {
+    let rest = line.strip_prefix(key)?.trim_start();
+    let rest = rest.strip_prefix('=')?.trim();
+    let rest = rest.strip_prefix('"')?;
+    let end = rest.find('"')?;
+    Some(rest[..end].to_string())
+}
+
+/// Build the resolved package, skipping git dependencies: a `source` of
+/// `git+...` isn't a published registry version, so neither OSV nor the
+/// registries can be meaningfully queried about it. Path dependencies and
+/// workspace members have no `source` line at all and are kept - they're
+/// still real edges in the dependency graph, just not externally
+/// queryable by name/version the way a registry crate is.
+fn finalize(current: Option<PendingPackage>) -> Option<LockedPackage> {
+    let pending = current?;
+    if pending.source.as_deref().is_some_and(|source| !source.starts_with("registry+")) {
+        return None;
+    }
+    Some(LockedPackage {
+        name: pending.name?,
+        version: pending.version?,
+        checksum: pending.checksum,
+        optional: false,
+        dependencies: pending.dependencies,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_packages() {
+        let content = r#"
+# This file is automatically @generated by Cargo.
+version = 4
+
+[[package]]
+name = "serde"
+version = "1.0.210"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "3f0e7b1b0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e"
+
+[[package]]
+name = "libc"
+version = "0.2.155"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+        let parser = CargoLockParser::new();
+        let packages = parser.parse(content);
+        assert_eq!(packages.len(), 2);
+
+        let serde = packages.iter().find(|p| p.name == "serde").unwrap();
+        assert_eq!(serde.version, "1.0.210");
+        assert!(serde.checksum.is_some());
+
+        let libc = packages.iter().find(|p| p.name == "libc").unwrap();
+        assert_eq!(libc.version, "0.2.155");
+        assert!(libc.checksum.is_none());
+    }
+
+    #[test]
+    fn test_parse_package_dependencies() {
+        let content = r#"
+[[package]]
+name = "my-app"
+version = "0.1.0"
+dependencies = [
+ "hyper",
+]
+
+[[package]]
+name = "hyper"
+version = "1.0.0"
+dependencies = [
+ "h2 0.4.0",
+]
+
+[[package]]
+name = "h2"
+version = "0.4.0"
+dependencies = []
+"#;
+        let parser = CargoLockParser::new();
+        let packages = parser.parse(content);
+
+        let my_app = packages.iter().find(|p| p.name == "my-app").unwrap();
+        assert_eq!(my_app.dependencies, vec!["hyper".to_string()]);
+
+        let hyper = packages.iter().find(|p| p.name == "hyper").unwrap();
+        assert_eq!(hyper.dependencies, vec!["h2".to_string()]);
+
+        let h2 = packages.iter().find(|p| p.name == "h2").unwrap();
+        assert!(h2.dependencies.is_empty());
+    }
+
+    #[test]
+    fn test_skips_git_dependency() {
+        let content = r#"
+[[package]]
+name = "serde"
+version = "1.0.210"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "my-fork"
+version = "0.1.0"
+source = "git+https://github.com/user/my-fork#abc123"
+"#;
+        let parser = CargoLockParser::new();
+        let packages = parser.parse(content);
+
+        assert_eq!(packages.len(), 1);
+        assert_eq!(packages[0].name, "serde");
+    }
+
+    #[test]
+    fn test_keeps_path_dependency_with_no_source() {
+        let content = r#"
+[[package]]
+name = "my-app"
+version = "0.1.0"
+"#;
+        let parser = CargoLockParser::new();
+        let packages = parser.parse(content);
+
+        assert_eq!(packages.len(), 1);
+        assert_eq!(packages[0].name, "my-app");
+    }
+}
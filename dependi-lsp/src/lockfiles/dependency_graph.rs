@@ -0,0 +1,173 @@
+//! Reverse-reachability over the resolved dependency graph recorded in a
+//! lockfile, used to explain *why* a transitive package is present (e.g.
+//! `my-app → hyper → h2 → vulnerable-crate`).
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use super::LockedPackage;
+
+/// Directed "depends on" graph built from a lockfile's resolved packages.
+///
+/// Nodes are package names (as recorded in the lockfile); an edge from `a`
+/// to `b` means `a` depends on `b`. Packages from formats that don't record
+/// per-package dependency lists (see [`LockedPackage::dependencies`])
+/// contribute no edges, so [`Self::shortest_path_to_root`] always falls back
+/// to treating the target itself as its own root for them.
+pub struct DependencyGraph {
+    edges: HashMap<String, Vec<String>>,
+}
+
+impl DependencyGraph {
+    /// Build the graph from a lockfile's packages, indexed by name.
+    pub fn from_locked(locked: &HashMap<String, LockedPackage>) -> Self {
+        let edges = locked
+            .values()
+            .map(|package| (package.name.clone(), package.dependencies.clone()))
+            .collect();
+        Self { edges }
+    }
+
+    /// Find the shortest chain from one of `roots` down to `target`,
+    /// inclusive of both ends, by running a breadth-first search over the
+    /// incoming edges of `target` (i.e. walking from dependency back to
+    /// depender).
+    ///
+    /// Returns `Some(vec![target])` when `target` is itself a root - the
+    /// direct-dependency case, where there's no intermediate chain to show.
+    /// Returns `None` if no root can reach `target` at all (e.g. the graph
+    /// has no edge data for this lockfile format).
+    pub fn shortest_path_to_root(&self, target: &str, roots: &HashSet<String>) -> Option<Vec<String>> {
+        if roots.contains(target) {
+            return Some(vec![target.to_string()]);
+        }
+
+        let dependers = self.dependers_of();
+
+        let mut visited = HashSet::new();
+        visited.insert(target.to_string());
+        let mut queue = VecDeque::new();
+        queue.push_back(target.to_string());
+        let mut parent: HashMap<String, String> = HashMap::new();
+
+        while let Some(node) = queue.pop_front() {
+            let Some(parents) = dependers.get(&node) else {
+                continue;
+            };
+            for depender in parents {
+                if visited.contains(depender) {
+                    continue;
+                }
+                visited.insert(depender.clone());
+                parent.insert(depender.clone(), node.clone());
+
+                if roots.contains(depender) {
+                    let mut path = vec![depender.clone()];
+                    let mut current = depender.clone();
+                    while let Some(next) = parent.get(&current) {
+                        path.push(next.clone());
+                        current = next.clone();
+                    }
+                    return Some(path);
+                }
+
+                queue.push_back(depender.clone());
+            }
+        }
+
+        None
+    }
+
+    /// Invert `edges` into "who depends on me" per node.
+    fn dependers_of(&self) -> HashMap<&str, Vec<&str>> {
+        let mut dependers: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (name, deps) in &self.edges {
+            for dep in deps {
+                dependers.entry(dep.as_str()).or_default().push(name.as_str());
+            }
+        }
+        dependers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locked(entries: &[(&str, &[&str])]) -> HashMap<String, LockedPackage> {
+        entries
+            .iter()
+            .map(|(name, deps)| {
+                (
+                    name.to_string(),
+                    LockedPackage {
+                        name: name.to_string(),
+                        version: "0.0.0".to_string(),
+                        checksum: None,
+                        optional: false,
+                        dependencies: deps.iter().map(|d| d.to_string()).collect(),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_direct_dependency_is_its_own_path() {
+        let locked = locked(&[("my-app", &["serde"]), ("serde", &[])]);
+        let graph = DependencyGraph::from_locked(&locked);
+        let roots = HashSet::from(["serde".to_string()]);
+
+        assert_eq!(
+            graph.shortest_path_to_root("serde", &roots),
+            Some(vec!["serde".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_transitive_chain_is_reconstructed_root_first() {
+        let locked = locked(&[
+            ("my-app", &["hyper"]),
+            ("hyper", &["h2"]),
+            ("h2", &["vulnerable-crate"]),
+            ("vulnerable-crate", &[]),
+        ]);
+        let graph = DependencyGraph::from_locked(&locked);
+        let roots = HashSet::from(["hyper".to_string()]);
+
+        assert_eq!(
+            graph.shortest_path_to_root("vulnerable-crate", &roots),
+            Some(vec![
+                "hyper".to_string(),
+                "h2".to_string(),
+                "vulnerable-crate".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_shortest_path_prefers_fewer_hops() {
+        let locked = locked(&[
+            ("my-app", &["a", "b"]),
+            ("a", &["target"]),
+            ("b", &["mid"]),
+            ("mid", &["target"]),
+            ("target", &[]),
+        ]);
+        let graph = DependencyGraph::from_locked(&locked);
+        let roots = HashSet::from(["a".to_string(), "b".to_string()]);
+
+        assert_eq!(
+            graph.shortest_path_to_root("target", &roots),
+            Some(vec!["a".to_string(), "target".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_no_path_when_roots_unreachable() {
+        let locked = locked(&[("other", &["target"]), ("target", &[])]);
+        let graph = DependencyGraph::from_locked(&locked);
+        let roots = HashSet::from(["unrelated".to_string()]);
+
+        assert_eq!(graph.shortest_path_to_root("target", &roots), None);
+    }
+}
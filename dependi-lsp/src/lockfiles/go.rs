@@ -0,0 +1,69 @@
+//! Parser for Go go.sum files
+
+use super::{LockedPackage, LockfileParser};
+
+/// Parser for Go go.sum checksum files
+#[derive(Debug, Default)]
+pub struct GoSumParser;
+
+impl GoSumParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl LockfileParser for GoSumParser {
+    fn parse(&self, content: &str) -> Vec<LockedPackage> {
+        let mut packages = Vec::new();
+
+        for line in content.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            // Each module has two lines: one for the module zip, one for its
+            // go.mod file (`module version/go.mod hash`). Only the former
+            // carries a checksum for the module's actual contents.
+            let mut parts = trimmed.split_whitespace();
+            let (Some(module), Some(version_field), Some(hash)) =
+                (parts.next(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+
+            if version_field.ends_with("/go.mod") {
+                continue;
+            }
+
+            packages.push(LockedPackage {
+                name: module.to_string(),
+                version: version_field.to_string(),
+                checksum: Some(hash.to_string()),
+                optional: false,
+                dependencies: Vec::new(),
+            });
+        }
+
+        packages
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_go_sum() {
+        let content = "\
+github.com/pkg/errors v0.9.1 h1:FEBLx1zS214owpjy7qsBeixbURkuhQAwrK5UwLGTwt4=
+github.com/pkg/errors v0.9.1/go.mod h1:bwawxfHBFNV+L2hUp1rHADufV3IMtnDRdf1r5NINEl0=
+";
+        let parser = GoSumParser::new();
+        let packages = parser.parse(content);
+        assert_eq!(packages.len(), 1);
+        assert_eq!(packages[0].name, "github.com/pkg/errors");
+        assert_eq!(packages[0].version, "v0.9.1");
+        assert!(packages[0].checksum.is_some());
+    }
+}
@@ -0,0 +1,59 @@
+//! Parsers for lockfiles (Cargo.lock, package-lock.json, etc.)
+//!
+//! Lockfiles record what was actually resolved/installed, which can differ
+//! from the range declared in a manifest. Unlike `parsers`, entries aren't
+//! tied to a line/column in an editable document - callers only need the
+//! resolved version and, where the format provides one, an integrity hash.
+
+use std::collections::HashMap;
+
+/// A single resolved package entry from a lockfile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockedPackage {
+    /// Package name, matching the manifest dependency name
+    pub name: String,
+    /// Exact version that was resolved
+    pub version: String,
+    /// Integrity hash/checksum, where the lockfile format provides one
+    pub checksum: Option<String>,
+    /// Whether the lockfile itself marks this resolution as optional (e.g.
+    /// Poetry's `optional = true`). `false` for formats with no such
+    /// concept - the manifest's own [`Dependency::optional`](crate::parsers::Dependency::optional)
+    /// is the source of truth there.
+    pub optional: bool,
+    /// Names of packages this resolution depends on, as recorded by the
+    /// lockfile itself - the edges [`dependency_graph::DependencyGraph`]
+    /// walks to explain why a transitive package is present. Empty for
+    /// formats that don't record per-package dependency lists.
+    pub dependencies: Vec<String>,
+}
+
+/// Trait for parsing lockfiles into their resolved packages
+pub trait LockfileParser: Send + Sync {
+    /// Parse the given lockfile content and extract resolved packages
+    fn parse(&self, content: &str) -> Vec<LockedPackage>;
+}
+
+/// Parse a lockfile and index its packages by name for dependency lookups.
+///
+/// If a lockfile lists the same package more than once (e.g. at different
+/// transitive versions), the first entry wins since that's consistently the
+/// top-level/direct resolution in every format handled here.
+pub fn index_by_name(
+    parser: &dyn LockfileParser,
+    content: &str,
+) -> HashMap<String, LockedPackage> {
+    let mut index = HashMap::new();
+    for package in parser.parse(content) {
+        index.entry(package.name.clone()).or_insert(package);
+    }
+    index
+}
+
+pub mod cargo;
+pub mod dependency_graph;
+pub mod go;
+pub mod npm;
+pub mod php;
+pub mod python;
+pub mod ruby;
@@ -0,0 +1,344 @@
+//! Parsers for npm lockfiles (package-lock.json, yarn.lock)
+
+use super::{LockedPackage, LockfileParser};
+
+/// Parser for npm `package-lock.json` files (lockfile versions 2 and 3,
+/// which key resolved packages by `node_modules/<name>` path under a single
+/// `"packages"` object)
+#[derive(Debug, Default)]
+pub struct PackageLockParser;
+
+impl PackageLockParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl LockfileParser for PackageLockParser {
+    fn parse(&self, content: &str) -> Vec<LockedPackage> {
+        let Some(packages_start) = content.find("\"packages\"") else {
+            return Vec::new();
+        };
+        let Some(body) = object_body_after(&content[packages_start..]) else {
+            return Vec::new();
+        };
+
+        let mut packages = Vec::new();
+        for (key, entry) in top_level_entries(body) {
+            // The root project itself is keyed by an empty string; skip it.
+            let Some(name) = key.rsplit("node_modules/").next().filter(|n| !n.is_empty()) else {
+                continue;
+            };
+            if name == key {
+                // Not a node_modules entry (e.g. a workspace package path).
+                continue;
+            }
+
+            let Some(version) = find_string_field(entry, "version") else {
+                continue;
+            };
+            let checksum = find_string_field(entry, "integrity");
+            let optional = find_bool_field(entry, "optional").unwrap_or(false);
+
+            packages.push(LockedPackage {
+                name: name.to_string(),
+                version,
+                checksum,
+                optional,
+                dependencies: Vec::new(),
+            });
+        }
+
+        packages
+    }
+}
+
+/// Parser for `yarn.lock` files
+#[derive(Debug, Default)]
+pub struct YarnLockParser;
+
+impl YarnLockParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl LockfileParser for YarnLockParser {
+    fn parse(&self, content: &str) -> Vec<LockedPackage> {
+        let mut packages = Vec::new();
+        let mut pending_name: Option<String> = None;
+        let mut version: Option<String> = None;
+        let mut checksum: Option<String> = None;
+
+        let flush = |name: &mut Option<String>,
+                     version: &mut Option<String>,
+                     checksum: &mut Option<String>,
+                     out: &mut Vec<LockedPackage>| {
+            if let (Some(name), Some(version)) = (name.take(), version.take()) {
+                out.push(LockedPackage {
+                    name,
+                    version,
+                    checksum: checksum.take(),
+                    optional: false,
+                    dependencies: Vec::new(),
+                });
+            } else {
+                checksum.take();
+            }
+        };
+
+        for line in content.lines() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            // A descriptor header is unindented and ends with ':', e.g.
+            // `lodash@^4.17.21, lodash@^4.17.4:`
+            if !line.starts_with(' ') && !line.starts_with('\t') && line.trim_end().ends_with(':') {
+                flush(&mut pending_name, &mut version, &mut checksum, &mut packages);
+                pending_name = first_descriptor_name(line.trim_end().trim_end_matches(':'));
+                continue;
+            }
+
+            let trimmed = line.trim();
+            if let Some(value) = trimmed.strip_prefix("version ") {
+                version = Some(unquote(value.trim()));
+            } else if let Some(value) = trimmed.strip_prefix("integrity ") {
+                checksum = Some(value.trim().to_string());
+            }
+        }
+        flush(&mut pending_name, &mut version, &mut checksum, &mut packages);
+
+        packages
+    }
+}
+
+/// From a yarn descriptor line's comma-separated entries, take the package
+/// name out of the first one (`name@range`, name may be scoped `@scope/name`).
+fn first_descriptor_name(descriptors: &str) -> Option<String> {
+    let first = descriptors.split(',').next()?.trim();
+    let first = unquote(first);
+    let at_pos = if let Some(rest) = first.strip_prefix('@') {
+        rest.find('@').map(|pos| pos + 1)
+    } else {
+        first.find('@')
+    }?;
+    Some(first[..at_pos].to_string())
+}
+
+fn unquote(s: &str) -> String {
+    let trimmed = s.trim();
+    if trimmed.len() >= 2 && trimmed.starts_with('"') && trimmed.ends_with('"') {
+        trimmed[1..trimmed.len() - 1].to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Given text starting at `"packages"`, return the body between the matching
+/// `{` and `}` that follow the key's colon.
+fn object_body_after(text: &str) -> Option<&str> {
+    let colon = text.find(':')?;
+    let after_colon = &text[colon + 1..];
+    let brace_start = after_colon.find('{')?;
+    let mut depth = 0i32;
+    for (i, ch) in after_colon[brace_start..].char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&after_colon[brace_start + 1..brace_start + i]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split a JSON object body into its top-level `"key": { ... }` entries.
+fn top_level_entries(body: &str) -> Vec<(&str, &str)> {
+    let mut entries = Vec::new();
+    let mut chars = body.char_indices().peekable();
+
+    while let Some((i, ch)) = chars.next() {
+        if ch != '"' {
+            continue;
+        }
+        let key_start = i + 1;
+        let Some(key_end) = body[key_start..].find('"').map(|p| key_start + p) else {
+            break;
+        };
+        let key = &body[key_start..key_end];
+
+        let Some(colon_rel) = body[key_end + 1..].find(':') else {
+            break;
+        };
+        let after_colon = &body[key_end + 1 + colon_rel + 1..];
+        let Some(brace_rel) = after_colon.find('{') else {
+            break;
+        };
+        let value_start = key_end + 1 + colon_rel + 1 + brace_rel;
+
+        let mut depth = 0i32;
+        let mut value_end = None;
+        for (j, vch) in body[value_start..].char_indices() {
+            match vch {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        value_end = Some(value_start + j + 1);
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        let Some(value_end) = value_end else { break };
+
+        entries.push((key, &body[value_start + 1..value_end - 1]));
+
+        // Resume scanning after this value.
+        while let Some(&(pos, _)) = chars.peek() {
+            if pos < value_end {
+                chars.next();
+            } else {
+                break;
+            }
+        }
+    }
+
+    entries
+}
+
+/// Find `"field": "value"` inside a flat JSON object body (does not descend
+/// into nested objects, which is fine since `version`/`integrity` are always
+/// top-level fields on a `packages` entry).
+fn find_string_field(body: &str, field: &str) -> Option<String> {
+    let needle = format!("\"{}\"", field);
+    let mut search_from = 0;
+    while let Some(rel) = body[search_from..].find(&needle) {
+        let pos = search_from + rel;
+        let after_key = &body[pos + needle.len()..];
+        let Some(colon) = after_key.find(':') else {
+            search_from = pos + needle.len();
+            continue;
+        };
+        let after_colon = after_key[colon + 1..].trim_start();
+        if let Some(rest) = after_colon.strip_prefix('"')
+            && let Some(end) = rest.find('"')
+        {
+            return Some(rest[..end].to_string());
+        }
+        search_from = pos + needle.len();
+    }
+    None
+}
+
+/// Find `"field": true`/`"field": false` inside a flat JSON object body, the
+/// boolean counterpart to [`find_string_field`].
+fn find_bool_field(body: &str, field: &str) -> Option<bool> {
+    let needle = format!("\"{}\"", field);
+    let mut search_from = 0;
+    while let Some(rel) = body[search_from..].find(&needle) {
+        let pos = search_from + rel;
+        let after_key = &body[pos + needle.len()..];
+        let Some(colon) = after_key.find(':') else {
+            search_from = pos + needle.len();
+            continue;
+        };
+        let after_colon = after_key[colon + 1..].trim_start();
+        if after_colon.starts_with("true") {
+            return Some(true);
+        }
+        if after_colon.starts_with("false") {
+            return Some(false);
+        }
+        search_from = pos + needle.len();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_package_lock() {
+        let content = r#"
+{
+  "name": "app",
+  "lockfileVersion": 3,
+  "packages": {
+    "": {
+      "name": "app",
+      "version": "1.0.0"
+    },
+    "node_modules/lodash": {
+      "version": "4.17.21",
+      "resolved": "https://registry.npmjs.org/lodash/-/lodash-4.17.21.tgz",
+      "integrity": "sha512-v2kDEe57lecTulaDIuNTPy3Ry4/GqqKGfl2zFC1YhYJKAfyH78bgGfaxBo2CZHBBdSIu7XZbjWjwTuf2iFJd0A=="
+    }
+  }
+}
+"#;
+        let parser = PackageLockParser::new();
+        let packages = parser.parse(content);
+        assert_eq!(packages.len(), 1);
+        assert_eq!(packages[0].name, "lodash");
+        assert_eq!(packages[0].version, "4.17.21");
+        assert!(packages[0].checksum.is_some());
+        assert!(!packages[0].optional);
+    }
+
+    #[test]
+    fn test_parse_package_lock_optional_dependency() {
+        let content = r#"
+{
+  "name": "app",
+  "lockfileVersion": 3,
+  "packages": {
+    "": {
+      "name": "app",
+      "version": "1.0.0"
+    },
+    "node_modules/fsevents": {
+      "version": "2.3.3",
+      "optional": true
+    }
+  }
+}
+"#;
+        let parser = PackageLockParser::new();
+        let packages = parser.parse(content);
+        let fsevents = packages.iter().find(|p| p.name == "fsevents").unwrap();
+        assert!(fsevents.optional);
+    }
+
+    #[test]
+    fn test_parse_yarn_lock() {
+        let content = "\
+lodash@^4.17.21, lodash@^4.17.4:
+  version \"4.17.21\"
+  resolved \"https://registry.yarnpkg.com/lodash/-/lodash-4.17.21.tgz#679591c564c3bffaae8454cf0b3df370c3d6911c\"
+  integrity sha512-v2kDEe57lecTulaDIuNTPy3Ry4/GqqKGfl2zFC1YhYJKAfyH78bgGfaxBo2CZHBBdSIu7XZbjWjwTuf2iFJd0A==
+
+\"@babel/core@^7.0.0\":
+  version \"7.24.0\"
+  resolved \"https://registry.yarnpkg.com/@babel/core/-/core-7.24.0.tgz\"
+";
+        let parser = YarnLockParser::new();
+        let packages = parser.parse(content);
+        assert_eq!(packages.len(), 2);
+
+        let lodash = packages.iter().find(|p| p.name == "lodash").unwrap();
+        assert_eq!(lodash.version, "4.17.21");
+        assert!(lodash.checksum.is_some());
+
+        let babel = packages.iter().find(|p| p.name == "@babel/core").unwrap();
+        assert_eq!(babel.version, "7.24.0");
+        assert!(babel.checksum.is_none());
+    }
+}
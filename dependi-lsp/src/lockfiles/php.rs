@@ -0,0 +1,164 @@
+//! Parser for Composer's composer.lock files
+
+use super::{LockedPackage, LockfileParser};
+
+/// Parser for PHP Composer `composer.lock` files
+#[derive(Debug, Default)]
+pub struct ComposerLockParser;
+
+impl ComposerLockParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl LockfileParser for ComposerLockParser {
+    fn parse(&self, content: &str) -> Vec<LockedPackage> {
+        let mut packages = Vec::new();
+        for section in ["\"packages\"", "\"packages-dev\""] {
+            let Some(start) = content.find(section) else {
+                continue;
+            };
+            let Some(array_body) = array_body_after(&content[start..]) else {
+                continue;
+            };
+            packages.extend(top_level_objects(array_body).into_iter().filter_map(parse_package));
+        }
+        packages
+    }
+}
+
+fn parse_package(object: &str) -> Option<LockedPackage> {
+    let name = find_string_field(object, "name")?;
+    let version = find_string_field(object, "version")?;
+
+    // `dist.shasum` is the package's integrity hash; Composer frequently
+    // ships it as an empty string when the source is used instead, which we
+    // treat the same as missing.
+    let checksum = find_string_field(object, "shasum").filter(|s| !s.is_empty());
+
+    Some(LockedPackage {
+        name,
+        version,
+        checksum,
+        optional: false,
+        dependencies: Vec::new(),
+    })
+}
+
+/// Given text starting at a `"packages"` key, return the body between the
+/// matching `[` and `]` of its array value.
+fn array_body_after(text: &str) -> Option<&str> {
+    let colon = text.find(':')?;
+    let after_colon = &text[colon + 1..];
+    let bracket_start = after_colon.find('[')?;
+    let mut depth = 0i32;
+    for (i, ch) in after_colon[bracket_start..].char_indices() {
+        match ch {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&after_colon[bracket_start + 1..bracket_start + i]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split a JSON array body into its top-level `{ ... }` objects.
+fn top_level_objects(body: &str) -> Vec<&str> {
+    let mut objects = Vec::new();
+    let mut depth = 0i32;
+    let mut start = None;
+
+    for (i, ch) in body.char_indices() {
+        match ch {
+            '{' => {
+                if depth == 0 {
+                    start = Some(i);
+                }
+                depth += 1;
+            }
+            '}' => {
+                depth -= 1;
+                if depth == 0
+                    && let Some(s) = start.take()
+                {
+                    objects.push(&body[s + 1..i]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    objects
+}
+
+/// Find `"field": "value"` in a flat JSON object, descending into one level
+/// of nesting (e.g. `dist.shasum`) since callers only look for scalar fields.
+fn find_string_field(body: &str, field: &str) -> Option<String> {
+    let needle = format!("\"{}\"", field);
+    let mut search_from = 0;
+    while let Some(rel) = body[search_from..].find(&needle) {
+        let pos = search_from + rel;
+        let after_key = &body[pos + needle.len()..];
+        let Some(colon) = after_key.find(':') else {
+            search_from = pos + needle.len();
+            continue;
+        };
+        let after_colon = after_key[colon + 1..].trim_start();
+        if let Some(rest) = after_colon.strip_prefix('"')
+            && let Some(end) = rest.find('"')
+        {
+            return Some(rest[..end].to_string());
+        }
+        search_from = pos + needle.len();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_composer_lock() {
+        let content = r#"
+{
+  "packages": [
+    {
+      "name": "monolog/monolog",
+      "version": "3.5.0",
+      "dist": {
+        "type": "zip",
+        "url": "https://api.github.com/repos/Seldaek/monolog/zipball/...",
+        "shasum": ""
+      }
+    },
+    {
+      "name": "psr/log",
+      "version": "3.0.0",
+      "dist": {
+        "type": "zip",
+        "shasum": "abc123"
+      }
+    }
+  ],
+  "packages-dev": []
+}
+"#;
+        let parser = ComposerLockParser::new();
+        let packages = parser.parse(content);
+        assert_eq!(packages.len(), 2);
+
+        let monolog = packages.iter().find(|p| p.name == "monolog/monolog").unwrap();
+        assert_eq!(monolog.version, "3.5.0");
+        assert!(monolog.checksum.is_none());
+
+        let psr = packages.iter().find(|p| p.name == "psr/log").unwrap();
+        assert_eq!(psr.checksum.as_deref(), Some("abc123"));
+    }
+}
@@ -0,0 +1,241 @@
+//! Parser for Poetry's poetry.lock files
+
+use super::{LockedPackage, LockfileParser};
+
+/// Parser for Python `poetry.lock` files
+#[derive(Debug, Default)]
+pub struct PoetryLockParser;
+
+impl PoetryLockParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl LockfileParser for PoetryLockParser {
+    fn parse(&self, content: &str) -> Vec<LockedPackage> {
+        let mut packages = Vec::new();
+        let mut name: Option<String> = None;
+        let mut version: Option<String> = None;
+        let mut checksum: Option<String> = None;
+        let mut optional = false;
+
+        for line in content.lines() {
+            let trimmed = line.trim();
+
+            if trimmed == "[[package]]" {
+                if let (Some(name), Some(version)) = (name.take(), version.take()) {
+                    packages.push(LockedPackage {
+                        name,
+                        version,
+                        checksum: checksum.take(),
+                        optional,
+                        dependencies: Vec::new(),
+                    });
+                }
+                checksum = None;
+                optional = false;
+                continue;
+            }
+
+            // Another table (e.g. `[package.dependencies]`, `[[package.files]]`)
+            // starts a nested section; the `hash` fields inside `[[package.files]]`
+            // entries are still the package's own integrity hashes, so keep
+            // scanning rather than treating this as the start of a new package.
+            if let Some(value) = parse_key(trimmed, "name") {
+                name = Some(value);
+            } else if let Some(value) = parse_key(trimmed, "version") {
+                version = Some(value);
+            } else if trimmed == "optional = true" {
+                optional = true;
+            } else if checksum.is_none() {
+                if let Some(value) = parse_key(trimmed, "hash") {
+                    checksum = Some(value);
+                }
+            }
+        }
+
+        if let (Some(name), Some(version)) = (name, version) {
+            packages.push(LockedPackage {
+                name,
+                version,
+                checksum,
+                optional,
+                dependencies: Vec::new(),
+            });
+        }
+
+        packages
+    }
+}
+
+/// Extract a quoted value for `key = "value"`, trimmed of surrounding quotes
+fn parse_key(line: &str, key: &str) -> Option<String> {
+    let rest = line.strip_prefix(key)?.trim_start();
+    let rest = rest.strip_prefix('=')?.trim();
+    let rest = rest.strip_prefix('"')?;
+    let end = rest.find('"')?;
+    Some(rest[..end].to_string())
+}
+
+/// Parser for `uv`'s `uv.lock` files
+///
+/// Structurally close enough to `poetry.lock`'s `[[package]]` tables (same
+/// `name`/`version` keys, same per-file `hash = "..."` checksums nested
+/// under `[[package.files]]`-equivalent tables) that the same line scan
+/// works; `uv` has no `optional = true` concept of its own, so that field
+/// is always `false` here - same as the manifest-driven ecosystems that
+/// don't mark it at the lockfile level.
+#[derive(Debug, Default)]
+pub struct UvLockParser;
+
+impl UvLockParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl LockfileParser for UvLockParser {
+    fn parse(&self, content: &str) -> Vec<LockedPackage> {
+        let mut packages = Vec::new();
+        let mut name: Option<String> = None;
+        let mut version: Option<String> = None;
+        let mut checksum: Option<String> = None;
+
+        for line in content.lines() {
+            let trimmed = line.trim();
+
+            if trimmed == "[[package]]" {
+                if let (Some(name), Some(version)) = (name.take(), version.take()) {
+                    packages.push(LockedPackage {
+                        name,
+                        version,
+                        checksum: checksum.take(),
+                        optional: false,
+                        dependencies: Vec::new(),
+                    });
+                }
+                checksum = None;
+                continue;
+            }
+
+            if let Some(value) = parse_key(trimmed, "name") {
+                name = Some(value);
+            } else if let Some(value) = parse_key(trimmed, "version") {
+                version = Some(value);
+            } else if checksum.is_none() {
+                if let Some(value) = parse_key(trimmed, "hash") {
+                    checksum = Some(value);
+                }
+            }
+        }
+
+        if let (Some(name), Some(version)) = (name, version) {
+            packages.push(LockedPackage {
+                name,
+                version,
+                checksum,
+                optional: false,
+                dependencies: Vec::new(),
+            });
+        }
+
+        packages
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_poetry_lock() {
+        let content = r#"
+[[package]]
+name = "requests"
+version = "2.31.0"
+description = "Python HTTP for Humans."
+
+[[package.files]]
+file = "requests-2.31.0-py3-none-any.whl"
+hash = "sha256:58cd2187c01e70e6e26505bca751777aa9f2ee0b7f4300988b709f44e013003"
+
+[[package]]
+name = "certifi"
+version = "2024.2.2"
+description = "Python package for providing Mozilla's CA Bundle."
+"#;
+        let parser = PoetryLockParser::new();
+        let packages = parser.parse(content);
+        assert_eq!(packages.len(), 2);
+
+        let requests = packages.iter().find(|p| p.name == "requests").unwrap();
+        assert_eq!(requests.version, "2.31.0");
+        assert!(requests.checksum.is_some());
+        assert!(!requests.optional);
+
+        let certifi = packages.iter().find(|p| p.name == "certifi").unwrap();
+        assert_eq!(certifi.version, "2024.2.2");
+        assert!(certifi.checksum.is_none());
+    }
+
+    #[test]
+    fn test_parse_poetry_lock_optional_package() {
+        let content = r#"
+[[package]]
+name = "colorama"
+version = "0.4.6"
+description = "Cross-platform colored terminal text."
+optional = true
+
+[[package]]
+name = "certifi"
+version = "2024.2.2"
+description = "Python package for providing Mozilla's CA Bundle."
+"#;
+        let parser = PoetryLockParser::new();
+        let packages = parser.parse(content);
+
+        let colorama = packages.iter().find(|p| p.name == "colorama").unwrap();
+        assert!(colorama.optional);
+
+        let certifi = packages.iter().find(|p| p.name == "certifi").unwrap();
+        assert!(!certifi.optional);
+    }
+
+    #[test]
+    fn test_parse_uv_lock() {
+        let content = r#"
+version = 1
+requires-python = ">=3.12"
+
+[[package]]
+name = "requests"
+version = "2.31.0"
+source = { registry = "https://pypi.org/simple" }
+
+[[package.metadata]]
+
+[[package.wheels]]
+url = "https://files.pythonhosted.org/packages/requests-2.31.0-py3-none-any.whl"
+hash = "sha256:58cd2187c01e70e6e26505bca751777aa9f2ee0b7f4300988b709f44e013003"
+
+[[package]]
+name = "certifi"
+version = "2024.2.2"
+source = { registry = "https://pypi.org/simple" }
+"#;
+        let parser = UvLockParser::new();
+        let packages = parser.parse(content);
+        assert_eq!(packages.len(), 2);
+
+        let requests = packages.iter().find(|p| p.name == "requests").unwrap();
+        assert_eq!(requests.version, "2.31.0");
+        assert!(requests.checksum.is_some());
+        assert!(!requests.optional);
+
+        let certifi = packages.iter().find(|p| p.name == "certifi").unwrap();
+        assert_eq!(certifi.version, "2024.2.2");
+        assert!(certifi.checksum.is_none());
+    }
+}
@@ -0,0 +1,147 @@
+//! Parser for Bundler's Gemfile.lock files
+
+use super::{LockedPackage, LockfileParser};
+
+/// Parser for Ruby Bundler `Gemfile.lock` files
+#[derive(Debug, Default)]
+pub struct GemfileLockParser;
+
+impl GemfileLockParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl LockfileParser for GemfileLockParser {
+    fn parse(&self, content: &str) -> Vec<LockedPackage> {
+        let mut packages = Vec::new();
+        let Some(specs_indent) = specs_body(content) else {
+            return packages;
+        };
+
+        for line in specs_indent {
+            // Gem entries sit one level under `specs:` (4 spaces); deeper
+            // indentation lists that gem's own dependency constraints, which
+            // don't carry a resolved version of their own and are skipped.
+            let indent = line.len() - line.trim_start().len();
+            if indent != 4 {
+                continue;
+            }
+
+            if let Some((name, version)) = parse_spec_line(line.trim()) {
+                packages.push(LockedPackage {
+                    name,
+                    version,
+                    checksum: None,
+                    optional: false,
+                    dependencies: Vec::new(),
+                });
+            }
+        }
+
+        packages
+    }
+}
+
+/// Return the lines of the `GEM` section's `specs:` block, if present.
+fn specs_body(content: &str) -> Option<std::str::Lines<'_>> {
+    let gem_start = if content.starts_with("GEM\n") {
+        0
+    } else {
+        content.find("\nGEM\n").map(|i| i + 1)?
+    };
+    let specs_rel = content[gem_start..].find("  specs:\n")?;
+    let body_start = gem_start + specs_rel + "  specs:\n".len();
+
+    // The section ends at the next blank line or top-level (unindented)
+    // heading, whichever comes first.
+    let body_end = content[body_start..]
+        .find("\n\n")
+        .map(|i| body_start + i)
+        .unwrap_or(content.len());
+
+    Some(content[body_start..body_end].lines())
+}
+
+/// Parse a `name (version)` spec line into its package name and version.
+fn parse_spec_line(line: &str) -> Option<(String, String)> {
+    let open = line.find('(')?;
+    let close = line.rfind(')')?;
+    if close <= open {
+        return None;
+    }
+
+    let name = line[..open].trim();
+    let version = line[open + 1..close].trim();
+    if name.is_empty() || version.is_empty() {
+        return None;
+    }
+
+    Some((name.to_string(), version.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gemfile_lock() {
+        let content = "\
+GEM
+  remote: https://rubygems.org/
+  specs:
+    actionpack (7.0.4.3)
+      actionview (= 7.0.4.3)
+      activesupport (= 7.0.4.3)
+    actionview (7.0.4.3)
+      activesupport (= 7.0.4.3)
+    activesupport (7.0.4.3)
+    rails (7.0.4.3)
+      actionpack (= 7.0.4.3)
+
+PLATFORMS
+  x86_64-linux
+
+DEPENDENCIES
+  rails (~> 7.0)
+
+BUNDLED WITH
+   2.4.10
+";
+        let parser = GemfileLockParser::new();
+        let packages = parser.parse(content);
+
+        assert_eq!(packages.len(), 4);
+        let rails = packages.iter().find(|p| p.name == "rails").unwrap();
+        assert_eq!(rails.version, "7.0.4.3");
+        assert!(rails.checksum.is_none());
+
+        let actionpack = packages.iter().find(|p| p.name == "actionpack").unwrap();
+        assert_eq!(actionpack.version, "7.0.4.3");
+    }
+
+    #[test]
+    fn test_ignores_nested_dependency_constraints() {
+        let content = "\
+GEM
+  remote: https://rubygems.org/
+  specs:
+    pg (1.5.4)
+
+PLATFORMS
+  x86_64-linux
+";
+        let parser = GemfileLockParser::new();
+        let packages = parser.parse(content);
+        assert_eq!(packages.len(), 1);
+        assert_eq!(packages[0].name, "pg");
+        assert_eq!(packages[0].version, "1.5.4");
+    }
+
+    #[test]
+    fn test_no_gem_section() {
+        let content = "PATH\n  remote: .\n  specs:\n";
+        let parser = GemfileLockParser::new();
+        assert_eq!(parser.parse(content).len(), 0);
+    }
+}
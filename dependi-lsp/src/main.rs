@@ -1,8 +1,13 @@
 mod backend;
 mod cache;
+mod config;
+mod ignore;
+mod lockfiles;
 mod parsers;
 mod providers;
 mod registries;
+mod reports;
+mod vulnerabilities;
 
 use tower_lsp::{LspService, Server};
 use tracing_subscriber::{EnvFilter, layer::SubscriberExt, util::SubscriberInitExt};
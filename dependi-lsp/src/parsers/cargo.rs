@@ -1,4 +1,14 @@
 //! Parser for Cargo.toml files
+//!
+//! Built on `toml_edit`'s span-preserving parse rather than a line-oriented
+//! scanner, so `version_start`/`version_end` stay correct even when a value
+//! sits inside a multi-line inline table or an explicit `[dependencies.foo]`
+//! sub-table, neither of which a single-line scan can represent.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use toml_edit::{DocumentMut, Item, Table, Value};
 
 use super::{Dependency, Parser};
 
@@ -10,322 +20,351 @@ impl CargoParser {
     pub fn new() -> Self {
         Self
     }
-}
-
-impl Parser for CargoParser {
-    fn parse(&self, content: &str) -> Vec<Dependency> {
-        let mut dependencies = Vec::new();
-        let mut current_section: Option<DependencySection> = None;
-        let mut in_table_dependency: Option<TableDependency> = None;
-
-        for (line_idx, line) in content.lines().enumerate() {
-            let line_num = line_idx as u32;
-            let trimmed = line.trim();
-
-            // Check for section headers
-            if let Some(section) = parse_section_header(trimmed) {
-                // If we were parsing a table dependency, finalize it
-                if let Some(table_dep) = in_table_dependency.take() {
-                    if let Some(dep) = table_dep.into_dependency() {
-                        dependencies.push(dep);
-                    }
-                }
 
-                // If this is a table dependency like [dependencies.reqwest],
-                // we need to track it separately and NOT set current_section
-                if let Some(name) = section.table_dependency {
-                    let dep_section = section
-                        .dependency_section
-                        .unwrap_or(DependencySection::Normal);
-                    in_table_dependency = Some(TableDependency {
-                        name,
-                        section: dep_section,
-                        version: None,
-                        version_line: 0,
-                        version_start: 0,
-                        version_end: 0,
-                        name_line: line_num,
-                        name_start: 0,
-                        name_end: 0,
-                        optional: false,
-                    });
-                    current_section = None; // Important: don't treat following lines as regular deps
-                } else {
-                    current_section = section.dependency_section;
-                    in_table_dependency = None;
-                }
-                continue;
-            }
+    /// Parse a `Cargo.toml`, resolving `workspace = true`/`pkg.workspace =
+    /// true` entries against a name→version table built from the workspace
+    /// root's `[workspace.dependencies]` via
+    /// [`Self::parse_workspace_dependencies`]. Pass an empty map for a
+    /// non-workspace member - behaves the same as [`Parser::parse`] then.
+    pub fn parse_with_workspace_versions(
+        &self,
+        content: &str,
+        workspace_versions: &HashMap<String, String>,
+    ) -> Vec<Dependency> {
+        let Ok(doc) = content.parse::<DocumentMut>() else {
+            return Vec::new();
+        };
+        let line_index = LineIndex::new(content);
 
-            // Skip if not in a dependencies section
-            let section = match current_section {
-                Some(s) => s,
-                None => {
-                    // Check if we're in a table dependency section
-                    if let Some(ref mut table_dep) = in_table_dependency {
-                        if let Some((key, value, value_start, value_end)) = parse_key_value(line) {
-                            match key {
-                                "version" => {
-                                    table_dep.version = Some(unquote(&value));
-                                    table_dep.version_line = line_num;
-                                    table_dep.version_start = value_start;
-                                    table_dep.version_end = value_end;
-                                }
-                                "optional" => {
-                                    table_dep.optional = value.trim() == "true";
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-                    continue;
+        let mut dependencies = Vec::new();
+        collect_sections(&doc, workspace_versions, &line_index, &mut dependencies);
+
+        // Platform-specific deps: [target.'cfg(...)'.dependencies] and its
+        // dev-/build- variants.
+        if let Some(target) = doc.get("target").and_then(Item::as_table) {
+            for (_cfg, target_item) in target.iter() {
+                if let Some(target_table) = target_item.as_table() {
+                    collect_sections(target_table, workspace_versions, &line_index, &mut dependencies);
                 }
-            };
-
-            // Skip empty lines and comments
-            if trimmed.is_empty() || trimmed.starts_with('#') {
-                continue;
-            }
-
-            // Parse dependency line
-            if let Some(dep) = parse_dependency_line(line, line_num, section) {
-                dependencies.push(dep);
-            }
-        }
-
-        // Finalize any remaining table dependency
-        if let Some(table_dep) = in_table_dependency {
-            if let Some(dep) = table_dep.into_dependency() {
-                dependencies.push(dep);
             }
         }
 
         dependencies
     }
 
-    fn file_patterns(&self) -> &[&str] {
-        &["Cargo.toml"]
-    }
-}
+    /// Parse the workspace root's `[workspace.dependencies]` (both the
+    /// section-table and `[workspace.dependencies.name]` per-entry forms)
+    /// into a name→version map, used to resolve member crates' `workspace =
+    /// true` entries.
+    pub fn parse_workspace_dependencies(content: &str) -> HashMap<String, String> {
+        let Ok(doc) = content.parse::<DocumentMut>() else {
+            return HashMap::new();
+        };
+        let line_index = LineIndex::new(content);
+
+        let Some(deps_table) = doc
+            .get("workspace")
+            .and_then(Item::as_table)
+            .and_then(|workspace| workspace.get("dependencies"))
+            .and_then(Item::as_table)
+        else {
+            return HashMap::new();
+        };
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum DependencySection {
-    Normal,
-    Dev,
-    Build,
-}
+        let mut dependencies = Vec::new();
+        collect_section_table(
+            deps_table,
+            false,
+            &HashMap::new(),
+            &line_index,
+            &mut dependencies,
+        );
 
-struct SectionHeader {
-    dependency_section: Option<DependencySection>,
-    table_dependency: Option<String>,
+        dependencies
+            .into_iter()
+            .map(|dep| (dep.name, dep.version))
+            .collect()
+    }
 }
 
-struct TableDependency {
-    name: String,
-    section: DependencySection,
-    version: Option<String>,
-    version_line: u32,
-    version_start: u32,
-    version_end: u32,
-    name_line: u32,
-    name_start: u32,
-    name_end: u32,
-    optional: bool,
-}
+impl Parser for CargoParser {
+    fn parse(&self, content: &str) -> Vec<Dependency> {
+        self.parse_with_workspace_versions(content, &HashMap::new())
+    }
 
-impl TableDependency {
-    fn into_dependency(self) -> Option<Dependency> {
-        let version = self.version?;
-        Some(Dependency {
-            name: self.name,
-            version,
-            line: self.version_line,
-            name_start: self.name_start,
-            name_end: self.name_end,
-            version_start: self.version_start,
-            version_end: self.version_end,
-            dev: self.section == DependencySection::Dev,
-            optional: self.optional,
-        })
+    fn file_patterns(&self) -> &[&str] {
+        &["Cargo.toml"]
     }
 }
 
-fn parse_section_header(line: &str) -> Option<SectionHeader> {
-    if !line.starts_with('[') || !line.ends_with(']') {
-        return None;
+/// The three dependency tables Cargo recognizes, alongside whether entries
+/// in them count as `dev` (only `dev-dependencies` does - `build-dependencies`
+/// is tracked separately from `dev` in [`Dependency`]).
+const SECTIONS: &[(&str, bool)] = &[
+    ("dependencies", false),
+    ("dev-dependencies", true),
+    ("build-dependencies", false),
+];
+
+/// Walk `table`'s `[dependencies]`/`[dev-dependencies]`/`[build-dependencies]`
+/// children, used both at the document root and inside each
+/// `[target.'cfg(...)']` table.
+fn collect_sections(
+    table: &Table,
+    workspace_versions: &HashMap<String, String>,
+    line_index: &LineIndex,
+    out: &mut Vec<Dependency>,
+) {
+    for (section_key, dev) in SECTIONS {
+        if let Some(section_table) = table.get(section_key).and_then(Item::as_table) {
+            collect_section_table(section_table, *dev, workspace_versions, line_index, out);
+        }
     }
+}
 
-    let inner = &line[1..line.len() - 1];
-
-    // Check for table dependency format: [dependencies.package-name]
-    if let Some(rest) = inner.strip_prefix("dependencies.") {
-        return Some(SectionHeader {
-            dependency_section: Some(DependencySection::Normal),
-            table_dependency: Some(rest.to_string()),
-        });
-    }
-    if let Some(rest) = inner.strip_prefix("dev-dependencies.") {
-        return Some(SectionHeader {
-            dependency_section: Some(DependencySection::Dev),
-            table_dependency: Some(rest.to_string()),
-        });
-    }
-    if let Some(rest) = inner.strip_prefix("build-dependencies.") {
-        return Some(SectionHeader {
-            dependency_section: Some(DependencySection::Build),
-            table_dependency: Some(rest.to_string()),
-        });
+fn collect_section_table(
+    table: &Table,
+    dev: bool,
+    workspace_versions: &HashMap<String, String>,
+    line_index: &LineIndex,
+    out: &mut Vec<Dependency>,
+) {
+    for (name, item) in table.iter() {
+        let key_span = table.key(name).and_then(|key| key.span());
+        if let Some(dep) =
+            dependency_from_item(name, item, dev, key_span, workspace_versions, line_index)
+        {
+            out.push(dep);
+        }
     }
-
-    // Regular section headers
-    let section = match inner {
-        "dependencies" => Some(DependencySection::Normal),
-        "dev-dependencies" => Some(DependencySection::Dev),
-        "build-dependencies" => Some(DependencySection::Build),
-        _ => None,
-    };
-
-    Some(SectionHeader {
-        dependency_section: section,
-        table_dependency: None,
-    })
 }
 
-fn parse_dependency_line(
-    line: &str,
-    line_num: u32,
-    section: DependencySection,
+/// Build a `Dependency` from one `[dependencies]`-style table entry, covering
+/// the three shapes Cargo.toml allows: a bare version string (`serde =
+/// "1.0"`), an inline table (`serde = { version = "1.0", ... }`, including
+/// dotted-key sugar `serde.workspace = true`, which `toml_edit` parses into
+/// the same shape as an explicit sub-table), and an explicit sub-table
+/// (`[dependencies.serde]`).
+fn dependency_from_item(
+    name: &str,
+    item: &Item,
+    dev: bool,
+    key_span: Option<Range<usize>>,
+    workspace_versions: &HashMap<String, String>,
+    line_index: &LineIndex,
 ) -> Option<Dependency> {
-    // Find the '=' sign
-    let eq_pos = line.find('=')?;
-
-    let name_part = &line[..eq_pos];
-    let value_part = &line[eq_pos + 1..];
-
-    let name = name_part.trim();
-    if name.is_empty() {
-        return None;
+    let (name_line, name_start, name_end) = name_position(key_span, line_index);
+
+    match item {
+        Item::Value(value @ Value::String(_)) => {
+            let (version, version_line, version_start, version_end) =
+                string_position(value, line_index)?;
+            Some(build_dependency(
+                name,
+                version,
+                name_line,
+                name_start,
+                name_end,
+                version_line,
+                version_start,
+                version_end,
+                dev,
+                false,
+                None,
+            ))
+        }
+        Item::Value(Value::InlineTable(table)) => {
+            if table.get("workspace").and_then(Value::as_bool) == Some(true) {
+                return resolve_workspace_dependency(
+                    name,
+                    name_line,
+                    name_start,
+                    name_end,
+                    dev,
+                    workspace_versions,
+                );
+            }
+            let (version, version_line, version_start, version_end) =
+                string_position(table.get("version")?, line_index)?;
+            let optional = table.get("optional").and_then(Value::as_bool).unwrap_or(false);
+            let registry = table
+                .get("registry")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            Some(build_dependency(
+                name,
+                version,
+                name_line,
+                name_start,
+                name_end,
+                version_line,
+                version_start,
+                version_end,
+                dev,
+                optional,
+                registry,
+            ))
+        }
+        Item::Table(sub_table) => {
+            if sub_table.get("workspace").and_then(Item::as_bool) == Some(true) {
+                return resolve_workspace_dependency(
+                    name,
+                    name_line,
+                    name_start,
+                    name_end,
+                    dev,
+                    workspace_versions,
+                );
+            }
+            let version_value = sub_table.get("version")?.as_value()?;
+            let (version, version_line, version_start, version_end) =
+                string_position(version_value, line_index)?;
+            let optional = sub_table
+                .get("optional")
+                .and_then(Item::as_bool)
+                .unwrap_or(false);
+            let registry = sub_table
+                .get("registry")
+                .and_then(Item::as_str)
+                .map(str::to_string);
+            Some(build_dependency(
+                name,
+                version,
+                name_line,
+                name_start,
+                name_end,
+                version_line,
+                version_start,
+                version_end,
+                dev,
+                optional,
+                registry,
+            ))
+        }
+        _ => None,
     }
+}
 
-    // Calculate name positions
-    let name_start = line.find(name)? as u32;
-    let name_end = name_start + name.len() as u32;
-
-    // Parse value - can be simple string or inline table
-    let value_trimmed = value_part.trim();
-
-    let (version, version_start, version_end, optional) = if value_trimmed.starts_with('{') {
-        // Inline table format: { version = "1.0", features = [...] }
-        parse_inline_table(line, eq_pos)?
-    } else if value_trimmed.starts_with('"') || value_trimmed.starts_with('\'') {
-        // Simple string format: "1.0.0"
-        let quote_char = value_trimmed.chars().next()?;
-        let inner_start = value_trimmed.find(quote_char)? + 1;
-        let inner_end = value_trimmed[inner_start..].find(quote_char)?;
-        let version = value_trimmed[inner_start..inner_start + inner_end].to_string();
-
-        // Calculate absolute positions
-        let abs_start = line.find(value_trimmed)? + inner_start;
-        let abs_end = abs_start + inner_end;
-
-        (version, abs_start as u32, abs_end as u32, false)
+/// Assemble the final `Dependency`. `name_start`/`name_end` are only kept
+/// when the name and version share a source line - table-dependency entries
+/// (`[dependencies.reqwest]`) and multi-line inline tables put the key on an
+/// earlier line than `version = "..."`, and `Dependency` has a single `line`
+/// (the version's, since that's what diagnostics and code actions operate
+/// on), so a name column on a different line can't be expressed and is left
+/// at 0 rather than pointing at the wrong text.
+fn build_dependency(
+    name: &str,
+    version: String,
+    name_line: u32,
+    name_start: u32,
+    name_end: u32,
+    version_line: u32,
+    version_start: u32,
+    version_end: u32,
+    dev: bool,
+    optional: bool,
+    registry: Option<String>,
+) -> Dependency {
+    let (name_start, name_end) = if name_line == version_line {
+        (name_start, name_end)
     } else {
-        // Might be a path or git dependency without version
-        return None;
+        (0, 0)
     };
 
-    Some(Dependency {
+    Dependency {
         name: name.to_string(),
         version,
-        line: line_num,
+        line: version_line,
         name_start,
         name_end,
         version_start,
         version_end,
-        dev: section == DependencySection::Dev,
+        dev,
         optional,
-    })
-}
-
-fn parse_inline_table(line: &str, eq_pos: usize) -> Option<(String, u32, u32, bool)> {
-    let value_part = &line[eq_pos + 1..];
-
-    // Find version in the inline table
-    // Look for: version = "x.y.z"
-    let version_key = "version";
-    let version_pos = value_part.find(version_key)?;
-
-    let after_version_key = &value_part[version_pos + version_key.len()..];
-    let eq_in_table = after_version_key.find('=')?;
-    let after_eq = &after_version_key[eq_in_table + 1..];
-
-    // Find the quoted version string
-    let trimmed = after_eq.trim_start();
-    let quote_char = trimmed.chars().next()?;
-    if quote_char != '"' && quote_char != '\'' {
-        return None;
+        registry,
+        source: None,
+        git_ref: None,
+        normalized_version: None,
+        marker: None,
     }
-
-    let quote_start = after_eq.find(quote_char)?;
-    let version_content_start = quote_start + 1;
-    let version_content_end = after_eq[version_content_start..].find(quote_char)?;
-    let version =
-        after_eq[version_content_start..version_content_start + version_content_end].to_string();
-
-    // Calculate absolute positions
-    let base_offset = eq_pos + 1 + version_pos + version_key.len() + eq_in_table + 1;
-    let abs_start = base_offset + version_content_start;
-    let abs_end = abs_start + version_content_end;
-
-    // Check for optional = true
-    let optional = value_part.contains("optional")
-        && value_part
-            .find("optional")
-            .and_then(|pos| {
-                let after = &value_part[pos..];
-                after.find("true")
-            })
-            .is_some();
-
-    Some((version, abs_start as u32, abs_end as u32, optional))
 }
 
-fn parse_key_value(line: &str) -> Option<(&str, String, u32, u32)> {
-    let eq_pos = line.find('=')?;
-    let key = line[..eq_pos].trim();
-    let value_part = &line[eq_pos + 1..];
-    let value_trimmed = value_part.trim();
-
-    // Handle quoted strings
-    if value_trimmed.starts_with('"') || value_trimmed.starts_with('\'') {
-        let quote_char = value_trimmed.chars().next()?;
-        let inner_start = 1;
-        let inner_end = value_trimmed[inner_start..].find(quote_char)?;
-        let value = value_trimmed[inner_start..inner_start + inner_end].to_string();
+/// Build a `Dependency` whose version is inherited from the workspace root's
+/// `[workspace.dependencies]` rather than written inline. There's no version
+/// text at this entry, so the span is zero-width just past the package name
+/// (mirrors how `parsers/csharp.rs` handles NuGet Central Package
+/// Management's version-less `PackageReference`).
+fn resolve_workspace_dependency(
+    name: &str,
+    name_line: u32,
+    name_start: u32,
+    name_end: u32,
+    dev: bool,
+    workspace_versions: &HashMap<String, String>,
+) -> Option<Dependency> {
+    let version = workspace_versions.get(name)?.clone();
+    Some(Dependency {
+        name: name.to_string(),
+        version,
+        line: name_line,
+        name_start,
+        name_end,
+        version_start: name_end,
+        version_end: name_end,
+        dev,
+        optional: false,
+        registry: None,
+        source: None,
+        git_ref: None,
+        normalized_version: None,
+        marker: None,
+    })
+}
 
-        let abs_start = line.find(value_trimmed)? + inner_start;
-        let abs_end = abs_start + inner_end;
+/// Maps the absolute byte offsets `toml_edit` spans are expressed in back to
+/// the (0-indexed line, column) pairs `Dependency` positions use.
+struct LineIndex {
+    line_starts: Vec<usize>,
+}
 
-        return Some((key, value, abs_start as u32, abs_end as u32));
+impl LineIndex {
+    fn new(content: &str) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(content.match_indices('\n').map(|(i, _)| i + 1));
+        Self { line_starts }
     }
 
-    // Handle unquoted values (like booleans)
-    let value = value_trimmed.to_string();
-    let abs_start = line.find(value_trimmed)? as u32;
-    let abs_end = abs_start + value.len() as u32;
+    /// Convert an absolute byte offset into (line, column), both 0-indexed.
+    fn position(&self, offset: usize) -> (u32, u32) {
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(exact) => exact,
+            Err(insert_at) => insert_at - 1,
+        };
+        (line as u32, (offset - self.line_starts[line]) as u32)
+    }
+}
 
-    Some((key, value, abs_start, abs_end))
+fn name_position(key_span: Option<Range<usize>>, line_index: &LineIndex) -> (u32, u32, u32) {
+    let Some(span) = key_span else {
+        return (0, 0, 0);
+    };
+    let (line, start_col) = line_index.position(span.start);
+    let (_, end_col) = line_index.position(span.end);
+    (line, start_col, end_col)
 }
 
-fn unquote(s: &str) -> String {
-    let trimmed = s.trim();
-    if (trimmed.starts_with('"') && trimmed.ends_with('"'))
-        || (trimmed.starts_with('\'') && trimmed.ends_with('\''))
-    {
-        trimmed[1..trimmed.len() - 1].to_string()
-    } else {
-        trimmed.to_string()
+/// The text, line and column span of a quoted string `Value`, with the
+/// surrounding quotes excluded from the column range so it highlights just
+/// the version text.
+fn string_position(value: &Value, line_index: &LineIndex) -> Option<(String, u32, u32, u32)> {
+    let text = value.as_str()?.to_string();
+    let span = value.span()?;
+    if span.len() < 2 {
+        return None;
     }
+    let (line, start_col) = line_index.position(span.start + 1);
+    let (_, end_col) = line_index.position(span.end - 1);
+    Some((text, line, start_col, end_col))
 }
 
 #[cfg(test)]
@@ -359,6 +398,25 @@ serde = { version = "1.0.0", features = ["derive"] }
         assert_eq!(deps[0].version, "1.0.0");
     }
 
+    #[test]
+    fn test_multiline_inline_table_dependency() {
+        let parser = CargoParser::new();
+        let content = r#"
+[dependencies]
+serde = {
+    version = "1.0.0",
+    features = ["derive"]
+}
+"#;
+        let deps = parser.parse(content);
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].name, "serde");
+        assert_eq!(deps[0].version, "1.0.0");
+        // The version lives on its own line inside the inline table - the
+        // reported line should follow it there, not the opening `serde = {`.
+        assert_eq!(deps[0].line, 3);
+    }
+
     #[test]
     fn test_dev_dependencies() {
         let parser = CargoParser::new();
@@ -427,4 +485,130 @@ optional-dep = { version = "1.0", optional = true }
         assert_eq!(deps.len(), 1);
         assert!(deps[0].optional);
     }
+
+    #[test]
+    fn test_inline_table_registry() {
+        let parser = CargoParser::new();
+        let content = r#"
+[dependencies]
+internal-crate = { version = "1.0", registry = "kellnr" }
+"#;
+        let deps = parser.parse(content);
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].registry.as_deref(), Some("kellnr"));
+    }
+
+    #[test]
+    fn test_table_dependency_registry() {
+        let parser = CargoParser::new();
+        let content = r#"
+[dependencies.internal-crate]
+version = "1.0"
+registry = "kellnr"
+"#;
+        let deps = parser.parse(content);
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].registry.as_deref(), Some("kellnr"));
+    }
+
+    #[test]
+    fn test_no_registry_by_default() {
+        let parser = CargoParser::new();
+        let content = r#"
+[dependencies]
+serde = "1.0"
+"#;
+        let deps = parser.parse(content);
+        assert_eq!(deps[0].registry, None);
+    }
+
+    #[test]
+    fn test_target_cfg_dependencies() {
+        let parser = CargoParser::new();
+        let content = r#"
+[dependencies]
+serde = "1.0"
+
+[target.'cfg(windows)'.dependencies]
+winapi = "0.3"
+"#;
+        let deps = parser.parse(content);
+        assert_eq!(deps.len(), 2);
+
+        let winapi = deps.iter().find(|d| d.name == "winapi").unwrap();
+        assert_eq!(winapi.version, "0.3");
+        assert!(!winapi.dev);
+    }
+
+    #[test]
+    fn test_parse_workspace_dependencies() {
+        let content = r#"
+[workspace]
+members = ["crates/*"]
+
+[workspace.dependencies]
+serde = "1.0.0"
+tokio = { version = "1.38", features = ["full"] }
+
+[workspace.dependencies.reqwest]
+version = "0.12"
+features = ["json"]
+"#;
+        let versions = CargoParser::parse_workspace_dependencies(content);
+
+        assert_eq!(versions.len(), 3);
+        assert_eq!(versions["serde"], "1.0.0");
+        assert_eq!(versions["tokio"], "1.38");
+        assert_eq!(versions["reqwest"], "0.12");
+    }
+
+    #[test]
+    fn test_inline_table_workspace_inheritance() {
+        let parser = CargoParser::new();
+        let content = r#"
+[dependencies]
+serde = { workspace = true, features = ["derive"] }
+"#;
+        let mut workspace_versions = HashMap::new();
+        workspace_versions.insert("serde".to_string(), "1.0.0".to_string());
+
+        let deps = parser.parse_with_workspace_versions(content, &workspace_versions);
+
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].name, "serde");
+        assert_eq!(deps[0].version, "1.0.0");
+        assert_eq!(deps[0].version_start, deps[0].name_end);
+        assert_eq!(deps[0].version_end, deps[0].name_end);
+    }
+
+    #[test]
+    fn test_dotted_key_workspace_inheritance() {
+        let parser = CargoParser::new();
+        let content = r#"
+[dependencies]
+serde.workspace = true
+"#;
+        let mut workspace_versions = HashMap::new();
+        workspace_versions.insert("serde".to_string(), "1.0.0".to_string());
+
+        let deps = parser.parse_with_workspace_versions(content, &workspace_versions);
+
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].name, "serde");
+        assert_eq!(deps[0].version, "1.0.0");
+        assert_eq!(deps[0].version_start, deps[0].name_end);
+        assert_eq!(deps[0].version_end, deps[0].name_end);
+    }
+
+    #[test]
+    fn test_workspace_inheritance_without_matching_entry_is_skipped() {
+        let parser = CargoParser::new();
+        let content = r#"
+[dependencies]
+serde = { workspace = true }
+"#;
+        let deps = parser.parse_with_workspace_versions(content, &HashMap::new());
+
+        assert!(deps.is_empty());
+    }
 }
@@ -1,8 +1,12 @@
-//! Parser for C# .csproj files (NuGet PackageReference format)
+//! Parser for C# dependency files: `.csproj` (NuGet PackageReference format)
+//! and the legacy `packages.config` format
+
+use std::collections::HashMap;
 
 use super::{Dependency, Parser};
+use crate::registries::version_scheme::{NuGetScheme, VersionScheme};
 
-/// Parser for C# .csproj files
+/// Parser for C# .csproj and packages.config dependency files
 #[derive(Debug, Default)]
 pub struct CsharpParser;
 
@@ -10,10 +14,17 @@ impl CsharpParser {
     pub fn new() -> Self {
         Self
     }
-}
 
-impl Parser for CsharpParser {
-    fn parse(&self, content: &str) -> Vec<Dependency> {
+    /// Parse a `.csproj`/`packages.config`, resolving version-less
+    /// `PackageReference` entries (NuGet Central Package Management) against
+    /// a `Directory.Packages.props` name→version map built by
+    /// [`Self::parse_central_package_versions`]. Pass an empty map when CPM
+    /// isn't in use - behaves the same as [`Parser::parse`] in that case.
+    pub fn parse_with_central_versions(
+        &self,
+        content: &str,
+        central_versions: &HashMap<String, String>,
+    ) -> Vec<Dependency> {
         let mut dependencies = Vec::new();
 
         for (line_idx, line) in content.lines().enumerate() {
@@ -23,8 +34,16 @@ impl Parser for CsharpParser {
             // Look for PackageReference elements
             // Format 1: <PackageReference Include="Package" Version="1.0.0" />
             // Format 2: <PackageReference Include="Package"><Version>1.0.0</Version></PackageReference>
+            // Format 3 (CPM): <PackageReference Include="Package" /> with the
+            // version resolved from Directory.Packages.props
             if trimmed.contains("<PackageReference") && trimmed.contains("Include=") {
-                if let Some(dep) = parse_package_reference(line, line_num) {
+                if let Some(dep) = parse_package_reference(line, line_num, central_versions) {
+                    dependencies.push(dep);
+                }
+            } else if trimmed.contains("<package ") && trimmed.contains("id=") {
+                // Legacy packages.config format:
+                // <package id="Package" version="1.0.0" targetFramework="net48" />
+                if let Some(dep) = parse_packages_config_entry(line, line_num) {
                     dependencies.push(dep);
                 }
             }
@@ -32,15 +51,65 @@ impl Parser for CsharpParser {
 
         dependencies
     }
+
+    /// Parse a `Directory.Packages.props` file into a name→version map, e.g.
+    /// `<PackageVersion Include="Newtonsoft.Json" Version="13.0.3" />`.
+    pub fn parse_central_package_versions(content: &str) -> HashMap<String, String> {
+        let mut versions = HashMap::new();
+
+        for line in content.lines() {
+            let trimmed = line.trim();
+            if !trimmed.contains("<PackageVersion") || !trimmed.contains("Include=") {
+                continue;
+            }
+
+            let Some(name) = extract_attr(line, "Include=\"") else {
+                continue;
+            };
+            let Some(version) = extract_attr(line, "Version=\"") else {
+                continue;
+            };
+
+            versions.insert(name, version);
+        }
+
+        versions
+    }
+}
+
+impl Parser for CsharpParser {
+    fn parse(&self, content: &str) -> Vec<Dependency> {
+        self.parse_with_central_versions(content, &HashMap::new())
+    }
+
+    fn version_scheme(&self) -> &'static dyn VersionScheme {
+        &NuGetScheme
+    }
+}
+
+/// Extract the value of an XML attribute given its `name="` prefix (e.g.
+/// `"Include=\""`).
+fn extract_attr(line: &str, attr_prefix: &str) -> Option<String> {
+    let start = line.find(attr_prefix)? + attr_prefix.len();
+    let content = &line[start..];
+    let end = content.find('"')?;
+    Some(content[..end].to_string())
 }
 
 /// Parse a PackageReference XML element
-fn parse_package_reference(line: &str, line_num: u32) -> Option<Dependency> {
+fn parse_package_reference(
+    line: &str,
+    line_num: u32,
+    central_versions: &HashMap<String, String>,
+) -> Option<Dependency> {
     // Extract Include attribute (package name)
-    let include_start = line.find("Include=\"")? + 9;
-    let include_content = &line[include_start..];
-    let include_end = include_content.find('"')?;
-    let name = &include_content[..include_end];
+    let name = extract_attr(line, "Include=\"")?;
+
+    // Calculate the name's position
+    let name_pattern = format!("\"{}\"", name);
+    let name_pos = line.find(&name_pattern)?;
+    let name_start = (name_pos + 1) as u32;
+    let name_end = name_start + name.len() as u32;
 
     // Try to find Version attribute on same line
     let version = if let Some(version_attr_start) = line.find("Version=\"") {
@@ -52,13 +121,64 @@ fn parse_package_reference(line: &str, line_num: u32) -> Option<Dependency> {
         let version_content = &line[version_elem_start + 9..];
         let version_end = version_content.find('<')?;
         version_content[..version_end].to_string()
+    } else if let Some(central_version) = central_versions.get(&name) {
+        // Version is centrally managed via Directory.Packages.props - there's
+        // no version text on this line, so point the span just past the
+        // package name (mirrors how a Ruby gem with no inline version is
+        // handled in parsers/ruby.rs).
+        return Some(Dependency {
+            name,
+            version: central_version.clone(),
+            line: line_num,
+            name_start,
+            name_end,
+            version_start: name_end,
+            version_end: name_end,
+            dev: false,
+            optional: false,
+            registry: None,
+            source: None,
+            git_ref: None,
+            normalized_version: None,
+            marker: None,
+        });
     } else {
-        // Version might be centrally managed (Directory.Packages.props)
-        // Skip for now
         return None;
     };
 
-    // Calculate positions
+    let version_start = line.find(&version)? as u32;
+    let version_end = version_start + version.len() as u32;
+
+    Some(Dependency {
+        name,
+        version,
+        line: line_num,
+        name_start,
+        name_end,
+        version_start,
+        version_end,
+        dev: false, // NuGet doesn't have explicit dev dependencies in .csproj
+        optional: false,
+        registry: None,
+        source: None,
+        git_ref: None,
+        normalized_version: None,
+        marker: None,
+    })
+}
+
+/// Parse a `<package id="..." version="..." />` element from packages.config
+fn parse_packages_config_entry(line: &str, line_num: u32) -> Option<Dependency> {
+    let id_start = line.find("id=\"")? + 4;
+    let id_content = &line[id_start..];
+    let id_end = id_content.find('"')?;
+    let name = &id_content[..id_end];
+
+    let version_attr_start = line.find("version=\"")? + 9;
+    let version_content = &line[version_attr_start..];
+    let version_end = version_content.find('"')?;
+    let version = version_content[..version_end].to_string();
+
     let name_pattern = format!("\"{}\"", name);
     let name_pos = line.find(&name_pattern)?;
     let name_start = (name_pos + 1) as u32;
@@ -75,8 +195,13 @@ fn parse_package_reference(line: &str, line_num: u32) -> Option<Dependency> {
         name_end,
         version_start,
         version_end,
-        dev: false, // NuGet doesn't have explicit dev dependencies in .csproj
+        dev: false, // packages.config doesn't distinguish dev dependencies
         optional: false,
+        registry: None,
+        source: None,
+        git_ref: None,
+        normalized_version: None,
+        marker: None,
     })
 }
 
@@ -161,6 +286,26 @@ mod tests {
         assert_eq!(deps[0].name, "Serilog");
     }
 
+    #[test]
+    fn test_parse_packages_config() {
+        let content = r#"<?xml version="1.0" encoding="utf-8"?>
+<packages>
+  <package id="Newtonsoft.Json" version="13.0.3" targetFramework="net48" />
+  <package id="Serilog" version="3.1.1" targetFramework="net48" />
+</packages>
+"#;
+        let parser = CsharpParser::new();
+        let deps = parser.parse(content);
+
+        assert_eq!(deps.len(), 2);
+
+        let newtonsoft = deps.iter().find(|d| d.name == "Newtonsoft.Json").unwrap();
+        assert_eq!(newtonsoft.version, "13.0.3");
+
+        let serilog = deps.iter().find(|d| d.name == "Serilog").unwrap();
+        assert_eq!(serilog.version, "3.1.1");
+    }
+
     #[test]
     fn test_multiple_item_groups() {
         let content = r#"
@@ -178,4 +323,58 @@ mod tests {
 
         assert_eq!(deps.len(), 2);
     }
+
+    #[test]
+    fn test_parse_central_package_versions() {
+        let content = r#"
+<Project>
+  <ItemGroup>
+    <PackageVersion Include="Newtonsoft.Json" Version="13.0.3" />
+    <PackageVersion Include="Serilog" Version="3.1.1" />
+  </ItemGroup>
+</Project>
+"#;
+        let versions = CsharpParser::parse_central_package_versions(content);
+
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions["Newtonsoft.Json"], "13.0.3");
+        assert_eq!(versions["Serilog"], "3.1.1");
+    }
+
+    #[test]
+    fn test_version_less_reference_resolved_from_central_versions() {
+        let content = r#"
+<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <PackageReference Include="Newtonsoft.Json" />
+  </ItemGroup>
+</Project>
+"#;
+        let mut central_versions = HashMap::new();
+        central_versions.insert("Newtonsoft.Json".to_string(), "13.0.3".to_string());
+
+        let parser = CsharpParser::new();
+        let deps = parser.parse_with_central_versions(content, &central_versions);
+
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].name, "Newtonsoft.Json");
+        assert_eq!(deps[0].version, "13.0.3");
+        assert_eq!(deps[0].version_start, deps[0].name_end);
+        assert_eq!(deps[0].version_end, deps[0].name_end);
+    }
+
+    #[test]
+    fn test_version_less_reference_without_central_entry_is_skipped() {
+        let content = r#"
+<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <PackageReference Include="Newtonsoft.Json" />
+  </ItemGroup>
+</Project>
+"#;
+        let parser = CsharpParser::new();
+        let deps = parser.parse_with_central_versions(content, &HashMap::new());
+
+        assert!(deps.is_empty());
+    }
 }
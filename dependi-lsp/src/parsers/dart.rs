@@ -150,6 +150,11 @@ fn parse_dart_dependency_line(line: &str, line_num: u32, dev: bool) -> Option<De
         version_end,
         dev,
         optional: false,
+        registry: None,
+        source: None,
+        git_ref: None,
+        normalized_version: None,
+        marker: None,
     })
 }
 
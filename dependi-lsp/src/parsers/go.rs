@@ -1,6 +1,9 @@
 //! Parser for Go module files (go.mod)
 
+use std::collections::HashMap;
+
 use super::{Dependency, Parser};
+use crate::registries::version_scheme::{GoScheme, VersionScheme};
 
 /// Parser for Go go.mod dependency files
 #[derive(Debug, Default)]
@@ -13,9 +16,23 @@ impl GoParser {
 }
 
 impl Parser for GoParser {
+    fn version_scheme(&self) -> &'static dyn VersionScheme {
+        &GoScheme
+    }
+
     fn parse(&self, content: &str) -> Vec<Dependency> {
         let mut dependencies = Vec::new();
         let mut in_require_block = false;
+        let mut in_replace_block = false;
+        let mut in_exclude_block = false;
+        let mut in_retract_block = false;
+
+        // `replace`/`exclude` target an already-required module by name, so
+        // they're collected here and applied to `dependencies` in a second
+        // pass rather than threaded through `parse_require_line` - a
+        // directive can appear before or after the `require` it modifies.
+        let mut replacements: HashMap<String, String> = HashMap::new();
+        let mut exclusions: HashMap<String, Vec<String>> = HashMap::new();
 
         for (line_idx, line) in content.lines().enumerate() {
             let line_num = line_idx as u32;
@@ -26,15 +43,30 @@ impl Parser for GoParser {
                 continue;
             }
 
-            // Check for require block start
+            // Check for block starts
             if trimmed == "require (" {
                 in_require_block = true;
                 continue;
             }
+            if trimmed == "replace (" {
+                in_replace_block = true;
+                continue;
+            }
+            if trimmed == "exclude (" {
+                in_exclude_block = true;
+                continue;
+            }
+            if trimmed == "retract (" {
+                in_retract_block = true;
+                continue;
+            }
 
             // Check for block end
             if trimmed == ")" {
                 in_require_block = false;
+                in_replace_block = false;
+                in_exclude_block = false;
+                in_retract_block = false;
                 continue;
             }
 
@@ -47,9 +79,53 @@ impl Parser for GoParser {
                 continue;
             }
 
-            // Parse lines inside require block
+            if trimmed.starts_with("replace ") && !trimmed.contains("(") {
+                if let Some((module, target)) = parse_replace_line(&trimmed[8..]) {
+                    replacements.insert(module, target);
+                }
+                continue;
+            }
+
+            if trimmed.starts_with("exclude ") && !trimmed.contains("(") {
+                if let Some((module, version)) = parse_exclude_line(&trimmed[8..]) {
+                    exclusions.entry(module).or_default().push(version);
+                }
+                continue;
+            }
+
+            if trimmed.starts_with("retract ") && !trimmed.contains("(") {
+                // `retract` marks versions of *this* module as withdrawn -
+                // there's no dependency it attaches to, so it's recognized
+                // (and kept from falling through to the generic cases below)
+                // purely so it doesn't get misparsed as anything else.
+                continue;
+            }
+
+            // Parse lines inside the relevant block
             if in_require_block && let Some(dep) = parse_require_line(line, trimmed, line_num) {
                 dependencies.push(dep);
+                continue;
+            }
+            if in_replace_block && let Some((module, target)) = parse_replace_line(trimmed) {
+                replacements.insert(module, target);
+                continue;
+            }
+            if in_exclude_block && let Some((module, version)) = parse_exclude_line(trimmed) {
+                exclusions.entry(module).or_default().push(version);
+                continue;
+            }
+            if in_retract_block {
+                continue;
+            }
+        }
+
+        for dep in &mut dependencies {
+            if let Some(target) = replacements.get(&dep.name) {
+                dep.source = Some(format!("replace: {}", target));
+            } else if let Some(versions) = exclusions.get(&dep.name)
+                && versions.iter().any(|v| v == &dep.version)
+            {
+                dep.source = Some(format!("excluded: {}", dep.version));
             }
         }
 
@@ -57,6 +133,47 @@ impl Parser for GoParser {
     }
 }
 
+/// Strip a trailing `// comment` from a directive's content.
+fn strip_comment(s: &str) -> &str {
+    match s.find("//") {
+        Some(pos) => s[..pos].trim(),
+        None => s.trim(),
+    }
+}
+
+/// Parse a `replace` directive's content (without the leading `replace `),
+/// either `old/module => new/module vX.Y.Z` (module swap, version required)
+/// or `old/module => ../local/path` (local path, no version) - the left side
+/// may additionally pin an old version (`old/module vX.Y.Z => ...`), which is
+/// ignored since only the replacement target matters for display. Returns
+/// `(old_module, target)` where `target` is either `"new/module vX.Y.Z"` or
+/// the bare path, suitable for the `"replace: {target}"` source label.
+fn parse_replace_line(content: &str) -> Option<(String, String)> {
+    let content = strip_comment(content);
+    let (left, right) = content.split_once("=>")?;
+
+    let old_module = left.trim().split_whitespace().next()?.to_string();
+    let target = right.trim();
+    if target.is_empty() {
+        return None;
+    }
+
+    Some((old_module, target.to_string()))
+}
+
+/// Parse an `exclude` directive's content (without the leading `exclude `):
+/// `module vX.Y.Z`. Returns `(module, version)`.
+fn parse_exclude_line(content: &str) -> Option<(String, String)> {
+    let content = strip_comment(content);
+    let mut parts = content.split_whitespace();
+    let module = parts.next()?.to_string();
+    let version = parts.next()?.to_string();
+    if !version.starts_with('v') {
+        return None;
+    }
+    Some((module, version))
+}
+
 /// Parse a require line (either standalone or inside a block)
 /// Format: module/path v1.2.3 [// indirect]
 fn parse_require_line(line: &str, content: &str, line_num: u32) -> Option<Dependency> {
@@ -105,6 +222,11 @@ fn parse_require_line(line: &str, content: &str, line_num: u32) -> Option<Depend
         version_end,
         dev: false,
         optional: is_indirect, // Mark indirect dependencies as optional
+        registry: None,
+        source: None,
+        git_ref: None,
+        normalized_version: None,
+        marker: None,
     })
 }
 
@@ -219,4 +341,106 @@ replace github.com/old/pkg => github.com/new/pkg v2.0.0
         assert_eq!(deps.len(), 1);
         assert_eq!(deps[0].name, "github.com/old/pkg");
     }
+
+    #[test]
+    fn test_replace_with_module_swap_marks_source() {
+        let parser = GoParser::new();
+        let content = r#"
+require github.com/old/pkg v1.0.0
+
+replace github.com/old/pkg => github.com/new/pkg v2.0.0
+"#;
+        let deps = parser.parse(content);
+        assert_eq!(deps.len(), 1);
+        assert_eq!(
+            deps[0].source.as_deref(),
+            Some("replace: github.com/new/pkg v2.0.0")
+        );
+    }
+
+    #[test]
+    fn test_replace_with_local_path_marks_source() {
+        let parser = GoParser::new();
+        let content = r#"
+require github.com/old/pkg v1.0.0
+
+replace github.com/old/pkg => ../local/pkg
+"#;
+        let deps = parser.parse(content);
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].source.as_deref(), Some("replace: ../local/pkg"));
+    }
+
+    #[test]
+    fn test_replace_block_form() {
+        let parser = GoParser::new();
+        let content = r#"
+require (
+    github.com/pkg/a v1.0.0
+    github.com/pkg/b v2.0.0
+)
+
+replace (
+    github.com/pkg/a => ../local/a
+    github.com/pkg/b v2.0.0 => github.com/pkg/b-fork v2.0.1
+)
+"#;
+        let deps = parser.parse(content);
+        let a = deps.iter().find(|d| d.name.contains("pkg/a")).unwrap();
+        assert_eq!(a.source.as_deref(), Some("replace: ../local/a"));
+
+        let b = deps.iter().find(|d| d.name.contains("pkg/b")).unwrap();
+        assert_eq!(
+            b.source.as_deref(),
+            Some("replace: github.com/pkg/b-fork v2.0.1")
+        );
+    }
+
+    #[test]
+    fn test_exclude_marks_matching_version_as_source() {
+        let parser = GoParser::new();
+        let content = r#"
+require github.com/pkg/a v1.0.0
+
+exclude github.com/pkg/a v1.0.0
+"#;
+        let deps = parser.parse(content);
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].source.as_deref(), Some("excluded: v1.0.0"));
+    }
+
+    #[test]
+    fn test_exclude_ignores_non_matching_version() {
+        let parser = GoParser::new();
+        let content = r#"
+require github.com/pkg/a v1.0.0
+
+exclude github.com/pkg/a v0.9.0
+"#;
+        let deps = parser.parse(content);
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].source, None);
+    }
+
+    #[test]
+    fn test_retract_block_does_not_affect_requires() {
+        let parser = GoParser::new();
+        let content = r#"
+module example.com/mymodule
+
+go 1.21
+
+require github.com/pkg/a v1.0.0
+
+retract (
+    v1.0.0
+    [v1.1.0, v1.2.0]
+)
+
+retract v0.9.0
+"#;
+        let deps = parser.parse(content);
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].name, "github.com/pkg/a");
+    }
 }
@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::registries::version_scheme::{SemverScheme, VersionScheme};
+
 /// Represents a dependency extracted from a manifest file
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Dependency {
@@ -25,12 +27,36 @@ pub struct Dependency {
     pub optional: bool,
     /// Custom registry name (Cargo only, e.g., "kellnr")
     pub registry: Option<String>,
+    /// Where this dependency is resolved from when it isn't a registry
+    /// (Ruby `git:`/`path:`/`github:`/`source:` gems), recorded as
+    /// `"key: value"`. `None` for registry-resolved dependencies.
+    pub source: Option<String>,
+    /// Git ref (`branch:`/`tag:`/`ref:`) pinning a `source`-resolved
+    /// dependency, where given.
+    pub git_ref: Option<String>,
+    /// `version` expanded into an explicit `>=X,<Y` range, for ecosystems
+    /// whose native operators (Poetry's `^`/`~`) aren't directly usable for
+    /// version math. `None` when `version` needs no such expansion; display
+    /// should still prefer `version` so the original spec is preserved.
+    pub normalized_version: Option<String>,
+    /// PEP 508 environment marker (the `; python_version >= "3.8"` suffix),
+    /// verbatim and unevaluated. `None` when the dependency has none, or for
+    /// ecosystems without a marker syntax. See [`pep508::evaluate_marker`].
+    pub marker: Option<String>,
 }
 
 /// Trait for parsing dependency files
 pub trait Parser: Send + Sync {
     /// Parse the given file content and extract dependencies
     fn parse(&self, content: &str) -> Vec<Dependency>;
+
+    /// The version ordering/requirement-matching rules for this ecosystem,
+    /// used by the registry/cache layer instead of assuming SemVer
+    /// everywhere. Defaults to [`SemverScheme`]; ecosystems with different
+    /// rules (e.g. NuGet's 4-component versions) override it.
+    fn version_scheme(&self) -> &'static dyn VersionScheme {
+        &SemverScheme
+    }
 }
 
 pub mod cargo;
@@ -38,6 +64,7 @@ pub mod csharp;
 pub mod dart;
 pub mod go;
 pub mod npm;
+pub mod pep508;
 pub mod php;
 pub mod python;
 pub mod ruby;
@@ -1,6 +1,7 @@
 //! Parser for package.json files
 
 use super::{Dependency, Parser};
+use crate::registries::version_scheme::{NpmScheme, VersionScheme};
 
 /// Parser for npm package.json dependency files
 #[derive(Debug, Default)]
@@ -13,6 +14,10 @@ impl NpmParser {
 }
 
 impl Parser for NpmParser {
+    fn version_scheme(&self) -> &'static dyn VersionScheme {
+        &NpmScheme
+    }
+
     fn parse(&self, content: &str) -> Vec<Dependency> {
         let mut dependencies = Vec::new();
 
@@ -151,9 +156,11 @@ fn parse_inline_dependencies(content: &str, line_num: u32, dep_type: DependencyT
 
         let version = &version_content[..version_end];
 
+        let (resolved_name, resolved_version, source) = resolve_npm_source(name, version);
+
         deps.push(Dependency {
-            name: name.to_string(),
-            version: version.to_string(),
+            name: resolved_name,
+            version: resolved_version,
             line: line_num,
             name_start: 0, // Approximate for inline
             name_end: 0,
@@ -161,6 +168,11 @@ fn parse_inline_dependencies(content: &str, line_num: u32, dep_type: DependencyT
             version_end: 0,
             dev: dep_type == DependencyType::Dev,
             optional: dep_type == DependencyType::Optional || dep_type == DependencyType::Peer,
+            registry: None,
+            source,
+            git_ref: None,
+            normalized_version: None,
+            marker: None,
         });
 
         remaining = &version_content[version_end + 1..];
@@ -216,9 +228,11 @@ fn parse_dependency_line(line: &str, line_num: u32, dep_type: DependencyType) ->
     let version_abs_start = (colon_pos + 1 + version_start_in_after) as u32;
     let version_abs_end = version_abs_start + version.len() as u32;
 
+    let (resolved_name, resolved_version, source) = resolve_npm_source(name, version);
+
     Some(Dependency {
-        name: name.to_string(),
-        version: version.to_string(),
+        name: resolved_name,
+        version: resolved_version,
         line: line_num,
         name_start,
         name_end: name_end_pos,
@@ -226,9 +240,50 @@ fn parse_dependency_line(line: &str, line_num: u32, dep_type: DependencyType) ->
         version_end: version_abs_end,
         dev: dep_type == DependencyType::Dev,
         optional: dep_type == DependencyType::Optional || dep_type == DependencyType::Peer,
+        registry: None,
+        source,
+        git_ref: None,
+        normalized_version: None,
+        marker: None,
     })
 }
 
+/// Classify a `package.json` dependency value, returning the (possibly
+/// remapped) `(name, version, source)` to store on the `Dependency`.
+///
+/// `github:`/`git+`/`git://` and `file:`/`workspace:` values don't resolve
+/// to a version on the npm registry, so `source` is populated (as a
+/// `"key: value"` string, mirroring the Ruby and Go parsers) and
+/// `backend.rs` skips registry/vulnerability lookups for them. An `npm:`
+/// alias (e.g. `"npm:real-pkg@^1.0.0"`) is resolved to the real package
+/// name and range instead, so the lookup still happens - just against the
+/// package the alias actually points to - and no `source` is recorded
+/// since it's still a plain registry dependency under the hood.
+fn resolve_npm_source(name: &str, value: &str) -> (String, String, Option<String>) {
+    if let Some(aliased) = value.strip_prefix("npm:")
+        && let Some(at_pos) = aliased.rfind('@')
+        && at_pos > 0
+    {
+        let real_name = &aliased[..at_pos];
+        let real_range = &aliased[at_pos + 1..];
+        if !real_range.is_empty() {
+            return (real_name.to_string(), real_range.to_string(), None);
+        }
+    }
+
+    let source = if value.starts_with("github:") || value.starts_with("git+") || value.starts_with("git://") {
+        Some(format!("git: {}", value))
+    } else if let Some(path) = value.strip_prefix("file:") {
+        Some(format!("file: {}", path))
+    } else if let Some(spec) = value.strip_prefix("workspace:") {
+        Some(format!("workspace: {}", spec))
+    } else {
+        None
+    };
+
+    (name.to_string(), value.to_string(), source)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -354,4 +409,98 @@ mod tests {
         assert_eq!(deps[0].name, "pkg");
         assert_eq!(deps[0].version, "1.0.0");
     }
+
+    #[test]
+    fn test_github_source_marks_git() {
+        let parser = NpmParser::new();
+        let content = r#"{
+  "dependencies": {
+    "my-fork": "github:user/repo#sha"
+  }
+}"#;
+        let deps = parser.parse(content);
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].name, "my-fork");
+        assert_eq!(
+            deps[0].source.as_deref(),
+            Some("git: github:user/repo#sha")
+        );
+    }
+
+    #[test]
+    fn test_git_protocol_source_marks_git() {
+        let parser = NpmParser::new();
+        let content = r#"{
+  "dependencies": {
+    "my-lib": "git+https://github.com/user/repo.git"
+  }
+}"#;
+        let deps = parser.parse(content);
+        assert_eq!(
+            deps[0].source.as_deref(),
+            Some("git: git+https://github.com/user/repo.git")
+        );
+    }
+
+    #[test]
+    fn test_file_source_marks_file() {
+        let parser = NpmParser::new();
+        let content = r#"{
+  "dependencies": {
+    "local-pkg": "file:../local"
+  }
+}"#;
+        let deps = parser.parse(content);
+        assert_eq!(deps[0].source.as_deref(), Some("file: ../local"));
+    }
+
+    #[test]
+    fn test_workspace_source_marks_workspace() {
+        let parser = NpmParser::new();
+        let content = r#"{
+  "dependencies": {
+    "sibling-pkg": "workspace:*"
+  }
+}"#;
+        let deps = parser.parse(content);
+        assert_eq!(deps[0].source.as_deref(), Some("workspace: *"));
+    }
+
+    #[test]
+    fn test_npm_alias_resolves_real_package() {
+        let parser = NpmParser::new();
+        let content = r#"{
+  "dependencies": {
+    "my-alias": "npm:real-pkg@^1.0.0"
+  }
+}"#;
+        let deps = parser.parse(content);
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].name, "real-pkg");
+        assert_eq!(deps[0].version, "^1.0.0");
+        assert!(deps[0].source.is_none());
+    }
+
+    #[test]
+    fn test_registry_versions_unaffected() {
+        let parser = NpmParser::new();
+        let content = r#"{
+  "dependencies": {
+    "react": "^18.2.0"
+  }
+}"#;
+        let deps = parser.parse(content);
+        assert_eq!(deps[0].name, "react");
+        assert_eq!(deps[0].version, "^18.2.0");
+        assert!(deps[0].source.is_none());
+    }
+
+    #[test]
+    fn test_inline_format_with_source() {
+        let parser = NpmParser::new();
+        let content = r#"{"dependencies": {"local-pkg": "file:../local"}}"#;
+        let deps = parser.parse(content);
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].source.as_deref(), Some("file: ../local"));
+    }
 }
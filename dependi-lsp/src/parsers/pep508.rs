@@ -0,0 +1,396 @@
+//! Evaluator for PEP 508 environment markers (the `; python_version >= "3.8"`
+//! suffix on a requirement). [`parsers::python::parse_requirement_line`] and
+//! [`parsers::python::parse_pep508_dependency`](super::python) capture the
+//! marker text verbatim onto [`Dependency::marker`](super::Dependency);
+//! this module parses and evaluates it against a concrete interpreter and
+//! platform so the LSP can tell a dependency is inactive there rather than
+//! just keeping the raw marker string around.
+
+use std::cmp::Ordering;
+
+use crate::registries::pep440::Version as Pep440Version;
+
+/// The interpreter/platform facts a PEP 508 marker can compare against.
+#[derive(Debug, Clone, Default)]
+pub struct Environment {
+    pub python_version: String,
+    pub python_full_version: String,
+    pub os_name: String,
+    pub sys_platform: String,
+    pub platform_machine: String,
+    pub implementation_name: String,
+    /// The `extra` name being evaluated for an optional-dependency group
+    /// (e.g. `"dev"`). `None` when evaluating a non-extra dependency, in
+    /// which case an `extra == "..."` clause is never satisfied.
+    pub extra: Option<String>,
+}
+
+impl Environment {
+    fn lookup(&self, name: &str) -> Option<String> {
+        Some(match name {
+            "python_version" => self.python_version.clone(),
+            "python_full_version" => self.python_full_version.clone(),
+            "os_name" => self.os_name.clone(),
+            "sys_platform" => self.sys_platform.clone(),
+            "platform_machine" => self.platform_machine.clone(),
+            "implementation_name" => self.implementation_name.clone(),
+            "extra" => self.extra.clone()?,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Term {
+    Var(String),
+    Str(String),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum CompareOp {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    In,
+    NotIn,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Compare(Term, CompareOp, Term),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Op(String),
+    LParen,
+    RParen,
+}
+
+fn tokenize(input: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        match c {
+            '(' => {
+                tokens.push(Token::LParen);
+                chars.next();
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                chars.next();
+            }
+            '\'' | '"' => {
+                let quote = c;
+                chars.next();
+                let mut s = String::new();
+                let mut closed = false;
+                for ch in chars.by_ref() {
+                    if ch == quote {
+                        closed = true;
+                        break;
+                    }
+                    s.push(ch);
+                }
+                if !closed {
+                    return None;
+                }
+                tokens.push(Token::Str(s));
+            }
+            '=' | '!' | '<' | '>' => {
+                let mut op = String::new();
+                op.push(c);
+                chars.next();
+                if let Some('=') = chars.peek() {
+                    op.push('=');
+                    chars.next();
+                }
+                tokens.push(Token::Op(op));
+            }
+            _ if c.is_alphanumeric() || c == '_' || c == '.' => {
+                let mut word = String::new();
+                while let Some(&ch) = chars.peek() {
+                    if ch.is_alphanumeric() || ch == '_' || ch == '.' {
+                        word.push(ch);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Ident(word));
+            }
+            // Unrecognized character (e.g. a marker we don't model) - bail
+            // out rather than guess at a meaning.
+            _ => return None,
+        }
+    }
+
+    Some(tokens)
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(tokens: &'a [Token]) -> Self {
+        Self { tokens, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.pos);
+        self.pos += 1;
+        token
+    }
+
+    fn eat_ident(&mut self, word: &str) -> bool {
+        match self.peek() {
+            Some(Token::Ident(w)) if w.eq_ignore_ascii_case(word) => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn parse_expr(&mut self) -> Option<Expr> {
+        let mut left = self.parse_and()?;
+        while self.eat_ident("or") {
+            let right = self.parse_and()?;
+            left = Expr::Or(Box::new(left), Box::new(right));
+        }
+        Some(left)
+    }
+
+    fn parse_and(&mut self) -> Option<Expr> {
+        let mut left = self.parse_not()?;
+        while self.eat_ident("and") {
+            let right = self.parse_not()?;
+            left = Expr::And(Box::new(left), Box::new(right));
+        }
+        Some(left)
+    }
+
+    fn parse_not(&mut self) -> Option<Expr> {
+        if self.eat_ident("not") {
+            let inner = self.parse_not()?;
+            return Some(Expr::Not(Box::new(inner)));
+        }
+        self.parse_atom()
+    }
+
+    fn parse_atom(&mut self) -> Option<Expr> {
+        if matches!(self.peek(), Some(Token::LParen)) {
+            self.pos += 1;
+            let inner = self.parse_expr()?;
+            if !matches!(self.peek(), Some(Token::RParen)) {
+                return None;
+            }
+            self.pos += 1;
+            return Some(inner);
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_term(&mut self) -> Option<Term> {
+        match self.advance()? {
+            Token::Str(s) => Some(Term::Str(s.clone())),
+            Token::Ident(name) => Some(Term::Var(name.clone())),
+            _ => None,
+        }
+    }
+
+    fn parse_comparison(&mut self) -> Option<Expr> {
+        let left = self.parse_term()?;
+
+        let op = if self.eat_ident("not") {
+            if !self.eat_ident("in") {
+                return None;
+            }
+            CompareOp::NotIn
+        } else if self.eat_ident("in") {
+            CompareOp::In
+        } else {
+            match self.advance()? {
+                Token::Op(op) => match op.as_str() {
+                    "==" => CompareOp::Eq,
+                    "!=" => CompareOp::NotEq,
+                    "<=" => CompareOp::LtEq,
+                    ">=" => CompareOp::GtEq,
+                    "<" => CompareOp::Lt,
+                    ">" => CompareOp::Gt,
+                    _ => return None,
+                },
+                _ => return None,
+            }
+        };
+
+        let right = self.parse_term()?;
+        Some(Expr::Compare(left, op, right))
+    }
+}
+
+fn resolve(term: &Term, env: &Environment) -> String {
+    match term {
+        Term::Str(s) => s.clone(),
+        Term::Var(name) => env.lookup(name).unwrap_or_default(),
+    }
+}
+
+fn compare_ordering(op: CompareOp, ord: Ordering) -> bool {
+    match op {
+        CompareOp::Lt => ord == Ordering::Less,
+        CompareOp::LtEq => ord != Ordering::Greater,
+        CompareOp::Gt => ord == Ordering::Greater,
+        CompareOp::GtEq => ord != Ordering::Less,
+        CompareOp::Eq | CompareOp::NotEq | CompareOp::In | CompareOp::NotIn => unreachable!(),
+    }
+}
+
+fn eval_compare(left: &Term, op: CompareOp, right: &Term, env: &Environment) -> bool {
+    let lhs = resolve(left, env);
+    let rhs = resolve(right, env);
+
+    match op {
+        CompareOp::Eq => lhs == rhs,
+        CompareOp::NotEq => lhs != rhs,
+        CompareOp::In => rhs.contains(&lhs),
+        CompareOp::NotIn => !rhs.contains(&lhs),
+        CompareOp::Lt | CompareOp::LtEq | CompareOp::Gt | CompareOp::GtEq => {
+            // Prefer PEP 440 version ordering (so "3.9" > "3.8" rather than
+            // the string comparison PEP 508 falls back to); fall back to a
+            // plain string comparison for non-version variables like
+            // `platform_machine`.
+            match (Pep440Version::parse(&lhs), Pep440Version::parse(&rhs)) {
+                (Ok(l), Ok(r)) => compare_ordering(op, l.cmp(&r)),
+                _ => compare_ordering(op, lhs.cmp(&rhs)),
+            }
+        }
+    }
+}
+
+fn eval(expr: &Expr, env: &Environment) -> bool {
+    match expr {
+        Expr::And(l, r) => eval(l, env) && eval(r, env),
+        Expr::Or(l, r) => eval(l, env) || eval(r, env),
+        Expr::Not(e) => !eval(e, env),
+        Expr::Compare(l, op, r) => eval_compare(l, *op, r, env),
+    }
+}
+
+/// Parses and evaluates a PEP 508 marker expression against `env`. Returns
+/// `None` if the marker doesn't parse, so callers can fail open (assume the
+/// dependency is active) rather than hide it based on a marker we
+/// misunderstood.
+pub fn evaluate_marker(marker: &str, env: &Environment) -> Option<bool> {
+    let tokens = tokenize(marker)?;
+    let mut parser = Parser::new(&tokens);
+    let expr = parser.parse_expr()?;
+    if parser.pos != tokens.len() {
+        return None;
+    }
+    Some(eval(&expr, env))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env() -> Environment {
+        Environment {
+            python_version: "3.10".to_string(),
+            python_full_version: "3.10.4".to_string(),
+            os_name: "posix".to_string(),
+            sys_platform: "linux".to_string(),
+            platform_machine: "x86_64".to_string(),
+            implementation_name: "cpython".to_string(),
+            extra: None,
+        }
+    }
+
+    #[test]
+    fn test_simple_version_comparison() {
+        assert_eq!(evaluate_marker(r#"python_version >= "3.8""#, &env()), Some(true));
+        assert_eq!(evaluate_marker(r#"python_version < "3.8""#, &env()), Some(false));
+    }
+
+    #[test]
+    fn test_equality_and_platform_checks() {
+        assert_eq!(evaluate_marker(r#"sys_platform == "linux""#, &env()), Some(true));
+        assert_eq!(evaluate_marker(r#"sys_platform == "win32""#, &env()), Some(false));
+        assert_eq!(evaluate_marker(r#"sys_platform != "win32""#, &env()), Some(true));
+    }
+
+    #[test]
+    fn test_extra_comparison() {
+        assert_eq!(evaluate_marker(r#"extra == "dev""#, &env()), Some(false));
+
+        let mut with_extra = env();
+        with_extra.extra = Some("dev".to_string());
+        assert_eq!(evaluate_marker(r#"extra == "dev""#, &with_extra), Some(true));
+    }
+
+    #[test]
+    fn test_boolean_and_or_not_combinations() {
+        let e = env();
+        assert_eq!(
+            evaluate_marker(r#"python_version >= "3.8" and sys_platform == "linux""#, &e),
+            Some(true)
+        );
+        assert_eq!(
+            evaluate_marker(r#"python_version < "3.8" or sys_platform == "linux""#, &e),
+            Some(true)
+        );
+        assert_eq!(
+            evaluate_marker(r#"not sys_platform == "win32""#, &e),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_in_and_not_in() {
+        assert_eq!(
+            evaluate_marker(r#"sys_platform in "linux,darwin""#, &env()),
+            Some(true)
+        );
+        assert_eq!(
+            evaluate_marker(r#"sys_platform not in "win32,cygwin""#, &env()),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_parenthesized_precedence() {
+        let e = env();
+        assert_eq!(
+            evaluate_marker(
+                r#"(python_version < "3.8" or python_version >= "3.9") and sys_platform == "linux""#,
+                &e
+            ),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_unparsable_marker_fails_open_with_none() {
+        assert_eq!(evaluate_marker("@#$%", &env()), None);
+    }
+}
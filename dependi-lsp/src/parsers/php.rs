@@ -1,6 +1,7 @@
 //! Parser for PHP Composer files (composer.json)
 
 use super::{Dependency, Parser};
+use crate::registries::version_scheme::{ComposerScheme, VersionScheme};
 
 /// Parser for PHP composer.json dependency files
 #[derive(Debug, Default)]
@@ -13,6 +14,10 @@ impl PhpParser {
 }
 
 impl Parser for PhpParser {
+    fn version_scheme(&self) -> &'static dyn VersionScheme {
+        &ComposerScheme
+    }
+
     fn parse(&self, content: &str) -> Vec<Dependency> {
         let mut dependencies = Vec::new();
         let mut current_section: Option<DependencyType> = None;
@@ -171,6 +176,11 @@ fn parse_dependency_from_pair(
         version_end,
         dev: dep_type == DependencyType::Dev,
         optional: false,
+        registry: None,
+        source: None,
+        git_ref: None,
+        normalized_version: None,
+        marker: None,
     })
 }
 
@@ -1,6 +1,7 @@
 //! Parser for Python dependency files (requirements.txt, constraints.txt, pyproject.toml)
 
 use super::{Dependency, Parser};
+use crate::registries::version_scheme::{PythonScheme, VersionScheme};
 
 /// Parser for Python dependency files
 #[derive(Debug, Default)]
@@ -13,12 +14,18 @@ impl PythonParser {
 }
 
 impl Parser for PythonParser {
+    fn version_scheme(&self) -> &'static dyn VersionScheme {
+        &PythonScheme
+    }
+
     fn parse(&self, content: &str) -> Vec<Dependency> {
         // Detect file type based on content
-        // Only parse as TOML if it contains valid pyproject.toml section headers
+        // Only parse as TOML if it contains valid pyproject.toml/Pipfile section headers
         // Use line-anchored detection to avoid false positives like "mypkg[project]==1.2"
         if is_pyproject_toml(content) {
             parse_pyproject_toml(content)
+        } else if is_pipfile(content) {
+            parse_pipfile(content)
         } else {
             parse_requirements_txt(content)
         }
@@ -66,6 +73,23 @@ fn is_valid_section_header(line: &str, prefix: &str) -> bool {
     after_bracket.is_empty() || after_bracket.starts_with('#')
 }
 
+/// Check if content is a Pipfile by looking for line-anchored `[packages]` /
+/// `[dev-packages]` section headers, the way [`is_pyproject_toml`] does.
+fn is_pipfile(content: &str) -> bool {
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("[packages") && is_valid_section_header(trimmed, "[packages") {
+            return true;
+        }
+        if trimmed.starts_with("[dev-packages")
+            && is_valid_section_header(trimmed, "[dev-packages")
+        {
+            return true;
+        }
+    }
+    false
+}
+
 /// Parse requirements.txt / constraints.txt format
 /// Format: package==1.0.0, package>=1.0.0, package~=1.0.0, etc.
 fn parse_requirements_txt(content: &str) -> Vec<Dependency> {
@@ -75,18 +99,36 @@ fn parse_requirements_txt(content: &str) -> Vec<Dependency> {
         let line_num = line_idx as u32;
         let trimmed = line.trim();
 
-        // Skip empty lines, comments, and special directives
-        if trimmed.is_empty()
-            || trimmed.starts_with('#')
-            || trimmed.starts_with('-')  // -r, -e, -c, etc.
-            || trimmed.starts_with("--")
-        // --index-url, etc.
-        {
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        // `-e` names a package (editable VCS/path install); other `-r`/`-c`/
+        // `--index-url`/etc. directives reference other files or configure
+        // the installer itself, not a dependency.
+        if trimmed.starts_with("-e ") {
+            if let Some(dep) = parse_vcs_requirement_line(line, line_num, false) {
+                dependencies.push(dep);
+            }
+            continue;
+        }
+        if trimmed.starts_with('-') {
+            continue;
+        }
+
+        // A bare VCS URL (no `-e`, e.g. `git+https://...#egg=name`)
+        if VCS_URL_PREFIXES.iter().any(|p| trimmed.starts_with(p)) {
+            if let Some(dep) = parse_vcs_requirement_line(line, line_num, false) {
+                dependencies.push(dep);
+            }
             continue;
         }
 
-        // Skip URL dependencies (package @ https://...)
+        // PEP 508 direct URL dependency (package @ https://...)
         if trimmed.contains(" @ ") {
+            if let Some(dep) = parse_url_requirement_line(line, line_num, false) {
+                dependencies.push(dep);
+            }
             continue;
         }
 
@@ -98,6 +140,100 @@ fn parse_requirements_txt(content: &str) -> Vec<Dependency> {
     dependencies
 }
 
+/// URL schemes pip recognizes as a direct VCS reference.
+const VCS_URL_PREFIXES: [&str; 4] = ["git+", "hg+", "svn+", "bzr+"];
+
+/// Parse a `package @ <url>` PEP 508 direct URL requirement. There's no
+/// registry version to track here, so `source` (mirroring `RubyParser`'s
+/// git/path handling) carries the URL instead, and `version` is left empty
+/// so update-checking is skipped rather than comparing against nothing.
+fn parse_url_requirement_line(line: &str, line_num: u32, dev: bool) -> Option<Dependency> {
+    let trimmed = line.trim();
+    // An inline comment is `<space>#...`; a URL fragment (`#egg=name`) has
+    // no space before it, so this won't mistake one for the other.
+    let without_comment = match trimmed.find(" #") {
+        Some(pos) => &trimmed[..pos],
+        None => trimmed,
+    };
+
+    let at_pos = without_comment.find(" @ ")?;
+    let name_part = &without_comment[..at_pos];
+    let name = name_part.find('[').map_or(name_part, |p| &name_part[..p]).trim();
+    if name.is_empty() {
+        return None;
+    }
+
+    let rest = without_comment[at_pos + 3..].trim();
+    let (source, marker) = match rest.find(';') {
+        Some(semi_pos) => (
+            rest[..semi_pos].trim().to_string(),
+            Some(rest[semi_pos + 1..].trim().to_string()).filter(|m| !m.is_empty()),
+        ),
+        None => (rest.to_string(), None),
+    };
+    if source.is_empty() {
+        return None;
+    }
+
+    let name_start = line.find(name)? as u32;
+    let name_end = name_start + name.len() as u32;
+
+    Some(Dependency {
+        name: name.to_string(),
+        version: String::new(),
+        line: line_num,
+        name_start,
+        name_end,
+        version_start: name_end,
+        version_end: name_end,
+        dev,
+        optional: false,
+        registry: None,
+        source: Some(source),
+        git_ref: None,
+        normalized_version: None,
+        marker,
+    })
+}
+
+/// Parse an editable (`-e <spec>`) or bare VCS URL requirement line. Only a
+/// spec with a `#egg=<name>` fragment can be attributed to a package name -
+/// anything else (e.g. a bare `-e .` for the current project) has no name
+/// to track and is skipped.
+fn parse_vcs_requirement_line(line: &str, line_num: u32, dev: bool) -> Option<Dependency> {
+    let trimmed = line.trim();
+    let spec = trimmed.strip_prefix("-e").map_or(trimmed, str::trim);
+
+    let egg_pos = spec.find("#egg=")?;
+    let name = spec[egg_pos + "#egg=".len()..]
+        .split(['&', ';'])
+        .next()?
+        .trim();
+    if name.is_empty() {
+        return None;
+    }
+
+    let name_start = line.find(name)? as u32;
+    let name_end = name_start + name.len() as u32;
+
+    Some(Dependency {
+        name: name.to_string(),
+        version: String::new(),
+        line: line_num,
+        name_start,
+        name_end,
+        version_start: name_end,
+        version_end: name_end,
+        dev,
+        optional: false,
+        registry: None,
+        source: Some(spec.to_string()),
+        git_ref: None,
+        normalized_version: None,
+        marker: None,
+    })
+}
+
 /// Parse a single requirement line
 fn parse_requirement_line(line: &str, line_num: u32, dev: bool) -> Option<Dependency> {
     let trimmed = line.trim();
@@ -145,24 +281,21 @@ fn parse_requirement_line(line: &str, line_num: u32, dev: bool) -> Option<Depend
         return None;
     }
 
-    // Extract version (including the operator, to align with Ruby/npm behavior)
+    // Extract version (including the operator, to align with Ruby/npm behavior).
+    // Comma-separated clauses (">=1.0,<2.0") are kept together rather than
+    // truncated to the first one, so the whole constraint is retained for
+    // `PythonScheme`/`pep440::SpecifierSet` to evaluate as a logical AND.
     let version = if let Some(op_pos) = version_op_pos {
         let operator = &without_comment[op_pos..op_pos + version_op_len];
         let version_part = &without_comment[op_pos + version_op_len..];
-        // Handle comma-separated version constraints: >=1.0,<2.0
-        let version_num = if let Some(comma_pos) = version_part.find(',') {
-            &version_part[..comma_pos]
-        } else {
-            version_part
-        };
         // Remove environment markers: ; python_version >= "3.8"
-        let version_num = if let Some(semi_pos) = version_num.find(';') {
-            &version_num[..semi_pos]
+        let version_part = if let Some(semi_pos) = version_part.find(';') {
+            &version_part[..semi_pos]
         } else {
-            version_num
+            version_part
         };
-        let version_num = version_num.trim();
-        format!("{}{}", operator, version_num)
+        let version_part = version_part.trim();
+        format!("{}{}", operator, version_part)
     } else {
         // No version specified
         return None;
@@ -172,6 +305,12 @@ fn parse_requirement_line(line: &str, line_num: u32, dev: bool) -> Option<Depend
         return None;
     }
 
+    // Capture the PEP 508 environment marker verbatim, if any.
+    let marker = without_comment
+        .find(';')
+        .map(|pos| without_comment[pos + 1..].trim().to_string())
+        .filter(|m| !m.is_empty());
+
     // Calculate positions
     let name_start = line.find(name)? as u32;
     let name_end = name_start + name.len() as u32;
@@ -191,6 +330,10 @@ fn parse_requirement_line(line: &str, line_num: u32, dev: bool) -> Option<Depend
         dev,
         optional: false,
         registry: None,
+        source: None,
+        git_ref: None,
+        normalized_version: None,
+        marker,
     })
 }
 
@@ -218,8 +361,8 @@ fn parse_pyproject_toml(content: &str) -> Vec<Dependency> {
             for item in items.iter() {
                 if let Some(dep_str) = item.as_str() {
                     let dep_str = dep_str.value();
-                    if let Some((name, version)) = parse_pep508_dependency(dep_str)
-                        && let Some(dep) = find_dependency_position(content, &name, &version, false)
+                    if let Some(parsed) = parse_pep508_dependency(dep_str)
+                        && let Some(dep) = find_dependency_position(content, parsed, false)
                     {
                         dependencies.push(dep);
                     }
@@ -237,9 +380,8 @@ fn parse_pyproject_toml(content: &str) -> Vec<Dependency> {
                     for item in items.iter() {
                         if let Some(dep_str) = item.as_str() {
                             let dep_str = dep_str.value();
-                            if let Some((name, version)) = parse_pep508_dependency(dep_str)
-                                && let Some(dep) =
-                                    find_dependency_position(content, &name, &version, true)
+                            if let Some(parsed) = parse_pep508_dependency(dep_str)
+                                && let Some(dep) = find_dependency_position(content, parsed, true)
                             {
                                 dependencies.push(dep);
                             }
@@ -267,9 +409,13 @@ fn parse_pyproject_toml(content: &str) -> Vec<Dependency> {
                 if name == "python" {
                     continue;
                 }
-                if let Some(version) = extract_poetry_version_taplo(value)
+                if let Some(version) = extract_poetry_version_taplo(value) {
+                    if let Some(dep) = find_poetry_dependency_position(content, &name, &version, false) {
+                        dependencies.push(dep);
+                    }
+                } else if let Some((source, git_ref)) = extract_poetry_source_taplo(value)
                     && let Some(dep) =
-                        find_poetry_dependency_position(content, &name, &version, false)
+                        find_poetry_source_dependency_position(content, &name, &source, git_ref, false)
                 {
                     dependencies.push(dep);
                 }
@@ -282,9 +428,13 @@ fn parse_pyproject_toml(content: &str) -> Vec<Dependency> {
             let entries = deps_table.entries().read();
             for (key, value) in entries.iter() {
                 let name = key.value().to_string();
-                if let Some(version) = extract_poetry_version_taplo(value)
+                if let Some(version) = extract_poetry_version_taplo(value) {
+                    if let Some(dep) = find_poetry_dependency_position(content, &name, &version, true) {
+                        dependencies.push(dep);
+                    }
+                } else if let Some((source, git_ref)) = extract_poetry_source_taplo(value)
                     && let Some(dep) =
-                        find_poetry_dependency_position(content, &name, &version, true)
+                        find_poetry_source_dependency_position(content, &name, &source, git_ref, true)
                 {
                     dependencies.push(dep);
                 }
@@ -304,9 +454,16 @@ fn parse_pyproject_toml(content: &str) -> Vec<Dependency> {
                         let entries = deps_table.entries().read();
                         for (key, value) in entries.iter() {
                             let name = key.value().to_string();
-                            if let Some(version) = extract_poetry_version_taplo(value)
-                                && let Some(dep) = find_poetry_dependency_position(
+                            if let Some(version) = extract_poetry_version_taplo(value) {
+                                if let Some(dep) = find_poetry_dependency_position(
                                     content, &name, &version, is_dev,
+                                ) {
+                                    dependencies.push(dep);
+                                }
+                            } else if let Some((source, git_ref)) =
+                                extract_poetry_source_taplo(value)
+                                && let Some(dep) = find_poetry_source_dependency_position(
+                                    content, &name, &source, git_ref, is_dev,
                                 )
                             {
                                 dependencies.push(dep);
@@ -326,18 +483,89 @@ fn parse_pyproject_toml(content: &str) -> Vec<Dependency> {
     dependencies
 }
 
-/// Parse PEP 508 dependency string: "package>=1.0.0" or "package[extra]>=1.0.0"
-fn parse_pep508_dependency(dep_str: &str) -> Option<(String, String)> {
+/// Parse Pipfile format (`[packages]` / `[dev-packages]` tables)
+fn parse_pipfile(content: &str) -> Vec<Dependency> {
+    let mut dependencies = Vec::new();
+
+    // Use taplo for parsing as it's more lenient and doesn't panic on malformed input
+    let parsed = taplo::parser::parse(content);
+
+    // If there are errors, skip this file
+    if !parsed.errors.is_empty() {
+        return dependencies;
+    }
+
+    let dom = parsed.into_dom();
+
+    for (table_name, dev) in [("packages", false), ("dev-packages", true)] {
+        let deps_node = dom.get(table_name);
+        if let Some(deps_table) = deps_node.as_table() {
+            let entries = deps_table.entries().read();
+            for (key, value) in entries.iter() {
+                let name = key.value().to_string();
+                if let Some(version) = extract_poetry_version_taplo(value) {
+                    if let Some(dep) = find_poetry_dependency_position(content, &name, &version, dev) {
+                        dependencies.push(dep);
+                    }
+                } else if let Some((source, git_ref)) = extract_poetry_source_taplo(value)
+                    && let Some(dep) =
+                        find_poetry_source_dependency_position(content, &name, &source, git_ref, dev)
+                {
+                    dependencies.push(dep);
+                }
+            }
+        }
+    }
+
+    dependencies
+}
+
+/// A parsed PEP 508 dependency string, either a version-constrained
+/// requirement ("package>=1.0.0") or a direct URL/VCS one
+/// ("package @ git+https://...").
+struct Pep508Dependency {
+    name: String,
+    /// Version specifier (operator + version), empty for a direct URL/VCS
+    /// dependency (`source` is set instead).
+    version: String,
+    /// Verbatim environment marker text after `;`, if any.
+    marker: Option<String>,
+    /// Direct URL/VCS source (`@ <url>` form), mirroring `RubyParser`'s
+    /// git/path handling. `None` for ordinary version-constrained deps.
+    source: Option<String>,
+}
+
+/// Parse PEP 508 dependency string: "package>=1.0.0", "package[extra]>=1.0.0",
+/// or "package @ <url>".
+fn parse_pep508_dependency(dep_str: &str) -> Option<Pep508Dependency> {
     let trimmed = dep_str.trim();
 
-    // Remove environment markers
-    let without_markers = if let Some(semi_pos) = trimmed.find(';') {
-        &trimmed[..semi_pos]
-    } else {
-        trimmed
+    // Split off the environment marker
+    let (without_markers, marker) = match trimmed.find(';') {
+        Some(semi_pos) => (
+            &trimmed[..semi_pos],
+            Some(trimmed[semi_pos + 1..].trim().to_string()).filter(|m| !m.is_empty()),
+        ),
+        None => (trimmed, None),
     };
     let without_markers = without_markers.trim();
 
+    // Direct URL/VCS dependency: no registry version to track.
+    if let Some(at_pos) = without_markers.find(" @ ") {
+        let name_part = &without_markers[..at_pos];
+        let name = name_part.find('[').map_or(name_part, |p| &name_part[..p]).trim();
+        let source = without_markers[at_pos + 3..].trim();
+        if name.is_empty() || source.is_empty() {
+            return None;
+        }
+        return Some(Pep508Dependency {
+            name: name.to_string(),
+            version: String::new(),
+            marker,
+            source: Some(source.to_string()),
+        });
+    }
+
     // Find version operator
     let operators = ["===", "==", ">=", "<=", "!=", "~=", ">", "<"];
     let mut op_pos = None;
@@ -363,21 +591,23 @@ fn parse_pep508_dependency(dep_str: &str) -> Option<(String, String)> {
     };
     let name = name.trim();
 
-    // Extract version (including operator, to align with requirements.txt behavior)
+    // Extract version (including operator, to align with requirements.txt
+    // behavior). Comma-separated clauses are kept together rather than
+    // truncated to the first one - see `parse_requirement_line`.
     let operator = &without_markers[op_pos..op_pos + op_len];
     let version_part = &without_markers[op_pos + op_len..];
-    let version_num = if let Some(comma_pos) = version_part.find(',') {
-        &version_part[..comma_pos]
-    } else {
-        version_part
-    };
-    let version_num = version_num.trim();
+    let version_num = version_part.trim();
 
     if name.is_empty() || version_num.is_empty() {
         return None;
     }
 
-    Some((name.to_string(), format!("{}{}", operator, version_num)))
+    Some(Pep508Dependency {
+        name: name.to_string(),
+        version: format!("{}{}", operator, version_num),
+        marker,
+        source: None,
+    })
 }
 
 /// Extract version from Poetry dependency value (using taplo Node)
@@ -398,31 +628,124 @@ fn extract_poetry_version_taplo(value: &taplo::dom::Node) -> Option<String> {
     None
 }
 
+/// Poetry table keys that mean a dependency is resolved from somewhere
+/// other than PyPI (a git repo or a local path), mirroring `RubyParser`'s
+/// `SOURCE_KEYS`.
+const POETRY_SOURCE_KEYS: [&str; 2] = ["git", "path"];
+/// Poetry table keys that pin a git-resolved dependency to a revision.
+const POETRY_REF_KEYS: [&str; 3] = ["branch", "tag", "rev"];
+
+/// Extract a `(source, git_ref)` pair from a Poetry table value resolved
+/// from a git repo or local path rather than a version constraint
+/// (`flask = { git = "...", branch = "..." }` / `{ path = "../flask" }`).
+/// `None` when `value` isn't such a table (callers fall back to
+/// [`extract_poetry_version_taplo`] first).
+fn extract_poetry_source_taplo(value: &taplo::dom::Node) -> Option<(String, Option<String>)> {
+    let t = value.as_table()?;
+    for key in POETRY_SOURCE_KEYS {
+        if let Some(source) = t.get(key).as_str() {
+            let git_ref = POETRY_REF_KEYS
+                .iter()
+                .find_map(|k| t.get(k).as_str().map(|s| s.value().to_string()));
+            return Some((source.value().to_string(), git_ref));
+        }
+    }
+    None
+}
+
+/// Expands a Poetry caret (`^`) or tilde (`~`) constraint into an explicit
+/// `>=X,<Y` PEP 440 range, since those operators aren't valid PEP 440 and
+/// nothing downstream (e.g. [`crate::registries::version_scheme::PythonScheme`])
+/// can evaluate them directly. Returns `None` for anything else (plain
+/// versions, `>=`/`==`/etc.), since those need no expansion.
+fn normalize_poetry_caret_tilde(version: &str) -> Option<String> {
+    let version = version.trim();
+    let (op, rest) = if let Some(rest) = version.strip_prefix('^') {
+        ('^', rest)
+    } else if let Some(rest) = version.strip_prefix('~') {
+        ('~', rest)
+    } else {
+        return None;
+    };
+
+    let components: Vec<u64> = rest
+        .split('.')
+        .map(|part| part.parse::<u64>().ok())
+        .collect::<Option<Vec<u64>>>()?;
+    if components.is_empty() {
+        return None;
+    }
+    let major = components[0];
+    let minor = components.get(1).copied();
+    let patch = components.get(2).copied();
+
+    let upper = if op == '^' {
+        // Caret allows changes that don't modify the left-most non-zero digit.
+        if major != 0 {
+            format!("{}.0.0", major + 1)
+        } else if minor.is_some_and(|m| m != 0) {
+            format!("0.{}.0", minor.unwrap() + 1)
+        } else if patch.is_some_and(|p| p != 0) {
+            format!("0.0.{}", patch.unwrap() + 1)
+        } else {
+            match components.len() {
+                1 => "1.0.0".to_string(),
+                2 => "0.1.0".to_string(),
+                _ => "0.0.1".to_string(),
+            }
+        }
+    } else {
+        // Tilde allows patch-level changes if minor is given, otherwise
+        // minor-level changes.
+        match minor {
+            Some(minor) => format!("{}.{}.0", major, minor + 1),
+            None => format!("{}.0.0", major + 1),
+        }
+    };
+
+    Some(format!(">={rest},<{upper}"))
+}
+
 /// Find position of a dependency in PEP 621 format (array of strings)
-fn find_dependency_position(
-    content: &str,
-    name: &str,
-    version: &str,
-    dev: bool,
-) -> Option<Dependency> {
+fn find_dependency_position(content: &str, parsed: Pep508Dependency, dev: bool) -> Option<Dependency> {
+    let Pep508Dependency {
+        name,
+        version,
+        marker,
+        source,
+    } = parsed;
+    // A direct URL/VCS dependency has no version to locate on the line -
+    // match on the source text instead, mirroring `RubyParser`'s handling
+    // of a source-only gem (no version span - just the name).
+    let needle = if version.is_empty() {
+        source.as_deref()?
+    } else {
+        version.as_str()
+    };
+
     for (line_idx, line) in content.lines().enumerate() {
         // Look for the dependency string in an array
-        if line.contains(name) && line.contains(version) {
+        if line.contains(&name) && line.contains(needle) {
             // Check it's likely a dependency line (contains quotes and version operator)
             if line.contains('"') || line.contains('\'') {
                 let line_num = line_idx as u32;
 
                 // Find name position
-                let name_start = line.find(name)? as u32;
+                let name_start = line.find(&name)? as u32;
                 let name_end = name_start + name.len() as u32;
 
-                // Find version position
-                let version_start = line.find(version)? as u32;
-                let version_end = version_start + version.len() as u32;
+                // Find version position; a URL/VCS dependency has no
+                // version token, so the span collapses to the name's end.
+                let (version_start, version_end) = if version.is_empty() {
+                    (name_end, name_end)
+                } else {
+                    let version_start = line.find(needle)? as u32;
+                    (version_start, version_start + needle.len() as u32)
+                };
 
                 return Some(Dependency {
-                    name: name.to_string(),
-                    version: version.to_string(),
+                    name,
+                    version,
                     line: line_num,
                     name_start,
                     name_end,
@@ -431,6 +754,10 @@ fn find_dependency_position(
                     dev,
                     optional: dev, // optional-dependencies are optional
                     registry: None,
+                    source,
+                    git_ref: None,
+                    normalized_version: None,
+                    marker,
                 });
             }
         }
@@ -473,6 +800,10 @@ fn find_poetry_dependency_position(
                     dev,
                     optional: false,
                     registry: None,
+                    source: None,
+                    git_ref: None,
+                    normalized_version: normalize_poetry_caret_tilde(version),
+                    marker: None,
                 });
             }
         }
@@ -480,6 +811,121 @@ fn find_poetry_dependency_position(
     None
 }
 
+/// Find position of a Poetry dependency resolved from git/path rather than
+/// a version constraint. Mirrors [`find_poetry_dependency_position`], but
+/// matches on the table's git/path value since there's no version to find.
+fn find_poetry_source_dependency_position(
+    content: &str,
+    name: &str,
+    source: &str,
+    git_ref: Option<String>,
+    dev: bool,
+) -> Option<Dependency> {
+    for (line_idx, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with(name) && trimmed.contains('=') && line.contains(source) {
+            let line_num = line_idx as u32;
+
+            let name_start = line.find(name)? as u32;
+            let name_end = name_start + name.len() as u32;
+
+            return Some(Dependency {
+                name: name.to_string(),
+                version: String::new(),
+                line: line_num,
+                name_start,
+                name_end,
+                version_start: name_end,
+                version_end: name_end,
+                dev,
+                optional: false,
+                registry: None,
+                source: Some(source.to_string()),
+                git_ref,
+                normalized_version: None,
+                marker: None,
+            });
+        }
+    }
+    None
+}
+
+/// In-place mutation helpers for Python dependency files, used by "bump to
+/// latest"/"remove dependency" code actions. Unlike [`PythonParser::parse`],
+/// which only locates dependencies, these operate on the full file text and
+/// return a rewritten copy, replacing only the dependency's own version
+/// token or line so unrelated formatting, ordering, and comments are left
+/// untouched.
+#[derive(Debug, Default)]
+pub struct PythonEditor;
+
+impl PythonEditor {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Replace `name`'s declared version with `new_version`. Returns
+    /// `content` unchanged if `name` isn't found.
+    pub fn set_dependency_version(&self, content: &str, name: &str, new_version: &str) -> String {
+        let Some(dep) = find_named_dependency(content, name) else {
+            return content.to_string();
+        };
+        let Some((line_start, _)) = line_byte_range(content, dep.line) else {
+            return content.to_string();
+        };
+
+        let version_start = line_start + dep.version_start as usize;
+        let version_end = line_start + dep.version_end as usize;
+
+        let mut rewritten = String::with_capacity(content.len());
+        rewritten.push_str(&content[..version_start]);
+        rewritten.push_str(new_version);
+        rewritten.push_str(&content[version_end..]);
+        rewritten
+    }
+
+    /// Remove `name`'s declaration entirely, including its line and the
+    /// line's trailing newline. Returns `content` unchanged if `name` isn't
+    /// found.
+    pub fn remove_dependency(&self, content: &str, name: &str) -> String {
+        let Some(dep) = find_named_dependency(content, name) else {
+            return content.to_string();
+        };
+        let Some((line_start, line_end)) = line_byte_range(content, dep.line) else {
+            return content.to_string();
+        };
+
+        let mut rewritten = String::with_capacity(content.len());
+        rewritten.push_str(&content[..line_start]);
+        rewritten.push_str(&content[line_end..]);
+        rewritten
+    }
+}
+
+/// Parses `content` and returns the first dependency named `name`, if any.
+/// Covers requirements.txt, pyproject.toml, and Pipfile alike, since
+/// [`PythonParser::parse`] already dispatches on content shape.
+fn find_named_dependency(content: &str, name: &str) -> Option<Dependency> {
+    PythonParser::new()
+        .parse(content)
+        .into_iter()
+        .find(|dep| dep.name == name)
+}
+
+/// Byte offsets of the start of line `line_num` and the start of the line
+/// after it, so the range includes the line's own trailing newline, if any.
+fn line_byte_range(content: &str, line_num: u32) -> Option<(usize, usize)> {
+    let mut offset = 0usize;
+    for (idx, line) in content.split_inclusive('\n').enumerate() {
+        if idx as u32 == line_num {
+            return Some((offset, offset + line.len()));
+        }
+        offset += line.len();
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -515,6 +961,77 @@ django~=4.0
         assert_eq!(deps[0].version, ">=0.20.0");
     }
 
+    #[test]
+    fn test_requirements_retains_full_comma_separated_constraint() {
+        let parser = PythonParser::new();
+        let content = "django>=3.2,<4.0; python_version >= \"3.8\"";
+        let deps = parser.parse(content);
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].version, ">=3.2,<4.0");
+    }
+
+    #[test]
+    fn test_requirements_captures_environment_marker() {
+        let parser = PythonParser::new();
+        let content = "django>=3.2,<4.0; python_version >= \"3.8\"\nflask==2.0.0";
+        let deps = parser.parse(content);
+
+        let django = deps.iter().find(|d| d.name == "django").unwrap();
+        assert_eq!(
+            django.marker,
+            Some("python_version >= \"3.8\"".to_string())
+        );
+
+        let flask = deps.iter().find(|d| d.name == "flask").unwrap();
+        assert_eq!(flask.marker, None);
+    }
+
+    #[test]
+    fn test_requirements_direct_url_dependency() {
+        let parser = PythonParser::new();
+        let content = "flask @ https://example.com/flask-2.0.0.tar.gz";
+        let deps = parser.parse(content);
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].name, "flask");
+        assert_eq!(deps[0].version, "");
+        assert_eq!(
+            deps[0].source.as_deref(),
+            Some("https://example.com/flask-2.0.0.tar.gz")
+        );
+    }
+
+    #[test]
+    fn test_requirements_editable_vcs_dependency() {
+        let parser = PythonParser::new();
+        let content = "-e git+https://github.com/psf/requests.git@main#egg=requests";
+        let deps = parser.parse(content);
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].name, "requests");
+        assert_eq!(deps[0].version, "");
+        assert_eq!(
+            deps[0].source.as_deref(),
+            Some("git+https://github.com/psf/requests.git@main#egg=requests")
+        );
+    }
+
+    #[test]
+    fn test_requirements_bare_vcs_url_dependency() {
+        let parser = PythonParser::new();
+        let content = "git+https://github.com/psf/requests.git@main#egg=requests";
+        let deps = parser.parse(content);
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].name, "requests");
+        assert!(deps[0].source.is_some());
+    }
+
+    #[test]
+    fn test_requirements_editable_path_without_egg_is_skipped() {
+        let parser = PythonParser::new();
+        let content = "-e .";
+        let deps = parser.parse(content);
+        assert_eq!(deps.len(), 0);
+    }
+
     #[test]
     fn test_requirements_with_comments() {
         let parser = PythonParser::new();
@@ -570,6 +1087,50 @@ dev = [
         assert!(pytest.dev);
     }
 
+    #[test]
+    fn test_pyproject_pep621_captures_environment_marker() {
+        let parser = PythonParser::new();
+        let content = r#"
+[project]
+name = "myproject"
+dependencies = [
+    "flask>=2.0.0",
+    "tomli>=2.0.0; python_version < \"3.11\"",
+]
+"#;
+        let deps = parser.parse(content);
+
+        let flask = deps.iter().find(|d| d.name == "flask").unwrap();
+        assert_eq!(flask.marker, None);
+
+        let tomli = deps.iter().find(|d| d.name == "tomli").unwrap();
+        assert_eq!(
+            tomli.marker,
+            Some("python_version < \"3.11\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_pyproject_pep621_direct_url_dependency() {
+        let parser = PythonParser::new();
+        let content = r#"
+[project]
+name = "myproject"
+dependencies = [
+    "flask>=2.0.0",
+    "requests @ https://example.com/requests-2.25.0.tar.gz",
+]
+"#;
+        let deps = parser.parse(content);
+
+        let requests = deps.iter().find(|d| d.name == "requests").unwrap();
+        assert_eq!(requests.version, "");
+        assert_eq!(
+            requests.source.as_deref(),
+            Some("https://example.com/requests-2.25.0.tar.gz")
+        );
+    }
+
     #[test]
     fn test_pyproject_poetry() {
         let parser = PythonParser::new();
@@ -598,6 +1159,134 @@ pytest = "^7.0.0"
         assert!(pytest.dev);
     }
 
+    #[test]
+    fn test_pyproject_poetry_git_and_path_source() {
+        let parser = PythonParser::new();
+        let content = r#"
+[tool.poetry.dependencies]
+flask = { git = "https://github.com/pallets/flask.git", branch = "main" }
+mylib = { path = "../mylib" }
+"#;
+        let deps = parser.parse(content);
+        assert_eq!(deps.len(), 2);
+
+        let flask = deps.iter().find(|d| d.name == "flask").unwrap();
+        assert_eq!(flask.version, "");
+        assert_eq!(
+            flask.source.as_deref(),
+            Some("https://github.com/pallets/flask.git")
+        );
+        assert_eq!(flask.git_ref.as_deref(), Some("main"));
+
+        let mylib = deps.iter().find(|d| d.name == "mylib").unwrap();
+        assert_eq!(mylib.version, "");
+        assert_eq!(mylib.source.as_deref(), Some("../mylib"));
+        assert_eq!(mylib.git_ref, None);
+    }
+
+    #[test]
+    fn test_pipfile() {
+        let parser = PythonParser::new();
+        let content = r#"
+[[source]]
+name = "pypi"
+url = "https://pypi.org/simple"
+verify_ssl = true
+
+[packages]
+requests = "==2.25.0"
+flask = { version = "^2.0.0", extras = ["async"] }
+
+[dev-packages]
+pytest = "==7.0.0"
+
+[requires]
+python_version = "3.9"
+"#;
+        let deps = parser.parse(content);
+        assert_eq!(deps.len(), 3);
+
+        let requests = deps.iter().find(|d| d.name == "requests").unwrap();
+        assert_eq!(requests.version, "==2.25.0");
+        assert!(!requests.dev);
+
+        let flask = deps.iter().find(|d| d.name == "flask").unwrap();
+        assert_eq!(flask.version, "^2.0.0");
+        assert!(!flask.dev);
+
+        let pytest = deps.iter().find(|d| d.name == "pytest").unwrap();
+        assert_eq!(pytest.version, "==7.0.0");
+        assert!(pytest.dev);
+    }
+
+    #[test]
+    fn test_pyproject_poetry_normalizes_caret_and_tilde() {
+        let parser = PythonParser::new();
+        let content = r#"
+[tool.poetry.dependencies]
+flask = "^2.0.0"
+
+[tool.poetry.dev-dependencies]
+pytest = "~7.1"
+"#;
+        let deps = parser.parse(content);
+
+        let flask = deps.iter().find(|d| d.name == "flask").unwrap();
+        assert_eq!(flask.version, "^2.0.0");
+        assert_eq!(flask.normalized_version.as_deref(), Some(">=2.0.0,<3.0.0"));
+
+        let pytest = deps.iter().find(|d| d.name == "pytest").unwrap();
+        assert_eq!(pytest.version, "~7.1");
+        assert_eq!(pytest.normalized_version.as_deref(), Some(">=7.1,<7.2.0"));
+    }
+
+    #[test]
+    fn test_normalize_poetry_caret_tilde_caret_rules() {
+        assert_eq!(
+            normalize_poetry_caret_tilde("^1.2.3"),
+            Some(">=1.2.3,<2.0.0".to_string())
+        );
+        assert_eq!(
+            normalize_poetry_caret_tilde("^0.2.3"),
+            Some(">=0.2.3,<0.3.0".to_string())
+        );
+        assert_eq!(
+            normalize_poetry_caret_tilde("^0.0.3"),
+            Some(">=0.0.3,<0.0.4".to_string())
+        );
+        assert_eq!(
+            normalize_poetry_caret_tilde("^0.0.0"),
+            Some(">=0.0.0,<0.0.1".to_string())
+        );
+        assert_eq!(
+            normalize_poetry_caret_tilde("^0"),
+            Some(">=0,<1.0.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_normalize_poetry_caret_tilde_tilde_rules() {
+        assert_eq!(
+            normalize_poetry_caret_tilde("~1.2.3"),
+            Some(">=1.2.3,<1.3.0".to_string())
+        );
+        assert_eq!(
+            normalize_poetry_caret_tilde("~1.2"),
+            Some(">=1.2,<1.3.0".to_string())
+        );
+        assert_eq!(
+            normalize_poetry_caret_tilde("~1"),
+            Some(">=1,<2.0.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_normalize_poetry_caret_tilde_ignores_other_operators() {
+        assert_eq!(normalize_poetry_caret_tilde(">=1.0.0"), None);
+        assert_eq!(normalize_poetry_caret_tilde("1.0.0"), None);
+        assert_eq!(normalize_poetry_caret_tilde("*"), None);
+    }
+
     #[test]
     fn test_version_position() {
         let parser = PythonParser::new();
@@ -668,4 +1357,45 @@ flask>=2.0.0
         assert!(!is_pyproject_toml("[tool.poetryextra]\nname = \"test\"")); // not [tool.poetry...]
         assert!(!is_pyproject_toml("flask>=2.0.0\nrequests>=2.25.0"));
     }
+
+    #[test]
+    fn test_editor_set_dependency_version_requirements_txt() {
+        let editor = PythonEditor::new();
+        let content = "flask==2.0.0\nrequests>=2.25.0  # pinned\n";
+        let updated = editor.set_dependency_version(content, "flask", "==2.31.0");
+        assert_eq!(updated, "flask==2.31.0\nrequests>=2.25.0  # pinned\n");
+    }
+
+    #[test]
+    fn test_editor_set_dependency_version_poetry_table() {
+        let editor = PythonEditor::new();
+        let content = "[tool.poetry.dependencies]\nflask = \"^2.0.0\"\n";
+        let updated = editor.set_dependency_version(content, "flask", "^3.0.0");
+        assert_eq!(updated, "[tool.poetry.dependencies]\nflask = \"^3.0.0\"\n");
+    }
+
+    #[test]
+    fn test_editor_set_dependency_version_missing_name_is_noop() {
+        let editor = PythonEditor::new();
+        let content = "flask==2.0.0\n";
+        assert_eq!(
+            editor.set_dependency_version(content, "django", "==5.0.0"),
+            content
+        );
+    }
+
+    #[test]
+    fn test_editor_remove_dependency_drops_only_its_line() {
+        let editor = PythonEditor::new();
+        let content = "flask==2.0.0\nrequests>=2.25.0\n";
+        let updated = editor.remove_dependency(content, "flask");
+        assert_eq!(updated, "requests>=2.25.0\n");
+    }
+
+    #[test]
+    fn test_editor_remove_dependency_missing_name_is_noop() {
+        let editor = PythonEditor::new();
+        let content = "flask==2.0.0\n";
+        assert_eq!(editor.remove_dependency(content, "django"), content);
+    }
 }
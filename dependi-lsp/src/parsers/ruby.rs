@@ -1,4 +1,4 @@
-//! Parser for Ruby Gemfile files
+//! Parser for Ruby Gemfile and .gemspec files
 //!
 //! Optimized for performance with reduced allocations.
 //!
@@ -6,8 +6,11 @@
 //! - Gemfile format (Bundler)
 //! - gem declarations with version constraints
 //! - group blocks for development dependencies
+//! - .gemspec `add_dependency`/`add_runtime_dependency`/
+//!   `add_development_dependency` calls
 
 use super::{Dependency, Parser};
+use crate::registries::version_scheme::{RubyScheme, VersionScheme};
 
 /// Parser for Ruby Gemfile dependency files
 #[derive(Debug, Default)]
@@ -20,6 +23,10 @@ impl RubyParser {
 }
 
 impl Parser for RubyParser {
+    fn version_scheme(&self) -> &'static dyn VersionScheme {
+        &RubyScheme
+    }
+
     fn parse(&self, content: &str) -> Vec<Dependency> {
         // Pre-allocate with reasonable capacity
         let mut dependencies = Vec::with_capacity(32);
@@ -67,6 +74,8 @@ impl Parser for RubyParser {
             // Parse gem declarations
             if let Some(dep) = parse_gem_declaration(line, line_num, in_dev_group) {
                 dependencies.push(dep);
+            } else if let Some(dep) = parse_gemspec_declaration(line, line_num) {
+                dependencies.push(dep);
             }
         }
 
@@ -89,75 +98,226 @@ fn parse_gem_declaration(line: &str, line_num: u32, dev: bool) -> Option<Depende
     };
 
     // Parse the arguments
-    let (name, version, name_start, name_end, version_start, version_end) =
-        parse_gem_args(line, after_gem)?;
+    let args = parse_gem_args(line, after_gem)?;
 
     Some(Dependency {
-        name,
-        version,
+        name: args.name,
+        version: args.version,
         line: line_num,
-        name_start,
-        name_end,
-        version_start,
-        version_end,
+        name_start: args.name_start,
+        name_end: args.name_end,
+        version_start: args.version_start,
+        version_end: args.version_end,
+        dev,
+        optional: false,
+        registry: None,
+        source: args.source,
+        git_ref: args.git_ref,
+        normalized_version: None,
+        marker: None,
+    })
+}
+
+/// Parse a `.gemspec` `add_dependency`/`add_runtime_dependency`/
+/// `add_development_dependency` call. The receiver may be any local
+/// variable (`spec.add_dependency`, `s.add_runtime_dependency`, ...), so
+/// this matches on the method suffix rather than a fixed receiver name.
+fn parse_gemspec_declaration(line: &str, line_num: u32) -> Option<Dependency> {
+    let trimmed = line.trim();
+
+    let (method, dev) = if trimmed.contains(".add_development_dependency") {
+        (".add_development_dependency", true)
+    } else if trimmed.contains(".add_runtime_dependency") {
+        (".add_runtime_dependency", false)
+    } else if trimmed.contains(".add_dependency") {
+        (".add_dependency", false)
+    } else {
+        return None;
+    };
+
+    let (_, after_method) = trimmed.split_once(method)?;
+    let args_str = match after_method.strip_prefix('(') {
+        Some(rest) => rest.strip_suffix(')').unwrap_or(rest),
+        None => after_method,
+    };
+
+    // Reuses `parse_gem_args`; a `.gemspec`'s `add_*dependency` calls don't
+    // carry git/path source options in practice, so `source`/`git_ref` are
+    // expected to stay `None` here.
+    let args = parse_gem_args(line, args_str)?;
+
+    Some(Dependency {
+        name: args.name,
+        version: args.version,
+        line: line_num,
+        name_start: args.name_start,
+        name_end: args.name_end,
+        version_start: args.version_start,
+        version_end: args.version_end,
         dev,
         optional: false,
+        registry: None,
+        source: args.source,
+        git_ref: args.git_ref,
+        normalized_version: None,
+        marker: None,
     })
 }
 
-/// Parse gem arguments and return (name, version, positions)
-fn parse_gem_args(line: &str, args_str: &str) -> Option<(String, String, u32, u32, u32, u32)> {
+/// Hash-option keys that mean a gem is resolved from somewhere other than
+/// a registry (a git repo, a local path, or a GitHub shorthand).
+const SOURCE_KEYS: [&str; 4] = ["git", "path", "github", "source"];
+/// Hash-option keys that pin a source-resolved gem to a particular revision.
+const REF_KEYS: [&str; 3] = ["branch", "tag", "ref"];
+
+/// Arguments parsed from a `gem`/`add_*dependency` call.
+struct GemArgs {
+    name: String,
+    version: String,
+    name_start: u32,
+    name_end: u32,
+    version_start: u32,
+    version_end: u32,
+    /// `git:`/`path:`/`github:`/`source:` value, for gems resolved outside
+    /// a registry (Gemfile only - a `.gemspec`'s `add_*dependency` calls
+    /// don't carry these).
+    source: Option<String>,
+    /// `branch:`/`tag:`/`ref:` value pinning a source-resolved gem.
+    git_ref: Option<String>,
+}
+
+/// Parse gem arguments and return the name, version constraint(s), their
+/// positions, and any git/path source info.
+///
+/// Bundler allows more than one comma-separated version constraint
+/// (`gem 'rails', '>= 6.0', '< 7.0'`); all consecutive quoted constraints
+/// after the name are collected and joined into a single `", "`-separated
+/// string, with positions spanning from the start of the first constraint
+/// to the end of the last. Collection stops at the first argument that
+/// isn't a quoted string - a hash option like `git:`/`path:`/`require:` -
+/// and any remaining `key: value` options are scanned for a source
+/// (`git`/`path`/`github`/`source`) and ref (`branch`/`tag`/`ref`), so a
+/// gem like `gem 'my_gem', git: '...'` is kept (just unresolvable against a
+/// registry) rather than dropped.
+fn parse_gem_args(line: &str, args_str: &str) -> Option<GemArgs> {
     let bytes = args_str.as_bytes();
     let len = bytes.len();
 
     // Parse first argument (name)
     let (name, name_end_idx) = parse_quoted_string(bytes, 0)?;
 
-    // Find comma after name
     let mut idx = name_end_idx;
-    while idx < len && bytes[idx] != b',' {
-        idx += 1;
-    }
-    if idx >= len {
-        return None; // No version
-    }
-    idx += 1; // Skip comma
+    let mut constraints: Vec<String> = Vec::new();
 
-    // Skip whitespace
-    while idx < len && (bytes[idx] == b' ' || bytes[idx] == b'\t') {
-        idx += 1;
-    }
-    if idx >= len {
-        return None;
-    }
+    loop {
+        // Find comma after the previous argument
+        while idx < len && bytes[idx] != b',' {
+            idx += 1;
+        }
+        if idx >= len {
+            break;
+        }
+        idx += 1; // Skip comma
 
-    // Check if this looks like a hash option (contains : but not quoted)
-    let next_byte = bytes[idx];
-    if next_byte != b'\'' && next_byte != b'"' {
-        // Not a quoted string, likely a hash option like git:
-        return None;
+        // Skip whitespace
+        while idx < len && (bytes[idx] == b' ' || bytes[idx] == b'\t') {
+            idx += 1;
+        }
+        if idx >= len {
+            break;
+        }
+
+        // Stop at the first hash option (contains : but not quoted)
+        let next_byte = bytes[idx];
+        if next_byte != b'\'' && next_byte != b'"' {
+            break;
+        }
+
+        let (constraint, end_idx) = parse_quoted_string(bytes, idx)?;
+        if constraint.is_empty() || constraint.contains(':') {
+            break;
+        }
+
+        idx = end_idx;
+        constraints.push(constraint);
     }
 
-    // Parse second argument (version)
-    let (version, _) = parse_quoted_string(bytes, idx)?;
+    let (source, git_ref) = parse_gem_source_options(args_str, idx);
 
-    // Skip if version looks like a hash key
-    if version.is_empty() || version.contains(':') {
-        return None;
+    if constraints.is_empty() && source.is_none() {
+        return None; // No version and nothing else worth surfacing
     }
 
-    // Find positions in the original line
     let (name_start, name_end) = find_quoted_position(line, &name)?;
-    let (version_start, version_end) = find_quoted_position(line, &version)?;
 
-    Some((
+    if constraints.is_empty() {
+        return Some(GemArgs {
+            name,
+            version: String::new(),
+            name_start,
+            name_end,
+            version_start: name_end,
+            version_end: name_end,
+            source,
+            git_ref,
+        });
+    }
+
+    let version = constraints.join(", ");
+
+    // Find positions in the original line, spanning from the start of the
+    // first constraint to the end of the last
+    let mut search_from = name_end as usize;
+    let mut version_start = 0;
+    let mut version_end = 0;
+    for (i, constraint) in constraints.iter().enumerate() {
+        let (start, end) = find_quoted_position_from(line, constraint, search_from)?;
+        if i == 0 {
+            version_start = start;
+        }
+        version_end = end;
+        search_from = end as usize;
+    }
+
+    Some(GemArgs {
         name,
         version,
         name_start,
         name_end,
         version_start,
         version_end,
-    ))
+        source,
+        git_ref,
+    })
+}
+
+/// Scan the remaining `key: 'value'` hash options in `args_str` (starting
+/// at byte offset `start`) for a gem source and ref, returning
+/// `(source, git_ref)`. The source is recorded as `"key: value"` (e.g.
+/// `"git: https://github.com/user/my_gem.git"`) so the kind of source is
+/// visible alongside its location.
+fn parse_gem_source_options(args_str: &str, start: usize) -> (Option<String>, Option<String>) {
+    let mut source = None;
+    let mut git_ref = None;
+
+    for option in args_str[start..].split(',') {
+        let Some((key, value)) = option.split_once(':') else {
+            continue;
+        };
+        let key = key.trim();
+        let value = value.trim().trim_matches(|c| c == '\'' || c == '"');
+        if value.is_empty() {
+            continue;
+        }
+
+        if SOURCE_KEYS.contains(&key) {
+            source.get_or_insert_with(|| format!("{}: {}", key, value));
+        } else if REF_KEYS.contains(&key) {
+            git_ref.get_or_insert_with(|| value.to_string());
+        }
+    }
+
+    (source, git_ref)
 }
 
 /// Parse a quoted string starting at index, return (string, end_index)
@@ -193,22 +353,31 @@ fn parse_quoted_string(bytes: &[u8], start: usize) -> Option<(String, usize)> {
 
 /// Find the position of a quoted string in a line
 fn find_quoted_position(line: &str, needle: &str) -> Option<(u32, u32)> {
+    find_quoted_position_from(line, needle, 0)
+}
+
+/// Like `find_quoted_position`, but only searches from `search_from`
+/// onward, so repeated constraint strings resolve to their next
+/// occurrence rather than always the first.
+fn find_quoted_position_from(line: &str, needle: &str, search_from: usize) -> Option<(u32, u32)> {
+    let haystack = line.get(search_from..)?;
+
     // Look for the string within single quotes first (more common in Ruby)
     let single_quoted = format!("'{}'", needle);
-    if let Some(pos) = line.find(&single_quoted) {
-        let start = (pos + 1) as u32;
+    if let Some(pos) = haystack.find(&single_quoted) {
+        let start = (search_from + pos + 1) as u32;
         return Some((start, start + needle.len() as u32));
     }
 
     // Try double quotes
     let double_quoted = format!("\"{}\"", needle);
-    if let Some(pos) = line.find(&double_quoted) {
-        let start = (pos + 1) as u32;
+    if let Some(pos) = haystack.find(&double_quoted) {
+        let start = (search_from + pos + 1) as u32;
         return Some((start, start + needle.len() as u32));
     }
 
     // Fallback to direct search
-    let pos = line.find(needle)? as u32;
+    let pos = (search_from + haystack.find(needle)?) as u32;
     Some((pos, pos + needle.len() as u32))
 }
 
@@ -287,7 +456,7 @@ gem 'pg', '~> 1.4'
     }
 
     #[test]
-    fn test_skip_git_and_path_gems() {
+    fn test_captures_git_and_path_gems_as_sourced() {
         let parser = RubyParser::new();
         let content = r#"
 gem 'rails', '~> 7.0'
@@ -297,9 +466,47 @@ gem 'pg', '~> 1.4'
 "#;
         let deps = parser.parse(content);
 
-        assert_eq!(deps.len(), 2);
+        assert_eq!(deps.len(), 4);
         assert_eq!(deps[0].name, "rails");
-        assert_eq!(deps[1].name, "pg");
+        assert!(deps[0].source.is_none());
+
+        let my_gem = deps.iter().find(|d| d.name == "my_gem").unwrap();
+        assert_eq!(
+            my_gem.source.as_deref(),
+            Some("git: https://github.com/user/my_gem.git")
+        );
+        assert_eq!(my_gem.version, "");
+
+        let local_gem = deps.iter().find(|d| d.name == "local_gem").unwrap();
+        assert_eq!(local_gem.source.as_deref(), Some("path: ../local_gem"));
+
+        assert_eq!(deps[3].name, "pg");
+        assert!(deps[3].source.is_none());
+    }
+
+    #[test]
+    fn test_github_shorthand_with_branch_ref() {
+        let parser = RubyParser::new();
+        let content = "gem 'rails', github: 'rails/rails', branch: 'main'\n";
+        let deps = parser.parse(content);
+
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].source.as_deref(), Some("github: rails/rails"));
+        assert_eq!(deps[0].git_ref.as_deref(), Some("main"));
+    }
+
+    #[test]
+    fn test_version_constraint_with_trailing_git_source() {
+        let parser = RubyParser::new();
+        let content = "gem 'rails', '~> 7.0', git: 'https://github.com/rails/rails.git'\n";
+        let deps = parser.parse(content);
+
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].version, "~> 7.0");
+        assert_eq!(
+            deps[0].source.as_deref(),
+            Some("git: https://github.com/rails/rails.git")
+        );
     }
 
     #[test]
@@ -420,4 +627,83 @@ end
         assert_eq!(deps[0].name, "rails");
         assert_eq!(deps[0].version, "~> 7.0");
     }
+
+    #[test]
+    fn test_gemspec_add_dependency() {
+        let parser = RubyParser::new();
+        let content = r#"
+Gem::Specification.new do |spec|
+  spec.add_dependency "rails", "~> 7.0"
+  spec.add_runtime_dependency "pg", ">= 1.4"
+  spec.add_development_dependency "rspec", "~> 3.12"
+end
+"#;
+        let deps = parser.parse(content);
+
+        assert_eq!(deps.len(), 3);
+
+        let rails = deps.iter().find(|d| d.name == "rails").unwrap();
+        assert_eq!(rails.version, "~> 7.0");
+        assert!(!rails.dev);
+
+        let pg = deps.iter().find(|d| d.name == "pg").unwrap();
+        assert_eq!(pg.version, ">= 1.4");
+        assert!(!pg.dev);
+
+        let rspec = deps.iter().find(|d| d.name == "rspec").unwrap();
+        assert_eq!(rspec.version, "~> 3.12");
+        assert!(rspec.dev);
+    }
+
+    #[test]
+    fn test_gemspec_any_receiver_name() {
+        let parser = RubyParser::new();
+        let content = r#"s.add_dependency "nokogiri", "~> 1.15""#;
+        let deps = parser.parse(content);
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].name, "nokogiri");
+    }
+
+    #[test]
+    fn test_gemspec_parenthesized_call() {
+        let parser = RubyParser::new();
+        let content = r#"spec.add_dependency("rails", "~> 7.0")"#;
+        let deps = parser.parse(content);
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].name, "rails");
+        assert_eq!(deps[0].version, "~> 7.0");
+    }
+
+    #[test]
+    fn test_multiple_version_constraints() {
+        let parser = RubyParser::new();
+        let content = "gem 'rails', '>= 6.0', '< 7.0'\n";
+        let deps = parser.parse(content);
+
+        assert_eq!(deps.len(), 1);
+        let dep = &deps[0];
+        assert_eq!(dep.name, "rails");
+        assert_eq!(dep.version, ">= 6.0, < 7.0");
+
+        let version_slice = &content[dep.version_start as usize..dep.version_end as usize];
+        assert_eq!(version_slice, ">= 6.0', '< 7.0");
+    }
+
+    #[test]
+    fn test_multiple_version_constraints_with_trailing_option() {
+        let parser = RubyParser::new();
+        let content = "gem 'rails', '>= 6.0', '< 7.0', require: false\n";
+        let deps = parser.parse(content);
+
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].version, ">= 6.0, < 7.0");
+    }
+
+    #[test]
+    fn test_gemspec_missing_version_is_skipped() {
+        let parser = RubyParser::new();
+        let content = r#"spec.add_dependency "rails""#;
+        let deps = parser.parse(content);
+        assert_eq!(deps.len(), 0);
+    }
 }
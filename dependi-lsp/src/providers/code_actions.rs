@@ -2,108 +2,553 @@
 
 use std::collections::HashMap;
 
+use chrono::Utc;
+use serde::Serialize;
 use tower_lsp::lsp_types::*;
 
 use crate::backend::FileType;
 use crate::cache::Cache;
+use crate::config::VersionPreference;
 use crate::parsers::Dependency;
-use crate::providers::inlay_hints::{VersionStatus, compare_versions};
+use crate::providers::inlay_hints::{
+    CooldownWindow, VersionStatus, compare_versions, eligible_update_candidates,
+    parse_current_version,
+};
+use crate::registries::version_scheme::{VersionScheme, normalize_version};
+use crate::registries::version_set::nearest_safe_version;
 
-/// Create code actions for dependencies in the given range
+/// Create code actions for dependencies in the given range: one or two
+/// per-dependency quick-fixes (see [`create_update_actions`]) plus, when more
+/// than one update is available, a `source.fixAll` action that bumps every
+/// eligible dependency in a single edit.
+/// `include_in_fix_all` lets the caller exclude dependencies (e.g. dev/optional)
+/// from that aggregate action; it doesn't affect the per-dependency actions.
 pub fn create_code_actions(
     dependencies: &[Dependency],
     cache: &impl Cache,
     uri: &Url,
     range: Range,
     file_type: FileType,
-    cache_key_fn: impl Fn(&str) -> String,
+    scheme: &dyn VersionScheme,
+    cache_key_fn: impl Fn(&Dependency) -> String,
+    cooldown: &CooldownWindow,
+    preference: VersionPreference,
+    include_in_fix_all: impl Fn(&Dependency) -> bool,
 ) -> Vec<CodeActionOrCommand> {
-    dependencies
+    let in_range: Vec<&Dependency> = dependencies
         .iter()
         .filter(|dep| dep.line >= range.start.line && dep.line <= range.end.line)
-        .filter_map(|dep| create_update_action(dep, cache, uri, file_type, &cache_key_fn))
-        .collect()
+        .collect();
+
+    let mut actions: Vec<CodeActionOrCommand> = in_range
+        .iter()
+        .copied()
+        .flat_map(|dep| {
+            create_update_actions(
+                dep,
+                cache,
+                uri,
+                file_type,
+                scheme,
+                &cache_key_fn,
+                cooldown,
+                preference,
+            )
+            .into_iter()
+            .chain(create_security_fix_action(dep, cache, uri, file_type, &cache_key_fn))
+        })
+        .collect();
+
+    if let Some(fix_all) = create_fix_all_action(
+        &in_range,
+        cache,
+        uri,
+        file_type,
+        scheme,
+        &cache_key_fn,
+        cooldown,
+        preference,
+        &include_in_fix_all,
+    ) {
+        actions.push(fix_all);
+    }
+
+    actions
+}
+
+/// The pieces of an available update needed to build either a per-dependency
+/// quick-fix or a slot in the aggregate "update all" edit.
+struct PendingUpdate {
+    edit: TextEdit,
+    new_version: String,
+    breaking: bool,
+    released_days_ago: Option<i64>,
+}
+
+/// Build the in-place edit that bumps `dep`'s declared version to
+/// `new_version`, preserving `dep.version`'s own operator prefix (cargo-edit's
+/// convention: a `^`/`~`/`~>` range stays a range, just pointed at the new
+/// version, rather than being silently tightened into an exact pin).
+pub(crate) fn make_edit(dep: &Dependency, new_version: &str, file_type: FileType) -> TextEdit {
+    let prefix = requirement_operator(&dep.version);
+    TextEdit {
+        range: Range {
+            start: Position {
+                line: dep.line,
+                character: dep.version_start,
+            },
+            end: Position {
+                line: dep.line,
+                character: dep.version_end,
+            },
+        },
+        new_text: format_version(prefix, new_version, file_type),
+    }
+}
+
+/// Look up a dependency's cached version info and, if an update is
+/// available, compute the edit that bumps it in place.
+fn compute_pending_update(
+    dep: &Dependency,
+    cache: &impl Cache,
+    file_type: FileType,
+    scheme: &dyn VersionScheme,
+    cache_key_fn: &impl Fn(&Dependency) -> String,
+    cooldown: &CooldownWindow,
+    preference: VersionPreference,
+) -> Option<PendingUpdate> {
+    let cache_key = cache_key_fn(dep);
+    let version_info = cache.get(&cache_key)?;
+
+    match compare_versions(&dep.version, &version_info, cooldown, scheme, preference) {
+        VersionStatus::UpdateAvailable {
+            version: new_version,
+            breaking,
+            released_days_ago,
+            ..
+        } => Some(PendingUpdate {
+            edit: make_edit(dep, &new_version, file_type),
+            new_version,
+            breaking,
+            released_days_ago,
+        }),
+        VersionStatus::UpToDate | VersionStatus::Unknown => None,
+    }
+}
+
+/// Format the "(released N days ago)" suffix used in both action titles and
+/// diagnostic messages when the cooldown feature reports a version's age.
+fn format_age_suffix(released_days_ago: Option<i64>) -> String {
+    released_days_ago
+        .map(|days| format!(" (released {} days ago)", days))
+        .unwrap_or_default()
+}
+
+/// The tiered update targets for a single dependency: the highest version
+/// still satisfying its declared requirement (a safe, in-range bump) - which,
+/// for a 0.x requirement, is narrower than "same major" since Cargo's caret
+/// locks the minor below 1.0 - and, only when it differs, the absolute
+/// latest eligible version even though it falls outside that requirement.
+struct TieredUpdates {
+    compatible: Option<PendingUpdate>,
+    latest_major: Option<PendingUpdate>,
+}
+
+/// Compute `dep`'s tiered update targets from its cached version list,
+/// subject to the cooldown window.
+///
+/// Under [`VersionPreference::LowestCompatible`] there's only ever a
+/// "compatible" tier - the oldest in-range version, mirroring
+/// `compare_versions`'s own `LowestCompatible` branch - and no "latest
+/// major" tier, since that tier exists to surface a breaking bump when the
+/// newest release outgrew the declared requirement, which doesn't apply
+/// when the whole point is staying in range.
+fn compute_tiered_updates(
+    dep: &Dependency,
+    cache: &impl Cache,
+    file_type: FileType,
+    scheme: &dyn VersionScheme,
+    cache_key_fn: &impl Fn(&Dependency) -> String,
+    cooldown: &CooldownWindow,
+    preference: VersionPreference,
+) -> TieredUpdates {
+    let empty = TieredUpdates {
+        compatible: None,
+        latest_major: None,
+    };
+
+    let cache_key = cache_key_fn(dep);
+    let Some(version_info) = cache.get(&cache_key) else {
+        return empty;
+    };
+
+    if preference == VersionPreference::LowestCompatible {
+        let Some(earliest) = scheme.earliest_satisfying(&dep.version, &version_info.versions)
+        else {
+            return empty;
+        };
+        if !scheme.is_newer(&dep.version, earliest) {
+            return empty;
+        }
+        let released_at = version_info.release_dates.get(earliest).copied();
+        let now = Utc::now();
+        return TieredUpdates {
+            compatible: Some(PendingUpdate {
+                edit: make_edit(dep, earliest, file_type),
+                new_version: earliest.to_string(),
+                breaking: false,
+                released_days_ago: released_at.map(|dt| now.signed_duration_since(dt).num_days()),
+            }),
+            latest_major: None,
+        };
+    }
+
+    let Some(current_ver) = parse_current_version(&dep.version) else {
+        return empty;
+    };
+
+    // Newest first, so the first match per tier is that tier's best candidate.
+    let candidates = eligible_update_candidates(&current_ver, &version_info, cooldown);
+    let now = Utc::now();
+
+    let to_pending = |raw: &str, released_at: Option<chrono::DateTime<Utc>>, breaking: bool| PendingUpdate {
+        edit: make_edit(dep, raw, file_type),
+        new_version: raw.to_string(),
+        breaking,
+        released_days_ago: released_at.map(|dt| now.signed_duration_since(dt).num_days()),
+    };
+
+    let compatible = candidates
+        .iter()
+        .find(|(raw, _, _)| scheme.satisfies(&dep.version, raw))
+        .map(|(raw, _, released_at)| to_pending(raw, *released_at, false));
+
+    let latest_major = candidates.first().and_then(|(raw, _, released_at)| {
+        let already_covered = compatible
+            .as_ref()
+            .is_some_and(|pending| pending.new_version == *raw);
+        (!already_covered).then(|| to_pending(raw, *released_at, true))
+    });
+
+    TieredUpdates {
+        compatible,
+        latest_major,
+    }
+}
+
+/// Build a "compatible" or "latest major" code action from a tiered update target.
+fn build_update_action(
+    dep: &Dependency,
+    uri: &Url,
+    pending: &PendingUpdate,
+    is_preferred: bool,
+) -> CodeActionOrCommand {
+    let mut changes = HashMap::new();
+    changes.insert(uri.clone(), vec![pending.edit.clone()]);
+
+    let age = format_age_suffix(pending.released_days_ago);
+    let title = if pending.breaking {
+        format!(
+            "Update {} to latest major {} — may be breaking{}",
+            dep.name, pending.new_version, age
+        )
+    } else {
+        format!("Update {} to {}{}", dep.name, pending.new_version, age)
+    };
+
+    CodeActionOrCommand::CodeAction(CodeAction {
+        title,
+        kind: Some(CodeActionKind::QUICKFIX),
+        diagnostics: None,
+        edit: Some(WorkspaceEdit {
+            changes: Some(changes),
+            document_changes: None,
+            change_annotations: None,
+        }),
+        command: None,
+        is_preferred: Some(is_preferred),
+        disabled: None,
+        data: None,
+    })
+}
+
+/// Create up to two "Update to X.Y.Z" code actions for a dependency: a
+/// same-major compatible bump (preferred) and, only when the absolute
+/// latest version crosses a major boundary, a second action for that bump.
+fn create_update_actions(
+    dep: &Dependency,
+    cache: &impl Cache,
+    uri: &Url,
+    file_type: FileType,
+    scheme: &dyn VersionScheme,
+    cache_key_fn: &impl Fn(&Dependency) -> String,
+    cooldown: &CooldownWindow,
+    preference: VersionPreference,
+) -> Vec<CodeActionOrCommand> {
+    let tiers = compute_tiered_updates(
+        dep,
+        cache,
+        file_type,
+        scheme,
+        cache_key_fn,
+        cooldown,
+        preference,
+    );
+
+    let mut actions = Vec::new();
+    if let Some(pending) = &tiers.compatible {
+        actions.push(build_update_action(dep, uri, pending, true));
+    }
+    if let Some(pending) = &tiers.latest_major {
+        actions.push(build_update_action(
+            dep,
+            uri,
+            pending,
+            tiers.compatible.is_none(),
+        ));
+    }
+    actions
 }
 
-/// Create an "Update to X.Y.Z" code action for a dependency
-fn create_update_action(
+/// Create a "nearest non-vulnerable version" quick-fix for a dependency with
+/// a cached advisory. Distinct from the ordinary update actions above: the
+/// target here is the lowest version that both satisfies `dep`'s declared
+/// constraint and clears every known vulnerability (see
+/// [`nearest_safe_version`]), not simply the newest release, so it's offered
+/// even when `dep` is otherwise up to date against the registry's `latest`.
+fn create_security_fix_action(
     dep: &Dependency,
     cache: &impl Cache,
     uri: &Url,
     file_type: FileType,
-    cache_key_fn: impl Fn(&str) -> String,
+    cache_key_fn: &impl Fn(&Dependency) -> String,
 ) -> Option<CodeActionOrCommand> {
-    let cache_key = cache_key_fn(&dep.name);
+    let cache_key = cache_key_fn(dep);
     let version_info = cache.get(&cache_key)?;
+    let current_ver = parse_current_version(&dep.version)?;
 
-    match compare_versions(&dep.version, &version_info) {
-        VersionStatus::UpdateAvailable(new_version) => {
-            let new_text = format_version(&new_version, file_type);
-
-            let edit = TextEdit {
-                range: Range {
-                    start: Position {
-                        line: dep.line,
-                        character: dep.version_start,
-                    },
-                    end: Position {
-                        line: dep.line,
-                        character: dep.version_end,
-                    },
-                },
-                new_text,
-            };
-
-            let mut changes = HashMap::new();
-            changes.insert(uri.clone(), vec![edit]);
-
-            Some(CodeActionOrCommand::CodeAction(CodeAction {
-                title: format!("Update {} to {}", dep.name, new_version),
-                kind: Some(CodeActionKind::QUICKFIX),
-                diagnostics: None,
-                edit: Some(WorkspaceEdit {
-                    changes: Some(changes),
-                    document_changes: None,
-                    change_annotations: None,
-                }),
-                command: None,
-                is_preferred: Some(true),
-                disabled: None,
-                data: None,
-            }))
-        }
-        VersionStatus::UpToDate | VersionStatus::Unknown => None,
+    // Nothing to resolve around if no cached advisory has a known range.
+    if version_info.vulnerabilities.iter().all(|v| v.ranges.is_empty()) {
+        return None;
+    }
+
+    let nearest = nearest_safe_version(
+        &dep.version,
+        &version_info.versions,
+        &version_info.vulnerabilities,
+    )?;
+    // `nearest_safe_version` falls back to "latest compatible" when no
+    // published version clears every advisory within the constraint - if
+    // that's not actually newer than what's already declared, there's no fix
+    // to offer.
+    let nearest_ver = semver::Version::parse(&normalize_version(&nearest)).ok()?;
+    if nearest_ver <= current_ver {
+        return None;
     }
+
+    let mut changes = HashMap::new();
+    changes.insert(uri.clone(), vec![make_edit(dep, &nearest, file_type)]);
+
+    Some(CodeActionOrCommand::CodeAction(CodeAction {
+        title: format!(
+            "Update {} to nearest non-vulnerable version {}",
+            dep.name, nearest
+        ),
+        kind: Some(CodeActionKind::QUICKFIX),
+        diagnostics: None,
+        edit: Some(WorkspaceEdit {
+            changes: Some(changes),
+            document_changes: None,
+            change_annotations: None,
+        }),
+        command: None,
+        is_preferred: Some(false),
+        disabled: None,
+        data: None,
+    }))
 }
 
-/// Format version string based on file type
-fn format_version(version: &str, file_type: FileType) -> String {
+/// Create a single `source.fixAll` action bundling every eligible update in
+/// `dependencies` into one `WorkspaceEdit`. Returns `None` when nothing is
+/// eligible (including when `include` excludes every candidate).
+fn create_fix_all_action(
+    dependencies: &[&Dependency],
+    cache: &impl Cache,
+    uri: &Url,
+    file_type: FileType,
+    scheme: &dyn VersionScheme,
+    cache_key_fn: &impl Fn(&Dependency) -> String,
+    cooldown: &CooldownWindow,
+    preference: VersionPreference,
+    include: &impl Fn(&Dependency) -> bool,
+) -> Option<CodeActionOrCommand> {
+    let mut edits: Vec<TextEdit> = dependencies
+        .iter()
+        .copied()
+        .filter(|dep| include(dep))
+        .filter_map(|dep| {
+            compute_pending_update(
+                dep,
+                cache,
+                file_type,
+                scheme,
+                cache_key_fn,
+                cooldown,
+                preference,
+            )
+        })
+        .map(|pending| pending.edit)
+        .collect();
+
+    // Order by position so the client applies them in document order, and
+    // drop exact duplicates (e.g. a dependency listed twice in the manifest).
+    edits.sort_by_key(|edit| (edit.range.start.line, edit.range.start.character));
+    edits.dedup_by(|a, b| a.range == b.range && a.new_text == b.new_text);
+
+    // A single eligible update is already covered by its own quick-fix.
+    if edits.len() < 2 {
+        return None;
+    }
+
+    let count = edits.len();
+    let mut changes = HashMap::new();
+    changes.insert(uri.clone(), edits);
+
+    Some(CodeActionOrCommand::CodeAction(CodeAction {
+        title: format!("Update all {} dependencies", count),
+        kind: Some(CodeActionKind::SOURCE_FIX_ALL),
+        diagnostics: None,
+        edit: Some(WorkspaceEdit {
+            changes: Some(changes),
+            document_changes: None,
+            change_annotations: None,
+        }),
+        command: None,
+        is_preferred: Some(false),
+        disabled: None,
+        data: None,
+    }))
+}
+
+/// One dependency's planned upgrade, as returned by [`plan_upgrades`]: the
+/// same edit a quick-fix would apply, surfaced without actually applying it.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedUpgrade {
+    /// Name of the package being upgraded.
+    pub package: String,
+    /// Declared version/requirement before the upgrade.
+    pub current_version: String,
+    /// Version the upgrade bumps to.
+    pub new_version: String,
+    /// Whether this crosses a major-version (or equivalent) boundary.
+    pub breaking: bool,
+    /// The edit itself, in the same shape a quick-fix's `WorkspaceEdit`
+    /// carries.
+    pub edit: TextEdit,
+}
+
+/// Dry-run counterpart to [`create_code_actions`]'s `source.fixAll`
+/// aggregate: computes the upgrade plan for every eligible dependency
+/// without constructing a `WorkspaceEdit` or touching the document, for
+/// callers (e.g. an `upgradeAll` editor command, or a CI "what would change"
+/// preview) that want to inspect or apply the edits themselves. `include`
+/// filters which dependencies are considered, matching `create_code_actions`'s
+/// `include_in_fix_all`. `allow_major` selects between the two tiers
+/// [`compute_tiered_updates`] offers per dependency: `false` only plans the
+/// in-range compatible bump (skipping a dependency with no such bump, even
+/// if a later one is available); `true` falls back to the later bump when
+/// that's the only update available.
+pub fn plan_upgrades(
+    dependencies: &[Dependency],
+    cache: &impl Cache,
+    file_type: FileType,
+    scheme: &dyn VersionScheme,
+    cache_key_fn: impl Fn(&Dependency) -> String,
+    cooldown: &CooldownWindow,
+    preference: VersionPreference,
+    include: impl Fn(&Dependency) -> bool,
+    allow_major: bool,
+) -> Vec<PlannedUpgrade> {
+    dependencies
+        .iter()
+        .filter(|dep| include(dep))
+        .filter_map(|dep| {
+            let tiers = compute_tiered_updates(
+                dep,
+                cache,
+                file_type,
+                scheme,
+                &cache_key_fn,
+                cooldown,
+                preference,
+            );
+            let pending = tiers
+                .compatible
+                .or_else(|| if allow_major { tiers.latest_major } else { None })?;
+            Some(PlannedUpgrade {
+                package: dep.name.clone(),
+                current_version: dep.version.clone(),
+                new_version: pending.new_version,
+                breaking: pending.breaking,
+                edit: pending.edit,
+            })
+        })
+        .collect()
+}
+
+/// Format a replacement version string based on file type, reusing `prefix`
+/// (see [`requirement_operator`]) as-is so the edit only ever changes the
+/// version number, never the declared requirement's operator.
+fn format_version(prefix: &str, version: &str, file_type: FileType) -> String {
     match file_type {
-        FileType::Cargo | FileType::Npm | FileType::Php => {
+        FileType::Cargo | FileType::Npm | FileType::Php | FileType::Csharp | FileType::Dart => {
             // Keep the version as-is - the range already includes the quotes in these formats
-            version.to_string()
+            format!("{prefix}{version}")
         }
-        FileType::Python => {
-            // Python uses operators like == or >=
+        FileType::Python | FileType::Ruby => {
+            // Python and Ruby use operators like ==, >=, or ~>
             // Just replace the version number
-            version.to_string()
+            format!("{prefix}{version}")
         }
         FileType::Go => {
-            // Go versions start with 'v'
+            // Go versions start with 'v'; `prefix` is always empty here
+            // since go.mod has no comparable requirement-operator syntax.
             if version.starts_with('v') {
-                version.to_string()
+                format!("{prefix}{version}")
             } else {
-                format!("v{}", version)
+                format!("{prefix}v{version}")
             }
         }
     }
 }
 
+/// The operators [`requirement_operator`] recognizes as a declared
+/// requirement's prefix, longest first so `~>` (Ruby's pessimistic operator)
+/// matches before a bare `~` would.
+const REQUIREMENT_OPERATORS: &[&str] = &["~>", "===", "==", ">=", "<=", "!=", "~=", "^", "~", "=", ">", "<"];
+
+/// Split `requirement` into its leading operator (including any whitespace
+/// before the version it's attached to, e.g. Ruby's `"~> 7.0"`) and the bare
+/// version that follows. A requirement with no recognized operator (Cargo/npm's
+/// implicit-caret bare `"1.2.3"`, a Go `"v1.2.3"` tag) returns an empty prefix.
+fn requirement_operator(requirement: &str) -> &str {
+    for op in REQUIREMENT_OPERATORS {
+        if let Some(rest) = requirement.strip_prefix(op) {
+            let ws_len = rest.len() - rest.trim_start().len();
+            return &requirement[..op.len() + ws_len];
+        }
+    }
+    ""
+}
+
 #[cfg(test)]
 mod tests {
+    use chrono::Utc;
+
     use super::*;
     use crate::cache::MemoryCache;
     use crate::registries::VersionInfo;
+    use crate::registries::version_scheme::SemverScheme;
 
     fn create_test_dependency(name: &str, version: &str, line: u32) -> Dependency {
         Dependency {
@@ -116,42 +561,185 @@ mod tests {
             version_end: name.len() as u32 + 4 + version.len() as u32,
             dev: false,
             optional: false,
+            registry: None,
+            source: None,
+            git_ref: None,
+        }
+    }
+
+    fn full_range() -> Range {
+        Range {
+            start: Position {
+                line: 0,
+                character: 0,
+            },
+            end: Position {
+                line: 10,
+                character: 0,
+            },
         }
     }
 
     #[test]
-    fn test_create_update_action() {
+    fn test_create_update_action_compatible_only() {
         let cache = MemoryCache::new();
         cache.insert(
             "test:serde".to_string(),
             VersionInfo {
-                latest: Some("2.0.0".to_string()),
+                latest: Some("1.5.0".to_string()),
+                versions: vec!["1.5.0".to_string()],
                 ..Default::default()
             },
         );
 
         let deps = vec![create_test_dependency("serde", "1.0.0", 5)];
         let uri = Url::parse("file:///test/Cargo.toml").unwrap();
-        let range = Range {
-            start: Position {
-                line: 0,
-                character: 0,
+
+        let actions = create_code_actions(
+            &deps,
+            &cache,
+            &uri,
+            full_range(),
+            FileType::Cargo,
+            &SemverScheme,
+            |dep| format!("test:{}", dep.name),
+            &CooldownWindow::disabled(),
+            VersionPreference::Highest,
+            |_| true,
+        );
+
+        // No major bump available, so only the compatible action is offered.
+        assert_eq!(actions.len(), 1);
+        match &actions[0] {
+            CodeActionOrCommand::CodeAction(action) => {
+                assert!(action.title.contains("Update serde to 1.5.0"));
+                assert!(!action.title.contains("latest major"));
+                assert_eq!(action.kind, Some(CodeActionKind::QUICKFIX));
+                assert_eq!(action.is_preferred, Some(true));
+            }
+            _ => panic!("Expected CodeAction"),
+        }
+    }
+
+    #[test]
+    fn test_create_update_action_tiered_compatible_and_latest_major() {
+        let cache = MemoryCache::new();
+        cache.insert(
+            "test:serde".to_string(),
+            VersionInfo {
+                latest: Some("2.0.0".to_string()),
+                versions: vec!["1.5.0".to_string(), "2.0.0".to_string()],
+                ..Default::default()
             },
-            end: Position {
-                line: 10,
-                character: 0,
+        );
+
+        let deps = vec![create_test_dependency("serde", "1.0.0", 5)];
+        let uri = Url::parse("file:///test/Cargo.toml").unwrap();
+
+        let actions = create_code_actions(
+            &deps,
+            &cache,
+            &uri,
+            full_range(),
+            FileType::Cargo,
+            &SemverScheme,
+            |dep| format!("test:{}", dep.name),
+            &CooldownWindow::disabled(),
+            VersionPreference::Highest,
+            |_| true,
+        );
+
+        assert_eq!(actions.len(), 2);
+        let (compatible, latest_major) = match (&actions[0], &actions[1]) {
+            (CodeActionOrCommand::CodeAction(a), CodeActionOrCommand::CodeAction(b)) => (a, b),
+            _ => panic!("Expected CodeActions"),
+        };
+
+        assert!(compatible.title.contains("Update serde to 1.5.0"));
+        assert_eq!(compatible.is_preferred, Some(true));
+
+        assert!(latest_major.title.contains("Update serde to latest major 2.0.0"));
+        assert!(latest_major.title.contains("may be breaking"));
+        assert_eq!(latest_major.is_preferred, Some(false));
+    }
+
+    #[test]
+    fn test_tiered_updates_narrow_compatible_range_for_0x_requirement() {
+        // `^0.12` only permits `0.12.y` - Cargo's caret locks the minor
+        // below 1.0 - so `0.13.0` must land in the "latest major" tier even
+        // though it shares the same major (0) as the declared requirement.
+        let cache = MemoryCache::new();
+        cache.insert(
+            "test:pre-1.0".to_string(),
+            VersionInfo {
+                latest: Some("0.13.0".to_string()),
+                versions: vec!["0.12.5".to_string(), "0.13.0".to_string()],
+                ..Default::default()
             },
+        );
+
+        let deps = vec![create_test_dependency("pre-1.0", "0.12.0", 5)];
+        let uri = Url::parse("file:///test/Cargo.toml").unwrap();
+
+        let actions = create_code_actions(
+            &deps,
+            &cache,
+            &uri,
+            full_range(),
+            FileType::Cargo,
+            &SemverScheme,
+            |dep| format!("test:{}", dep.name),
+            &CooldownWindow::disabled(),
+            VersionPreference::Highest,
+            |_| true,
+        );
+
+        assert_eq!(actions.len(), 2);
+        let (compatible, latest_major) = match (&actions[0], &actions[1]) {
+            (CodeActionOrCommand::CodeAction(a), CodeActionOrCommand::CodeAction(b)) => (a, b),
+            _ => panic!("Expected CodeActions"),
         };
 
-        let actions = create_code_actions(&deps, &cache, &uri, range, FileType::Cargo, |name| {
-            format!("test:{}", name)
-        });
+        assert!(compatible.title.contains("Update pre-1.0 to 0.12.5"));
+        assert!(latest_major.title.contains("Update pre-1.0 to latest major 0.13.0"));
+        assert!(latest_major.title.contains("may be breaking"));
+    }
+
+    #[test]
+    fn test_create_update_action_latest_major_only() {
+        let cache = MemoryCache::new();
+        cache.insert(
+            "test:serde".to_string(),
+            VersionInfo {
+                latest: Some("2.0.0".to_string()),
+                versions: vec!["2.0.0".to_string()],
+                ..Default::default()
+            },
+        );
 
+        let deps = vec![create_test_dependency("serde", "1.0.0", 5)];
+        let uri = Url::parse("file:///test/Cargo.toml").unwrap();
+
+        let actions = create_code_actions(
+            &deps,
+            &cache,
+            &uri,
+            full_range(),
+            FileType::Cargo,
+            &SemverScheme,
+            |dep| format!("test:{}", dep.name),
+            &CooldownWindow::disabled(),
+            VersionPreference::Highest,
+            |_| true,
+        );
+
+        // No same-major version exists, so the sole action is the breaking
+        // one - still preferred, since it's the only option offered.
         assert_eq!(actions.len(), 1);
         match &actions[0] {
             CodeActionOrCommand::CodeAction(action) => {
-                assert!(action.title.contains("Update serde to 2.0.0"));
-                assert_eq!(action.kind, Some(CodeActionKind::QUICKFIX));
+                assert!(action.title.contains("Update serde to latest major 2.0.0"));
+                assert_eq!(action.is_preferred, Some(true));
             }
             _ => panic!("Expected CodeAction"),
         }
@@ -170,30 +758,501 @@ mod tests {
 
         let deps = vec![create_test_dependency("serde", "1.0.0", 5)];
         let uri = Url::parse("file:///test/Cargo.toml").unwrap();
-        let range = Range {
-            start: Position {
-                line: 0,
-                character: 0,
-            },
-            end: Position {
-                line: 10,
-                character: 0,
+
+        let actions = create_code_actions(
+            &deps,
+            &cache,
+            &uri,
+            full_range(),
+            FileType::Cargo,
+            &SemverScheme,
+            |dep| format!("test:{}", dep.name),
+            &CooldownWindow::disabled(),
+            VersionPreference::Highest,
+            |_| true,
+        );
+
+        assert_eq!(actions.len(), 0);
+    }
+
+    #[test]
+    fn test_create_update_action_includes_age_when_cooldown_enabled() {
+        let cache = MemoryCache::new();
+        let mut release_dates = HashMap::new();
+        release_dates.insert("1.5.0".to_string(), Utc::now() - chrono::Duration::days(21));
+        cache.insert(
+            "test:serde".to_string(),
+            VersionInfo {
+                latest: Some("1.5.0".to_string()),
+                versions: vec!["1.5.0".to_string()],
+                release_dates,
+                ..Default::default()
             },
-        };
+        );
 
-        let actions = create_code_actions(&deps, &cache, &uri, range, FileType::Cargo, |name| {
-            format!("test:{}", name)
+        let deps = vec![create_test_dependency("serde", "1.0.0", 5)];
+        let uri = Url::parse("file:///test/Cargo.toml").unwrap();
+        let cooldown = CooldownWindow::from_config(&crate::config::CooldownConfig {
+            enabled: true,
+            days: 14,
+            strict: false,
         });
 
-        assert_eq!(actions.len(), 0);
+        let actions = create_code_actions(
+            &deps,
+            &cache,
+            &uri,
+            full_range(),
+            FileType::Cargo,
+            &SemverScheme,
+            |dep| format!("test:{}", dep.name),
+            &cooldown,
+            VersionPreference::Highest,
+            |_| true,
+        );
+
+        assert_eq!(actions.len(), 1);
+        match &actions[0] {
+            CodeActionOrCommand::CodeAction(action) => {
+                assert!(action.title.contains("Update serde to 1.5.0"));
+                assert!(action.title.contains("released 21 days ago"));
+            }
+            _ => panic!("Expected CodeAction"),
+        }
+    }
+
+    #[test]
+    fn test_fix_all_action_aggregates_updates() {
+        let cache = MemoryCache::new();
+        cache.insert(
+            "test:serde".to_string(),
+            VersionInfo {
+                latest: Some("2.0.0".to_string()),
+                versions: vec!["2.0.0".to_string()],
+                ..Default::default()
+            },
+        );
+        cache.insert(
+            "test:tokio".to_string(),
+            VersionInfo {
+                latest: Some("1.5.0".to_string()),
+                versions: vec!["1.5.0".to_string()],
+                ..Default::default()
+            },
+        );
+
+        let deps = vec![
+            create_test_dependency("serde", "1.0.0", 5),
+            create_test_dependency("tokio", "1.0.0", 6),
+        ];
+        let uri = Url::parse("file:///test/Cargo.toml").unwrap();
+
+        let actions = create_code_actions(
+            &deps,
+            &cache,
+            &uri,
+            full_range(),
+            FileType::Cargo,
+            &SemverScheme,
+            |dep| format!("test:{}", dep.name),
+            &CooldownWindow::disabled(),
+            VersionPreference::Highest,
+            |_| true,
+        );
+
+        // Two per-dependency quick-fixes plus one aggregate action.
+        assert_eq!(actions.len(), 3);
+        let fix_all = actions
+            .iter()
+            .find_map(|action| match action {
+                CodeActionOrCommand::CodeAction(action)
+                    if action.kind == Some(CodeActionKind::SOURCE_FIX_ALL) =>
+                {
+                    Some(action)
+                }
+                _ => None,
+            })
+            .expect("expected a source.fixAll action");
+
+        assert_eq!(fix_all.title, "Update all 2 dependencies");
+        let edits = &fix_all.edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri];
+        assert_eq!(edits.len(), 2);
+        // Ordered by line: serde (line 5) before tokio (line 6).
+        assert_eq!(edits[0].new_text, "2.0.0");
+        assert_eq!(edits[1].new_text, "1.5.0");
+    }
+
+    #[test]
+    fn test_fix_all_action_excludes_filtered_dependencies() {
+        let cache = MemoryCache::new();
+        cache.insert(
+            "test:serde".to_string(),
+            VersionInfo {
+                latest: Some("2.0.0".to_string()),
+                versions: vec!["2.0.0".to_string()],
+                ..Default::default()
+            },
+        );
+        cache.insert(
+            "test:tokio".to_string(),
+            VersionInfo {
+                latest: Some("1.5.0".to_string()),
+                versions: vec!["1.5.0".to_string()],
+                ..Default::default()
+            },
+        );
+        cache.insert(
+            "test:dev-only".to_string(),
+            VersionInfo {
+                latest: Some("9.0.0".to_string()),
+                versions: vec!["9.0.0".to_string()],
+                ..Default::default()
+            },
+        );
+
+        let mut dev_dep = create_test_dependency("dev-only", "1.0.0", 7);
+        dev_dep.dev = true;
+        let deps = vec![
+            create_test_dependency("serde", "1.0.0", 5),
+            create_test_dependency("tokio", "1.0.0", 6),
+            dev_dep,
+        ];
+        let uri = Url::parse("file:///test/Cargo.toml").unwrap();
+
+        let actions = create_code_actions(
+            &deps,
+            &cache,
+            &uri,
+            full_range(),
+            FileType::Cargo,
+            &SemverScheme,
+            |dep| format!("test:{}", dep.name),
+            &CooldownWindow::disabled(),
+            VersionPreference::Highest,
+            |dep| !dep.dev && !dep.optional,
+        );
+
+        let fix_all = actions
+            .iter()
+            .find_map(|action| match action {
+                CodeActionOrCommand::CodeAction(action)
+                    if action.kind == Some(CodeActionKind::SOURCE_FIX_ALL) =>
+                {
+                    Some(action)
+                }
+                _ => None,
+            })
+            .expect("expected a source.fixAll action");
+
+        // Only serde and tokio clear the filter; the dev-only dependency
+        // is excluded even though it too has an update available.
+        assert_eq!(fix_all.title, "Update all 2 dependencies");
+    }
+
+    #[test]
+    fn test_no_fix_all_action_when_filter_excludes_everything() {
+        let cache = MemoryCache::new();
+        cache.insert(
+            "test:tokio".to_string(),
+            VersionInfo {
+                latest: Some("1.5.0".to_string()),
+                versions: vec!["1.5.0".to_string()],
+                ..Default::default()
+            },
+        );
+
+        let mut dev_dep = create_test_dependency("tokio", "1.0.0", 6);
+        dev_dep.dev = true;
+        let deps = vec![dev_dep];
+        let uri = Url::parse("file:///test/Cargo.toml").unwrap();
+
+        let actions = create_code_actions(
+            &deps,
+            &cache,
+            &uri,
+            full_range(),
+            FileType::Cargo,
+            &SemverScheme,
+            |dep| format!("test:{}", dep.name),
+            &CooldownWindow::disabled(),
+            VersionPreference::Highest,
+            |dep| !dep.dev && !dep.optional,
+        );
+
+        // The per-dependency quick-fix still fires, but no aggregate action
+        // is offered once the dev dependency is the only candidate.
+        assert_eq!(actions.len(), 1);
+        assert!(
+            !actions
+                .iter()
+                .any(|action| matches!(action, CodeActionOrCommand::CodeAction(a) if a.kind == Some(CodeActionKind::SOURCE_FIX_ALL)))
+        );
     }
 
     #[test]
     fn test_format_version() {
-        assert_eq!(format_version("1.0.0", FileType::Cargo), "1.0.0");
-        assert_eq!(format_version("1.0.0", FileType::Npm), "1.0.0");
-        assert_eq!(format_version("1.0.0", FileType::Python), "1.0.0");
-        assert_eq!(format_version("1.0.0", FileType::Go), "v1.0.0");
-        assert_eq!(format_version("v1.0.0", FileType::Go), "v1.0.0");
+        assert_eq!(format_version("", "1.0.0", FileType::Cargo), "1.0.0");
+        assert_eq!(format_version("", "1.0.0", FileType::Npm), "1.0.0");
+        assert_eq!(format_version("", "1.0.0", FileType::Python), "1.0.0");
+        assert_eq!(format_version("", "1.0.0", FileType::Go), "v1.0.0");
+        assert_eq!(format_version("", "v1.0.0", FileType::Go), "v1.0.0");
+        assert_eq!(format_version("^", "1.0.0", FileType::Npm), "^1.0.0");
+        assert_eq!(format_version("~> ", "7.1", FileType::Ruby), "~> 7.1");
+    }
+
+    #[test]
+    fn test_requirement_operator() {
+        assert_eq!(requirement_operator("1.2.3"), "");
+        assert_eq!(requirement_operator("^1.2.3"), "^");
+        assert_eq!(requirement_operator("~1.2.3"), "~");
+        assert_eq!(requirement_operator("~> 7.0"), "~> ");
+        assert_eq!(requirement_operator(">=1.0,<2.0"), ">=");
+        assert_eq!(requirement_operator("==1.2.3"), "==");
+    }
+
+    #[test]
+    fn test_update_action_preserves_caret_operator() {
+        let cache = MemoryCache::new();
+        cache.insert(
+            "test:serde".to_string(),
+            VersionInfo {
+                latest: Some("1.5.0".to_string()),
+                versions: vec!["1.5.0".to_string()],
+                ..Default::default()
+            },
+        );
+
+        let deps = vec![create_test_dependency("serde", "^1.0.0", 5)];
+        let uri = Url::parse("file:///test/package.json").unwrap();
+
+        let actions = create_code_actions(
+            &deps,
+            &cache,
+            &uri,
+            full_range(),
+            FileType::Npm,
+            &SemverScheme,
+            |dep| format!("test:{}", dep.name),
+            &CooldownWindow::disabled(),
+            VersionPreference::Highest,
+            |_| true,
+        );
+
+        assert_eq!(actions.len(), 1);
+        match &actions[0] {
+            CodeActionOrCommand::CodeAction(action) => {
+                let edits = &action.edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri];
+                assert_eq!(edits[0].new_text, "^1.5.0");
+            }
+            _ => panic!("Expected CodeAction"),
+        }
+    }
+
+    #[test]
+    fn test_make_edit_preserves_ruby_pessimistic_operator() {
+        // `compute_tiered_updates` is SemVer-only and can't parse Ruby's `~>`
+        // syntax, so this exercises `make_edit` directly rather than through
+        // `create_code_actions`.
+        let dep = create_test_dependency("rails", "~> 7.0", 5);
+        let edit = make_edit(&dep, "7.1", FileType::Ruby);
+        assert_eq!(edit.new_text, "~> 7.1");
+    }
+
+    #[test]
+    fn test_plan_upgrades_returns_compatible_bump_without_applying() {
+        let cache = MemoryCache::new();
+        cache.insert(
+            "test:serde".to_string(),
+            VersionInfo {
+                latest: Some("1.5.0".to_string()),
+                versions: vec!["1.5.0".to_string()],
+                ..Default::default()
+            },
+        );
+        cache.insert(
+            "test:up-to-date".to_string(),
+            VersionInfo {
+                latest: Some("1.0.0".to_string()),
+                versions: vec!["1.0.0".to_string()],
+                ..Default::default()
+            },
+        );
+
+        let deps = vec![
+            create_test_dependency("serde", "^1.0.0", 5),
+            create_test_dependency("up-to-date", "1.0.0", 6),
+        ];
+
+        let plan = plan_upgrades(
+            &deps,
+            &cache,
+            FileType::Npm,
+            &SemverScheme,
+            |dep| format!("test:{}", dep.name),
+            &CooldownWindow::disabled(),
+            VersionPreference::Highest,
+            |_| true,
+            false,
+        );
+
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan[0].package, "serde");
+        assert_eq!(plan[0].current_version, "^1.0.0");
+        assert_eq!(plan[0].new_version, "1.5.0");
+        assert!(!plan[0].breaking);
+        assert_eq!(plan[0].edit.new_text, "^1.5.0");
+    }
+
+    #[test]
+    fn test_plan_upgrades_skips_major_bump_unless_allowed() {
+        let cache = MemoryCache::new();
+        cache.insert(
+            "test:serde".to_string(),
+            VersionInfo {
+                latest: Some("2.0.0".to_string()),
+                versions: vec!["2.0.0".to_string()],
+                ..Default::default()
+            },
+        );
+
+        let deps = vec![create_test_dependency("serde", "1.0.0", 5)];
+
+        let without_major = plan_upgrades(
+            &deps,
+            &cache,
+            FileType::Cargo,
+            &SemverScheme,
+            |dep| format!("test:{}", dep.name),
+            &CooldownWindow::disabled(),
+            VersionPreference::Highest,
+            |_| true,
+            false,
+        );
+        assert_eq!(without_major.len(), 0);
+
+        let with_major = plan_upgrades(
+            &deps,
+            &cache,
+            FileType::Cargo,
+            &SemverScheme,
+            |dep| format!("test:{}", dep.name),
+            &CooldownWindow::disabled(),
+            VersionPreference::Highest,
+            |_| true,
+            true,
+        );
+        assert_eq!(with_major.len(), 1);
+        assert!(with_major[0].breaking);
+        assert_eq!(with_major[0].new_version, "2.0.0");
+    }
+
+    #[test]
+    fn test_security_fix_action_targets_nearest_patched_version() {
+        use crate::registries::{Vulnerability, VulnerabilitySeverity, VulnerableRange};
+
+        let cache = MemoryCache::new();
+        cache.insert(
+            "test:serde".to_string(),
+            VersionInfo {
+                latest: Some("1.3.0".to_string()),
+                versions: vec![
+                    "1.0.0".to_string(),
+                    "1.1.5".to_string(),
+                    "1.2.0".to_string(),
+                    "1.3.0".to_string(),
+                ],
+                vulnerabilities: vec![Vulnerability {
+                    id: "CVE-2021-23337".to_string(),
+                    severity: VulnerabilitySeverity::High,
+                    description: "example".to_string(),
+                    url: None,
+                    fixed_version: Some("1.2.0".to_string()),
+                    ranges: vec![VulnerableRange {
+                        introduced: None,
+                        fixed: Some("1.2.0".to_string()),
+                    }],
+                    aliases: vec![],
+                    related: vec![],
+                }],
+                ..Default::default()
+            },
+        );
+
+        let deps = vec![create_test_dependency("serde", "1.0.0", 5)];
+        let uri = Url::parse("file:///test/Cargo.toml").unwrap();
+
+        let actions = create_code_actions(
+            &deps,
+            &cache,
+            &uri,
+            full_range(),
+            FileType::Cargo,
+            &SemverScheme,
+            |dep| format!("test:{}", dep.name),
+            &CooldownWindow::disabled(),
+            VersionPreference::Highest,
+            |_| true,
+        );
+
+        let fix = actions
+            .iter()
+            .find_map(|action| match action {
+                CodeActionOrCommand::CodeAction(a) if a.title.contains("non-vulnerable") => {
+                    Some(a)
+                }
+                _ => None,
+            })
+            .expect("expected a security fix action");
+
+        // 1.2.0 is the lowest candidate that clears the advisory, not 1.3.0
+        // (the ordinary "latest" bump already offered alongside it).
+        assert!(fix.title.contains("1.2.0"));
+        let edits = &fix.edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri];
+        assert_eq!(edits[0].new_text, "1.2.0");
+    }
+
+    #[test]
+    fn test_no_security_fix_action_without_fixed_version() {
+        use crate::registries::{Vulnerability, VulnerabilitySeverity};
+
+        let cache = MemoryCache::new();
+        cache.insert(
+            "test:serde".to_string(),
+            VersionInfo {
+                latest: Some("1.0.0".to_string()),
+                versions: vec!["1.0.0".to_string()],
+                vulnerabilities: vec![Vulnerability {
+                    id: "CVE-2021-23337".to_string(),
+                    severity: VulnerabilitySeverity::High,
+                    description: "example".to_string(),
+                    url: None,
+                    fixed_version: None,
+                    ranges: vec![],
+                    aliases: vec![],
+                    related: vec![],
+                }],
+                ..Default::default()
+            },
+        );
+
+        let deps = vec![create_test_dependency("serde", "1.0.0", 5)];
+        let uri = Url::parse("file:///test/Cargo.toml").unwrap();
+
+        let actions = create_code_actions(
+            &deps,
+            &cache,
+            &uri,
+            full_range(),
+            FileType::Cargo,
+            &SemverScheme,
+            |dep| format!("test:{}", dep.name),
+            &CooldownWindow::disabled(),
+            VersionPreference::Highest,
+            |_| true,
+        );
+
+        // No known fix, and the dependency is already at `latest` - no
+        // action of any kind to offer.
+        assert_eq!(actions.len(), 0);
     }
 }
@@ -10,7 +10,7 @@ pub fn get_completions(
     dependencies: &[Dependency],
     position: Position,
     cache: &impl Cache,
-    cache_key_fn: impl Fn(&str) -> String,
+    cache_key_fn: impl Fn(&Dependency) -> String,
 ) -> Option<Vec<CompletionItem>> {
     // Find if we're inside a version field
     let dep = dependencies.iter().find(|d| {
@@ -19,7 +19,7 @@ pub fn get_completions(
             && position.character <= d.version_end
     })?;
 
-    let cache_key = cache_key_fn(&dep.name);
+    let cache_key = cache_key_fn(dep);
     let version_info = cache.get(&cache_key)?;
 
     // Return the last 10 versions as completions
@@ -64,6 +64,9 @@ mod tests {
             version_end: name.len() as u32 + 4 + version.len() as u32,
             dev: false,
             optional: false,
+            registry: None,
+            source: None,
+            git_ref: None,
         }
     }
 
@@ -90,7 +93,7 @@ mod tests {
             character: 13, // Within version_start to version_end
         };
 
-        let completions = get_completions(&deps, position, &cache, |name| format!("test:{}", name));
+        let completions = get_completions(&deps, position, &cache, |dep| format!("test:{}", dep.name));
 
         assert!(completions.is_some());
         let items = completions.unwrap();
@@ -118,7 +121,7 @@ mod tests {
             character: 0, // At the start, not in version
         };
 
-        let completions = get_completions(&deps, position, &cache, |name| format!("test:{}", name));
+        let completions = get_completions(&deps, position, &cache, |dep| format!("test:{}", dep.name));
 
         assert!(completions.is_none());
     }
@@ -141,7 +144,7 @@ mod tests {
             character: 13,
         };
 
-        let completions = get_completions(&deps, position, &cache, |name| format!("test:{}", name));
+        let completions = get_completions(&deps, position, &cache, |dep| format!("test:{}", dep.name));
 
         assert!(completions.is_none());
     }
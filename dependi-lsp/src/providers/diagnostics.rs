@@ -1,31 +1,129 @@
 //! Diagnostics provider for outdated dependencies and vulnerabilities
 
+use std::collections::{HashMap, HashSet};
+
 use tower_lsp::lsp_types::*;
 
 use crate::cache::Cache;
+use crate::config::{SecurityConfig, VersionPreference};
+use crate::lockfiles::{LockedPackage, dependency_graph::DependencyGraph};
 use crate::parsers::Dependency;
-use crate::providers::inlay_hints::{VersionStatus, compare_versions};
-use crate::registries::{Vulnerability, VulnerabilitySeverity};
+use crate::providers::inlay_hints::{
+    CooldownWindow, VersionStatus, compare_versions, satisfies_requirement,
+};
+use crate::registries::version_scheme::VersionScheme;
+use crate::registries::version_set::{nearest_safe_version, version_is_affected};
+use crate::registries::{Vulnerability, VulnerabilitySeverity, VersionInfo};
 
 /// Create diagnostics for a list of dependencies
+///
+/// Vulnerability scanning covers more than the manifest's direct
+/// dependencies: every package the lockfile actually resolves is checked
+/// too, since that's what really gets built. A transitive-only hit has no
+/// manifest line of its own, so it's anchored to the direct dependency
+/// that pulls it in (per the dependency graph's reverse BFS), with the
+/// chain spelled out in `related_information`.
 pub fn create_diagnostics(
+    uri: &Url,
     dependencies: &[Dependency],
     cache: &impl Cache,
-    cache_key_fn: impl Fn(&str) -> String,
+    cache_key_fn: impl Fn(&str, Option<&str>) -> String,
+    locked: &HashMap<String, LockedPackage>,
+    cooldown: &CooldownWindow,
+    security: &SecurityConfig,
+    scheme: &dyn VersionScheme,
+    preference: VersionPreference,
 ) -> Vec<Diagnostic> {
     let mut diagnostics = Vec::new();
 
+    // Direct manifest dependencies are the roots the dependency graph's
+    // reverse BFS walks back to - see `create_vulnerability_diagnostic`.
+    let roots: HashSet<String> = dependencies.iter().map(|dep| dep.name.clone()).collect();
+    let graph = DependencyGraph::from_locked(locked);
+
     for dep in dependencies {
         // Add outdated version diagnostic
-        if let Some(diag) = create_outdated_diagnostic(dep, cache, &cache_key_fn) {
+        if let Some(diag) = create_outdated_diagnostic(
+            dep,
+            cache,
+            |d| cache_key_fn(&d.name, d.registry.as_deref()),
+            cooldown,
+            scheme,
+            preference,
+        ) {
+            diagnostics.push(diag);
+        }
+
+        // Add vulnerability diagnostics, filtered to the configured severity
+        // band and ignore list, and to advisories that actually affect the
+        // resolved version - the lockfile's exact pin when one exists,
+        // since the declared requirement is a range that may not match what
+        // actually got installed.
+        let resolved_version = locked
+            .get(&dep.name)
+            .map(|pkg| pkg.version.as_str())
+            .unwrap_or(&dep.version);
+        let cache_key = cache_key_fn(&dep.name, dep.registry.as_deref());
+        if let Some(version_info) = cache.get(&cache_key) {
+            for vuln in &version_info.vulnerabilities {
+                if security.should_report(vuln) && version_is_affected(resolved_version, vuln) {
+                    diagnostics.push(create_vulnerability_diagnostic(
+                        uri,
+                        dep,
+                        &dep.name,
+                        vuln,
+                        version_info,
+                        security,
+                        &graph,
+                        &roots,
+                    ));
+                }
+            }
+        }
+
+        // Add lockfile drift/integrity diagnostics
+        if let Some(locked_pkg) = locked.get(&dep.name)
+            && let Some(diag) = create_lockfile_diagnostic(dep, locked_pkg)
+        {
             diagnostics.push(diag);
         }
+    }
 
-        // Add vulnerability diagnostics
-        let cache_key = cache_key_fn(&dep.name);
+    // Transitive-only packages: resolved by the lockfile but never declared
+    // in the manifest, so there's no `Dependency` to anchor a diagnostic on.
+    // Anchor instead on the direct dependency that pulls each one in, found
+    // via the same reverse BFS used for the "Pulled in via" chain. Lockfile
+    // formats that don't record per-package dependency edges (anything but
+    // Cargo today, see `DependencyGraph::from_locked`) have no route back to
+    // a root, so they're silently skipped here rather than misattributed.
+    let dep_by_name: HashMap<&str, &Dependency> =
+        dependencies.iter().map(|dep| (dep.name.as_str(), dep)).collect();
+    for package in locked.values() {
+        if roots.contains(&package.name) {
+            continue;
+        }
+        let Some(path) = graph.shortest_path_to_root(&package.name, &roots) else {
+            continue;
+        };
+        let Some(anchor) = path.first().and_then(|root| dep_by_name.get(root.as_str())) else {
+            continue;
+        };
+
+        let cache_key = cache_key_fn(&package.name, None);
         if let Some(version_info) = cache.get(&cache_key) {
             for vuln in &version_info.vulnerabilities {
-                diagnostics.push(create_vulnerability_diagnostic(dep, vuln));
+                if security.should_report(vuln) && version_is_affected(&package.version, vuln) {
+                    diagnostics.push(create_vulnerability_diagnostic(
+                        uri,
+                        anchor,
+                        &package.name,
+                        vuln,
+                        version_info,
+                        security,
+                        &graph,
+                        &roots,
+                    ));
+                }
             }
         }
     }
@@ -33,48 +131,159 @@ pub fn create_diagnostics(
     diagnostics
 }
 
+/// Create a diagnostic for a lockfile entry that has drifted from the
+/// manifest requirement, or is missing an integrity checksum. Drift is the
+/// more actionable signal, so it's reported in preference to a missing
+/// checksum when a package has both.
+fn create_lockfile_diagnostic(dep: &Dependency, locked: &LockedPackage) -> Option<Diagnostic> {
+    let range = Range {
+        start: Position {
+            line: dep.line,
+            character: dep.version_start,
+        },
+        end: Position {
+            line: dep.line,
+            character: dep.version_end,
+        },
+    };
+
+    if !satisfies_requirement(&dep.version, &locked.version) {
+        return Some(Diagnostic {
+            range,
+            severity: Some(DiagnosticSeverity::WARNING),
+            code: Some(NumberOrString::String("lockfile-drift".to_string())),
+            source: Some("dependi".to_string()),
+            message: format!(
+                "Locked version {} no longer satisfies requirement {}",
+                locked.version, dep.version
+            ),
+            related_information: None,
+            tags: None,
+            code_description: None,
+            data: None,
+        });
+    }
+
+    if locked.checksum.is_none() {
+        return Some(Diagnostic {
+            range,
+            severity: Some(DiagnosticSeverity::HINT),
+            code: Some(NumberOrString::String("missing-integrity".to_string())),
+            source: Some("dependi".to_string()),
+            message: format!(
+                "Lockfile entry for {} {} has no integrity checksum",
+                locked.name, locked.version
+            ),
+            related_information: None,
+            tags: None,
+            code_description: None,
+            data: None,
+        });
+    }
+
+    None
+}
+
 /// Create a diagnostic for an outdated dependency
 fn create_outdated_diagnostic(
     dep: &Dependency,
     cache: &impl Cache,
-    cache_key_fn: impl Fn(&str) -> String,
+    cache_key_fn: impl Fn(&Dependency) -> String,
+    cooldown: &CooldownWindow,
+    scheme: &dyn VersionScheme,
+    preference: VersionPreference,
 ) -> Option<Diagnostic> {
-    let cache_key = cache_key_fn(&dep.name);
+    let cache_key = cache_key_fn(dep);
     let version_info = cache.get(&cache_key)?;
 
-    match compare_versions(&dep.version, &version_info) {
-        VersionStatus::UpdateAvailable(new_version) => Some(Diagnostic {
-            range: Range {
-                start: Position {
-                    line: dep.line,
-                    character: dep.version_start,
+    match compare_versions(&dep.version, &version_info, cooldown, scheme, preference) {
+        VersionStatus::UpdateAvailable {
+            version,
+            breaking,
+            released_days_ago,
+            ..
+        } => {
+            let age = released_days_ago
+                .map(|days| format!(" (released {} days ago)", days))
+                .unwrap_or_default();
+
+            Some(Diagnostic {
+                range: Range {
+                    start: Position {
+                        line: dep.line,
+                        character: dep.version_start,
+                    },
+                    end: Position {
+                        line: dep.line,
+                        character: dep.version_end,
+                    },
                 },
-                end: Position {
-                    line: dep.line,
-                    character: dep.version_end,
+                // Breaking updates (outside the declared requirement) warrant more
+                // attention than an in-range patch/minor bump.
+                severity: Some(if breaking {
+                    DiagnosticSeverity::WARNING
+                } else {
+                    DiagnosticSeverity::HINT
+                }),
+                code: Some(NumberOrString::String(
+                    if breaking { "major-update" } else { "outdated" }.to_string(),
+                )),
+                source: Some("dependi".to_string()),
+                message: if breaking {
+                    format!(
+                        "Breaking update available: {} → {} (outside current requirement){}",
+                        dep.version, version, age
+                    )
+                } else {
+                    format!("Update available: {} → {}{}", dep.version, version, age)
                 },
-            },
-            severity: Some(DiagnosticSeverity::HINT),
-            code: Some(NumberOrString::String("outdated".to_string())),
-            source: Some("dependi".to_string()),
-            message: format!("Update available: {} → {}", dep.version, new_version),
-            related_information: None,
-            tags: None,
-            code_description: None,
-            data: None,
-        }),
+                related_information: None,
+                tags: None,
+                code_description: None,
+                data: None,
+            })
+        }
         VersionStatus::UpToDate | VersionStatus::Unknown => None,
     }
 }
 
 /// Create a diagnostic for a security vulnerability
-fn create_vulnerability_diagnostic(dep: &Dependency, vuln: &Vulnerability) -> Diagnostic {
-    // Map vulnerability severity to diagnostic severity
-    let severity = match vuln.severity {
+///
+/// `anchor` is the manifest dependency the diagnostic's range is drawn
+/// against, and `package_name` is the actually-vulnerable package. These are
+/// the same dependency for a direct hit; for a transitive-only hit,
+/// `anchor` is the direct dependency that pulls `package_name` in (there's
+/// no manifest line for a package nothing declares directly).
+fn create_vulnerability_diagnostic(
+    uri: &Url,
+    anchor: &Dependency,
+    package_name: &str,
+    vuln: &Vulnerability,
+    version_info: &VersionInfo,
+    security: &SecurityConfig,
+    graph: &DependencyGraph,
+    roots: &HashSet<String>,
+) -> Diagnostic {
+    // Policy-driven severity mapping: an ignored advisory downgraded rather
+    // than hidden (`should_report` let it through) always renders as a
+    // HINT; otherwise a configured per-severity override wins, falling back
+    // to the default Critical/High -> ERROR, Medium -> WARNING, Low -> HINT
+    // mapping.
+    let default_severity = match vuln.severity {
         VulnerabilitySeverity::Critical | VulnerabilitySeverity::High => DiagnosticSeverity::ERROR,
         VulnerabilitySeverity::Medium => DiagnosticSeverity::WARNING,
         VulnerabilitySeverity::Low => DiagnosticSeverity::HINT,
     };
+    let severity = if security.version >= 2
+        && security.downgrade_ignored
+        && security.is_ignored_advisory(&vuln.id)
+    {
+        DiagnosticSeverity::HINT
+    } else if let Some(level) = security.severity_level_override(vuln.severity) {
+        parse_diagnostic_severity(level).unwrap_or(default_severity)
+    } else {
+        default_severity
+    };
 
     let severity_text = match vuln.severity {
         VulnerabilitySeverity::Critical => "CRITICAL",
@@ -83,29 +292,52 @@ fn create_vulnerability_diagnostic(dep: &Dependency, vuln: &Vulnerability) -> Di
         VulnerabilitySeverity::Low => "LOW",
     };
 
-    let message = format!(
-        "Security vulnerability {} ({}): {}",
-        vuln.id,
-        severity_text,
-        truncate_string(&vuln.description, 150)
-    );
+    let message = if package_name == anchor.name {
+        // The nearest version that both satisfies the declared requirement
+        // and clears every cached advisory - see
+        // `registries::version_set::nearest_safe_version`. Only suggested
+        // when it's actually different from what's already declared.
+        let fix_suggestion = nearest_safe_version(
+            &anchor.version,
+            &version_info.versions,
+            &version_info.vulnerabilities,
+        )
+        .filter(|version| version != &anchor.version)
+        .map(|version| format!(" Update to {} to resolve.", version))
+        .unwrap_or_default();
 
-    Diagnostic {
-        range: Range {
-            start: Position {
-                line: dep.line,
-                character: dep.version_start,
-            },
-            end: Position {
-                line: dep.line,
-                character: dep.version_end,
-            },
+        format!(
+            "Security vulnerability {} ({}): {}{}",
+            vuln.id,
+            severity_text,
+            truncate_string(&vuln.description, 150),
+            fix_suggestion
+        )
+    } else {
+        format!(
+            "Security vulnerability {} ({}) in transitive dependency {}: {}",
+            vuln.id,
+            severity_text,
+            package_name,
+            truncate_string(&vuln.description, 150)
+        )
+    };
+
+    let range = Range {
+        start: Position {
+            line: anchor.line,
+            character: anchor.version_start,
         },
-        severity: Some(severity),
-        code: Some(NumberOrString::String(vuln.id.clone())),
-        source: Some("dependi-security".to_string()),
-        message,
-        related_information: vuln.url.as_ref().map(|url| {
+        end: Position {
+            line: anchor.line,
+            character: anchor.version_end,
+        },
+    };
+
+    let mut related_information: Vec<DiagnosticRelatedInformation> = vuln
+        .url
+        .as_ref()
+        .map(|url| {
             vec![DiagnosticRelatedInformation {
                 location: Location {
                     uri: Url::parse(url).unwrap_or_else(|_| {
@@ -115,7 +347,36 @@ fn create_vulnerability_diagnostic(dep: &Dependency, vuln: &Vulnerability) -> Di
                 },
                 message: "View security advisory".to_string(),
             }]
-        }),
+        })
+        .unwrap_or_default();
+
+    // For a transitive package, explain how it's pulled in - one entry per
+    // intermediate crate between the root manifest dependency and the
+    // vulnerable package itself. Direct dependencies resolve to a
+    // single-element path (just themselves), adding nothing here.
+    if let Some(path) = graph.shortest_path_to_root(package_name, roots)
+        && path.len() > 1
+    {
+        related_information.push(DiagnosticRelatedInformation {
+            location: Location {
+                uri: uri.clone(),
+                range,
+            },
+            message: format!("Pulled in via: {}", path.join(" → ")),
+        });
+    }
+
+    Diagnostic {
+        range,
+        severity: Some(severity),
+        code: Some(NumberOrString::String(vuln.id.clone())),
+        source: Some("dependi-security".to_string()),
+        message,
+        related_information: if related_information.is_empty() {
+            None
+        } else {
+            Some(related_information)
+        },
         tags: None,
         code_description: vuln
             .url
@@ -125,6 +386,22 @@ fn create_vulnerability_diagnostic(dep: &Dependency, vuln: &Vulnerability) -> Di
     }
 }
 
+/// Parse a configured diagnostic level override ("error", "warning",
+/// "information", or "hint", matched case-insensitively) to a
+/// `DiagnosticSeverity`, matching `SecurityConfig::severity_levels`'s
+/// documented value format. `None` for anything unrecognized, so the caller
+/// falls back to its own default mapping rather than silently miscoloring
+/// the diagnostic.
+fn parse_diagnostic_severity(level: &str) -> Option<DiagnosticSeverity> {
+    match level.to_lowercase().as_str() {
+        "error" => Some(DiagnosticSeverity::ERROR),
+        "warning" => Some(DiagnosticSeverity::WARNING),
+        "information" => Some(DiagnosticSeverity::INFORMATION),
+        "hint" => Some(DiagnosticSeverity::HINT),
+        _ => None,
+    }
+}
+
 /// Truncate a string to max length with ellipsis
 fn truncate_string(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
@@ -139,6 +416,11 @@ mod tests {
     use super::*;
     use crate::cache::MemoryCache;
     use crate::registries::VersionInfo;
+    use crate::registries::version_scheme::SemverScheme;
+
+    fn test_uri() -> Url {
+        Url::parse("file:///Cargo.toml").unwrap()
+    }
 
     fn create_test_dependency(name: &str, version: &str, line: u32) -> Dependency {
         Dependency {
@@ -151,11 +433,14 @@ mod tests {
             version_end: name.len() as u32 + 4 + version.len() as u32,
             dev: false,
             optional: false,
+            registry: None,
+            source: None,
+            git_ref: None,
         }
     }
 
     #[test]
-    fn test_create_diagnostic_outdated() {
+    fn test_create_diagnostic_outdated_breaking() {
         let cache = MemoryCache::new();
         cache.insert(
             "test:serde".to_string(),
@@ -165,11 +450,53 @@ mod tests {
             },
         );
 
+        // "1.0.0" is an exact/caret requirement, so a 2.0.0 latest falls outside it.
         let deps = vec![create_test_dependency("serde", "1.0.0", 5)];
-        let diagnostics = create_diagnostics(&deps, &cache, |name| format!("test:{}", name));
+        let diagnostics = create_diagnostics(
+            &test_uri(),
+            &deps,
+            &cache,
+            |name, _registry| format!("test:{}", name),
+            &HashMap::new(),
+            &CooldownWindow::disabled(),
+            &SecurityConfig::default(),
+            &SemverScheme,
+            VersionPreference::Highest,
+        );
 
         assert_eq!(diagnostics.len(), 1);
         assert!(diagnostics[0].message.contains("2.0.0"));
+        assert!(diagnostics[0].message.contains("Breaking"));
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::WARNING));
+    }
+
+    #[test]
+    fn test_create_diagnostic_outdated_in_range() {
+        let cache = MemoryCache::new();
+        cache.insert(
+            "test:serde".to_string(),
+            VersionInfo {
+                latest: Some("1.5.0".to_string()),
+                ..Default::default()
+            },
+        );
+
+        // "^1.0.0" is satisfied by 1.5.0, so the bump is non-breaking.
+        let deps = vec![create_test_dependency("serde", "^1.0.0", 5)];
+        let diagnostics = create_diagnostics(
+            &test_uri(),
+            &deps,
+            &cache,
+            |name, _registry| format!("test:{}", name),
+            &HashMap::new(),
+            &CooldownWindow::disabled(),
+            &SecurityConfig::default(),
+            &SemverScheme,
+            VersionPreference::Highest,
+        );
+
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.contains("1.5.0"));
         assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::HINT));
     }
 
@@ -185,7 +512,17 @@ mod tests {
         );
 
         let deps = vec![create_test_dependency("serde", "1.0.0", 5)];
-        let diagnostics = create_diagnostics(&deps, &cache, |name| format!("test:{}", name));
+        let diagnostics = create_diagnostics(
+            &test_uri(),
+            &deps,
+            &cache,
+            |name, _registry| format!("test:{}", name),
+            &HashMap::new(),
+            &CooldownWindow::disabled(),
+            &SecurityConfig::default(),
+            &SemverScheme,
+            VersionPreference::Highest,
+        );
 
         assert_eq!(diagnostics.len(), 0);
     }
@@ -194,7 +531,481 @@ mod tests {
     fn test_no_diagnostic_no_cache() {
         let cache = MemoryCache::new();
         let deps = vec![create_test_dependency("unknown", "1.0.0", 5)];
-        let diagnostics = create_diagnostics(&deps, &cache, |name| format!("test:{}", name));
+        let diagnostics = create_diagnostics(
+            &test_uri(),
+            &deps,
+            &cache,
+            |name, _registry| format!("test:{}", name),
+            &HashMap::new(),
+            &CooldownWindow::disabled(),
+            &SecurityConfig::default(),
+            &SemverScheme,
+            VersionPreference::Highest,
+        );
+
+        assert_eq!(diagnostics.len(), 0);
+    }
+
+    fn locked_package(name: &str, version: &str, checksum: Option<&str>) -> LockedPackage {
+        LockedPackage {
+            name: name.to_string(),
+            version: version.to_string(),
+            checksum: checksum.map(str::to_string),
+            optional: false,
+            dependencies: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_lockfile_drift_diagnostic() {
+        let cache = MemoryCache::new();
+        let deps = vec![create_test_dependency("serde", "^1.0.0", 5)];
+        let mut locked = HashMap::new();
+        locked.insert(
+            "serde".to_string(),
+            locked_package("serde", "2.0.0", Some("sha256:abc")),
+        );
+
+        let diagnostics =
+            create_diagnostics(
+                &test_uri(),
+                &deps,
+                &cache,
+                |name, _registry| format!("test:{}", name),
+                &locked,
+                &CooldownWindow::disabled(),
+                &SecurityConfig::default(),
+                &SemverScheme,
+                VersionPreference::Highest,
+            );
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code,
+            Some(NumberOrString::String("lockfile-drift".to_string()))
+        );
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::WARNING));
+    }
+
+    #[test]
+    fn test_missing_integrity_diagnostic() {
+        let cache = MemoryCache::new();
+        let deps = vec![create_test_dependency("serde", "^1.0.0", 5)];
+        let mut locked = HashMap::new();
+        locked.insert("serde".to_string(), locked_package("serde", "1.0.5", None));
+
+        let diagnostics =
+            create_diagnostics(
+                &test_uri(),
+                &deps,
+                &cache,
+                |name, _registry| format!("test:{}", name),
+                &locked,
+                &CooldownWindow::disabled(),
+                &SecurityConfig::default(),
+                &SemverScheme,
+                VersionPreference::Highest,
+            );
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code,
+            Some(NumberOrString::String("missing-integrity".to_string()))
+        );
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::HINT));
+    }
+
+    #[test]
+    fn test_no_lockfile_diagnostic_when_in_sync_and_checksummed() {
+        let cache = MemoryCache::new();
+        let deps = vec![create_test_dependency("serde", "^1.0.0", 5)];
+        let mut locked = HashMap::new();
+        locked.insert(
+            "serde".to_string(),
+            locked_package("serde", "1.0.5", Some("sha256:abc")),
+        );
+
+        let diagnostics =
+            create_diagnostics(
+                &test_uri(),
+                &deps,
+                &cache,
+                |name, _registry| format!("test:{}", name),
+                &locked,
+                &CooldownWindow::disabled(),
+                &SecurityConfig::default(),
+                &SemverScheme,
+                VersionPreference::Highest,
+            );
+
+        assert_eq!(diagnostics.len(), 0);
+    }
+
+    #[test]
+    fn test_outdated_diagnostic_includes_age_when_cooldown_enabled() {
+        let cache = MemoryCache::new();
+        let mut release_dates = HashMap::new();
+        release_dates.insert(
+            "2.0.0".to_string(),
+            chrono::Utc::now() - chrono::Duration::days(21),
+        );
+        cache.insert(
+            "test:serde".to_string(),
+            VersionInfo {
+                latest: Some("2.0.0".to_string()),
+                versions: vec!["2.0.0".to_string()],
+                release_dates,
+                ..Default::default()
+            },
+        );
+
+        let deps = vec![create_test_dependency("serde", "1.0.0", 5)];
+        let cooldown = CooldownWindow::from_config(&crate::config::CooldownConfig {
+            enabled: true,
+            days: 14,
+            strict: false,
+        });
+        let diagnostics = create_diagnostics(
+            &test_uri(),
+            &deps,
+            &cache,
+            |name, _registry| format!("test:{}", name),
+            &HashMap::new(),
+            &cooldown,
+            &SecurityConfig::default(),
+            &SemverScheme,
+            VersionPreference::Highest,
+        );
+
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.contains("released 21 days ago"));
+    }
+
+    #[test]
+    fn test_vulnerability_diagnostic_respects_severity_band() {
+        let cache = MemoryCache::new();
+        cache.insert(
+            "test:serde".to_string(),
+            VersionInfo {
+                latest: Some("1.0.0".to_string()),
+                vulnerabilities: vec![Vulnerability {
+                    id: "CVE-2021-23337".to_string(),
+                    severity: VulnerabilitySeverity::Low,
+                    description: "example".to_string(),
+                    url: None,
+                    fixed_version: None,
+                    ranges: vec![],
+                    aliases: vec![],
+                    related: vec![],
+                }],
+                ..Default::default()
+            },
+        );
+
+        let deps = vec![create_test_dependency("serde", "1.0.0", 5)];
+        let security = crate::config::SecurityConfig {
+            min_severity: "high".to_string(),
+            ..Default::default()
+        };
+        let diagnostics = create_diagnostics(
+            &test_uri(),
+            &deps,
+            &cache,
+            |name, _registry| format!("test:{}", name),
+            &HashMap::new(),
+            &CooldownWindow::disabled(),
+            &security,
+            &SemverScheme,
+            VersionPreference::Highest,
+        );
+
+        assert_eq!(diagnostics.len(), 0);
+    }
+
+    #[test]
+    fn test_vulnerability_diagnostic_direct_dependency_has_single_related_entry() {
+        let dep = create_test_dependency("serde", "1.0.0", 5);
+        let vuln = Vulnerability {
+            id: "CVE-2021-23337".to_string(),
+            severity: VulnerabilitySeverity::High,
+            description: "example".to_string(),
+            url: Some("https://example.com/advisory".to_string()),
+            fixed_version: None,
+            ranges: vec![],
+            aliases: vec![],
+            related: vec![],
+        };
+        let locked = HashMap::new();
+        let graph = crate::lockfiles::dependency_graph::DependencyGraph::from_locked(&locked);
+        let roots = HashSet::from(["serde".to_string()]);
+
+        let diagnostic = create_vulnerability_diagnostic(
+            &test_uri(),
+            &dep,
+            &dep.name,
+            &vuln,
+            &crate::registries::VersionInfo::default(),
+            &SecurityConfig::default(),
+            &graph,
+            &roots,
+        );
+
+        let related = diagnostic.related_information.unwrap();
+        assert_eq!(related.len(), 1);
+        assert_eq!(related[0].message, "View security advisory");
+    }
+
+    #[test]
+    fn test_vulnerability_diagnostic_transitive_dependency_includes_chain() {
+        let dep = create_test_dependency("h2", "0.4.0", 5);
+        let vuln = Vulnerability {
+            id: "RUSTSEC-2024-0001".to_string(),
+            severity: VulnerabilitySeverity::Critical,
+            description: "example".to_string(),
+            url: None,
+            fixed_version: None,
+            ranges: vec![],
+            aliases: vec![],
+            related: vec![],
+        };
+
+        let mut locked = HashMap::new();
+        locked.insert("hyper".to_string(), locked_package("hyper", "1.0.0", None));
+        locked.insert("h2".to_string(), locked_package("h2", "0.4.0", None));
+        // `dependencies` isn't threaded through `locked_package`'s helper
+        // signature, so set it directly on the entry that matters.
+        locked.get_mut("hyper").unwrap().dependencies = vec!["h2".to_string()];
+
+        let graph = crate::lockfiles::dependency_graph::DependencyGraph::from_locked(&locked);
+        let roots = HashSet::from(["hyper".to_string()]);
+
+        let diagnostic = create_vulnerability_diagnostic(
+            &test_uri(),
+            &dep,
+            &dep.name,
+            &vuln,
+            &crate::registries::VersionInfo::default(),
+            &SecurityConfig::default(),
+            &graph,
+            &roots,
+        );
+
+        let related = diagnostic.related_information.unwrap();
+        assert_eq!(related.len(), 1);
+        assert!(related[0].message.contains("hyper → h2"));
+    }
+
+    #[test]
+    fn test_vulnerability_diagnostic_suggests_nearest_safe_version() {
+        use crate::registries::VulnerableRange;
+
+        let dep = create_test_dependency("serde", "1.0.0", 5);
+        let vuln = Vulnerability {
+            id: "CVE-2021-23337".to_string(),
+            severity: VulnerabilitySeverity::High,
+            description: "example".to_string(),
+            url: None,
+            fixed_version: Some("1.2.0".to_string()),
+            ranges: vec![VulnerableRange {
+                introduced: None,
+                fixed: Some("1.2.0".to_string()),
+            }],
+            aliases: vec![],
+            related: vec![],
+        };
+        let version_info = VersionInfo {
+            latest: Some("1.5.0".to_string()),
+            versions: vec![
+                "1.0.0".to_string(),
+                "1.1.0".to_string(),
+                "1.2.0".to_string(),
+                "1.5.0".to_string(),
+            ],
+            ..Default::default()
+        };
+        let locked = HashMap::new();
+        let graph = crate::lockfiles::dependency_graph::DependencyGraph::from_locked(&locked);
+        let roots = HashSet::from(["serde".to_string()]);
+
+        let diagnostic = create_vulnerability_diagnostic(
+            &test_uri(),
+            &dep,
+            &dep.name,
+            &vuln,
+            &version_info,
+            &SecurityConfig::default(),
+            &graph,
+            &roots,
+        );
+
+        // 1.2.0 clears the advisory and is nearer than the registry's
+        // overall latest (1.5.0).
+        assert!(diagnostic.message.contains("Update to 1.2.0 to resolve"));
+    }
+
+    #[test]
+    fn test_vulnerability_diagnostic_honors_severity_level_override() {
+        let dep = create_test_dependency("serde", "1.0.0", 5);
+        let vuln = Vulnerability {
+            id: "CVE-2021-23337".to_string(),
+            severity: VulnerabilitySeverity::Medium,
+            description: "example".to_string(),
+            url: None,
+            fixed_version: None,
+            ranges: vec![],
+            aliases: vec![],
+            related: vec![],
+        };
+        let locked = HashMap::new();
+        let graph = crate::lockfiles::dependency_graph::DependencyGraph::from_locked(&locked);
+        let roots = HashSet::from(["serde".to_string()]);
+        let security = SecurityConfig {
+            version: 2,
+            severity_levels: HashMap::from([("medium".to_string(), "error".to_string())]),
+            ..Default::default()
+        };
+
+        let diagnostic = create_vulnerability_diagnostic(
+            &test_uri(),
+            &dep,
+            &dep.name,
+            &vuln,
+            &crate::registries::VersionInfo::default(),
+            &security,
+            &graph,
+            &roots,
+        );
+
+        assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::ERROR));
+    }
+
+    #[test]
+    fn test_vulnerability_diagnostic_downgrades_ignored_advisory_to_hint() {
+        let dep = create_test_dependency("serde", "1.0.0", 5);
+        let vuln = Vulnerability {
+            id: "CVE-2021-23337".to_string(),
+            severity: VulnerabilitySeverity::Critical,
+            description: "example".to_string(),
+            url: None,
+            fixed_version: None,
+            ranges: vec![],
+            aliases: vec![],
+            related: vec![],
+        };
+        let locked = HashMap::new();
+        let graph = crate::lockfiles::dependency_graph::DependencyGraph::from_locked(&locked);
+        let roots = HashSet::from(["serde".to_string()]);
+        let security = SecurityConfig {
+            version: 2,
+            downgrade_ignored: true,
+            ignore_advisories: vec!["CVE-2021-23337".to_string()],
+            ..Default::default()
+        };
+
+        let diagnostic = create_vulnerability_diagnostic(
+            &test_uri(),
+            &dep,
+            &dep.name,
+            &vuln,
+            &crate::registries::VersionInfo::default(),
+            &security,
+            &graph,
+            &roots,
+        );
+
+        assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::HINT));
+    }
+
+    #[test]
+    fn test_create_diagnostics_scans_transitive_only_lockfile_packages() {
+        let cache = MemoryCache::new();
+        cache.insert(
+            "test:h2".to_string(),
+            VersionInfo {
+                latest: Some("0.4.0".to_string()),
+                vulnerabilities: vec![Vulnerability {
+                    id: "RUSTSEC-2024-0001".to_string(),
+                    severity: VulnerabilitySeverity::Critical,
+                    description: "example".to_string(),
+                    url: None,
+                    fixed_version: None,
+                    ranges: vec![],
+                    aliases: vec![],
+                    related: vec![],
+                }],
+                ..Default::default()
+            },
+        );
+
+        // `hyper` is the only manifest dependency; `h2` is pulled in
+        // transitively and never appears in `dependencies`.
+        let deps = vec![create_test_dependency("hyper", "1.0.0", 5)];
+        let mut locked = HashMap::new();
+        locked.insert("hyper".to_string(), locked_package("hyper", "1.0.0", None));
+        locked.insert("h2".to_string(), locked_package("h2", "0.4.0", None));
+        locked.get_mut("hyper").unwrap().dependencies = vec!["h2".to_string()];
+
+        let diagnostics = create_diagnostics(
+            &test_uri(),
+            &deps,
+            &cache,
+            |name, _registry| format!("test:{}", name),
+            &locked,
+            &CooldownWindow::disabled(),
+            &SecurityConfig::default(),
+            &SemverScheme,
+            VersionPreference::Highest,
+        );
+
+        assert_eq!(diagnostics.len(), 1);
+        let diag = &diagnostics[0];
+        // No manifest line exists for `h2`, so the diagnostic is anchored to
+        // the `hyper` dependency that pulls it in.
+        assert_eq!(diag.range.start.line, 5);
+        assert!(diag.message.contains("transitive dependency h2"));
+        let related = diag.related_information.as_ref().unwrap();
+        assert!(related.iter().any(|r| r.message.contains("hyper → h2")));
+    }
+
+    #[test]
+    fn test_create_diagnostics_skips_transitive_package_unreachable_from_any_root() {
+        let cache = MemoryCache::new();
+        cache.insert(
+            "test:orphan".to_string(),
+            VersionInfo {
+                latest: Some("1.0.0".to_string()),
+                vulnerabilities: vec![Vulnerability {
+                    id: "RUSTSEC-2024-0002".to_string(),
+                    severity: VulnerabilitySeverity::Critical,
+                    description: "example".to_string(),
+                    url: None,
+                    fixed_version: None,
+                    ranges: vec![],
+                    aliases: vec![],
+                    related: vec![],
+                }],
+                ..Default::default()
+            },
+        );
+
+        let deps = vec![create_test_dependency("serde", "1.0.0", 5)];
+        let mut locked = HashMap::new();
+        locked.insert("serde".to_string(), locked_package("serde", "1.0.0", None));
+        // No lockfile edges record `orphan` as reachable from `serde`, so
+        // there's nowhere honest to anchor a diagnostic for it.
+        locked.insert("orphan".to_string(), locked_package("orphan", "1.0.0", None));
+
+        let diagnostics = create_diagnostics(
+            &test_uri(),
+            &deps,
+            &cache,
+            |name, _registry| format!("test:{}", name),
+            &locked,
+            &CooldownWindow::disabled(),
+            &SecurityConfig::default(),
+            &SemverScheme,
+            VersionPreference::Highest,
+        );
 
         assert_eq!(diagnostics.len(), 0);
     }
@@ -1,34 +1,151 @@
 //! Inlay hints provider for dependency versions
 
+use chrono::{DateTime, Duration, Utc};
 use tower_lsp::lsp_types::{InlayHint, InlayHintKind, InlayHintLabel, Position};
 
+use crate::backend::FileType;
+use crate::config::{CooldownConfig, SecurityConfig, VersionPreference};
+use crate::lockfiles::LockedPackage;
 use crate::parsers::Dependency;
-use crate::registries::{VersionInfo, VulnerabilitySeverity};
+use crate::providers::code_actions::make_edit;
+use crate::registries::version_scheme::{
+    SemverScheme, VersionScheme, normalize_version, resolve_update,
+};
+use crate::registries::{Vulnerability, VersionInfo, VulnerabilitySeverity};
+
+/// Cooldown window parameters for gating how soon a new version is recommended.
+///
+/// Built from [`CooldownConfig`] via [`CooldownWindow::from_config`]; kept
+/// separate from the config type itself so callers that don't have a
+/// `Config` at hand (tests, call sites without the cooldown feature) can
+/// use [`CooldownWindow::disabled`] directly.
+#[derive(Debug, Clone, Copy)]
+pub struct CooldownWindow {
+    /// Minimum age a version must have before it's recommended. `None` disables gating.
+    window: Option<Duration>,
+    /// Versions with no known release date are ineligible (`true`) or recommended anyway (`false`).
+    strict: bool,
+}
+
+impl CooldownWindow {
+    /// No cooldown gating: the newest available version is always eligible.
+    pub fn disabled() -> Self {
+        Self {
+            window: None,
+            strict: false,
+        }
+    }
+
+    /// Build a cooldown window from the resolved LSP config.
+    pub fn from_config(config: &CooldownConfig) -> Self {
+        if !config.enabled {
+            return Self::disabled();
+        }
+        Self {
+            window: Some(Duration::days(config.days as i64)),
+            strict: config.strict,
+        }
+    }
+}
 
 /// Result of comparing a dependency version with the latest available
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum VersionStatus {
     /// Version is up to date
     UpToDate,
-    /// Update available to the given version
-    UpdateAvailable(String),
+    /// An update exists. `breaking` distinguishes a version that still
+    /// satisfies the declared requirement (safe to bump in place) from one
+    /// that falls outside it (requires editing the requirement itself).
+    /// `compatible_version` is `Some` only when `breaking` is true and a
+    /// lower, still-compatible update is also available - letting the hint
+    /// tell the user a safe `1.2.3 -> 1.2.9` bump exists alongside the
+    /// `1.x -> 2.0` one it's recommending. `released_days_ago` is `Some` when
+    /// the registry reported a publish date for the recommended version
+    /// (used to surface its age to the user).
+    UpdateAvailable {
+        version: String,
+        breaking: bool,
+        compatible_version: Option<String>,
+        released_days_ago: Option<i64>,
+    },
     /// Could not determine version status
     Unknown,
 }
 
 /// Generate an inlay hint for a dependency
-pub fn create_inlay_hint(dep: &Dependency, version_info: Option<&VersionInfo>) -> InlayHint {
+pub fn create_inlay_hint(
+    dep: &Dependency,
+    version_info: Option<&VersionInfo>,
+    locked: Option<&LockedPackage>,
+    cooldown: &CooldownWindow,
+    security: &SecurityConfig,
+    scheme: &dyn VersionScheme,
+    file_type: FileType,
+    show_prereleases: bool,
+    preference: VersionPreference,
+) -> InlayHint {
+    if let Some((label, tooltip)) = create_sourced_hint(dep) {
+        return InlayHint {
+            position: Position {
+                line: dep.line,
+                character: dep.version_end + 1,
+            },
+            label: InlayHintLabel::String(format!(" {}", label)),
+            kind: Some(InlayHintKind::PARAMETER),
+            text_edits: None,
+            tooltip: Some(tower_lsp::lsp_types::InlayHintTooltip::String(tooltip)),
+            padding_left: Some(true),
+            padding_right: None,
+            data: None,
+        };
+    }
+
     let status = match version_info {
-        Some(info) => compare_versions(&dep.version, info),
+        Some(info) => compare_versions(&dep.version, info, cooldown, scheme, preference),
         None => VersionStatus::Unknown,
     };
 
-    // Check for vulnerabilities
-    let vuln_count = version_info
-        .map(|info| info.vulnerabilities.len())
-        .unwrap_or(0);
+    // Check for vulnerabilities, filtered to the configured severity band and
+    // ignore list so suppressed/accepted advisories don't clutter the hint
+    let reported_vulns: Vec<&Vulnerability> = version_info
+        .map(|info| {
+            info.vulnerabilities
+                .iter()
+                .filter(|vuln| security.should_report(vuln))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let (mut label, mut tooltip) =
+        create_hint_label_and_tooltip(&status, &reported_vulns, dep);
+
+    if let Some((locked_label, locked_tooltip)) = format_locked_suffix(dep, locked) {
+        label = format!("{} {}", label, locked_label);
+        tooltip = Some(match tooltip {
+            Some(existing) => format!("{}\n\n---\n{}", existing, locked_tooltip),
+            None => locked_tooltip,
+        });
+    }
+
+    if show_prereleases
+        && let Some(note) = prerelease_note(&dep.version, version_info, scheme)
+    {
+        tooltip = Some(match tooltip {
+            Some(existing) => format!("{}\n\n{}", existing, note),
+            None => note,
+        });
+    }
 
-    let (label, tooltip) = create_hint_label_and_tooltip(&status, vuln_count, dep, version_info);
+    // An available update gets a one-click edit bumping the declared version
+    // in place, the same edit a quick-fix code action would apply - reusing
+    // `make_edit` keeps the two entry points in sync rather than each
+    // formatting the replacement text its own way.
+    let text_edits = match &status {
+        VersionStatus::UpdateAvailable { version, .. } => {
+            Some(vec![make_edit(dep, version, file_type)])
+        }
+        VersionStatus::UpToDate | VersionStatus::Unknown => None,
+    };
 
     InlayHint {
         position: Position {
@@ -37,7 +154,7 @@ pub fn create_inlay_hint(dep: &Dependency, version_info: Option<&VersionInfo>) -
         },
         label: InlayHintLabel::String(format!(" {}", label)),
         kind: Some(InlayHintKind::PARAMETER),
-        text_edits: None,
+        text_edits,
         tooltip: tooltip.map(tower_lsp::lsp_types::InlayHintTooltip::String),
         padding_left: Some(true),
         padding_right: None,
@@ -45,25 +162,88 @@ pub fn create_inlay_hint(dep: &Dependency, version_info: Option<&VersionInfo>) -
     }
 }
 
+/// Build a "🔗 sourced" label/tooltip pair for a dependency resolved from
+/// git/path/a GitHub shorthand rather than a registry. There's no registry
+/// version to compare against, so this replaces the usual status hint
+/// entirely instead of decorating it.
+fn create_sourced_hint(dep: &Dependency) -> Option<(String, String)> {
+    let source = dep.source.as_deref()?;
+
+    let mut tooltip = format!("**Sourced externally:** {}", source);
+    if let Some(git_ref) = &dep.git_ref {
+        tooltip.push_str(&format!("\n**Ref:** {}", git_ref));
+    }
+    tooltip.push_str("\n\nNot resolvable against a registry.");
+
+    Some(("🔗 sourced".to_string(), tooltip))
+}
+
+/// Build a "🔒 <version>" label/tooltip pair for the locked resolution of a
+/// dependency, flagging drift from the declared requirement or a missing
+/// integrity checksum.
+fn format_locked_suffix(dep: &Dependency, locked: Option<&LockedPackage>) -> Option<(String, String)> {
+    let locked = locked?;
+    let drifted = !satisfies_requirement(&dep.version, &locked.version);
+
+    let icon = if drifted { "🔒!" } else { "🔒" };
+    let label = format!("{} {}", icon, locked.version);
+
+    let mut tooltip = format!("**Locked:** {}", locked.version);
+    if drifted {
+        tooltip.push_str(&format!(" (no longer satisfies `{}`)", dep.version));
+    }
+    if locked.checksum.is_none() {
+        tooltip.push_str("\n⚠ Missing integrity checksum in lockfile");
+    }
+
+    Some((label, tooltip))
+}
+
+/// Build an informational "pre-release available" tooltip note when the
+/// registry has reported a pre-release newer than `current`. This never
+/// changes the primary recommendation (see `compare_versions`, which only
+/// ever targets `info.latest`) - it's purely an annotation, following
+/// cargo-update's "(vX available)" treatment of pre-releases.
+fn prerelease_note(current: &str, info: Option<&VersionInfo>, scheme: &dyn VersionScheme) -> Option<String> {
+    let prerelease = info?.latest_prerelease.as_deref()?;
+    scheme
+        .is_newer(current, prerelease)
+        .then(|| format!("ℹ pre-release available: {}", prerelease))
+}
+
+/// Whether a locked/resolved version still satisfies a manifest requirement.
+///
+/// Requirements or locked versions this repo's manifests can't express as
+/// semver (e.g. Go's `v2.3.1` tags, or a VCS-pinned entry) fail open - we'd
+/// rather stay silent than flag drift we can't actually confirm.
+pub(crate) fn satisfies_requirement(requirement: &str, locked_version: &str) -> bool {
+    let Ok(req) = semver::VersionReq::parse(requirement.trim()) else {
+        return true;
+    };
+    let Ok(locked) = semver::Version::parse(&normalize_version(locked_version)) else {
+        return true;
+    };
+    req.matches(&locked)
+}
+
 /// Create label and tooltip based on version status and vulnerabilities
 fn create_hint_label_and_tooltip(
     status: &VersionStatus,
-    vuln_count: usize,
+    reported_vulns: &[&Vulnerability],
     dep: &Dependency,
-    version_info: Option<&VersionInfo>,
 ) -> (String, Option<String>) {
     // Handle vulnerabilities first (they take priority)
-    if vuln_count > 0 {
-        let vuln_label = format!("⚠ {}", vuln_count);
-        let vuln_tooltip = format_vulnerability_tooltip(version_info.unwrap());
+    if !reported_vulns.is_empty() {
+        let vuln_label = format!("⚠ {}", reported_vulns.len());
+        let vuln_tooltip = format_vulnerability_tooltip(reported_vulns);
 
         // Combine with update info if available
         return match status {
-            VersionStatus::UpdateAvailable(latest) => {
-                let label = format!("{} ⬆ {}", vuln_label, latest);
+            VersionStatus::UpdateAvailable { version, .. } => {
+                let label = format!("{} ⬆ {}", vuln_label, version);
                 let tooltip = format!(
                     "{}\n\n---\n**Update available:** {} → {}",
-                    vuln_tooltip, dep.version, latest
+                    vuln_tooltip, dep.version, version
                 );
                 (label, Some(tooltip))
             }
@@ -74,9 +254,38 @@ fn create_hint_label_and_tooltip(
     // No vulnerabilities - show version status
     match status {
         VersionStatus::UpToDate => ("✓".to_string(), Some("Up to date".to_string())),
-        VersionStatus::UpdateAvailable(latest) => {
-            let label = format!("⬆ {}", latest);
-            let tooltip = format!("Update available: {} → {}", dep.version, latest);
+        VersionStatus::UpdateAvailable {
+            version,
+            breaking,
+            compatible_version,
+            released_days_ago,
+        } => {
+            // `⬆` for an in-range bump (safe to apply as-is), `⇧` for one
+            // that falls outside the declared requirement (editing the
+            // requirement itself is needed), mirroring cargo-edit's
+            // "latest compatible vs latest incompatible" distinction.
+            let icon = if *breaking { "⇧" } else { "⬆" };
+            let label = format!("{} {}", icon, version);
+            let kind = if *breaking {
+                "Breaking update available"
+            } else {
+                "Update available"
+            };
+            let age = released_days_ago
+                .map(|days| format!(" (released {} days ago)", days))
+                .unwrap_or_default();
+            let mut tooltip = format!("{}: {} → {}{}", kind, dep.version, version, age);
+            if *breaking {
+                tooltip.push_str(
+                    "\n\nThis version is outside the declared requirement and may require manual intervention.",
+                );
+                if let Some(compatible) = compatible_version {
+                    tooltip.push_str(&format!(
+                        "\nA compatible update to {} is also available.",
+                        compatible
+                    ));
+                }
+            }
             (label, Some(tooltip))
         }
         VersionStatus::Unknown => (
@@ -87,13 +296,13 @@ fn create_hint_label_and_tooltip(
 }
 
 /// Format vulnerability details for tooltip
-fn format_vulnerability_tooltip(info: &VersionInfo) -> String {
+fn format_vulnerability_tooltip(reported_vulns: &[&Vulnerability]) -> String {
     let mut lines = vec![format!(
         "**⚠ {} Security Vulnerabilities Found**\n",
-        info.vulnerabilities.len()
+        reported_vulns.len()
     )];
 
-    for (i, vuln) in info.vulnerabilities.iter().take(5).enumerate() {
+    for (i, vuln) in reported_vulns.iter().take(5).enumerate() {
         let severity_icon = match vuln.severity {
             VulnerabilitySeverity::Critical => "🔴 CRITICAL",
             VulnerabilitySeverity::High => "🟠 HIGH",
@@ -114,10 +323,10 @@ fn format_vulnerability_tooltip(info: &VersionInfo) -> String {
         }
     }
 
-    if info.vulnerabilities.len() > 5 {
+    if reported_vulns.len() > 5 {
         lines.push(format!(
             "\n... and {} more vulnerabilities",
-            info.vulnerabilities.len() - 5
+            reported_vulns.len() - 5
         ));
     }
 
@@ -133,69 +342,183 @@ fn truncate_string(s: &str, max_len: usize) -> String {
     }
 }
 
-/// Compare a dependency version with the latest available
-pub fn compare_versions(current: &str, info: &VersionInfo) -> VersionStatus {
+/// Compare a dependency version with the latest available, subject to a
+/// cooldown window.
+///
+/// Delegates ordering and requirement-matching to `scheme`, the ecosystem's
+/// [`VersionScheme`] (Cargo's caret/tilde syntax under [`SemverScheme`],
+/// NuGet's 4-component/bracket-range rules under `NuGetScheme`, etc.) - if
+/// the absolute latest version falls outside the declared requirement, the
+/// update is flagged as breaking, and `compatible_version` is filled in
+/// with the highest version that still satisfies the declared requirement
+/// (via [`VersionScheme::latest_satisfying`]) when one exists and differs
+/// from the recommended, breaking version.
+///
+/// When `cooldown` has a window configured, the absolute latest version
+/// isn't automatically preferred - candidates are walked newest to oldest
+/// and the first one old enough to clear the window is recommended instead,
+/// using `info.release_dates` for publish dates. A version with no known
+/// release date is skipped in strict mode, or accepted anyway otherwise. If
+/// no candidate clears the window, the dependency is reported up to date.
+/// Cooldown-gated candidate selection is currently SemVer-only (see
+/// [`eligible_update_candidates`]) regardless of `scheme`.
+///
+/// Under [`VersionPreference::LowestCompatible`], none of the above applies:
+/// the recommendation is instead the oldest version that still satisfies
+/// the declared requirement (Cargo's `minimal-versions` resolution), and
+/// `cooldown` is ignored - there's nothing to gate when the candidate is
+/// already the oldest one in range.
+pub fn compare_versions(
+    current: &str,
+    info: &VersionInfo,
+    cooldown: &CooldownWindow,
+    scheme: &dyn VersionScheme,
+    preference: VersionPreference,
+) -> VersionStatus {
+    if preference == VersionPreference::LowestCompatible {
+        return compare_versions_lowest_compatible(current, info, scheme);
+    }
+
     let Some(latest) = &info.latest else {
         return VersionStatus::Unknown;
     };
 
-    // Normalize versions for comparison
-    let current_normalized = normalize_version(current);
-    let latest_normalized = normalize_version(latest);
-
-    // Parse as semver for proper comparison
-    match (
-        semver::Version::parse(&current_normalized),
-        semver::Version::parse(&latest_normalized),
-    ) {
-        (Ok(current_ver), Ok(latest_ver)) => {
-            if current_ver >= latest_ver {
-                VersionStatus::UpToDate
-            } else {
-                VersionStatus::UpdateAvailable(latest.clone())
-            }
-        }
-        _ => {
-            // Fallback to string comparison if semver parsing fails
-            if current_normalized == latest_normalized {
-                VersionStatus::UpToDate
-            } else {
-                VersionStatus::UpdateAvailable(latest.clone())
+    if !scheme.is_newer(current, latest) {
+        return VersionStatus::UpToDate;
+    }
+
+    if cooldown.window.is_none() {
+        // Does the highest available version still satisfy the declared
+        // requirement? If so this is a safe in-range bump; otherwise it's
+        // breaking - and `resolve_update` tells us what the safe bump would
+        // have been instead.
+        let breaking = !scheme.satisfies(current, latest);
+        let compatible_version = breaking
+            .then(|| resolve_update(current, info, scheme).compatible_latest)
+            .flatten();
+
+        return VersionStatus::UpdateAvailable {
+            version: latest.clone(),
+            breaking,
+            compatible_version,
+            released_days_ago: None,
+        };
+    }
+
+    let Some(current_ver) = parse_current_version(current) else {
+        return VersionStatus::UpToDate;
+    };
+
+    let now = Utc::now();
+    let chosen = eligible_update_candidates(&current_ver, info, cooldown).into_iter().next();
+
+    match chosen {
+        Some((raw, _, released_at)) => {
+            let breaking = !scheme.satisfies(current, raw);
+            let compatible_version = compatible_update(current, raw, info, scheme);
+            let released_days_ago = released_at.map(|dt| now.signed_duration_since(dt).num_days());
+
+            VersionStatus::UpdateAvailable {
+                version: raw.to_string(),
+                breaking,
+                compatible_version,
+                released_days_ago,
             }
         }
+        None => VersionStatus::UpToDate,
+    }
+}
+
+/// The `VersionPreference::LowestCompatible` branch of [`compare_versions`]:
+/// recommend the oldest version satisfying the declared requirement rather
+/// than the newest. Never breaking - by construction, the recommendation is
+/// always in range - so there's no compatible-version fallback to surface.
+fn compare_versions_lowest_compatible(
+    current: &str,
+    info: &VersionInfo,
+    scheme: &dyn VersionScheme,
+) -> VersionStatus {
+    let Some(earliest) = scheme.earliest_satisfying(current, &info.versions) else {
+        return VersionStatus::UpToDate;
+    };
+
+    if !scheme.is_newer(current, earliest) {
+        return VersionStatus::UpToDate;
+    }
+
+    VersionStatus::UpdateAvailable {
+        version: earliest.to_string(),
+        breaking: false,
+        compatible_version: None,
+        released_days_ago: None,
     }
 }
 
-/// Normalize a version string for comparison
-/// Handles version specifiers like ^, ~, >=, etc.
-fn normalize_version(version: &str) -> String {
-    let version = version.trim();
-
-    // Remove common prefixes
-    let version = version
-        .strip_prefix('^')
-        .or_else(|| version.strip_prefix('~'))
-        .or_else(|| version.strip_prefix(">="))
-        .or_else(|| version.strip_prefix("<="))
-        .or_else(|| version.strip_prefix('>'))
-        .or_else(|| version.strip_prefix('<'))
-        .or_else(|| version.strip_prefix('='))
-        .unwrap_or(version);
-
-    // Handle version ranges like ">=1.0, <2.0" - take the first part
-    let version = version.split(',').next().unwrap_or(version).trim();
-
-    // Ensure we have at least major.minor.patch
-    let parts: Vec<&str> = version.split('.').collect();
-    match parts.len() {
-        1 => format!("{}.0.0", parts[0]),
-        2 => format!("{}.{}.0", parts[0], parts[1]),
-        _ => version.to_string(),
+/// The highest version in `info.versions` that still satisfies `current`
+/// (the declared requirement), when `recommended` - the version actually
+/// being proposed - falls outside it. `None` either when the recommended
+/// version is itself in-range (there's nothing extra to surface) or no
+/// in-range version exists at all.
+fn compatible_update(
+    current: &str,
+    recommended: &str,
+    info: &VersionInfo,
+    scheme: &dyn VersionScheme,
+) -> Option<String> {
+    if scheme.satisfies(current, recommended) {
+        return None;
     }
+    scheme
+        .latest_satisfying(current, &info.versions)
+        .map(str::to_string)
+}
+
+/// Versions from `info.versions` newer than `current_ver`, sorted newest
+/// first and filtered to whatever the cooldown window allows right now.
+/// Shared by `compare_versions` and the tiered "compatible vs. latest
+/// major" update actions, which both need the same "what's safe to
+/// recommend" candidate set.
+pub(crate) fn eligible_update_candidates<'a>(
+    current_ver: &semver::Version,
+    info: &'a VersionInfo,
+    cooldown: &CooldownWindow,
+) -> Vec<(&'a str, semver::Version, Option<DateTime<Utc>>)> {
+    let now = Utc::now();
+    let mut candidates: Vec<(&str, semver::Version)> = info
+        .versions
+        .iter()
+        .filter_map(|v| {
+            semver::Version::parse(&normalize_version(v))
+                .ok()
+                .map(|parsed| (v.as_str(), parsed))
+        })
+        .filter(|(_, parsed)| parsed > current_ver)
+        .collect();
+    candidates.sort_by(|a, b| b.1.cmp(&a.1));
+
+    candidates
+        .into_iter()
+        .filter_map(|(raw, parsed)| match cooldown.window {
+            Some(window) => match info.release_dates.get(raw) {
+                Some(released_at) => (now.signed_duration_since(*released_at) >= window)
+                    .then_some((raw, parsed, Some(*released_at))),
+                None => (!cooldown.strict).then_some((raw, parsed, None)),
+            },
+            None => Some((raw, parsed, info.release_dates.get(raw).copied())),
+        })
+        .collect()
+}
+
+/// Parse a dependency's declared version/requirement string down to a
+/// concrete semver, applying the same normalization `compare_versions` uses.
+pub(crate) fn parse_current_version(current: &str) -> Option<semver::Version> {
+    semver::Version::parse(&normalize_version(current)).ok()
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use super::*;
 
     fn make_version_info(latest: &str) -> VersionInfo {
@@ -209,7 +532,13 @@ mod tests {
     fn test_compare_versions_up_to_date() {
         let info = make_version_info("1.0.0");
         assert!(matches!(
-            compare_versions("1.0.0", &info),
+            compare_versions(
+                "1.0.0",
+                &info,
+                &CooldownWindow::disabled(),
+                &SemverScheme,
+                VersionPreference::Highest,
+            ),
             VersionStatus::UpToDate
         ));
     }
@@ -217,8 +546,14 @@ mod tests {
     #[test]
     fn test_compare_versions_update_available() {
         let info = make_version_info("2.0.0");
-        match compare_versions("1.0.0", &info) {
-            VersionStatus::UpdateAvailable(v) => assert_eq!(v, "2.0.0"),
+        match compare_versions(
+            "1.0.0",
+            &info,
+            &CooldownWindow::disabled(),
+            &SemverScheme,
+            VersionPreference::Highest,
+        ) {
+            VersionStatus::UpdateAvailable { version, .. } => assert_eq!(version, "2.0.0"),
             _ => panic!("Expected UpdateAvailable"),
         }
     }
@@ -226,8 +561,14 @@ mod tests {
     #[test]
     fn test_compare_versions_with_caret() {
         let info = make_version_info("1.5.0");
-        match compare_versions("^1.0", &info) {
-            VersionStatus::UpdateAvailable(v) => assert_eq!(v, "1.5.0"),
+        match compare_versions(
+            "^1.0",
+            &info,
+            &CooldownWindow::disabled(),
+            &SemverScheme,
+            VersionPreference::Highest,
+        ) {
+            VersionStatus::UpdateAvailable { version, .. } => assert_eq!(version, "1.5.0"),
             _ => panic!("Expected UpdateAvailable"),
         }
     }
@@ -235,20 +576,85 @@ mod tests {
     #[test]
     fn test_compare_versions_with_tilde() {
         let info = make_version_info("1.0.5");
-        match compare_versions("~1.0.0", &info) {
-            VersionStatus::UpdateAvailable(v) => assert_eq!(v, "1.0.5"),
+        match compare_versions(
+            "~1.0.0",
+            &info,
+            &CooldownWindow::disabled(),
+            &SemverScheme,
+            VersionPreference::Highest,
+        ) {
+            VersionStatus::UpdateAvailable { version, .. } => assert_eq!(version, "1.0.5"),
+            _ => panic!("Expected UpdateAvailable"),
+        }
+    }
+
+    #[test]
+    fn test_compare_versions_in_range_is_non_breaking() {
+        let info = make_version_info("1.5.0");
+        match compare_versions(
+            "^1.0.0",
+            &info,
+            &CooldownWindow::disabled(),
+            &SemverScheme,
+            VersionPreference::Highest,
+        ) {
+            VersionStatus::UpdateAvailable { breaking, .. } => assert!(!breaking),
+            _ => panic!("Expected UpdateAvailable"),
+        }
+    }
+
+    #[test]
+    fn test_compare_versions_out_of_range_is_breaking() {
+        let info = make_version_info("2.0.0");
+        match compare_versions(
+            "^1.0.0",
+            &info,
+            &CooldownWindow::disabled(),
+            &SemverScheme,
+            VersionPreference::Highest,
+        ) {
+            VersionStatus::UpdateAvailable { breaking, .. } => assert!(breaking),
             _ => panic!("Expected UpdateAvailable"),
         }
     }
 
     #[test]
-    fn test_normalize_version() {
-        assert_eq!(normalize_version("1.0.0"), "1.0.0");
-        assert_eq!(normalize_version("^1.0"), "1.0.0");
-        assert_eq!(normalize_version("~1.0.0"), "1.0.0");
-        assert_eq!(normalize_version(">=1.0, <2.0"), "1.0.0");
-        assert_eq!(normalize_version("1"), "1.0.0");
-        assert_eq!(normalize_version("1.2"), "1.2.0");
+    fn test_compare_versions_lowest_compatible_recommends_oldest_in_range() {
+        let mut info = make_version_info("2.0.0");
+        info.versions = vec!["1.2.0".to_string(), "1.5.0".to_string(), "2.0.0".to_string()];
+
+        match compare_versions(
+            "^1.0.0",
+            &info,
+            &CooldownWindow::disabled(),
+            &SemverScheme,
+            VersionPreference::LowestCompatible,
+        ) {
+            VersionStatus::UpdateAvailable {
+                version, breaking, ..
+            } => {
+                assert_eq!(version, "1.2.0");
+                assert!(!breaking);
+            }
+            other => panic!("Expected UpdateAvailable, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_compare_versions_lowest_compatible_up_to_date_when_already_minimal() {
+        let mut info = make_version_info("2.0.0");
+        info.versions = vec!["1.0.0".to_string(), "2.0.0".to_string()];
+
+        assert_eq!(
+            compare_versions(
+                "^1.0.0",
+                &info,
+                &CooldownWindow::disabled(),
+                &SemverScheme,
+                VersionPreference::LowestCompatible,
+            ),
+            VersionStatus::UpToDate
+        );
     }
 
     #[test]
@@ -263,9 +669,22 @@ mod tests {
             version_end: 16,
             dev: false,
             optional: false,
+            registry: None,
+            source: None,
+            git_ref: None,
         };
         let info = make_version_info("1.0.0");
-        let hint = create_inlay_hint(&dep, Some(&info));
+        let hint = create_inlay_hint(
+            &dep,
+            Some(&info),
+            None,
+            &CooldownWindow::disabled(),
+            &SecurityConfig::default(),
+            &SemverScheme,
+            FileType::Cargo,
+            false,
+            VersionPreference::Highest,
+        );
 
         assert_eq!(hint.position.line, 5);
         match hint.label {
@@ -286,9 +705,22 @@ mod tests {
             version_end: 16,
             dev: false,
             optional: false,
+            registry: None,
+            source: None,
+            git_ref: None,
         };
         let info = make_version_info("2.0.0");
-        let hint = create_inlay_hint(&dep, Some(&info));
+        let hint = create_inlay_hint(
+            &dep,
+            Some(&info),
+            None,
+            &CooldownWindow::disabled(),
+            &SecurityConfig::default(),
+            &SemverScheme,
+            FileType::Cargo,
+            false,
+            VersionPreference::Highest,
+        );
 
         match hint.label {
             InlayHintLabel::String(s) => {
@@ -297,5 +729,504 @@ mod tests {
             }
             _ => panic!("Expected string label"),
         }
+
+        let edits = hint.text_edits.expect("expected a text edit for the update");
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].new_text, "2.0.0");
+        assert_eq!(edits[0].range.start, Position::new(5, 9));
+        assert_eq!(edits[0].range.end, Position::new(5, 16));
+    }
+
+    #[test]
+    fn test_create_inlay_hint_up_to_date_has_no_text_edit() {
+        let dep = Dependency {
+            name: "serde".to_string(),
+            version: "1.0.0".to_string(),
+            line: 5,
+            name_start: 0,
+            name_end: 5,
+            version_start: 9,
+            version_end: 16,
+            dev: false,
+            optional: false,
+            registry: None,
+            source: None,
+            git_ref: None,
+        };
+        let info = make_version_info("1.0.0");
+        let hint = create_inlay_hint(
+            &dep,
+            Some(&info),
+            None,
+            &CooldownWindow::disabled(),
+            &SecurityConfig::default(),
+            &SemverScheme,
+            FileType::Cargo,
+            false,
+            VersionPreference::Highest,
+        );
+
+        assert!(hint.text_edits.is_none());
+    }
+
+    #[test]
+    fn test_create_inlay_hint_prerelease_note_shown_when_enabled() {
+        let dep = Dependency {
+            name: "serde".to_string(),
+            version: "1.0.0".to_string(),
+            line: 5,
+            name_start: 0,
+            name_end: 5,
+            version_start: 9,
+            version_end: 16,
+            dev: false,
+            optional: false,
+            registry: None,
+            source: None,
+            git_ref: None,
+        };
+        let mut info = make_version_info("1.0.0");
+        info.latest_prerelease = Some("1.1.0-beta.1".to_string());
+        let hint = create_inlay_hint(
+            &dep,
+            Some(&info),
+            None,
+            &CooldownWindow::disabled(),
+            &SecurityConfig::default(),
+            &SemverScheme,
+            FileType::Cargo,
+            true,
+            VersionPreference::Highest,
+        );
+
+        let tooltip = match hint.tooltip {
+            Some(tower_lsp::lsp_types::InlayHintTooltip::String(s)) => s,
+            _ => panic!("Expected string tooltip"),
+        };
+        assert!(tooltip.contains("pre-release available: 1.1.0-beta.1"));
+    }
+
+    #[test]
+    fn test_create_inlay_hint_prerelease_note_hidden_when_disabled() {
+        let dep = Dependency {
+            name: "serde".to_string(),
+            version: "1.0.0".to_string(),
+            line: 5,
+            name_start: 0,
+            name_end: 5,
+            version_start: 9,
+            version_end: 16,
+            dev: false,
+            optional: false,
+            registry: None,
+            source: None,
+            git_ref: None,
+        };
+        let mut info = make_version_info("1.0.0");
+        info.latest_prerelease = Some("1.1.0-beta.1".to_string());
+        let hint = create_inlay_hint(
+            &dep,
+            Some(&info),
+            None,
+            &CooldownWindow::disabled(),
+            &SecurityConfig::default(),
+            &SemverScheme,
+            FileType::Cargo,
+            false,
+            VersionPreference::Highest,
+        );
+
+        let tooltip = match hint.tooltip {
+            Some(tower_lsp::lsp_types::InlayHintTooltip::String(s)) => s,
+            _ => panic!("Expected string tooltip"),
+        };
+        assert!(!tooltip.contains("pre-release available"));
+    }
+
+    #[test]
+    fn test_create_inlay_hint_breaking_update_uses_distinct_icon_and_tooltip() {
+        let dep = Dependency {
+            name: "serde".to_string(),
+            version: "^1.0.0".to_string(),
+            line: 5,
+            name_start: 0,
+            name_end: 5,
+            version_start: 9,
+            version_end: 16,
+            dev: false,
+            optional: false,
+            registry: None,
+            source: None,
+            git_ref: None,
+        };
+        let info = make_version_info("2.0.0");
+        let hint = create_inlay_hint(
+            &dep,
+            Some(&info),
+            None,
+            &CooldownWindow::disabled(),
+            &SecurityConfig::default(),
+            &SemverScheme,
+            FileType::Cargo,
+            false,
+            VersionPreference::Highest,
+        );
+
+        match hint.label {
+            InlayHintLabel::String(s) => assert!(s.contains("⇧")),
+            _ => panic!("Expected string label"),
+        }
+        match hint.tooltip {
+            Some(tower_lsp::lsp_types::InlayHintTooltip::String(s)) => {
+                assert!(s.contains("manual intervention"));
+            }
+            _ => panic!("Expected string tooltip"),
+        }
+    }
+
+    #[test]
+    fn test_compare_versions_breaking_update_surfaces_compatible_alternative() {
+        let mut info = make_version_info("2.0.0");
+        info.versions = vec!["1.5.0".to_string(), "2.0.0".to_string()];
+
+        match compare_versions(
+            "^1.0.0",
+            &info,
+            &CooldownWindow::disabled(),
+            &SemverScheme,
+            VersionPreference::Highest,
+        ) {
+            VersionStatus::UpdateAvailable {
+                breaking,
+                compatible_version,
+                ..
+            } => {
+                assert!(breaking);
+                assert_eq!(compatible_version, Some("1.5.0".to_string()));
+            }
+            other => panic!("Expected UpdateAvailable, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_compare_versions_non_breaking_update_has_no_compatible_alternative() {
+        let mut info = make_version_info("1.5.0");
+        info.versions = vec!["1.5.0".to_string()];
+
+        match compare_versions(
+            "^1.0.0",
+            &info,
+            &CooldownWindow::disabled(),
+            &SemverScheme,
+            VersionPreference::Highest,
+        ) {
+            VersionStatus::UpdateAvailable {
+                breaking,
+                compatible_version,
+                ..
+            } => {
+                assert!(!breaking);
+                assert_eq!(compatible_version, None);
+            }
+            other => panic!("Expected UpdateAvailable, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_create_inlay_hint_breaking_update_tooltip_mentions_compatible_alternative() {
+        let dep = Dependency {
+            name: "serde".to_string(),
+            version: "^1.0.0".to_string(),
+            line: 5,
+            name_start: 0,
+            name_end: 5,
+            version_start: 9,
+            version_end: 16,
+            dev: false,
+            optional: false,
+            registry: None,
+            source: None,
+            git_ref: None,
+        };
+        let mut info = make_version_info("2.0.0");
+        info.versions = vec!["1.5.0".to_string(), "2.0.0".to_string()];
+        let hint = create_inlay_hint(
+            &dep,
+            Some(&info),
+            None,
+            &CooldownWindow::disabled(),
+            &SecurityConfig::default(),
+            &SemverScheme,
+            FileType::Cargo,
+            false,
+            VersionPreference::Highest,
+        );
+
+        match hint.tooltip {
+            Some(tower_lsp::lsp_types::InlayHintTooltip::String(s)) => {
+                assert!(s.contains("compatible update to 1.5.0"));
+            }
+            _ => panic!("Expected string tooltip"),
+        }
+    }
+
+    #[test]
+    fn test_create_inlay_hint_sourced_externally() {
+        let dep = Dependency {
+            name: "my_gem".to_string(),
+            version: String::new(),
+            line: 5,
+            name_start: 0,
+            name_end: 6,
+            version_start: 6,
+            version_end: 6,
+            dev: false,
+            optional: false,
+            registry: None,
+            source: Some("git: https://github.com/user/my_gem.git".to_string()),
+            git_ref: Some("main".to_string()),
+        };
+        let hint = create_inlay_hint(
+            &dep,
+            None,
+            None,
+            &CooldownWindow::disabled(),
+            &SecurityConfig::default(),
+            &SemverScheme,
+            FileType::Cargo,
+            false,
+            VersionPreference::Highest,
+        );
+
+        match hint.label {
+            InlayHintLabel::String(s) => assert!(s.contains("sourced")),
+            _ => panic!("Expected string label"),
+        }
+        match hint.tooltip {
+            Some(tower_lsp::lsp_types::InlayHintTooltip::String(s)) => {
+                assert!(s.contains("git: https://github.com/user/my_gem.git"));
+                assert!(s.contains("main"));
+            }
+            _ => panic!("Expected string tooltip"),
+        }
+    }
+
+    #[test]
+    fn test_create_inlay_hint_ignores_vulnerability_outside_severity_band() {
+        let dep = Dependency {
+            name: "serde".to_string(),
+            version: "1.0.0".to_string(),
+            line: 5,
+            name_start: 0,
+            name_end: 5,
+            version_start: 9,
+            version_end: 16,
+            dev: false,
+            optional: false,
+            registry: None,
+            source: None,
+            git_ref: None,
+        };
+        let info = VersionInfo {
+            latest: Some("1.0.0".to_string()),
+            vulnerabilities: vec![Vulnerability {
+                id: "CVE-2021-23337".to_string(),
+                severity: VulnerabilitySeverity::Low,
+                description: "example".to_string(),
+                url: None,
+                fixed_version: None,
+                ranges: vec![],
+                aliases: vec![],
+                related: vec![],
+            }],
+            ..Default::default()
+        };
+        let security = SecurityConfig {
+            min_severity: "high".to_string(),
+            ..Default::default()
+        };
+        let hint = create_inlay_hint(
+            &dep,
+            Some(&info),
+            None,
+            &CooldownWindow::disabled(),
+            &security,
+            &SemverScheme,
+            FileType::Cargo,
+            false,
+            VersionPreference::Highest,
+        );
+
+        match hint.label {
+            InlayHintLabel::String(s) => assert!(s.contains("✓")),
+            _ => panic!("Expected string label"),
+        }
+    }
+
+    fn make_version_info_with_dates(
+        latest: &str,
+        versions: &[(&str, Option<DateTime<Utc>>)],
+    ) -> VersionInfo {
+        let mut release_dates = HashMap::new();
+        let mut all_versions = Vec::new();
+        for (version, released_at) in versions {
+            all_versions.push(version.to_string());
+            if let Some(dt) = released_at {
+                release_dates.insert(version.to_string(), *dt);
+            }
+        }
+
+        VersionInfo {
+            latest: Some(latest.to_string()),
+            versions: all_versions,
+            release_dates,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_compare_versions_cooldown_skips_recent_version() {
+        let now = Utc::now();
+        let info = make_version_info_with_dates(
+            "2.0.0",
+            &[
+                ("2.0.0", Some(now - Duration::days(2))),
+                ("1.5.0", Some(now - Duration::days(30))),
+            ],
+        );
+        let cooldown = CooldownWindow {
+            window: Some(Duration::days(14)),
+            strict: false,
+        };
+
+        match compare_versions(
+            "1.0.0",
+            &info,
+            &cooldown,
+            &SemverScheme,
+            VersionPreference::Highest,
+        ) {
+            VersionStatus::UpdateAvailable {
+                version,
+                released_days_ago,
+                ..
+            } => {
+                assert_eq!(version, "1.5.0");
+                assert_eq!(released_days_ago, Some(30));
+            }
+            other => panic!("Expected UpdateAvailable, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_compare_versions_cooldown_up_to_date_when_all_too_new() {
+        let now = Utc::now();
+        let info = make_version_info_with_dates("2.0.0", &[("2.0.0", Some(now - Duration::days(1)))]);
+        let cooldown = CooldownWindow {
+            window: Some(Duration::days(14)),
+            strict: false,
+        };
+
+        assert!(matches!(
+            compare_versions("1.0.0", &info, &cooldown, &SemverScheme, VersionPreference::Highest),
+            VersionStatus::UpToDate
+        ));
+    }
+
+    #[test]
+    fn test_compare_versions_cooldown_strict_rejects_unknown_date() {
+        let info = make_version_info_with_dates("2.0.0", &[("2.0.0", None)]);
+        let cooldown = CooldownWindow {
+            window: Some(Duration::days(14)),
+            strict: true,
+        };
+
+        assert!(matches!(
+            compare_versions("1.0.0", &info, &cooldown, &SemverScheme, VersionPreference::Highest),
+            VersionStatus::UpToDate
+        ));
+    }
+
+    #[test]
+    fn test_compare_versions_cooldown_lenient_accepts_unknown_date() {
+        let info = make_version_info_with_dates("2.0.0", &[("2.0.0", None)]);
+        let cooldown = CooldownWindow {
+            window: Some(Duration::days(14)),
+            strict: false,
+        };
+
+        match compare_versions(
+            "1.0.0",
+            &info,
+            &cooldown,
+            &SemverScheme,
+            VersionPreference::Highest,
+        ) {
+            VersionStatus::UpdateAvailable {
+                version,
+                released_days_ago,
+                ..
+            } => {
+                assert_eq!(version, "2.0.0");
+                assert_eq!(released_days_ago, None);
+            }
+            other => panic!("Expected UpdateAvailable, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_cooldown_window_from_config_disabled_by_default() {
+        let config = crate::config::CooldownConfig::default();
+        let window = CooldownWindow::from_config(&config);
+        assert!(window.window.is_none());
+    }
+
+    #[test]
+    fn test_cooldown_window_from_config_enabled() {
+        let config = crate::config::CooldownConfig {
+            enabled: true,
+            days: 7,
+            strict: true,
+        };
+        let window = CooldownWindow::from_config(&config);
+        assert_eq!(window.window, Some(Duration::days(7)));
+        assert!(window.strict);
+    }
+
+    #[test]
+    fn test_compare_versions_uses_nuget_scheme_for_four_component_versions() {
+        use crate::registries::version_scheme::NuGetScheme;
+
+        // 1.2.3.4 doesn't parse as SemVer, so under SemverScheme this would
+        // fall back to "any textual difference is a breaking update" - the
+        // NuGet scheme should instead recognize it as the same version.
+        let info = make_version_info("1.2.3.4");
+        assert!(matches!(
+            compare_versions(
+                "1.2.3.4",
+                &info,
+                &CooldownWindow::disabled(),
+                &NuGetScheme,
+                VersionPreference::Highest,
+            ),
+            VersionStatus::UpToDate
+        ));
+    }
+
+    #[test]
+    fn test_compare_versions_nuget_scheme_flags_update() {
+        use crate::registries::version_scheme::NuGetScheme;
+
+        let info = make_version_info("1.2.4.0");
+        match compare_versions(
+            "1.2.3",
+            &info,
+            &CooldownWindow::disabled(),
+            &NuGetScheme,
+            VersionPreference::Highest,
+        ) {
+            VersionStatus::UpdateAvailable { version, .. } => assert_eq!(version, "1.2.4.0"),
+            other => panic!("Expected UpdateAvailable, got {:?}", other),
+        }
     }
 }
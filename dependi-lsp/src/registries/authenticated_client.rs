@@ -0,0 +1,178 @@
+//! Authenticated, pooled HTTP client that consults [`TokenProviderManager`]
+//! automatically.
+//!
+//! Without this, auth headers have to be fetched from
+//! `TokenProviderManager` and attached by hand at every call site, and
+//! nothing guarantees a single pooled `reqwest::Client` is actually reused.
+//! [`AuthenticatedClient`] wraps both: it holds one connection-pooled
+//! `reqwest::Client` alongside an `Arc<TokenProviderManager>`, and attaches
+//! whatever headers the manager resolves (via longest-prefix matching) to
+//! every request it builds.
+//!
+//! # Runtime affinity
+//!
+//! A `reqwest::Client`'s connector is bound to the tokio runtime that built
+//! it; issuing a request from a different runtime panics on first use. So
+//! an [`AuthenticatedClient`] should be built once per runtime - via
+//! [`AuthenticatedClient::new`] from within that runtime - rather than
+//! constructed once and shared across runtimes (e.g. across independent
+//! `#[tokio::test]` runtimes, or a worker pool that spins up its own
+//! runtime per thread).
+
+use std::sync::Arc;
+
+use reqwest::{Client, IntoUrl, Method, RequestBuilder};
+use tokio::runtime::{Handle, Id};
+
+use crate::auth::TokenProviderManager;
+
+/// A pooled `reqwest::Client` that attaches [`TokenProviderManager`]'s auth
+/// headers to every request it builds, scoped to the tokio runtime it was
+/// created on.
+pub struct AuthenticatedClient {
+    client: Client,
+    token_manager: Arc<TokenProviderManager>,
+    runtime_id: Id,
+}
+
+impl AuthenticatedClient {
+    /// Build a client around `client` and `token_manager`, bound to the
+    /// tokio runtime currently executing. Call this once per runtime rather
+    /// than sharing an instance across runtimes - see the module docs.
+    pub fn new(client: Client, token_manager: Arc<TokenProviderManager>) -> Self {
+        Self {
+            client,
+            token_manager,
+            runtime_id: Handle::current().id(),
+        }
+    }
+
+    /// Whether `self` was built on the tokio runtime currently executing.
+    /// A caller holding onto an `AuthenticatedClient` across an unknown
+    /// number of `block_on` calls (e.g. a cached instance reused between
+    /// test runtimes) should check this before issuing a request and
+    /// rebuild via [`Self::new`] if it no longer matches.
+    pub fn belongs_to_current_runtime(&self) -> bool {
+        Handle::current().id() == self.runtime_id
+    }
+
+    /// Start a GET request, pre-populated with whatever auth headers
+    /// [`TokenProviderManager`] has for `url`'s registry.
+    pub async fn get<U: IntoUrl>(&self, url: U) -> reqwest::Result<RequestBuilder> {
+        self.request(Method::GET, url).await
+    }
+
+    /// Start a request of `method`, pre-populated with whatever auth
+    /// headers [`TokenProviderManager`] has for `url`'s registry.
+    ///
+    /// # Security
+    /// Auth headers are only attached to HTTPS requests. A resolved,
+    /// non-empty header set for a non-HTTPS URL is dropped rather than sent
+    /// in the clear - the request still goes out, just unauthenticated.
+    pub async fn request<U: IntoUrl>(&self, method: Method, url: U) -> reqwest::Result<RequestBuilder> {
+        let url = url.into_url()?;
+        let mut builder = self.client.request(method, url.clone());
+
+        let headers = self.token_manager.get_auth_headers(url.as_str()).await;
+        if should_attach_headers(&url, &headers) {
+            builder = builder.headers(headers);
+        } else if !headers.is_empty() {
+            tracing::error!(
+                "SECURITY: Withholding auth headers for non-HTTPS request to {}",
+                url
+            );
+        }
+
+        Ok(builder)
+    }
+}
+
+/// Whether a resolved header set should actually be attached to a request
+/// for `url`: never for an empty set, and never for a non-HTTPS URL even if
+/// headers were resolved - a second, independent check against whichever
+/// URL construction bypassed [`TokenProviderManager::register`]'s own
+/// HTTPS-only enforcement.
+fn should_attach_headers(url: &reqwest::Url, headers: &reqwest::header::HeaderMap) -> bool {
+    !headers.is_empty() && url.scheme() == "https"
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::{AUTHORIZATION, HeaderValue};
+
+    use super::*;
+    use crate::auth::EnvTokenProvider;
+
+    fn client() -> Client {
+        Client::builder().build().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_request_attaches_auth_headers_for_matching_https_registry() {
+        let token_manager = Arc::new(TokenProviderManager::new());
+        token_manager
+            .register(
+                "https://npm.company.com".to_string(),
+                Arc::new(EnvTokenProvider::new("secret_token".to_string())),
+            )
+            .await;
+
+        let authenticated = AuthenticatedClient::new(client(), token_manager);
+        let request = authenticated
+            .get("https://npm.company.com/@company/pkg")
+            .await
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            request.headers().get(AUTHORIZATION),
+            Some(&HeaderValue::from_static("Bearer secret_token"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_no_headers_for_unregistered_registry() {
+        let token_manager = Arc::new(TokenProviderManager::new());
+        let authenticated = AuthenticatedClient::new(client(), token_manager);
+
+        let request = authenticated
+            .get("https://registry.npmjs.org/lodash")
+            .await
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert!(request.headers().get(AUTHORIZATION).is_none());
+    }
+
+    #[test]
+    fn test_should_attach_headers_true_for_https_with_headers() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer x"));
+        let url = reqwest::Url::parse("https://example.com").unwrap();
+        assert!(should_attach_headers(&url, &headers));
+    }
+
+    #[test]
+    fn test_should_attach_headers_false_for_http_even_with_headers() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer x"));
+        let url = reqwest::Url::parse("http://example.com").unwrap();
+        assert!(!should_attach_headers(&url, &headers));
+    }
+
+    #[test]
+    fn test_should_attach_headers_false_when_empty() {
+        let headers = reqwest::header::HeaderMap::new();
+        let url = reqwest::Url::parse("https://example.com").unwrap();
+        assert!(!should_attach_headers(&url, &headers));
+    }
+
+    #[tokio::test]
+    async fn test_belongs_to_current_runtime_true_within_same_runtime() {
+        let token_manager = Arc::new(TokenProviderManager::new());
+        let authenticated = AuthenticatedClient::new(client(), token_manager);
+        assert!(authenticated.belongs_to_current_runtime());
+    }
+}
@@ -0,0 +1,475 @@
+//! On-disk response cache with TTL and conditional (ETag/Last-Modified)
+//! revalidation for registry lookups.
+//!
+//! Every registry's `get_version_info` currently re-hits the network on
+//! every call - nothing persists across restarts, and nothing takes
+//! advantage of the conditional-request support most registry CDNs offer.
+//! [`ResponseCache`] stores the serialized [`VersionInfo`] plus whatever
+//! `ETag`/`Last-Modified` the registry returned, keyed by `(registry,
+//! lowercased package id)`, mirroring [`crate::vulnerabilities::cache`]'s
+//! DashMap-plus-SQLite shape. [`CachingRegistry`] wraps any [`Registry`] so
+//! a cache hit within the TTL skips the network entirely, and a stale hit
+//! revalidates with `If-None-Match`/`If-Modified-Since` rather than paying
+//! for a full re-parse on a `304 Not Modified`.
+
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use dashmap::DashMap;
+use rusqlite::{Connection, params};
+
+use super::{Registry, VersionInfo};
+
+/// Default TTL for cached responses (5 minutes), matching the NuGet
+/// client's documented caching strategy.
+const DEFAULT_RESPONSE_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// A cached registry response: the parsed version info plus the
+/// conditional-request validators needed to revalidate it cheaply.
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    /// The parsed version info as of the last real fetch.
+    pub info: VersionInfo,
+    /// `ETag` from the last response, if the registry sent one.
+    pub etag: Option<String>,
+    /// `Last-Modified` from the last response, if the registry sent one.
+    pub last_modified: Option<String>,
+}
+
+/// Outcome of a conditional fetch attempt.
+pub enum ConditionalFetch {
+    /// The registry confirmed nothing changed (`304 Not Modified`); the
+    /// caller should keep serving its existing cached [`VersionInfo`].
+    NotModified,
+    /// Fresh data, along with whatever validators the response carried.
+    Modified {
+        info: VersionInfo,
+        etag: Option<String>,
+        last_modified: Option<String>,
+    },
+}
+
+/// A [`Registry`] that can attempt a conditional fetch using previously
+/// seen `ETag`/`Last-Modified` validators.
+///
+/// The default body just falls back to a plain [`Registry::get_version_info`]
+/// and reports it as always-modified with no validators - implement this
+/// trait with `impl ConditionalRegistry for X {}` for a registry that has no
+/// real conditional-request support yet, or override the method for one
+/// (like NuGet's) that can actually send `If-None-Match`/`If-Modified-Since`
+/// and get a `304` back.
+#[allow(async_fn_in_trait)]
+pub trait ConditionalRegistry: Registry {
+    /// Fetches version info, honoring `etag`/`last_modified` as
+    /// conditional-request validators when the underlying registry
+    /// supports them.
+    async fn get_version_info_conditional(
+        &self,
+        package_name: &str,
+        _etag: Option<&str>,
+        _last_modified: Option<&str>,
+    ) -> anyhow::Result<ConditionalFetch> {
+        let info = self.get_version_info(package_name).await?;
+        Ok(ConditionalFetch::Modified {
+            info,
+            etag: None,
+            last_modified: None,
+        })
+    }
+}
+
+/// In-memory cache entry, with the insertion time tracked as an
+/// [`Instant`] for cheap TTL checks.
+struct CacheEntry {
+    response: CachedResponse,
+    inserted_at: Instant,
+}
+
+/// On-disk persistence for the response cache, so a cold start can reuse
+/// validators (and serve fresh-enough data) without hitting the network.
+struct PersistentStore {
+    conn: Mutex<Connection>,
+}
+
+impl PersistentStore {
+    fn open(path: &Path) -> anyhow::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(path)?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS registry_responses (
+                key TEXT PRIMARY KEY,
+                data TEXT NOT NULL,
+                etag TEXT,
+                last_modified TEXT,
+                inserted_at INTEGER NOT NULL,
+                ttl_secs INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn get(&self, key: &str) -> Option<(CachedResponse, i64)> {
+        let conn = self.conn.lock().ok()?;
+        let (data, etag, last_modified, inserted_at): (
+            String,
+            Option<String>,
+            Option<String>,
+            i64,
+        ) = conn
+            .query_row(
+                "SELECT data, etag, last_modified, inserted_at FROM registry_responses WHERE key = ?",
+                params![key],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .ok()?;
+        let info = serde_json::from_str(&data).ok()?;
+        Some((
+            CachedResponse {
+                info,
+                etag,
+                last_modified,
+            },
+            inserted_at,
+        ))
+    }
+
+    fn insert(&self, key: &str, response: &CachedResponse, ttl_secs: u64) {
+        let Ok(conn) = self.conn.lock() else {
+            return;
+        };
+        let Ok(data) = serde_json::to_string(&response.info) else {
+            return;
+        };
+        let _ = conn.execute(
+            "INSERT OR REPLACE INTO registry_responses
+             (key, data, etag, last_modified, inserted_at, ttl_secs)
+             VALUES (?, ?, ?, ?, ?, ?)",
+            params![
+                key,
+                data,
+                response.etag,
+                response.last_modified,
+                current_timestamp(),
+                ttl_secs as i64,
+            ],
+        );
+    }
+
+    fn clear(&self) {
+        if let Ok(conn) = self.conn.lock() {
+            let _ = conn.execute("DELETE FROM registry_responses", []);
+        }
+    }
+}
+
+fn current_timestamp() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+/// Builds the `(registry, lowercased package id)` cache key.
+fn cache_key(registry: &str, package: &str) -> String {
+    format!("{}:{}", registry, package.to_lowercase())
+}
+
+/// Default location for the response cache database
+/// (`~/.cache/dependi/registry-cache.db`), mirroring
+/// [`crate::cache::sqlite::SqliteCache`]'s cache directory.
+pub fn default_cache_path() -> anyhow::Result<std::path::PathBuf> {
+    let cache_dir = dirs::cache_dir()
+        .ok_or_else(|| anyhow::anyhow!("Could not determine user cache directory"))?
+        .join("dependi");
+    std::fs::create_dir_all(&cache_dir)?;
+    Ok(cache_dir.join("registry-cache.db"))
+}
+
+/// Removes all entries from the on-disk response cache at
+/// [`default_cache_path`], so a user can manually purge stale data.
+pub fn clear_cache() -> anyhow::Result<()> {
+    let path = default_cache_path()?;
+    if path.exists() {
+        PersistentStore::open(&path)?.clear();
+    }
+    Ok(())
+}
+
+/// TTL-bounded, optionally SQLite-backed cache of registry responses.
+pub struct ResponseCache {
+    entries: DashMap<String, CacheEntry>,
+    ttl: Duration,
+    store: Option<PersistentStore>,
+}
+
+impl ResponseCache {
+    /// Create an in-memory-only cache with the default TTL (5 minutes).
+    pub fn new() -> Self {
+        Self {
+            entries: DashMap::new(),
+            ttl: DEFAULT_RESPONSE_CACHE_TTL,
+            store: None,
+        }
+    }
+
+    /// Create an in-memory-only cache with a custom TTL.
+    pub fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            entries: DashMap::new(),
+            ttl,
+            store: None,
+        }
+    }
+
+    /// Create a cache backed by a SQLite file at `path`.
+    pub fn with_persistent_store(path: impl AsRef<Path>, ttl: Duration) -> anyhow::Result<Self> {
+        Ok(Self {
+            entries: DashMap::new(),
+            ttl,
+            store: Some(PersistentStore::open(path.as_ref())?),
+        })
+    }
+
+    /// Create a cache backed by the default on-disk location.
+    pub fn with_default_store(ttl: Duration) -> anyhow::Result<Self> {
+        Self::with_persistent_store(default_cache_path()?, ttl)
+    }
+
+    /// Returns the cached response for `(registry, package)` if present,
+    /// regardless of whether it's still within the TTL - callers use
+    /// [`ResponseCache::is_fresh`] to decide whether to serve it as-is or
+    /// revalidate first.
+    pub fn get(&self, registry: &str, package: &str) -> Option<(CachedResponse, bool)> {
+        let key = cache_key(registry, package);
+
+        if let Some(entry) = self.entries.get(&key) {
+            let fresh = entry.inserted_at.elapsed() < self.ttl;
+            return Some((entry.response.clone(), fresh));
+        }
+
+        let store = self.store.as_ref()?;
+        let (response, inserted_at) = store.get(&key)?;
+        let age = Duration::from_secs((current_timestamp() - inserted_at).max(0) as u64);
+        let fresh = age < self.ttl;
+        self.entries.insert(
+            key,
+            CacheEntry {
+                response: response.clone(),
+                inserted_at: Instant::now().checked_sub(age).unwrap_or_else(Instant::now),
+            },
+        );
+        Some((response, fresh))
+    }
+
+    /// Inserts (or replaces) the cached response, writing through to the
+    /// on-disk store when configured.
+    pub fn insert(&self, registry: &str, package: &str, response: CachedResponse) {
+        let key = cache_key(registry, package);
+        if let Some(store) = &self.store {
+            store.insert(&key, &response, self.ttl.as_secs());
+        }
+        self.entries.insert(
+            key,
+            CacheEntry {
+                response,
+                inserted_at: Instant::now(),
+            },
+        );
+    }
+
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Wraps any [`ConditionalRegistry`] with a [`ResponseCache`]: a fresh
+/// cache hit is served with no network call; a stale hit is revalidated
+/// with `If-None-Match`/`If-Modified-Since`, and a `304` just refreshes
+/// the cached entry's timestamp instead of re-parsing a response body.
+pub struct CachingRegistry<R: ConditionalRegistry> {
+    inner: R,
+    cache: ResponseCache,
+    registry_name: String,
+}
+
+impl<R: ConditionalRegistry> CachingRegistry<R> {
+    /// Wraps `inner`, identifying its entries in the shared cache as
+    /// `registry_name` (e.g. `"nuget"`).
+    pub fn new(inner: R, registry_name: impl Into<String>, cache: ResponseCache) -> Self {
+        Self {
+            inner,
+            cache,
+            registry_name: registry_name.into(),
+        }
+    }
+}
+
+impl<R: ConditionalRegistry> Registry for CachingRegistry<R> {
+    async fn get_version_info(&self, package_name: &str) -> anyhow::Result<VersionInfo> {
+        let cached = self.cache.get(&self.registry_name, package_name);
+
+        if let Some((response, true)) = &cached {
+            return Ok(response.info.clone());
+        }
+
+        let (etag, last_modified) = cached
+            .as_ref()
+            .map(|(response, _)| (response.etag.clone(), response.last_modified.clone()))
+            .unwrap_or((None, None));
+
+        match self
+            .inner
+            .get_version_info_conditional(package_name, etag.as_deref(), last_modified.as_deref())
+            .await?
+        {
+            ConditionalFetch::NotModified => {
+                let (mut response, _) = cached.ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "{} reported 304 Not Modified with no prior cached response for {}",
+                        self.registry_name,
+                        package_name
+                    )
+                })?;
+                response.etag = etag;
+                response.last_modified = last_modified;
+                self.cache
+                    .insert(&self.registry_name, package_name, response.clone());
+                Ok(response.info)
+            }
+            ConditionalFetch::Modified {
+                info,
+                etag,
+                last_modified,
+            } => {
+                let response = CachedResponse {
+                    info: info.clone(),
+                    etag,
+                    last_modified,
+                };
+                self.cache.insert(&self.registry_name, package_name, response);
+                Ok(info)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_info(latest: &str) -> VersionInfo {
+        VersionInfo {
+            latest: Some(latest.to_string()),
+            versions: vec![latest.to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_cache_key_lowercases_package() {
+        assert_eq!(cache_key("nuget", "Newtonsoft.Json"), "nuget:newtonsoft.json");
+    }
+
+    #[test]
+    fn test_insert_and_get_is_fresh() {
+        let cache = ResponseCache::new();
+        cache.insert(
+            "nuget",
+            "Newtonsoft.Json",
+            CachedResponse {
+                info: sample_info("13.0.1"),
+                etag: Some("\"abc\"".to_string()),
+                last_modified: None,
+            },
+        );
+
+        let (response, fresh) = cache.get("nuget", "newtonsoft.json").unwrap();
+        assert!(fresh);
+        assert_eq!(response.info.latest.as_deref(), Some("13.0.1"));
+        assert_eq!(response.etag.as_deref(), Some("\"abc\""));
+    }
+
+    #[test]
+    fn test_stale_entry_reported_as_not_fresh() {
+        let cache = ResponseCache::with_ttl(Duration::from_millis(1));
+        cache.insert(
+            "npm",
+            "lodash",
+            CachedResponse {
+                info: sample_info("4.17.21"),
+                etag: None,
+                last_modified: None,
+            },
+        );
+        std::thread::sleep(Duration::from_millis(10));
+
+        let (_, fresh) = cache.get("npm", "lodash").unwrap();
+        assert!(!fresh);
+    }
+
+    fn temp_db_path(name: &str) -> std::path::PathBuf {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let id = COUNTER.fetch_add(1, Ordering::SeqCst);
+        std::env::temp_dir().join(format!("dependi-registry-cache-test-{}-{}.db", name, id))
+    }
+
+    #[test]
+    fn test_persistent_store_survives_reload() {
+        let path = temp_db_path("reload");
+        {
+            let cache = ResponseCache::with_persistent_store(&path, Duration::from_secs(3600))
+                .unwrap();
+            cache.insert(
+                "nuget",
+                "newtonsoft.json",
+                CachedResponse {
+                    info: sample_info("13.0.1"),
+                    etag: Some("\"abc\"".to_string()),
+                    last_modified: None,
+                },
+            );
+        }
+
+        let reloaded =
+            ResponseCache::with_persistent_store(&path, Duration::from_secs(3600)).unwrap();
+        let (response, fresh) = reloaded.get("nuget", "newtonsoft.json").unwrap();
+        assert!(fresh);
+        assert_eq!(response.info.latest.as_deref(), Some("13.0.1"));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_clear_removes_persisted_rows() {
+        let path = temp_db_path("clear");
+        let cache =
+            ResponseCache::with_persistent_store(&path, Duration::from_secs(3600)).unwrap();
+        cache.insert(
+            "npm",
+            "lodash",
+            CachedResponse {
+                info: sample_info("4.17.21"),
+                etag: None,
+                last_modified: None,
+            },
+        );
+
+        PersistentStore::open(&path).unwrap().clear();
+
+        let reloaded =
+            ResponseCache::with_persistent_store(&path, Duration::from_secs(3600)).unwrap();
+        assert!(reloaded.get("npm", "lodash").is_none());
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
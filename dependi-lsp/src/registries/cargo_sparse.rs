@@ -2,17 +2,26 @@
 //!
 //! Client for alternative Cargo registries using the sparse index protocol.
 //! Supports registries like Kellnr, Cloudsmith, and other Cargo-compatible registries.
+//!
+//! ## Caching Strategy
+//!
+//! Like NuGet, the sparse index is revalidated with `If-None-Match`/
+//! `If-Modified-Since` (see [`ConditionalRegistry`] below) so an unchanged
+//! crate costs a `304` instead of a full re-download and re-parse of its
+//! index file.
 
 use std::collections::HashMap;
 use std::sync::Arc;
 
 use reqwest::Client;
-use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
+use reqwest::header::HeaderMap;
 use serde::Deserialize;
 
+use super::cache::{ConditionalFetch, ConditionalRegistry};
 use super::http_client::create_shared_client;
 use super::version_utils::is_prerelease_rust;
-use super::{Registry, VersionInfo};
+use super::{Registry, VersionDependency, VersionInfo, VersionMeta};
+use crate::auth::{EnvTokenProvider, TokenProvider, TokenProviderManager};
 
 /// Compute the sparse index path for a crate name.
 ///
@@ -32,6 +41,41 @@ fn sparse_index_path(name: &str) -> String {
     }
 }
 
+/// One entry of a sparse-index crate's `deps[]` array.
+#[derive(Debug, Deserialize)]
+struct SparseIndexDep {
+    name: String,
+    req: String,
+    #[serde(default)]
+    features: Vec<String>,
+    #[serde(default)]
+    optional: bool,
+    #[serde(default = "default_true")]
+    default_features: bool,
+    target: Option<String>,
+    kind: Option<String>,
+    package: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl From<SparseIndexDep> for VersionDependency {
+    fn from(dep: SparseIndexDep) -> Self {
+        VersionDependency {
+            name: dep.name,
+            package: dep.package,
+            req: dep.req,
+            features: dep.features,
+            optional: dep.optional,
+            default_features: dep.default_features,
+            target: dep.target,
+            kind: dep.kind,
+        }
+    }
+}
+
 /// A single entry from the sparse index (one JSON line per version)
 #[derive(Debug, Deserialize)]
 struct SparseIndexEntry {
@@ -40,38 +84,111 @@ struct SparseIndexEntry {
     vers: String,
     #[serde(default)]
     yanked: bool,
+    /// Minimum supported Rust version declared by this release, if any
+    /// (Cargo's `rust-version` manifest field, e.g. `"1.70"`).
+    rust_version: Option<String>,
+    /// Declared dependencies.
+    #[serde(default)]
+    deps: Vec<SparseIndexDep>,
+    /// Feature name to the list of features/dependencies it enables.
+    #[serde(default)]
+    features: HashMap<String, Vec<String>>,
+    /// Cargo >= 1.60's namespaced/weak-dependency-feature syntax, kept
+    /// separate from `features` so older Cargo (and this parser, pre-`v2`)
+    /// can ignore it - merged into [`VersionMeta::features`] regardless,
+    /// since both are just feature-to-enabled-items lists to a consumer.
+    #[serde(default)]
+    features2: HashMap<String, Vec<String>>,
+    /// Native library link name (Cargo's `links` manifest field), if any.
+    /// Parsed so it's not silently dropped, though nothing surfaces it yet.
+    #[allow(dead_code)]
+    links: Option<String>,
+    /// Sparse-index schema version this entry was written in. `None`/`1` is
+    /// the original schema; `2` adds `features2`. Anything higher is a
+    /// future schema this parser doesn't know about yet - still read
+    /// gracefully since every field above is read by name, not position.
+    #[allow(dead_code)]
+    v: Option<u32>,
 }
 
 /// Client for Cargo sparse registries (alternative registries)
 pub struct CargoSparseRegistry {
     client: Arc<Client>,
     index_url: String,
-    auth_headers: Option<HeaderMap>,
+    /// Consulted at request time when the token manager has no matching
+    /// entry - e.g. a `secret-key`-configured registry's
+    /// [`AsymmetricTokenProvider`](crate::auth::asymmetric::AsymmetricTokenProvider),
+    /// which mints a fresh signed token per call rather than a static header.
+    fallback_provider: Option<Arc<dyn TokenProvider>>,
+    /// Consulted at request time ahead of `fallback_provider` - lets a
+    /// `DEPENDI_AUTH_TOKENS`-style bundle registered after construction (or
+    /// reconfigured later) override the static token this client was built
+    /// with.
+    token_manager: Arc<TokenProviderManager>,
 }
 
 impl CargoSparseRegistry {
-    /// Create a new sparse registry client with the given configuration.
+    /// Create a new sparse registry client with a bearer `auth_token` and an
+    /// empty token manager - use [`Self::with_client_config_and_manager`] to
+    /// carry one already populated from the environment or discovery, or
+    /// [`Self::with_client_fallback_and_manager`] for non-bearer auth (e.g.
+    /// asymmetric signed requests).
     pub fn with_client_and_config(
         client: Arc<Client>,
         index_url: String,
         auth_token: Option<String>,
     ) -> Self {
-        let auth_headers = auth_token.and_then(|token| {
-            let mut headers = HeaderMap::new();
-            let auth_value = format!("Bearer {}", token);
-            if let Ok(value) = HeaderValue::from_str(&auth_value) {
-                headers.insert(AUTHORIZATION, value);
-                Some(headers)
-            } else {
-                None
-            }
-        });
+        Self::with_client_config_and_manager(
+            client,
+            index_url,
+            auth_token,
+            Arc::new(TokenProviderManager::new()),
+        )
+    }
 
+    /// Create a new sparse registry client with a bearer `auth_token`
+    /// fallback, and a [`TokenProviderManager`] consulted at request time
+    /// ahead of it.
+    pub fn with_client_config_and_manager(
+        client: Arc<Client>,
+        index_url: String,
+        auth_token: Option<String>,
+        token_manager: Arc<TokenProviderManager>,
+    ) -> Self {
+        let fallback_provider = auth_token
+            .map(|token| Arc::new(EnvTokenProvider::new(token)) as Arc<dyn TokenProvider>);
+        Self::with_client_fallback_and_manager(client, index_url, fallback_provider, token_manager)
+    }
+
+    /// Create a new sparse registry client with an arbitrary fallback
+    /// [`TokenProvider`] (bearer, asymmetric, or anything else the caller
+    /// resolved), and a [`TokenProviderManager`] consulted at request time
+    /// ahead of it.
+    pub fn with_client_fallback_and_manager(
+        client: Arc<Client>,
+        index_url: String,
+        fallback_provider: Option<Arc<dyn TokenProvider>>,
+        token_manager: Arc<TokenProviderManager>,
+    ) -> Self {
         Self {
             client,
             index_url: index_url.trim_end_matches('/').to_string(),
-            auth_headers,
+            fallback_provider,
+            token_manager,
+        }
+    }
+
+    /// Resolve the headers to attach to a request to `url`: the token
+    /// manager's provider for this URL, if one matches, else the fallback
+    /// provider this client was constructed with.
+    async fn resolve_auth_headers(&self, url: &str) -> Option<HeaderMap> {
+        let managed = self.token_manager.get_auth_headers(url).await;
+        if !managed.is_empty() {
+            return Some(managed);
         }
+        self.fallback_provider
+            .as_ref()
+            .and_then(|provider| provider.get_auth_headers(url))
     }
 }
 
@@ -85,6 +202,125 @@ impl Default for CargoSparseRegistry {
     }
 }
 
+/// Parses a sparse index response body (newline-delimited JSON entries) into
+/// a [`VersionInfo`]. Split out of [`CargoSparseRegistry::get_version_info`]
+/// so [`ConditionalRegistry::get_version_info_conditional`] can reuse it
+/// without re-fetching on a `304`.
+fn parse_index_body(package_name: &str, body: &str) -> VersionInfo {
+    let mut all_versions: Vec<String> = Vec::new();
+    let mut yanked_versions: Vec<String> = Vec::new();
+    let mut rust_version: HashMap<String, String> = HashMap::new();
+    let mut version_metadata: HashMap<String, VersionMeta> = HashMap::new();
+
+    for line in body.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        match serde_json::from_str::<SparseIndexEntry>(line) {
+            Ok(entry) => {
+                if let Some(rv) = entry.rust_version {
+                    rust_version.insert(entry.vers.clone(), rv);
+                }
+
+                let mut features = entry.features;
+                for (name, enables) in entry.features2 {
+                    features.entry(name).or_default().extend(enables);
+                }
+                version_metadata.insert(
+                    entry.vers.clone(),
+                    VersionMeta {
+                        deps: entry
+                            .deps
+                            .into_iter()
+                            .map(VersionDependency::from)
+                            .collect(),
+                        features,
+                    },
+                );
+
+                if entry.yanked {
+                    yanked_versions.push(entry.vers);
+                } else {
+                    all_versions.push(entry.vers);
+                }
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to parse sparse index entry for {}: {}",
+                    package_name,
+                    e
+                );
+            }
+        }
+    }
+
+    // Find latest stable version (not yanked, not prerelease)
+    let latest_stable = all_versions
+        .iter()
+        .filter(|v| !is_prerelease_rust(v))
+        .max_by(|a, b| {
+            semver::Version::parse(a)
+                .unwrap_or_else(|_| semver::Version::new(0, 0, 0))
+                .cmp(&semver::Version::parse(b).unwrap_or_else(|_| semver::Version::new(0, 0, 0)))
+        })
+        .cloned();
+
+    // Find latest prerelease
+    let latest_prerelease = all_versions
+        .iter()
+        .filter(|v| is_prerelease_rust(v))
+        .max_by(|a, b| {
+            semver::Version::parse(a)
+                .unwrap_or_else(|_| semver::Version::new(0, 0, 0))
+                .cmp(&semver::Version::parse(b).unwrap_or_else(|_| semver::Version::new(0, 0, 0)))
+        })
+        .cloned();
+
+    // Check if the most recent version overall (by semver) is yanked
+    let semver_cmp = |a: &&String, b: &&String| {
+        semver::Version::parse(a)
+            .unwrap_or_else(|_| semver::Version::new(0, 0, 0))
+            .cmp(&semver::Version::parse(b).unwrap_or_else(|_| semver::Version::new(0, 0, 0)))
+    };
+    let max_non_yanked = all_versions.iter().max_by(semver_cmp);
+    let max_yanked = yanked_versions.iter().max_by(semver_cmp);
+    let yanked = match (max_non_yanked, max_yanked) {
+        (Some(non_yanked), Some(yanked_ver)) => {
+            // If the highest yanked version is newer than the highest non-yanked, yanked = true
+            semver_cmp(&yanked_ver, &non_yanked) == std::cmp::Ordering::Greater
+        }
+        (None, Some(_)) => true, // All versions are yanked
+        _ => false,              // No yanked versions, or no versions at all
+    };
+
+    VersionInfo {
+        latest: latest_stable,
+        latest_prerelease,
+        versions: all_versions,
+        description: None,
+        homepage: None,
+        repository: None,
+        license: None,
+        vulnerabilities: vec![],
+        deprecated: false,
+        yanked,
+        yanked_versions,
+        release_dates: HashMap::new(),
+        platforms: HashMap::new(),
+        dependency_groups: HashMap::new(),
+        deprecation_messages: HashMap::new(),
+        requires_python: HashMap::new(),
+        rust_version,
+        yanked_reasons: HashMap::new(),
+        latest_breaking_major: None,
+        latest_compatible: None,
+        alternative_version: None,
+        version_metadata,
+    }
+}
+
 impl Registry for CargoSparseRegistry {
     fn http_client(&self) -> Arc<Client> {
         Arc::clone(&self.client)
@@ -95,7 +331,7 @@ impl Registry for CargoSparseRegistry {
         let url = format!("{}/{}", self.index_url, path);
 
         let mut request = self.client.get(&url);
-        if let Some(headers) = &self.auth_headers {
+        if let Some(headers) = self.resolve_auth_headers(&url).await {
             for (key, value) in headers.iter() {
                 request = request.header(key, value);
             }
@@ -112,93 +348,70 @@ impl Registry for CargoSparseRegistry {
         }
 
         let body = response.text().await?;
+        Ok(parse_index_body(package_name, &body))
+    }
+}
 
-        // Parse newline-delimited JSON entries
-        let mut all_versions: Vec<String> = Vec::new();
-        let mut yanked_versions: Vec<String> = Vec::new();
+impl ConditionalRegistry for CargoSparseRegistry {
+    /// Revalidates the sparse index entry with `If-None-Match`/
+    /// `If-Modified-Since`, so an unchanged crate (the common case on most
+    /// sparse-index CDNs) costs a `304` instead of a full re-download and
+    /// re-parse of its index file. Auth headers are still attached, same as
+    /// a plain [`Registry::get_version_info`] call.
+    async fn get_version_info_conditional(
+        &self,
+        package_name: &str,
+        etag: Option<&str>,
+        last_modified: Option<&str>,
+    ) -> anyhow::Result<ConditionalFetch> {
+        let path = sparse_index_path(package_name);
+        let url = format!("{}/{}", self.index_url, path);
 
-        for line in body.lines() {
-            let line = line.trim();
-            if line.is_empty() {
-                continue;
+        let mut request = self.client.get(&url);
+        if let Some(headers) = self.resolve_auth_headers(&url).await {
+            for (key, value) in headers.iter() {
+                request = request.header(key, value);
             }
+        }
+        if let Some(etag) = etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = last_modified {
+            request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+        }
 
-            match serde_json::from_str::<SparseIndexEntry>(line) {
-                Ok(entry) => {
-                    if entry.yanked {
-                        yanked_versions.push(entry.vers);
-                    } else {
-                        all_versions.push(entry.vers);
-                    }
-                }
-                Err(e) => {
-                    tracing::warn!(
-                        "Failed to parse sparse index entry for {}: {}",
-                        package_name,
-                        e
-                    );
-                }
-            }
+        let response = request.send().await?;
+
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(ConditionalFetch::NotModified);
         }
 
-        // Find latest stable version (not yanked, not prerelease)
-        let latest_stable = all_versions
-            .iter()
-            .filter(|v| !is_prerelease_rust(v))
-            .max_by(|a, b| {
-                semver::Version::parse(a)
-                    .unwrap_or_else(|_| semver::Version::new(0, 0, 0))
-                    .cmp(
-                        &semver::Version::parse(b)
-                            .unwrap_or_else(|_| semver::Version::new(0, 0, 0)),
-                    )
-            })
-            .cloned();
-
-        // Find latest prerelease
-        let latest_prerelease = all_versions
-            .iter()
-            .filter(|v| is_prerelease_rust(v))
-            .max_by(|a, b| {
-                semver::Version::parse(a)
-                    .unwrap_or_else(|_| semver::Version::new(0, 0, 0))
-                    .cmp(
-                        &semver::Version::parse(b)
-                            .unwrap_or_else(|_| semver::Version::new(0, 0, 0)),
-                    )
-            })
-            .cloned();
-
-        // Check if the most recent version overall (by semver) is yanked
-        let semver_cmp = |a: &&String, b: &&String| {
-            semver::Version::parse(a)
-                .unwrap_or_else(|_| semver::Version::new(0, 0, 0))
-                .cmp(&semver::Version::parse(b).unwrap_or_else(|_| semver::Version::new(0, 0, 0)))
-        };
-        let max_non_yanked = all_versions.iter().max_by(semver_cmp);
-        let max_yanked = yanked_versions.iter().max_by(semver_cmp);
-        let yanked = match (max_non_yanked, max_yanked) {
-            (Some(non_yanked), Some(yanked_ver)) => {
-                // If the highest yanked version is newer than the highest non-yanked, yanked = true
-                semver_cmp(&yanked_ver, &non_yanked) == std::cmp::Ordering::Greater
-            }
-            (None, Some(_)) => true, // All versions are yanked
-            _ => false,              // No yanked versions, or no versions at all
-        };
-
-        Ok(VersionInfo {
-            latest: latest_stable,
-            latest_prerelease,
-            versions: all_versions,
-            description: None,
-            homepage: None,
-            repository: None,
-            license: None,
-            vulnerabilities: vec![],
-            deprecated: false,
-            yanked,
-            yanked_versions,
-            release_dates: HashMap::new(),
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "Failed to fetch crate info for {} from sparse registry: {}",
+                package_name,
+                response.status()
+            );
+        }
+
+        let new_etag = response
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string());
+        let new_last_modified = response
+            .headers()
+            .get(reqwest::header::LAST_MODIFIED)
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string());
+
+        let body = response.text().await?;
+        let info = parse_index_body(package_name, &body);
+
+        Ok(ConditionalFetch::Modified {
+            info,
+            etag: new_etag,
+            last_modified: new_last_modified,
         })
     }
 }
@@ -251,4 +464,80 @@ mod tests {
         assert_eq!(entry.vers, "0.9.0");
         assert!(entry.yanked);
     }
+
+    #[test]
+    fn test_parse_sparse_index_entry_rust_version() {
+        let line = r#"{"name":"serde","vers":"1.0.200","deps":[],"cksum":"abc","features":{},"yanked":false,"rust_version":"1.70"}"#;
+        let entry: SparseIndexEntry = serde_json::from_str(line).unwrap();
+        assert_eq!(entry.rust_version.as_deref(), Some("1.70"));
+    }
+
+    #[test]
+    fn test_parse_sparse_index_entry_missing_rust_version() {
+        let line = r#"{"name":"serde","vers":"1.0.0","deps":[],"cksum":"abc","features":{},"yanked":false}"#;
+        let entry: SparseIndexEntry = serde_json::from_str(line).unwrap();
+        assert_eq!(entry.rust_version, None);
+    }
+
+    #[test]
+    fn test_parse_index_body_picks_latest_stable_and_prerelease() {
+        let body = r#"{"name":"foo","vers":"1.0.0","deps":[],"cksum":"a","features":{},"yanked":false}
+{"name":"foo","vers":"1.1.0-beta.1","deps":[],"cksum":"b","features":{},"yanked":false}
+{"name":"foo","vers":"0.9.0","deps":[],"cksum":"c","features":{},"yanked":false}"#;
+        let info = parse_index_body("foo", body);
+        assert_eq!(info.latest.as_deref(), Some("1.0.0"));
+        assert_eq!(info.latest_prerelease.as_deref(), Some("1.1.0-beta.1"));
+        assert!(!info.yanked);
+    }
+
+    #[test]
+    fn test_parse_index_body_flags_yanked_when_latest_is_yanked() {
+        let body = r#"{"name":"foo","vers":"1.0.0","deps":[],"cksum":"a","features":{},"yanked":false}
+{"name":"foo","vers":"2.0.0","deps":[],"cksum":"b","features":{},"yanked":true}"#;
+        let info = parse_index_body("foo", body);
+        assert_eq!(info.latest.as_deref(), Some("1.0.0"));
+        assert_eq!(info.yanked_versions, vec!["2.0.0".to_string()]);
+        assert!(info.yanked);
+    }
+
+    #[test]
+    fn test_parse_index_body_skips_unparseable_lines() {
+        let body = "not json\n{\"name\":\"foo\",\"vers\":\"1.0.0\",\"deps\":[],\"cksum\":\"a\",\"features\":{},\"yanked\":false}";
+        let info = parse_index_body("foo", body);
+        assert_eq!(info.versions, vec!["1.0.0".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_index_body_surfaces_deps_and_features() {
+        let line = r#"{"name":"foo","vers":"1.0.0","deps":[{"name":"bar","req":"^1.0","features":["f1"],"optional":true,"default_features":false,"target":null,"kind":"normal","package":null}],"cksum":"a","features":{"default":["f1"]},"yanked":false}"#;
+        let info = parse_index_body("foo", line);
+        let meta = info.version_metadata.get("1.0.0").unwrap();
+        assert_eq!(meta.deps.len(), 1);
+        assert_eq!(meta.deps[0].name, "bar");
+        assert_eq!(meta.deps[0].req, "^1.0");
+        assert!(meta.deps[0].optional);
+        assert!(!meta.deps[0].default_features);
+        assert_eq!(meta.features.get("default"), Some(&vec!["f1".to_string()]));
+    }
+
+    #[test]
+    fn test_parse_index_body_merges_features2() {
+        let line = r#"{"name":"foo","vers":"1.0.0","deps":[],"cksum":"a","features":{"default":["f1"]},"features2":{"weak":["dep:bar"]},"yanked":false,"v":2}"#;
+        let info = parse_index_body("foo", line);
+        let meta = info.version_metadata.get("1.0.0").unwrap();
+        assert_eq!(meta.features.get("default"), Some(&vec!["f1".to_string()]));
+        assert_eq!(
+            meta.features.get("weak"),
+            Some(&vec!["dep:bar".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_parse_index_body_handles_v1_entry_with_no_deps_or_features() {
+        let line = r#"{"name":"foo","vers":"1.0.0","yanked":false}"#;
+        let info = parse_index_body("foo", line);
+        let meta = info.version_metadata.get("1.0.0").unwrap();
+        assert!(meta.deps.is_empty());
+        assert!(meta.features.is_empty());
+    }
 }
@@ -77,21 +77,33 @@ use reqwest::Client;
 use serde::Deserialize;
 use tokio::sync::Mutex;
 
+use super::cache::{CachedResponse, ResponseCache};
 use super::http_client::create_shared_client;
 use super::version_utils::is_prerelease_rust;
-use super::{Registry, VersionInfo};
+use super::{IndexSource, Registry, RegistryConfig, VersionInfo};
 
-/// Rate limiter to respect crates.io's 1 request/second limit
+/// Default number of times a `429` is retried before
+/// [`CratesIoRegistry::with_client`] gives up.
+const DEFAULT_MAX_RATE_LIMIT_RETRIES: u32 = 3;
+
+/// Rate limiter enforcing crates.io's 1 request/second baseline, adapted in
+/// both directions by the server's own signals: a successful response's
+/// `X-RateLimit-Remaining`/`X-RateLimit-Reset` widen `min_interval` as quota
+/// runs low, and a `429`'s `Retry-After` (or, absent that, bounded
+/// exponential backoff) is slept out before the request is retried.
 struct RateLimiter {
     last_request: Instant,
     min_interval: Duration,
+    base_interval: Duration,
 }
 
 impl RateLimiter {
     fn new(requests_per_second: f64) -> Self {
+        let base_interval = Duration::from_secs_f64(1.0 / requests_per_second);
         Self {
             last_request: Instant::now() - Duration::from_secs(10),
-            min_interval: Duration::from_secs_f64(1.0 / requests_per_second),
+            min_interval: base_interval,
+            base_interval,
         }
     }
 
@@ -102,13 +114,68 @@ impl RateLimiter {
         }
         self.last_request = Instant::now();
     }
+
+    /// Widens `min_interval` as crates.io's own quota counter runs low, so
+    /// concurrent callers slow down proactively instead of only reacting
+    /// once the server starts returning `429`s. Falls back to
+    /// `base_interval` when the headers are absent or unparseable.
+    fn observe_rate_limit_headers(&mut self, headers: &reqwest::header::HeaderMap) {
+        let remaining = headers
+            .get("x-ratelimit-remaining")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok());
+        let reset_secs = headers
+            .get("x-ratelimit-reset")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok());
+
+        self.min_interval = match (remaining, reset_secs) {
+            (Some(0), reset) => {
+                // No quota left: wait out the whole reset window.
+                reset.map(Duration::from_secs).unwrap_or(self.base_interval)
+            }
+            (Some(remaining), Some(reset)) => {
+                // Spread the remaining quota evenly across the time left
+                // until it resets, so throughput tapers off rather than
+                // bursting right up to the limit.
+                Duration::from_secs_f64(reset as f64 / remaining as f64)
+            }
+            _ => self.base_interval,
+        }
+        .max(self.base_interval);
+    }
+}
+
+/// Bounded exponential backoff for a `429` that carries no usable
+/// `Retry-After` header: 1s, 2s, 4s, ..., capped at 30s.
+fn exponential_backoff(attempt: u32) -> Duration {
+    Duration::from_secs(2u64.saturating_pow(attempt).min(30))
+}
+
+/// Parses a `Retry-After` header as either delta-seconds or an HTTP-date
+/// (`Sun, 06 Nov 1994 08:49:37 GMT`).
+fn parse_retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    let value = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?;
+
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let date = chrono::NaiveDateTime::parse_from_str(value, "%a, %d %b %Y %H:%M:%S GMT").ok()?;
+    let delay = date.and_utc().signed_duration_since(Utc::now());
+    delay.to_std().ok()
 }
 
 /// Client for the crates.io registry
 pub struct CratesIoRegistry {
     client: Arc<Client>,
     rate_limiter: Arc<Mutex<RateLimiter>>,
-    base_url: String,
+    /// Upstream endpoints to try, in order, for every lookup - the public
+    /// crates.io index unless overridden via [`CratesIoRegistry::with_sources`].
+    sources: Vec<IndexSource>,
+    max_retries: u32,
+    timeout: Option<Duration>,
+    cache: Option<ResponseCache>,
 }
 
 impl CratesIoRegistry {
@@ -129,10 +196,138 @@ impl CratesIoRegistry {
     /// let registry = CratesIoRegistry::with_client(client);
     /// ```
     pub fn with_client(client: Arc<Client>) -> Self {
+        Self::with_max_retries(client, DEFAULT_MAX_RATE_LIMIT_RETRIES)
+    }
+
+    /// Like [`CratesIoRegistry::with_client`], but with a configurable cap
+    /// on how many times a `429` is retried (via `Retry-After` or bounded
+    /// exponential backoff) before the request gives up.
+    pub fn with_max_retries(client: Arc<Client>, max_retries: u32) -> Self {
+        Self::with_config(client, max_retries, RegistryConfig::default())
+    }
+
+    /// Like [`CratesIoRegistry::with_max_retries`], additionally honoring a
+    /// [`RegistryConfig`]'s alternate base URL, per-request timeout, and
+    /// response cache TTL.
+    pub fn with_config(client: Arc<Client>, max_retries: u32, config: RegistryConfig) -> Self {
+        let base_url = config
+            .base_url
+            .clone()
+            .unwrap_or_else(|| "https://crates.io/api/v1".to_string());
+        Self::with_sources_and_config(client, vec![IndexSource::new(base_url)], max_retries, config)
+    }
+
+    /// Like [`CratesIoRegistry::with_client`], but resolving every lookup
+    /// against `sources` in priority order - e.g. a private registry mirror
+    /// before falling back to the public crates.io index - instead of just
+    /// crates.io. A source's [`IndexAuth`], if any, is applied to every
+    /// request sent to it.
+    pub fn with_sources(client: Arc<Client>, sources: Vec<IndexSource>) -> Self {
+        Self::with_sources_and_config(
+            client,
+            sources,
+            DEFAULT_MAX_RATE_LIMIT_RETRIES,
+            RegistryConfig::default(),
+        )
+    }
+
+    /// Like [`CratesIoRegistry::with_sources`], additionally honoring a
+    /// [`RegistryConfig`] and a configurable `429` retry cap.
+    pub fn with_sources_and_config(
+        client: Arc<Client>,
+        sources: Vec<IndexSource>,
+        max_retries: u32,
+        config: RegistryConfig,
+    ) -> Self {
+        let sources = if sources.is_empty() {
+            let base_url = config
+                .base_url
+                .clone()
+                .unwrap_or_else(|| "https://crates.io/api/v1".to_string());
+            vec![IndexSource::new(base_url)]
+        } else {
+            sources
+        };
         Self {
             client,
             rate_limiter: Arc::new(Mutex::new(RateLimiter::new(1.0))),
-            base_url: "https://crates.io/api/v1".to_string(),
+            sources,
+            max_retries,
+            timeout: config.timeout,
+            cache: config.cache_ttl.map(ResponseCache::with_ttl),
+        }
+    }
+
+    /// Tries every configured source in priority order, returning the first
+    /// successful (`2xx`) response. If none succeed, returns the last
+    /// response/error seen so the caller's own status-code error message
+    /// reflects what actually happened.
+    async fn fetch_from_sources(&self, package_name: &str) -> anyhow::Result<reqwest::Response> {
+        let mut last_result = None;
+        for source in &self.sources {
+            let url = format!(
+                "{}/crates/{}",
+                source.base_url.trim_end_matches('/'),
+                package_name
+            );
+            let result = self.request_with_backoff(&url, source, package_name).await;
+            let succeeded = matches!(&result, Ok(response) if response.status().is_success());
+            last_result = Some(result);
+            if succeeded {
+                break;
+            }
+        }
+        last_result
+            .unwrap_or_else(|| anyhow::bail!("no index sources configured for {}", package_name))
+    }
+
+    /// Sends `GET url` against `source`, respecting the shared rate limiter
+    /// and retrying a `429` response with its `Retry-After` delay (or
+    /// bounded exponential backoff if the header is absent/unparseable), up
+    /// to `max_retries` times.
+    async fn request_with_backoff(
+        &self,
+        url: &str,
+        source: &IndexSource,
+        package_name: &str,
+    ) -> anyhow::Result<reqwest::Response> {
+        let mut attempt = 0;
+        loop {
+            {
+                let mut limiter = self.rate_limiter.lock().await;
+                limiter.wait().await;
+            }
+
+            let mut request = self.client.get(url);
+            if let Some(timeout) = self.timeout {
+                request = request.timeout(timeout);
+            }
+            if let Some(auth) = &source.auth {
+                request = auth.apply(request);
+            }
+            let response = request.send().await?;
+
+            {
+                let mut limiter = self.rate_limiter.lock().await;
+                limiter.observe_rate_limit_headers(response.headers());
+            }
+
+            if response.status() != reqwest::StatusCode::TOO_MANY_REQUESTS {
+                return Ok(response);
+            }
+
+            if attempt >= self.max_retries {
+                anyhow::bail!(
+                    "Failed to fetch crate info for {}: still rate limited after {} retries",
+                    package_name,
+                    attempt
+                );
+            }
+
+            let delay =
+                parse_retry_after(response.headers()).unwrap_or_else(|| exponential_backoff(attempt));
+            tokio::time::sleep(delay).await;
+            attempt += 1;
         }
     }
 }
@@ -181,6 +376,9 @@ struct VersionEntry {
     yanked: bool,
     license: Option<String>,
     created_at: Option<String>,
+    /// Minimum supported Rust version declared by this release, if any
+    /// (Cargo's `rust-version` manifest field, e.g. `"1.70"`).
+    rust_version: Option<String>,
 }
 
 impl Registry for CratesIoRegistry {
@@ -189,15 +387,13 @@ impl Registry for CratesIoRegistry {
     }
 
     async fn get_version_info(&self, package_name: &str) -> anyhow::Result<VersionInfo> {
-        // Rate limiting
-        {
-            let mut limiter = self.rate_limiter.lock().await;
-            limiter.wait().await;
+        if let Some(cache) = &self.cache {
+            if let Some((cached, true)) = cache.get("crates.io", package_name) {
+                return Ok(cached.info);
+            }
         }
 
-        let url = format!("{}/crates/{}", self.base_url, package_name);
-
-        let response = self.client.get(&url).send().await?;
+        let response = self.fetch_from_sources(package_name).await?;
 
         if !response.status().is_success() {
             anyhow::bail!(
@@ -264,10 +460,17 @@ impl Registry for CratesIoRegistry {
             })
             .collect();
 
+        // Collect MSRV (rust-version) for all versions that declare one
+        let rust_version: HashMap<String, String> = crate_response
+            .versions
+            .iter()
+            .filter_map(|v| v.rust_version.clone().map(|rv| (v.num.clone(), rv)))
+            .collect();
+
         // Check if latest version is yanked (kept for backwards compatibility)
         let yanked = crate_response.versions.first().is_some_and(|v| v.yanked);
 
-        Ok(VersionInfo {
+        let info = VersionInfo {
             latest: latest_stable,
             latest_prerelease,
             versions,
@@ -280,7 +483,31 @@ impl Registry for CratesIoRegistry {
             yanked,
             yanked_versions,
             release_dates,
-        })
+            platforms: HashMap::new(),
+            dependency_groups: HashMap::new(),
+            deprecation_messages: HashMap::new(),
+            requires_python: HashMap::new(),
+            rust_version,
+            yanked_reasons: HashMap::new(), // crates.io doesn't expose a yank reason
+            latest_breaking_major: None,
+            latest_compatible: None,
+            alternative_version: None,
+            version_metadata: HashMap::new(),
+        };
+
+        if let Some(cache) = &self.cache {
+            cache.insert(
+                "crates.io",
+                package_name,
+                CachedResponse {
+                    info: info.clone(),
+                    etag: None,
+                    last_modified: None,
+                },
+            );
+        }
+
+        Ok(info)
     }
 }
 
@@ -296,4 +523,62 @@ mod tests {
         assert!(!is_prerelease_rust("1.0.0"));
         assert!(!is_prerelease_rust("2.3.4"));
     }
+
+    #[test]
+    fn test_with_config_overrides_base_url() {
+        let client = create_shared_client().unwrap();
+        let config = RegistryConfig::new().with_base_url("https://mirror.example.com/api/v1");
+        let registry = CratesIoRegistry::with_config(client, DEFAULT_MAX_RATE_LIMIT_RETRIES, config);
+
+        assert_eq!(registry.sources.len(), 1);
+        assert_eq!(registry.sources[0].base_url, "https://mirror.example.com/api/v1");
+    }
+
+    #[test]
+    fn test_with_config_defaults_to_crates_io_base_url() {
+        let client = create_shared_client().unwrap();
+        let registry =
+            CratesIoRegistry::with_config(client, DEFAULT_MAX_RATE_LIMIT_RETRIES, RegistryConfig::new());
+
+        assert_eq!(registry.sources[0].base_url, "https://crates.io/api/v1");
+    }
+
+    #[test]
+    fn test_with_config_cache_ttl_enables_cache() {
+        let client = create_shared_client().unwrap();
+        let config = RegistryConfig::new().with_cache_ttl(Duration::from_secs(60));
+        let registry = CratesIoRegistry::with_config(client, DEFAULT_MAX_RATE_LIMIT_RETRIES, config);
+
+        assert!(registry.cache.is_some());
+    }
+
+    #[test]
+    fn test_with_sources_tries_in_priority_order() {
+        let client = create_shared_client().unwrap();
+        let sources = vec![
+            IndexSource::new("https://private.example.com/api/v1").with_bearer_token("secret"),
+            IndexSource::new("https://crates.io/api/v1"),
+        ];
+        let registry = CratesIoRegistry::with_sources(client, sources);
+
+        assert_eq!(registry.sources.len(), 2);
+        assert_eq!(registry.sources[0].base_url, "https://private.example.com/api/v1");
+        assert!(registry.sources[0].auth.is_some());
+        assert_eq!(registry.sources[1].base_url, "https://crates.io/api/v1");
+    }
+
+    #[test]
+    fn test_with_sources_empty_falls_back_to_config_base_url() {
+        let client = create_shared_client().unwrap();
+        let config = RegistryConfig::new().with_base_url("https://mirror.example.com/api/v1");
+        let registry = CratesIoRegistry::with_sources_and_config(
+            client,
+            vec![],
+            DEFAULT_MAX_RATE_LIMIT_RETRIES,
+            config,
+        );
+
+        assert_eq!(registry.sources.len(), 1);
+        assert_eq!(registry.sources[0].base_url, "https://mirror.example.com/api/v1");
+    }
 }
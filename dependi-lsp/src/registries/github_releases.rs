@@ -0,0 +1,248 @@
+//! # GitHub Releases Registry Client
+//!
+//! Client for version sources that aren't served by a package registry at
+//! all - a vendored tool, or a Go module whose `require` line points
+//! straight at its GitHub repo - but that publish versions as git tags or
+//! GitHub Releases. Each source is configured with a `repo` (`owner/repo`)
+//! and an optional regex `select_search`/`select_replace` pair so tags like
+//! `release-1.2.3` or `v1.2.3-rc1` can be normalized into versions the rest
+//! of the pipeline can compare.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use reqwest::Client;
+use serde::Deserialize;
+
+use super::{Registry, VersionInfo};
+
+/// Client for a single GitHub-releases-backed version source.
+pub struct GithubReleasesRegistry {
+    client: Arc<Client>,
+    base_url: String,
+    /// `owner/repo` to list tags/releases from
+    repo: String,
+    /// Regex applied to each raw tag name to extract/normalize its version.
+    /// `None` means the tag name is used as-is.
+    select_search: Option<Regex>,
+    /// Replacement template (`$1`, `$2`, ...) applied to a `select_search`
+    /// match. `None` means the whole match is used unchanged.
+    select_replace: Option<String>,
+    /// Drop tags that don't parse as valid semver after normalization.
+    semantic_only: bool,
+}
+
+impl GithubReleasesRegistry {
+    /// Create a new GitHub releases registry client for `repo` (`owner/repo`).
+    ///
+    /// An invalid `select_search` pattern is treated the same as `None` -
+    /// tags are used as-is - rather than failing construction.
+    pub fn with_client_and_config(
+        client: Arc<Client>,
+        repo: String,
+        select_search: Option<String>,
+        select_replace: Option<String>,
+        semantic_only: bool,
+    ) -> Self {
+        let select_search = select_search.and_then(|pattern| Regex::new(&pattern).ok());
+        Self {
+            client,
+            base_url: "https://api.github.com".to_string(),
+            repo,
+            select_search,
+            select_replace,
+            semantic_only,
+        }
+    }
+
+    /// Normalize a raw tag name into a comparable version, or `None` if it
+    /// should be dropped (no `select_search` match, or `semantic_only`
+    /// rejects it after normalization).
+    fn normalize_tag(&self, tag: &str) -> Option<String> {
+        let normalized = match &self.select_search {
+            Some(search) => {
+                let replacement = self.select_replace.as_deref().unwrap_or("$0");
+                search.captures(tag)?;
+                search.replace(tag, replacement).into_owned()
+            }
+            None => tag.to_string(),
+        };
+
+        if self.semantic_only
+            && semver::Version::parse(normalized.trim_start_matches('v')).is_err()
+        {
+            return None;
+        }
+
+        Some(normalized)
+    }
+}
+
+/// A single entry from `GET /repos/{repo}/tags`
+#[derive(Debug, Deserialize)]
+struct TagResponse {
+    name: String,
+}
+
+/// A single entry from `GET /repos/{repo}/releases`
+#[derive(Debug, Deserialize)]
+struct ReleaseResponse {
+    tag_name: String,
+    published_at: Option<String>,
+}
+
+impl Registry for GithubReleasesRegistry {
+    fn http_client(&self) -> Arc<Client> {
+        Arc::clone(&self.client)
+    }
+
+    /// `package_name` is ignored - a `GithubReleasesRegistry` is already
+    /// bound to a single, fixed `repo` at construction time.
+    async fn get_version_info(&self, _package_name: &str) -> anyhow::Result<VersionInfo> {
+        let releases_url = format!("{}/repos/{}/releases", self.base_url, self.repo);
+        let (raw_tags, raw_dates): (Vec<String>, HashMap<String, DateTime<Utc>>) =
+            match self.client.get(&releases_url).send().await {
+                Ok(response) if response.status().is_success() => {
+                    let releases: Vec<ReleaseResponse> = response.json().await.unwrap_or_default();
+                    let dates = releases
+                        .iter()
+                        .filter_map(|r| {
+                            r.published_at.as_deref().and_then(|time_str| {
+                                DateTime::parse_from_rfc3339(time_str)
+                                    .ok()
+                                    .map(|dt| (r.tag_name.clone(), dt.with_timezone(&Utc)))
+                            })
+                        })
+                        .collect();
+                    (releases.into_iter().map(|r| r.tag_name).collect(), dates)
+                }
+                _ => {
+                    // No GitHub Releases configured for this repo (or the
+                    // request failed) - fall back to plain git tags, which
+                    // don't carry a publish date.
+                    let tags_url = format!("{}/repos/{}/tags", self.base_url, self.repo);
+                    let response = self.client.get(&tags_url).send().await?;
+                    if !response.status().is_success() {
+                        anyhow::bail!(
+                            "Failed to fetch tags for {}: {}",
+                            self.repo,
+                            response.status()
+                        );
+                    }
+                    let tags: Vec<TagResponse> = response.json().await.unwrap_or_default();
+                    (tags.into_iter().map(|t| t.name).collect(), HashMap::new())
+                }
+            };
+
+        let mut versions: Vec<String> = Vec::new();
+        let mut release_dates = HashMap::new();
+        for tag in &raw_tags {
+            let Some(version) = self.normalize_tag(tag) else {
+                continue;
+            };
+            if let Some(date) = raw_dates.get(tag) {
+                release_dates.entry(version.clone()).or_insert(*date);
+            }
+            if !versions.contains(&version) {
+                versions.push(version);
+            }
+        }
+
+        versions.sort_by(|a, b| {
+            match (
+                semver::Version::parse(a.trim_start_matches('v')),
+                semver::Version::parse(b.trim_start_matches('v')),
+            ) {
+                (Ok(va), Ok(vb)) => vb.cmp(&va),
+                _ => b.cmp(a),
+            }
+        });
+
+        let latest = versions.first().cloned();
+
+        Ok(VersionInfo {
+            latest,
+            latest_prerelease: None,
+            versions,
+            description: None,
+            homepage: Some(format!("https://github.com/{}", self.repo)),
+            repository: Some(format!("https://github.com/{}", self.repo)),
+            license: None,
+            vulnerabilities: vec![],
+            deprecated: false,
+            yanked: false,
+            yanked_versions: vec![],
+            release_dates,
+            platforms: HashMap::new(),
+            dependency_groups: HashMap::new(),
+            deprecation_messages: HashMap::new(),
+            requires_python: HashMap::new(),
+            rust_version: HashMap::new(),
+            yanked_reasons: HashMap::new(),
+            latest_breaking_major: None,
+            latest_compatible: None,
+            alternative_version: None,
+            version_metadata: HashMap::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registries::http_client::create_shared_client;
+
+    fn registry(
+        select_search: Option<&str>,
+        select_replace: Option<&str>,
+        semantic_only: bool,
+    ) -> GithubReleasesRegistry {
+        GithubReleasesRegistry::with_client_and_config(
+            create_shared_client().expect("Failed to create HTTP client"),
+            "example/tool".to_string(),
+            select_search.map(str::to_string),
+            select_replace.map(str::to_string),
+            semantic_only,
+        )
+    }
+
+    #[test]
+    fn test_normalize_tag_without_selector() {
+        let registry = registry(None, None, false);
+        assert_eq!(registry.normalize_tag("v1.2.3").as_deref(), Some("v1.2.3"));
+    }
+
+    #[test]
+    fn test_normalize_tag_with_search_and_replace() {
+        let registry = registry(Some(r"^release-(\d+\.\d+\.\d+)$"), Some("$1"), false);
+        assert_eq!(
+            registry.normalize_tag("release-1.2.3").as_deref(),
+            Some("1.2.3")
+        );
+        assert_eq!(registry.normalize_tag("v1.2.3"), None);
+    }
+
+    #[test]
+    fn test_normalize_tag_semantic_only_drops_non_semver() {
+        let registry = registry(Some(r"^v(.+)$"), Some("$1"), true);
+        assert_eq!(registry.normalize_tag("v1.2.3").as_deref(), Some("1.2.3"));
+        assert_eq!(registry.normalize_tag("vnightly"), None);
+    }
+
+    #[test]
+    fn test_normalize_tag_captures_commit_pinned_pseudo_version() {
+        // e.g. a tag like `20240102150405,abcdef1` for a commit-pinned Go
+        // dependency with no proper release tag of its own.
+        let registry = registry(
+            Some(r"^(\d{14}),([0-9a-f]+)$"),
+            Some("v0.0.0-$1-$2"),
+            false,
+        );
+        assert_eq!(
+            registry.normalize_tag("20240102150405,abcdef1").as_deref(),
+            Some("v0.0.0-20240102150405-abcdef1")
+        );
+    }
+}
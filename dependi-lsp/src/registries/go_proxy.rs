@@ -82,6 +82,15 @@ impl Registry for GoProxyRegistry {
         // Fetch latest version info
         let latest = self.fetch_latest(&encoded_path).await.ok();
 
+        // Go encodes majors 2+ in the module path itself, so `latest` above
+        // only ever reflects the current major. Probe beyond it for a
+        // higher major published under a `/vN` suffix.
+        let (base_path, current_major) = split_major_suffix(module_path);
+        let latest_breaking_major = self
+            .probe_next_major(base_path, current_major.unwrap_or(1))
+            .await
+            .map(|(major, version)| format!("{base_path}/v{major}@{version}"));
+
         // Sort versions in descending order
         let mut sorted_versions = versions.clone();
         sorted_versions.sort_by(|a, b| compare_go_versions(b, a));
@@ -126,8 +135,36 @@ impl Registry for GoProxyRegistry {
             yanked: false,
             yanked_versions: vec![], // Not applicable to Go
             release_dates,
+            platforms: HashMap::new(),
+            dependency_groups: HashMap::new(),
+            deprecation_messages: HashMap::new(),
+            requires_python: HashMap::new(),
+            rust_version: HashMap::new(),
+            yanked_reasons: HashMap::new(),
+            latest_breaking_major,
+            latest_compatible: None,
+            alternative_version: None,
+            version_metadata: HashMap::new(),
         })
     }
+
+    /// `go.mod` requirements are exact pins, not ranges, so there's nothing
+    /// to match against - the compatible version is just the current
+    /// major's latest.
+    async fn get_version_info_for_requirement(
+        &self,
+        module_path: &str,
+        _requirement: &str,
+    ) -> anyhow::Result<VersionInfo> {
+        let mut info = self.get_version_info(module_path).await?;
+        info.latest_compatible = info.latest.clone();
+        // Always None: go.mod pins an exact version rather than a range, so
+        // there's no "latest within range" vs. "latest overall" distinction
+        // here - `latest_breaking_major` already covers the "a higher major
+        // exists" case with its own `module@vN` semantics.
+        info.alternative_version = None;
+        Ok(info)
+    }
 }
 
 impl GoProxyRegistry {
@@ -186,6 +223,35 @@ impl GoProxyRegistry {
         response.json().await.ok()
     }
 
+    /// Probes `base_path/v{N}` for `N` = `current_major + 1, current_major +
+    /// 2, ...` - Go modules don't announce future majors anywhere, so the
+    /// only way to discover the highest one actually published is to walk
+    /// the candidate paths until `@v/list` comes back empty (a 404 is
+    /// folded into the same empty result by [`Self::fetch_versions`]).
+    /// Returns the highest reachable major and its latest stable version,
+    /// or `None` if `current_major` is already the highest. Capped at 20
+    /// probes past the current major as a defensive bound against an
+    /// unexpectedly long chain of responses.
+    async fn probe_next_major(&self, base_path: &str, current_major: u32) -> Option<(u32, String)> {
+        let mut highest = None;
+
+        for major in (current_major + 1)..(current_major + 1 + 20) {
+            let candidate_path = format!("{base_path}/v{major}");
+            let encoded = encode_module_path(&candidate_path);
+            let versions = self.fetch_versions(&encoded).await.unwrap_or_default();
+
+            let mut sorted = versions;
+            sorted.sort_by(|a, b| compare_go_versions(b, a));
+            let Some(latest) = sorted.into_iter().find(|v| !is_prerelease_go(v)) else {
+                break;
+            };
+
+            highest = Some((major, latest));
+        }
+
+        highest
+    }
+
     /// Fetch release times for a list of versions (limited to first 10 for performance)
     async fn fetch_version_times(
         &self,
@@ -232,6 +298,23 @@ fn encode_module_path(path: &str) -> String {
     result
 }
 
+/// Splits a Go module path into its base path and explicit major-version
+/// suffix, per Go's "semantic import versioning" rule that only majors 2+
+/// are encoded in the path (`github.com/foo/bar/v3`) - `v0`/`v1` modules
+/// have no suffix at all. Returns `None` for the suffix when the path
+/// doesn't end in one.
+fn split_major_suffix(module_path: &str) -> (&str, Option<u32>) {
+    if let Some(idx) = module_path.rfind("/v") {
+        let suffix = &module_path[idx + 2..];
+        if let Ok(major) = suffix.parse::<u32>()
+            && major >= 2
+        {
+            return (&module_path[..idx], Some(major));
+        }
+    }
+    (module_path, None)
+}
+
 /// Compare Go versions for sorting
 fn compare_go_versions(a: &str, b: &str) -> std::cmp::Ordering {
     // Strip 'v' prefix if present
@@ -243,7 +326,11 @@ fn compare_go_versions(a: &str, b: &str) -> std::cmp::Ordering {
         semver::Version::parse(a_stripped),
         semver::Version::parse(b_stripped),
     ) {
-        (Ok(va), Ok(vb)) => va.cmp(&vb),
+        // `cmp_precedence` ignores build metadata, so pre-modules
+        // `+incompatible` tags (`v2.0.0+incompatible`) sort identically to
+        // their bare version - `cmp` alone would treat the build metadata
+        // as a tiebreaker and rank it above the same version without one.
+        (Ok(va), Ok(vb)) => va.cmp_precedence(&vb),
         _ => {
             // Fallback to string comparison
             compare_version_strings(a_stripped, b_stripped)
@@ -307,4 +394,40 @@ mod tests {
         assert_eq!(compare_go_versions("v1.0.0", "v1.0.0"), Ordering::Equal);
         assert_eq!(compare_go_versions("v1.10.0", "v1.9.0"), Ordering::Greater);
     }
+
+    #[test]
+    fn test_compare_go_versions_ignores_incompatible_build_tag() {
+        use std::cmp::Ordering;
+
+        assert_eq!(
+            compare_go_versions("v2.0.0+incompatible", "v2.0.0"),
+            Ordering::Equal
+        );
+        assert_eq!(
+            compare_go_versions("v2.0.0+incompatible", "v1.9.0"),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn test_is_prerelease_does_not_misclassify_incompatible() {
+        assert!(!is_prerelease_go("v2.0.0+incompatible"));
+    }
+
+    #[test]
+    fn test_split_major_suffix() {
+        assert_eq!(
+            split_major_suffix("github.com/foo/bar/v3"),
+            ("github.com/foo/bar", Some(3))
+        );
+        assert_eq!(
+            split_major_suffix("github.com/foo/bar"),
+            ("github.com/foo/bar", None)
+        );
+        // v0 and v1 aren't encoded in the path per Go's own convention.
+        assert_eq!(
+            split_major_suffix("github.com/foo/bar/v1"),
+            ("github.com/foo/bar/v1", None)
+        );
+    }
 }
@@ -15,27 +15,112 @@ use std::time::Duration;
 
 use reqwest::Client;
 
+use crate::auth::TokenProviderManager;
+use crate::config::HttpConfig;
+
 const USER_AGENT: &str = concat!(
     "dependi-lsp/",
     env!("CARGO_PKG_VERSION"),
     " (https://github.com/mpiton/zed-dependi)"
 );
 
-const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
 const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
 const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
 
-pub fn create_shared_client() -> anyhow::Result<Arc<Client>> {
-    let client = Client::builder()
+/// Build a `reqwest::Client` honoring the user's proxy, extra CA certificate,
+/// and timeout settings.
+fn build_client(config: &HttpConfig) -> anyhow::Result<Client> {
+    let mut builder = Client::builder()
         .user_agent(USER_AGENT)
-        .timeout(DEFAULT_TIMEOUT)
+        .timeout(Duration::from_secs(config.timeout_secs))
         .connect_timeout(CONNECT_TIMEOUT)
         .pool_idle_timeout(POOL_IDLE_TIMEOUT)
         .pool_max_idle_per_host(10)
-        .tcp_keepalive(Duration::from_secs(60))
-        .build()?;
+        .tcp_keepalive(Duration::from_secs(60));
+
+    if let Some(proxy_url) = &config.proxy {
+        let mut proxy = reqwest::Proxy::all(proxy_url)?;
+        if !config.no_proxy.is_empty() {
+            let no_proxy = reqwest::NoProxy::from_string(&config.no_proxy.join(","));
+            proxy = proxy.no_proxy(no_proxy);
+        }
+        builder = builder.proxy(proxy);
+    }
+
+    for ca_cert_path in &config.extra_ca_certs {
+        let pem = std::fs::read(ca_cert_path)?;
+        builder = builder.add_root_certificate(reqwest::Certificate::from_pem(&pem)?);
+    }
+
+    Ok(builder.build()?)
+}
+
+/// Create a shared HTTP client with default settings (no proxy, no extra CA
+/// certificates, default timeout). Used when no user configuration is
+/// available yet, e.g. before `initialize` has run.
+pub fn create_shared_client() -> anyhow::Result<Arc<Client>> {
+    Ok(Arc::new(build_client(&HttpConfig::default())?))
+}
+
+/// Builds and holds the shared HTTP client used by every registry,
+/// reconfigurable from user `initializationOptions` (proxy, extra root CA
+/// certificates, timeouts), with a hard offline toggle that callers should
+/// check before making any network request.
+///
+/// Also carries the shared [`TokenProviderManager`] every registry client is
+/// built with, so private-registry auth (e.g. the `DEPENDI_AUTH_TOKENS`
+/// bundle) reaches the same clients that actually issue requests, rather
+/// than living only in its own module's unit tests.
+pub struct HttpClientProvider {
+    client: Arc<Client>,
+    offline: bool,
+    token_manager: Arc<TokenProviderManager>,
+}
+
+impl HttpClientProvider {
+    /// Build a provider from user configuration, with an empty token
+    /// manager - use [`Self::with_token_manager`] to carry over one already
+    /// populated from the environment.
+    pub fn new(config: &HttpConfig) -> anyhow::Result<Self> {
+        Self::with_token_manager(config, Arc::new(TokenProviderManager::new()))
+    }
+
+    /// Build a provider from user configuration and a pre-populated token
+    /// manager, so every registry client built from this provider
+    /// transparently authenticates through it.
+    pub fn with_token_manager(
+        config: &HttpConfig,
+        token_manager: Arc<TokenProviderManager>,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            client: Arc::new(build_client(config)?),
+            offline: config.offline,
+            token_manager,
+        })
+    }
 
-    Ok(Arc::new(client))
+    /// The shared client registries should issue requests through
+    pub fn client(&self) -> Arc<Client> {
+        Arc::clone(&self.client)
+    }
+
+    /// The shared token manager registry clients should consult for
+    /// private-registry auth headers.
+    pub fn token_manager(&self) -> Arc<TokenProviderManager> {
+        Arc::clone(&self.token_manager)
+    }
+
+    /// Whether network calls should be short-circuited to cache-only
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+}
+
+impl Default for HttpClientProvider {
+    fn default() -> Self {
+        Self::new(&HttpConfig::default())
+            .expect("building the HTTP client with default settings should never fail")
+    }
 }
 
 #[cfg(test)]
@@ -102,4 +187,29 @@ mod tests {
             Arc::as_ptr(&npm.http_client())
         );
     }
+
+    #[test]
+    fn test_provider_default_is_online() {
+        let provider = HttpClientProvider::default();
+        assert!(!provider.is_offline());
+    }
+
+    #[test]
+    fn test_provider_honors_offline_flag() {
+        let config = HttpConfig {
+            offline: true,
+            ..HttpConfig::default()
+        };
+        let provider = HttpClientProvider::new(&config).expect("client should build");
+        assert!(provider.is_offline());
+    }
+
+    #[test]
+    fn test_provider_rejects_missing_ca_cert() {
+        let config = HttpConfig {
+            extra_ca_certs: vec!["/nonexistent/ca.pem".to_string()],
+            ..HttpConfig::default()
+        };
+        assert!(HttpClientProvider::new(&config).is_err());
+    }
 }
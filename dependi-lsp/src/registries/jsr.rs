@@ -0,0 +1,209 @@
+//! Client for the JSR (JavaScript Registry), used by Deno/JSR packages
+//! referenced as `jsr:@scope/name`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use reqwest::Client;
+use serde::Deserialize;
+
+use super::http_client::create_shared_client;
+use super::version_utils::is_prerelease_jsr;
+use super::{Registry, VersionInfo};
+use crate::vulnerabilities::cache::VulnerabilityCache;
+use crate::vulnerabilities::osv::OsvClient;
+use crate::vulnerabilities::{Ecosystem, VulnerabilityQuery};
+
+/// Builds an `OsvClient` sharing `client`'s connection pool, with a
+/// persistent on-disk vulnerability cache attached when one can be opened -
+/// falling back to an uncached client (still correct, just always hitting
+/// the network) rather than failing registry construction over a cache
+/// file that couldn't be opened.
+fn osv_client(client: Arc<Client>) -> Arc<OsvClient> {
+    let mut osv = OsvClient::with_client(client);
+    if let Ok(cache) = VulnerabilityCache::open_default() {
+        osv = osv.with_cache(Arc::new(cache));
+    }
+    Arc::new(osv)
+}
+
+/// Client for the JSR registry
+pub struct JsrRegistry {
+    client: Arc<Client>,
+    base_url: String,
+    osv: Arc<OsvClient>,
+}
+
+impl JsrRegistry {
+    /// Creates a `JsrRegistry` that uses the provided shared HTTP client and
+    /// targets the public JSR registry.
+    pub fn with_client(client: Arc<Client>) -> Self {
+        let osv = osv_client(Arc::clone(&client));
+        Self {
+            client,
+            base_url: "https://jsr.io".to_string(),
+            osv,
+        }
+    }
+
+    /// Queries OSV for known vulnerabilities affecting `latest` and
+    /// `latest_prerelease`, tolerating any OSV outage by reporting no
+    /// vulnerabilities rather than failing the whole version-info fetch.
+    async fn fetch_vulnerabilities(
+        &self,
+        package_name: &str,
+        latest: Option<&str>,
+        latest_prerelease: Option<&str>,
+    ) -> Vec<super::Vulnerability> {
+        let mut versions: Vec<String> = [latest, latest_prerelease]
+            .into_iter()
+            .flatten()
+            .map(|v| v.to_string())
+            .collect();
+        versions.sort_unstable();
+        versions.dedup();
+
+        if versions.is_empty() {
+            return vec![];
+        }
+
+        let queries: Vec<VulnerabilityQuery> = versions
+            .into_iter()
+            .map(|version| VulnerabilityQuery {
+                package_name: package_name.to_string(),
+                version,
+                ecosystem: Ecosystem::Jsr,
+            })
+            .collect();
+
+        self.osv
+            .query_batch_hydrated(&queries)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
+
+impl Default for JsrRegistry {
+    fn default() -> Self {
+        Self::with_client(create_shared_client().expect("Failed to create HTTP client"))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct JsrMetaResponse {
+    latest: Option<String>,
+    versions: Option<HashMap<String, JsrVersionMeta>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsrVersionMeta {
+    #[serde(default)]
+    yanked: bool,
+}
+
+impl Registry for JsrRegistry {
+    fn http_client(&self) -> Arc<Client> {
+        Arc::clone(&self.client)
+    }
+
+    async fn get_version_info(&self, package_name: &str) -> anyhow::Result<VersionInfo> {
+        // Unlike npm scoped packages, JSR uses `@scope/name` literally in the
+        // path - no `%2f` encoding of the slash.
+        let name = package_name.strip_prefix("jsr:").unwrap_or(package_name);
+        let url = format!("{}/{}/meta.json", self.base_url, name);
+
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "Failed to fetch package info for {}: {}",
+                package_name,
+                response.status()
+            );
+        }
+
+        let meta: JsrMetaResponse = response.json().await?;
+
+        // Sort versions in descending order (newest first)
+        let mut versions: Vec<String> = meta
+            .versions
+            .as_ref()
+            .map(|v| v.keys().cloned().collect())
+            .unwrap_or_default();
+        versions.sort_by(|a, b| match (semver::Version::parse(a), semver::Version::parse(b)) {
+            (Ok(va), Ok(vb)) => vb.cmp(&va),
+            _ => b.cmp(a),
+        });
+
+        // Find latest prerelease
+        let latest_prerelease = versions.iter().find(|v| is_prerelease_jsr(v)).cloned();
+
+        // JSR exposes yanking directly on each version entry, unlike npm.
+        let yanked_versions = meta
+            .versions
+            .as_ref()
+            .map(|v| {
+                v.iter()
+                    .filter(|(_, meta)| meta.yanked)
+                    .map(|(version, _)| version.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let vulnerabilities = self
+            .fetch_vulnerabilities(
+                package_name,
+                meta.latest.as_deref(),
+                latest_prerelease.as_deref(),
+            )
+            .await;
+
+        Ok(VersionInfo {
+            latest: meta.latest,
+            latest_prerelease,
+            versions,
+            description: None,
+            homepage: None,
+            repository: None,
+            license: None,
+            vulnerabilities,
+            deprecated: false,
+            yanked: false,
+            yanked_versions,
+            release_dates: HashMap::new(),
+            platforms: HashMap::new(),
+            dependency_groups: HashMap::new(),
+            deprecation_messages: HashMap::new(),
+            requires_python: HashMap::new(),
+            rust_version: HashMap::new(),
+            yanked_reasons: HashMap::new(),
+            latest_breaking_major: None,
+            latest_compatible: None,
+            alternative_version: None,
+            version_metadata: HashMap::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_prerelease() {
+        assert!(is_prerelease_jsr("1.0.0-alpha"));
+        assert!(is_prerelease_jsr("1.0.0-rc.1"));
+        assert!(!is_prerelease_jsr("1.0.0"));
+    }
+
+    #[test]
+    fn test_strips_jsr_prefix() {
+        assert_eq!(
+            "jsr:@scope/name".strip_prefix("jsr:").unwrap(),
+            "@scope/name"
+        );
+    }
+}
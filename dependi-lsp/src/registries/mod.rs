@@ -1,5 +1,9 @@
 //! Registry clients for fetching package version information
 
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use reqwest::RequestBuilder;
 use serde::{Deserialize, Serialize};
 
 /// Information about a package version from a registry
@@ -25,6 +29,211 @@ pub struct VersionInfo {
     pub deprecated: bool,
     /// Whether the version is yanked (Rust specific)
     pub yanked: bool,
+    /// Versions that have been yanked/withdrawn by the publisher
+    pub yanked_versions: Vec<String>,
+    /// Publish date for each known version, where available
+    pub release_dates: HashMap<String, DateTime<Utc>>,
+    /// Platform-specific build variants observed for a version (e.g. RubyGems'
+    /// `-java`/`-x86_64-linux` gem builds), keyed by the base version in
+    /// `versions`. Empty for registries without platform-specific builds.
+    pub platforms: HashMap<String, Vec<String>>,
+    /// Per-target-framework dependency groups (NuGet's `dependencyGroups`),
+    /// keyed by the version in `versions`. Empty for registries without a
+    /// notion of per-framework dependency sets.
+    pub dependency_groups: HashMap<String, Vec<DependencyGroup>>,
+    /// Human-readable deprecation message per version, where the registry
+    /// provides one (e.g. npm's `versions[v].deprecated` string). Empty for
+    /// registries that only expose a yes/no deprecation flag.
+    pub deprecation_messages: HashMap<String, String>,
+    /// PEP 440 `Requires-Python` specifier per version (PyPI-specific), e.g.
+    /// `">=3.8,<4.0"`. Empty for registries without an interpreter
+    /// constraint, or for a version the registry didn't report one for.
+    pub requires_python: HashMap<String, String>,
+    /// Cargo's `rust-version` (MSRV) per version (crates.io-specific), e.g.
+    /// `"1.70"`. Empty for registries without an MSRV concept, or for a
+    /// version the registry didn't report one for.
+    pub rust_version: HashMap<String, String>,
+    /// Human-readable reason a version in `yanked_versions` was yanked
+    /// (PEP 592's `yanked_reason`, PyPI-specific), where the registry
+    /// provided one. Empty for registries without a per-version yank
+    /// reason, or for a yanked version that wasn't given one.
+    pub yanked_reasons: HashMap<String, String>,
+    /// The highest major version published beyond the module's current
+    /// major-version path (Go-specific), as `module@version` (e.g.
+    /// `github.com/foo/bar/v3@v3.1.0`) - ready to drop straight into `go
+    /// get`. `latest` only ever reflects the current major, since Go
+    /// encodes majors 2+ in the module path itself. `None` when no higher
+    /// major was published, or for registries without Go's
+    /// major-version-in-path convention.
+    pub latest_breaking_major: Option<String>,
+    /// The newest entry in `versions` that still satisfies a declared
+    /// requirement, filled in by [`Registry::get_version_info_for_requirement`]
+    /// - the same compatible-vs-breaking distinction Cargo draws between a
+    /// safe `cargo update` and a `cargo update --breaking`. Unset (`None`)
+    /// on a [`VersionInfo`] from the plain [`Registry::get_version_info`],
+    /// which doesn't know the caller's requirement.
+    pub latest_compatible: Option<String>,
+    /// The absolute newest entry in `versions`, also filled in by
+    /// [`Registry::get_version_info_for_requirement`], when it falls
+    /// *outside* the declared requirement - i.e. the breaking upgrade
+    /// [`latest_compatible`] can't reach. `None` when the absolute latest is
+    /// itself in range (nothing extra to surface beyond `latest_compatible`)
+    /// or, like `latest_compatible`, on a [`VersionInfo`] from the plain
+    /// [`Registry::get_version_info`]. Mirrors `cargo-update`'s
+    /// `newest_version` vs. `alternative_version` split.
+    ///
+    /// [`latest_compatible`]: VersionInfo::latest_compatible
+    pub alternative_version: Option<String>,
+    /// Per-version dependency and feature metadata (Cargo's sparse-index
+    /// `deps[]`/`features` fields), keyed by the version in `versions`.
+    /// Populated by
+    /// [`cargo_sparse::CargoSparseRegistry`](super::cargo_sparse::CargoSparseRegistry);
+    /// empty for registries without Cargo's per-version `deps`/`features`
+    /// schema.
+    pub version_metadata: HashMap<String, VersionMeta>,
+}
+
+/// A single dependency declared by a crate version in the sparse index's
+/// `deps[]` array.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionDependency {
+    /// Dependency package name as it appears in `features`/code, which may
+    /// differ from the published crate name - see `package`.
+    pub name: String,
+    /// The published crate name, if `name` is a `package = "..."` rename.
+    pub package: Option<String>,
+    /// Declared SemVer requirement, e.g. `"^1.0"`.
+    pub req: String,
+    /// Features of the dependency this crate enables.
+    pub features: Vec<String>,
+    /// Whether this is an optional dependency.
+    pub optional: bool,
+    /// Whether the dependency's default features are enabled.
+    pub default_features: bool,
+    /// `cfg(...)`/target-triple string this dependency applies to, if
+    /// platform-specific.
+    pub target: Option<String>,
+    /// Dependency kind (`"normal"`, `"dev"`, or `"build"`).
+    pub kind: Option<String>,
+}
+
+/// Per-version dependency and feature metadata parsed from a sparse index
+/// entry. `features` merges the index's `features` and (schema `v >= 2`)
+/// `features2` maps, since both are just feature-name-to-enabled-items
+/// lists from a consumer's point of view - `features2` only exists to let
+/// older Cargo versions ignore weak-dependency-feature syntax they don't
+/// understand.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct VersionMeta {
+    /// This version's declared dependencies.
+    pub deps: Vec<VersionDependency>,
+    /// Feature name to the list of features/dependencies it enables.
+    pub features: HashMap<String, Vec<String>>,
+}
+
+/// A set of dependencies declared for one target framework (e.g. NuGet's
+/// `net8.0`, `netstandard2.0`). `target_framework` is `None` for a
+/// framework-agnostic group.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyGroup {
+    /// Target framework moniker this group applies to, if any.
+    pub target_framework: Option<String>,
+    /// Dependencies declared for this framework.
+    pub dependencies: Vec<PackageDependency>,
+}
+
+/// A single declared dependency within a [`DependencyGroup`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageDependency {
+    /// Dependency package id.
+    pub id: String,
+    /// Declared version range, if the registry specified one.
+    pub range: Option<VersionRange>,
+}
+
+/// A version range with structured min/max bounds, e.g. NuGet's
+/// `[1.0.0, )` (inclusive minimum only) or `(,3.0.0]` (inclusive maximum
+/// only).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionRange {
+    /// Minimum version bound, if any.
+    pub min: Option<String>,
+    /// Whether `min` itself satisfies the range.
+    pub min_inclusive: bool,
+    /// Maximum version bound, if any.
+    pub max: Option<String>,
+    /// Whether `max` itself satisfies the range.
+    pub max_inclusive: bool,
+}
+
+impl VersionInfo {
+    /// The highest non-prerelease version in `versions` under `ord`'s rules,
+    /// computed from the raw version list rather than trusting the
+    /// registry-reported `latest` field (some registries' "latest" tag
+    /// lags behind their own version list, or omits prerelease filtering
+    /// entirely).
+    pub fn latest_stable(&self, ord: &dyn version_utils::PrecedenceOrd) -> Option<&str> {
+        ord.highest(&self.versions, false)
+    }
+
+    /// The highest version in `versions` under `ord`'s rules, prereleases
+    /// included.
+    pub fn latest_including_prerelease(
+        &self,
+        ord: &dyn version_utils::PrecedenceOrd,
+    ) -> Option<&str> {
+        ord.highest(&self.versions, true)
+    }
+
+    /// The highest version in `versions` whose recorded [`requires_python`]
+    /// specifier - if any - is satisfied by `python_version`, skipping
+    /// releases the caller's interpreter can't run. A version with no
+    /// recorded specifier is assumed compatible.
+    ///
+    /// [`requires_python`]: VersionInfo::requires_python
+    pub fn latest_compatible_with_python(
+        &self,
+        ord: &dyn version_utils::PrecedenceOrd,
+        python_version: &str,
+        include_prerelease: bool,
+    ) -> Option<&str> {
+        self.versions
+            .iter()
+            .filter(|v| include_prerelease || !ord.is_prerelease(v))
+            .filter(|v| {
+                self.requires_python
+                    .get(*v)
+                    .map(|req| version_scheme::PythonScheme.satisfies(req, python_version))
+                    .unwrap_or(true)
+            })
+            .max_by(|a, b| ord.compare(a, b))
+            .map(String::as_str)
+    }
+
+    /// The highest version in `versions` whose recorded [`rust_version`]
+    /// (MSRV) - if any - is satisfied by `toolchain`, skipping releases the
+    /// caller's Rust compiler is too old for. A version with no recorded
+    /// MSRV is assumed compatible.
+    ///
+    /// [`rust_version`]: VersionInfo::rust_version
+    pub fn latest_compatible_with_rust_version(
+        &self,
+        ord: &dyn version_utils::PrecedenceOrd,
+        toolchain: &str,
+        include_prerelease: bool,
+    ) -> Option<&str> {
+        self.versions
+            .iter()
+            .filter(|v| include_prerelease || !ord.is_prerelease(v))
+            .filter(|v| {
+                self.rust_version
+                    .get(*v)
+                    .map(|min| version_scheme::satisfies_rust_version(min, toolchain))
+                    .unwrap_or(true)
+            })
+            .max_by(|a, b| ord.compare(a, b))
+            .map(String::as_str)
+    }
 }
 
 /// Vulnerability information
@@ -38,10 +247,39 @@ pub struct Vulnerability {
     pub description: String,
     /// URL for more information
     pub url: Option<String>,
+    /// The version the advisory reports this vulnerability as fixed in, if
+    /// the source provides one (e.g. OSV's range `fixed` event). `None` when
+    /// no fix is known yet, or the source doesn't track fixed versions.
+    pub fixed_version: Option<String>,
+    /// Every affected range the advisory reports, used by
+    /// [`version_set::nearest_safe_version`] to compute the minimal version
+    /// that clears all of them at once rather than just the first.
+    pub ranges: Vec<VulnerableRange>,
+    /// Other IDs the source considers equivalent to [`Vulnerability::id`]
+    /// (e.g. a GHSA advisory's CVE alias), used to de-duplicate the same
+    /// advisory reported under different IDs by different sources.
+    pub aliases: Vec<String>,
+    /// IDs of other advisories the source considers related (e.g. an OSV
+    /// entry pointing at the RustSec advisory it was derived from), kept
+    /// alongside but distinct from `aliases` since these aren't claimed to
+    /// identify the *same* vulnerability.
+    pub related: Vec<String>,
+}
+
+/// A single affected range from an advisory: the version it starts being
+/// vulnerable at (`None` meaning "from the start") and the version it's
+/// fixed at (`None` meaning still unpatched as of this scan). Kept as raw
+/// strings - parsed into SemVer only where [`version_set`] needs precision,
+/// since not every consumer of [`Vulnerability`] does.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct VulnerableRange {
+    pub introduced: Option<String>,
+    pub fixed: Option<String>,
 }
 
-/// Vulnerability severity levels
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+/// Vulnerability severity levels, ordered low to high so a range check can
+/// compare them directly (`Low < Medium < High < Critical`).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum VulnerabilitySeverity {
     Low,
     Medium,
@@ -59,14 +297,716 @@ pub trait Registry: Send + Sync {
     async fn get_latest_version(&self, package_name: &str) -> anyhow::Result<Option<String>> {
         Ok(self.get_version_info(package_name).await?.latest)
     }
+
+    /// Resolves a declared version requirement (e.g. npm/JSR's `^1.2.0`, or
+    /// NuGet's `[1.0.0,2.0.0)`) to the highest entry in
+    /// [`VersionInfo::versions`] that satisfies it, so callers can tell a
+    /// pinned range apart from "a newer major is available".
+    ///
+    /// Prereleases are excluded unless `req` itself names one at the same
+    /// major.minor.patch, matching [`semver::VersionReq`]'s own (node-semver)
+    /// opt-in rule. Returns `Ok(None)` when `req` doesn't parse or nothing in
+    /// `versions` matches.
+    async fn resolve_requirement(
+        &self,
+        package: &str,
+        req: &str,
+    ) -> anyhow::Result<Option<String>> {
+        let Some(version_req) = parse_requirement(req) else {
+            return Ok(None);
+        };
+        let info = self.get_version_info(package).await?;
+
+        Ok(info
+            .versions
+            .iter()
+            .filter_map(|v| semver::Version::parse(v).ok().map(|parsed| (parsed, v)))
+            .filter(|(parsed, _)| version_req.matches(parsed))
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, v)| v.clone()))
+    }
+
+    /// Resolves a Composer- or pubspec-style constraint (e.g. Composer's
+    /// `^1.2`, `~1.2.3`, `1.2.*`, `>=1.0 <2.0`, or a `||`-combined set of
+    /// those) to the highest non-prerelease entry in [`VersionInfo::versions`]
+    /// that satisfies it, so callers can tell "an update is available within
+    /// your declared range" apart from "only a major upgrade is available".
+    ///
+    /// Delegates matching to [`version_scheme::ComposerScheme`], the same
+    /// logic `parsers::php` already relies on for update classification, so
+    /// the two never disagree about what a given constraint allows. Unlike
+    /// [`resolve_requirement`](Registry::resolve_requirement), prereleases
+    /// are always excluded - neither Composer's nor pubspec's constraint
+    /// syntax has node-semver's "a prerelease requirement opts a matching
+    /// prerelease candidate back in" rule.
+    async fn resolve_matching(
+        &self,
+        package: &str,
+        constraint: &str,
+    ) -> anyhow::Result<Option<String>> {
+        let info = self.get_version_info(package).await?;
+        Ok(resolve_matching_version(&info.versions, constraint))
+    }
+
+    /// Fetches version info the same as [`Self::get_version_info`], with
+    /// [`VersionInfo::latest_compatible`] and [`VersionInfo::alternative_version`]
+    /// additionally filled in from the caller's declared `requirement` - the
+    /// same compatible-vs-breaking distinction Cargo draws between a safe
+    /// `cargo update` and a `cargo update --breaking`, computed here instead
+    /// of duplicated by every caller. The default delegates to
+    /// [`Self::resolve_requirement`] (true SemVer, via `semver::VersionReq`);
+    /// override this for a registry whose requirement syntax doesn't parse as
+    /// SemVer (see
+    /// [`packagist::PackagistRegistry`](super::packagist::PackagistRegistry)
+    /// for Composer's, and
+    /// [`go_proxy::GoProxyRegistry`](super::go_proxy::GoProxyRegistry) for
+    /// Go's exact-pin model).
+    async fn get_version_info_for_requirement(
+        &self,
+        package: &str,
+        requirement: &str,
+    ) -> anyhow::Result<VersionInfo> {
+        let mut info = self.get_version_info(package).await?;
+        info.latest_compatible = self
+            .resolve_requirement(package, requirement)
+            .await
+            .unwrap_or(None);
+        info.alternative_version = alternative_version(&info);
+        Ok(info)
+    }
+
+    /// Resolves an incomplete version like Cargo's "allow incomplete
+    /// versions when unambiguous": `"1"` resolves to the highest non-prerelease
+    /// version with major `1`, `"1.2"` to the highest `1.2.z`, and a fully
+    /// specified `"1.2.3"` matches that version exactly. Returns `Ok(None)`
+    /// when `partial` doesn't parse as a [`PartialVersion`] or nothing in
+    /// [`VersionInfo::versions`] matches.
+    async fn best_match(&self, package: &str, partial: &str) -> anyhow::Result<Option<String>> {
+        let info = self.get_version_info(package).await?;
+        Ok(best_partial_match(&info.versions, partial))
+    }
+}
+
+/// An incomplete SemVer version, i.e. a bare major (`1`) or major.minor
+/// (`1.2`), as opposed to the full `major.minor.patch` a declared
+/// requirement normally pins. Never carries a prerelease or build
+/// component - `"1.2.3-beta"` isn't a *partial* version, it's a complete
+/// one naming a prerelease, and [`parse_partial_version`] rejects it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartialVersion {
+    pub major: u64,
+    pub minor: Option<u64>,
+    pub patch: Option<u64>,
+}
+
+/// Parse `s` as a [`PartialVersion`]: one to three dot-separated numeric
+/// components with no prerelease/build metadata. `None` for anything else,
+/// including an empty string (handled separately by [`best_partial_match`]).
+pub fn parse_partial_version(s: &str) -> Option<PartialVersion> {
+    if s.is_empty() || s.contains('-') || s.contains('+') {
+        return None;
+    }
+
+    let mut parts = s.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().map(str::parse).transpose().ok()?;
+    let patch = parts.next().map(str::parse).transpose().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some(PartialVersion { major, minor, patch })
+}
+
+/// See [`Registry::best_match`]. Split out as a free function over a plain
+/// version list so it can be unit-tested without an async round-trip.
+///
+/// An empty `partial` only resolves when every candidate shares a single
+/// major version - otherwise which major the caller meant is genuinely
+/// ambiguous, so this returns `None` rather than silently picking one.
+pub(crate) fn best_partial_match(versions: &[String], partial: &str) -> Option<String> {
+    let stable: Vec<(semver::Version, &String)> = versions
+        .iter()
+        .filter_map(|v| semver::Version::parse(v).ok().map(|parsed| (parsed, v)))
+        .filter(|(parsed, _)| parsed.pre.is_empty())
+        .collect();
+
+    if partial.is_empty() {
+        let mut majors = stable.iter().map(|(parsed, _)| parsed.major);
+        let first_major = majors.next()?;
+        if majors.any(|major| major != first_major) {
+            return None;
+        }
+        return stable
+            .iter()
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, v)| (*v).clone());
+    }
+
+    let partial = parse_partial_version(partial)?;
+
+    stable
+        .iter()
+        .filter(|(parsed, _)| parsed.major == partial.major)
+        .filter(|(parsed, _)| partial.minor.is_none_or(|minor| parsed.minor == minor))
+        .filter(|(parsed, _)| partial.patch.is_none_or(|patch| parsed.patch == patch))
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, v)| (*v).clone())
+}
+
+/// See [`Registry::resolve_matching`]. Split out as a free function over a
+/// plain version list so it can be unit-tested without an async round-trip.
+pub(crate) fn resolve_matching_version(versions: &[String], constraint: &str) -> Option<String> {
+    use version_scheme::{ComposerScheme, VersionScheme, normalize_version};
+
+    let strip_v = |v: &str| v.strip_prefix('v').unwrap_or(v);
+
+    versions
+        .iter()
+        .filter(|v| {
+            let v = strip_v(v);
+            semver::Version::parse(&normalize_version(v))
+                .map(|parsed| parsed.pre.is_empty())
+                .unwrap_or(true)
+        })
+        .filter(|v| ComposerScheme.satisfies(constraint, strip_v(v)))
+        .max_by(|a, b| packagist::compare_packagist_versions(a, b))
+        .cloned()
+}
+
+/// See [`VersionInfo::alternative_version`]. Split out as a free function so
+/// the default [`Registry::get_version_info_for_requirement`] and the
+/// [`packagist::PackagistRegistry`](super::packagist::PackagistRegistry)
+/// override can share it instead of duplicating the "is the absolute latest
+/// actually outside the compatible range" check.
+pub(crate) fn alternative_version(info: &VersionInfo) -> Option<String> {
+    if info.latest.is_some() && info.latest != info.latest_compatible {
+        info.latest.clone()
+    } else {
+        None
+    }
+}
+
+/// Builder for the handful of knobs registry clients expose beyond their
+/// hardcoded defaults: an alternate base URL (e.g. a mirror or test
+/// server), a per-request timeout distinct from the shared HTTP client's
+/// own, and how long fetched version data stays fresh in that registry's
+/// response cache. Unset fields fall back to the registry's own defaults.
+///
+/// ```ignore
+/// use std::time::Duration;
+/// use dependi_lsp::registries::RegistryConfig;
+///
+/// let config = RegistryConfig::new()
+///     .with_timeout(Duration::from_secs(30))
+///     .with_cache_ttl(Duration::from_secs(60 * 60));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RegistryConfig {
+    pub(crate) base_url: Option<String>,
+    pub(crate) timeout: Option<std::time::Duration>,
+    pub(crate) cache_ttl: Option<std::time::Duration>,
+}
+
+impl RegistryConfig {
+    /// A config with every knob left at the registry's own default.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides the registry's API base URL, e.g. to point at a mirror or
+    /// a private instance.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Overrides the per-request timeout, independent of whatever timeout
+    /// the shared `reqwest::Client` carries.
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Overrides how long fetched version data is served from cache before
+    /// the registry is hit again.
+    pub fn with_cache_ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.cache_ttl = Some(ttl);
+        self
+    }
+}
+
+/// One upstream endpoint an index-backed registry (crates.io, PyPI, ...)
+/// can be pointed at in addition to (or instead of) the public default,
+/// e.g. a private Cargo registry or a devpi/Artifactory/Nexus PyPI mirror,
+/// with an optional credential sent on every request to it.
+#[derive(Debug, Clone)]
+pub struct IndexSource {
+    pub base_url: String,
+    pub auth: Option<IndexAuth>,
+}
+
+impl IndexSource {
+    /// A source with no authentication.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            auth: None,
+        }
+    }
+
+    /// Sends `Authorization: Bearer <token>` on every request to this
+    /// source.
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.auth = Some(IndexAuth::Bearer(token.into()));
+        self
+    }
+
+    /// Sends HTTP Basic authentication on every request to this source.
+    pub fn with_basic_auth(mut self, username: impl Into<String>, password: impl Into<String>) -> Self {
+        self.auth = Some(IndexAuth::Basic {
+            username: username.into(),
+            password: password.into(),
+        });
+        self
+    }
+}
+
+/// Credential attached to an [`IndexSource`].
+#[derive(Debug, Clone)]
+pub enum IndexAuth {
+    Bearer(String),
+    Basic { username: String, password: String },
+}
+
+impl IndexAuth {
+    /// Applies this credential to `request` as an `Authorization` header,
+    /// delegating the actual header encoding to `reqwest`.
+    pub fn apply(&self, request: RequestBuilder) -> RequestBuilder {
+        match self {
+            IndexAuth::Bearer(token) => request.bearer_auth(token),
+            IndexAuth::Basic { username, password } => request.basic_auth(username, Some(password)),
+        }
+    }
 }
 
+/// Parses a declared requirement string into a [`semver::VersionReq`].
+///
+/// NuGet's bracket/paren interval syntax (`[1.0.0,2.0.0)`) isn't valid
+/// `VersionReq` grammar, so it's translated into an equivalent comparator
+/// chain first; npm/JSR's caret/tilde ranges and Cargo's own requirement
+/// syntax are close enough to `VersionReq`'s grammar to parse directly.
+fn parse_requirement(req: &str) -> Option<semver::VersionReq> {
+    if let Some(translated) = nuget_range_to_semver_req(req) {
+        return semver::VersionReq::parse(&translated).ok();
+    }
+    semver::VersionReq::parse(req).ok()
+}
+
+/// Translates a NuGet bracket/paren version range into an equivalent
+/// `VersionReq` comparator string, or `None` if `req` isn't that syntax.
+fn nuget_range_to_semver_req(req: &str) -> Option<String> {
+    let req = req.trim();
+    let min_inclusive = req.starts_with('[');
+    let max_inclusive = req.ends_with(']');
+    if !(min_inclusive || req.starts_with('(')) || !(max_inclusive || req.ends_with(')')) {
+        return None;
+    }
+    let inner = req.get(1..req.len() - 1)?;
+
+    if !inner.contains(',') {
+        // No comma: an exact-pin range, e.g. `[1.0.0]`.
+        let version = inner.trim();
+        return if version.is_empty() {
+            None
+        } else {
+            Some(format!("={}", version))
+        };
+    }
+
+    let mut parts = inner.splitn(2, ',');
+    let min = parts.next().unwrap_or("").trim();
+    let max = parts.next().unwrap_or("").trim();
+
+    let mut clauses = Vec::new();
+    if !min.is_empty() {
+        clauses.push(format!("{}{}", if min_inclusive { ">=" } else { ">" }, min));
+    }
+    if !max.is_empty() {
+        clauses.push(format!("{}{}", if max_inclusive { "<=" } else { "<" }, max));
+    }
+
+    Some(if clauses.is_empty() {
+        "*".to_string()
+    } else {
+        clauses.join(", ")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nuget_range_bracket_to_semver_req() {
+        assert_eq!(
+            nuget_range_to_semver_req("[1.0.0,2.0.0)"),
+            Some(">=1.0.0, <2.0.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_nuget_range_open_ended_maximum() {
+        assert_eq!(
+            nuget_range_to_semver_req("(,2.0.0]"),
+            Some("<=2.0.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_nuget_range_exact_pin() {
+        assert_eq!(
+            nuget_range_to_semver_req("[1.0.0]"),
+            Some("=1.0.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_nuget_range_rejects_non_bracket_syntax() {
+        assert_eq!(nuget_range_to_semver_req("^1.0.0"), None);
+    }
+
+    #[test]
+    fn test_parse_requirement_npm_caret() {
+        let req = parse_requirement("^1.2.0").unwrap();
+        assert!(req.matches(&semver::Version::parse("1.3.0").unwrap()));
+        assert!(!req.matches(&semver::Version::parse("2.0.0").unwrap()));
+    }
+
+    #[test]
+    fn test_parse_requirement_nuget_range() {
+        let req = parse_requirement("[1.0.0,2.0.0)").unwrap();
+        assert!(req.matches(&semver::Version::parse("1.5.0").unwrap()));
+        assert!(!req.matches(&semver::Version::parse("2.0.0").unwrap()));
+    }
+
+    #[test]
+    fn test_parse_requirement_excludes_prerelease_by_default() {
+        let req = parse_requirement("^1.0.0").unwrap();
+        assert!(!req.matches(&semver::Version::parse("1.5.0-beta.1").unwrap()));
+    }
+
+    #[test]
+    fn test_resolve_matching_version_caret_excludes_major_bump() {
+        let versions = vec![
+            "1.0.0".to_string(),
+            "1.5.0".to_string(),
+            "2.0.0".to_string(),
+        ];
+        assert_eq!(
+            resolve_matching_version(&versions, "^1.2"),
+            Some("1.5.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_matching_version_or_group() {
+        let versions = vec![
+            "1.5.0".to_string(),
+            "2.5.0".to_string(),
+            "3.0.0".to_string(),
+        ];
+        assert_eq!(
+            resolve_matching_version(&versions, "^1.0.0 || ^2.0.0"),
+            Some("2.5.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_matching_version_wildcard() {
+        let versions = vec!["1.2.0".to_string(), "1.2.9".to_string(), "1.3.0".to_string()];
+        assert_eq!(
+            resolve_matching_version(&versions, "1.2.*"),
+            Some("1.2.9".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_matching_version_strips_v_prefix() {
+        let versions = vec!["v1.2.0".to_string(), "v1.9.0".to_string(), "v2.0.0".to_string()];
+        assert_eq!(
+            resolve_matching_version(&versions, "^1.0"),
+            Some("v1.9.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_matching_version_excludes_prerelease() {
+        let versions = vec!["1.2.0".to_string(), "1.3.0-beta.1".to_string()];
+        assert_eq!(
+            resolve_matching_version(&versions, ">=1.0 <2.0"),
+            Some("1.2.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_partial_version_major_only() {
+        assert_eq!(
+            parse_partial_version("1"),
+            Some(PartialVersion {
+                major: 1,
+                minor: None,
+                patch: None
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_partial_version_major_minor() {
+        assert_eq!(
+            parse_partial_version("1.2"),
+            Some(PartialVersion {
+                major: 1,
+                minor: Some(2),
+                patch: None
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_partial_version_full_triple() {
+        assert_eq!(
+            parse_partial_version("1.2.3"),
+            Some(PartialVersion {
+                major: 1,
+                minor: Some(2),
+                patch: Some(3)
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_partial_version_rejects_prerelease_and_build() {
+        assert_eq!(parse_partial_version("1.2.3-beta"), None);
+        assert_eq!(parse_partial_version("1.2.3+build"), None);
+    }
+
+    #[test]
+    fn test_parse_partial_version_rejects_too_many_components() {
+        assert_eq!(parse_partial_version("1.2.3.4"), None);
+    }
+
+    #[test]
+    fn test_parse_partial_version_rejects_non_numeric() {
+        assert_eq!(parse_partial_version("1.x"), None);
+    }
+
+    #[test]
+    fn test_best_partial_match_major_only() {
+        let versions = vec!["1.0.0".to_string(), "1.5.0".to_string(), "2.0.0".to_string()];
+        assert_eq!(
+            best_partial_match(&versions, "1"),
+            Some("1.5.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_best_partial_match_major_minor() {
+        let versions = vec!["1.2.0".to_string(), "1.2.9".to_string(), "1.3.0".to_string()];
+        assert_eq!(
+            best_partial_match(&versions, "1.2"),
+            Some("1.2.9".to_string())
+        );
+    }
+
+    #[test]
+    fn test_best_partial_match_full_triple() {
+        let versions = vec!["1.2.0".to_string(), "1.2.9".to_string()];
+        assert_eq!(
+            best_partial_match(&versions, "1.2.0"),
+            Some("1.2.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_best_partial_match_excludes_prerelease() {
+        let versions = vec!["1.2.0".to_string(), "1.3.0-beta.1".to_string()];
+        assert_eq!(
+            best_partial_match(&versions, "1"),
+            Some("1.2.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_best_partial_match_no_match_returns_none() {
+        let versions = vec!["1.2.0".to_string()];
+        assert_eq!(best_partial_match(&versions, "2"), None);
+    }
+
+    #[test]
+    fn test_best_partial_match_empty_partial_unambiguous_single_major() {
+        let versions = vec!["1.0.0".to_string(), "1.5.0".to_string()];
+        assert_eq!(
+            best_partial_match(&versions, ""),
+            Some("1.5.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_best_partial_match_empty_partial_ambiguous_multiple_majors() {
+        let versions = vec!["1.5.0".to_string(), "2.0.0".to_string()];
+        assert_eq!(best_partial_match(&versions, ""), None);
+    }
+
+    #[test]
+    fn test_alternative_version_none_when_latest_is_compatible() {
+        let info = VersionInfo {
+            latest: Some("1.5.0".to_string()),
+            latest_compatible: Some("1.5.0".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(alternative_version(&info), None);
+    }
+
+    #[test]
+    fn test_alternative_version_surfaces_breaking_upgrade() {
+        let info = VersionInfo {
+            latest: Some("2.0.0".to_string()),
+            latest_compatible: Some("1.5.0".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(alternative_version(&info), Some("2.0.0".to_string()));
+    }
+
+    #[test]
+    fn test_alternative_version_none_without_a_latest() {
+        let info = VersionInfo {
+            latest_compatible: Some("1.5.0".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(alternative_version(&info), None);
+    }
+
+    #[test]
+    fn test_registry_config_defaults_to_unset() {
+        let config = RegistryConfig::new();
+        assert!(config.base_url.is_none());
+        assert!(config.timeout.is_none());
+        assert!(config.cache_ttl.is_none());
+    }
+
+    #[test]
+    fn test_registry_config_builder_chains() {
+        let config = RegistryConfig::new()
+            .with_base_url("https://mirror.example.com")
+            .with_timeout(std::time::Duration::from_secs(30))
+            .with_cache_ttl(std::time::Duration::from_secs(3600));
+
+        assert_eq!(config.base_url.as_deref(), Some("https://mirror.example.com"));
+        assert_eq!(config.timeout, Some(std::time::Duration::from_secs(30)));
+        assert_eq!(config.cache_ttl, Some(std::time::Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn test_index_source_defaults_to_no_auth() {
+        let source = IndexSource::new("https://index.example.com");
+        assert_eq!(source.base_url, "https://index.example.com");
+        assert!(source.auth.is_none());
+    }
+
+    #[test]
+    fn test_index_source_with_bearer_token() {
+        let source = IndexSource::new("https://index.example.com").with_bearer_token("secret");
+        assert!(matches!(source.auth, Some(IndexAuth::Bearer(ref t)) if t == "secret"));
+    }
+
+    #[test]
+    fn test_index_source_with_basic_auth() {
+        let source =
+            IndexSource::new("https://index.example.com").with_basic_auth("alice", "hunter2");
+        assert!(matches!(
+            source.auth,
+            Some(IndexAuth::Basic { ref username, ref password })
+                if username == "alice" && password == "hunter2"
+        ));
+    }
+
+    #[test]
+    fn test_latest_compatible_with_python_skips_incompatible_releases() {
+        let mut info = VersionInfo {
+            versions: vec!["1.0.0".to_string(), "2.0.0".to_string()],
+            ..Default::default()
+        };
+        info.requires_python
+            .insert("2.0.0".to_string(), ">=3.10".to_string());
+
+        assert_eq!(
+            info.latest_compatible_with_python(&version_utils::Pep440Precedence, "3.8", false),
+            Some("1.0.0")
+        );
+        assert_eq!(
+            info.latest_compatible_with_python(&version_utils::Pep440Precedence, "3.11", false),
+            Some("2.0.0")
+        );
+    }
+
+    #[test]
+    fn test_latest_compatible_with_python_assumes_compatible_when_unrecorded() {
+        let info = VersionInfo {
+            versions: vec!["1.0.0".to_string()],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            info.latest_compatible_with_python(&version_utils::Pep440Precedence, "3.8", false),
+            Some("1.0.0")
+        );
+    }
+
+    #[test]
+    fn test_latest_compatible_with_rust_version_skips_incompatible_releases() {
+        let mut info = VersionInfo {
+            versions: vec!["1.0.0".to_string(), "2.0.0".to_string()],
+            ..Default::default()
+        };
+        info.rust_version
+            .insert("2.0.0".to_string(), "1.75".to_string());
+
+        assert_eq!(
+            info.latest_compatible_with_rust_version(
+                &version_utils::SemverPrecedence,
+                "1.70.0",
+                false
+            ),
+            Some("1.0.0")
+        );
+        assert_eq!(
+            info.latest_compatible_with_rust_version(
+                &version_utils::SemverPrecedence,
+                "1.80.0",
+                false
+            ),
+            Some("2.0.0")
+        );
+    }
+}
+
+pub mod authenticated_client;
+pub mod cache;
+pub mod cargo_sparse;
 pub mod crates_io;
+pub mod github_releases;
+pub mod http_client;
+pub mod jsr;
 pub mod npm;
+pub mod nuget;
+pub mod pep440;
+pub mod pub_dev;
 pub mod pypi;
 pub mod go_proxy;
 pub mod packagist;
-
-// TODO: Implement additional registry clients
-// pub mod pub_dev;
-// pub mod nuget;
+pub mod registry_config;
+pub mod rubygems;
+pub mod version_scheme;
+pub mod version_set;
+pub mod version_utils;
@@ -1,46 +1,164 @@
 //! Client for npm registry
 
 use std::collections::HashMap;
-use std::time::Duration;
+use std::sync::Arc;
 
+use chrono::{DateTime, Utc};
 use reqwest::Client;
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
 use serde::Deserialize;
 
+use super::http_client::create_shared_client;
+use super::version_utils::is_prerelease_npm;
 use super::{Registry, VersionInfo};
+use crate::auth::TokenProviderManager;
+use crate::vulnerabilities::cache::VulnerabilityCache;
+use crate::vulnerabilities::osv::OsvClient;
+use crate::vulnerabilities::{Ecosystem, VulnerabilityQuery};
+
+/// Builds an `OsvClient` sharing `client`'s connection pool, with a
+/// persistent on-disk vulnerability cache attached when one can be opened -
+/// falling back to an uncached client (still correct, just always hitting
+/// the network) rather than failing registry construction over a cache
+/// file that couldn't be opened.
+fn osv_client(client: Arc<Client>) -> Arc<OsvClient> {
+    let mut osv = OsvClient::with_client(client);
+    if let Ok(cache) = VulnerabilityCache::open_default() {
+        osv = osv.with_cache(Arc::new(cache));
+    }
+    Arc::new(osv)
+}
 
 /// Client for the npm registry
 pub struct NpmRegistry {
-    client: Client,
+    client: Arc<Client>,
     base_url: String,
+    auth_headers: Option<HeaderMap>,
+    /// Consulted at request time for this registry's URL, taking precedence
+    /// over `auth_headers` - lets a `DEPENDI_AUTH_TOKENS`-style bundle or an
+    /// `.npmrc`-derived provider override the static bearer token this
+    /// client was built with.
+    token_manager: Arc<TokenProviderManager>,
+    osv: Arc<OsvClient>,
 }
 
 impl NpmRegistry {
-    pub fn new() -> anyhow::Result<Self> {
-        let client = Client::builder()
-            .user_agent("dependi-lsp (https://github.com/mathieu/zed-dependi)")
-            .timeout(Duration::from_secs(10))
-            .build()?;
-
-        Ok(Self {
+    /// Creates an `NpmRegistry` that uses the provided shared HTTP client
+    /// and targets the public npm registry.
+    pub fn with_client(client: Arc<Client>) -> Self {
+        let osv = osv_client(Arc::clone(&client));
+        Self {
             client,
             base_url: "https://registry.npmjs.org".to_string(),
-        })
+            auth_headers: None,
+            token_manager: Arc::new(TokenProviderManager::new()),
+            osv,
+        }
+    }
+
+    /// Creates an `NpmRegistry` targeting an alternate/private registry, e.g.
+    /// one resolved from `.npmrc`'s `@scope:registry=`, with its auth token
+    /// (`.npmrc`'s `//host/:_authToken=`) sent as a bearer token and an empty
+    /// token manager - use [`Self::with_client_config_and_manager`] to carry
+    /// one already populated from the environment or discovery.
+    pub fn with_client_and_config(
+        client: Arc<Client>,
+        base_url: String,
+        auth_token: Option<String>,
+    ) -> Self {
+        Self::with_client_config_and_manager(
+            client,
+            base_url,
+            auth_token,
+            Arc::new(TokenProviderManager::new()),
+        )
     }
 
-    #[cfg(test)]
-    pub fn with_base_url(base_url: String) -> anyhow::Result<Self> {
-        let client = Client::builder()
-            .user_agent("dependi-lsp (https://github.com/mathieu/zed-dependi)")
-            .timeout(Duration::from_secs(10))
-            .build()?;
+    /// Creates an `NpmRegistry` targeting an alternate/private registry, with
+    /// a [`TokenProviderManager`] consulted at request time ahead of the
+    /// static `auth_token` fallback.
+    pub fn with_client_config_and_manager(
+        client: Arc<Client>,
+        base_url: String,
+        auth_token: Option<String>,
+        token_manager: Arc<TokenProviderManager>,
+    ) -> Self {
+        let auth_headers = auth_token.and_then(|token| {
+            let mut headers = HeaderMap::new();
+            let auth_value = format!("Bearer {}", token);
+            if let Ok(value) = HeaderValue::from_str(&auth_value) {
+                headers.insert(AUTHORIZATION, value);
+                Some(headers)
+            } else {
+                None
+            }
+        });
+
+        let osv = osv_client(Arc::clone(&client));
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            auth_headers,
+            token_manager,
+            osv,
+        }
+    }
+
+    /// Resolve the headers to attach to a request to `url`: the token
+    /// manager's provider for this URL, if one matches, else the static
+    /// bearer token this client was constructed with.
+    async fn resolve_auth_headers(&self, url: &str) -> Option<HeaderMap> {
+        let managed = self.token_manager.get_auth_headers(url).await;
+        if !managed.is_empty() {
+            return Some(managed);
+        }
+        self.auth_headers.clone()
+    }
+
+    /// Queries OSV for known vulnerabilities affecting `latest` and
+    /// `latest_prerelease` (the versions a user is actually looking at),
+    /// tolerating any OSV outage by reporting no vulnerabilities rather
+    /// than failing the whole version-info fetch.
+    async fn fetch_vulnerabilities(
+        &self,
+        package_name: &str,
+        latest: Option<&str>,
+        latest_prerelease: Option<&str>,
+    ) -> Vec<super::Vulnerability> {
+        let mut versions: Vec<String> = [latest, latest_prerelease]
+            .into_iter()
+            .flatten()
+            .map(|v| v.to_string())
+            .collect();
+        versions.sort_unstable();
+        versions.dedup();
+
+        if versions.is_empty() {
+            return vec![];
+        }
 
-        Ok(Self { client, base_url })
+        let queries: Vec<VulnerabilityQuery> = versions
+            .into_iter()
+            .map(|version| VulnerabilityQuery {
+                package_name: package_name.to_string(),
+                version,
+                ecosystem: Ecosystem::Npm,
+            })
+            .collect();
+
+        self.osv
+            .query_batch_hydrated(&queries)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .collect()
     }
 }
 
 impl Default for NpmRegistry {
     fn default() -> Self {
-        Self::new().expect("Failed to create NpmRegistry")
+        Self::with_client(create_shared_client().expect("Failed to create HTTP client"))
     }
 }
 
@@ -55,6 +173,9 @@ struct PackageResponse {
     #[serde(rename = "dist-tags")]
     dist_tags: Option<DistTags>,
     versions: Option<HashMap<String, VersionMetadata>>,
+    /// Publish timestamp per version, plus synthetic `created`/`modified`
+    /// keys that aren't versions at all and must be filtered out.
+    time: Option<HashMap<String, String>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -117,6 +238,10 @@ fn normalize_repo_url(url: &str) -> String {
 }
 
 impl Registry for NpmRegistry {
+    fn http_client(&self) -> Arc<Client> {
+        Arc::clone(&self.client)
+    }
+
     async fn get_version_info(&self, package_name: &str) -> anyhow::Result<VersionInfo> {
         // Handle scoped packages (@scope/name -> @scope%2fname)
         let encoded_name = if package_name.starts_with('@') {
@@ -127,7 +252,14 @@ impl Registry for NpmRegistry {
 
         let url = format!("{}/{}", self.base_url, encoded_name);
 
-        let response = self.client.get(&url).send().await?;
+        let mut request = self.client.get(&url);
+        if let Some(headers) = self.resolve_auth_headers(&url).await {
+            for (key, value) in headers.iter() {
+                request = request.header(key, value);
+            }
+        }
+
+        let response = request.send().await?;
 
         if !response.status().is_success() {
             anyhow::bail!(
@@ -173,9 +305,44 @@ impl Registry for NpmRegistry {
             .and_then(|v| latest.as_ref().and_then(|l| v.get(l)))
             .is_some_and(|m| m.deprecated.is_some());
 
+        // Deprecation message per version, so the LSP can show why a
+        // version was deprecated rather than just that it was.
+        let deprecation_messages: HashMap<String, String> = pkg
+            .versions
+            .as_ref()
+            .map(|v| {
+                v.iter()
+                    .filter_map(|(version, meta)| {
+                        meta.deprecated.clone().map(|msg| (version.clone(), msg))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // Publish date per version. `time` also carries synthetic
+        // `created`/`modified` keys that aren't versions, so skip those.
+        let release_dates: HashMap<String, DateTime<Utc>> = pkg
+            .time
+            .as_ref()
+            .map(|t| {
+                t.iter()
+                    .filter(|(key, _)| key.as_str() != "created" && key.as_str() != "modified")
+                    .filter_map(|(version, time_str)| {
+                        DateTime::parse_from_rfc3339(time_str)
+                            .ok()
+                            .map(|dt| (version.clone(), dt.with_timezone(&Utc)))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
         // Get repository URL
         let repository = pkg.repository.as_ref().and_then(|r| r.url());
 
+        let vulnerabilities = self
+            .fetch_vulnerabilities(package_name, latest.as_deref(), latest_prerelease.as_deref())
+            .await;
+
         Ok(VersionInfo {
             latest,
             latest_prerelease,
@@ -184,20 +351,24 @@ impl Registry for NpmRegistry {
             homepage: pkg.homepage,
             repository,
             license: pkg.license.and_then(|l| l.as_string()),
-            vulnerabilities: vec![], // TODO: Integrate npm audit
+            vulnerabilities,
             deprecated,
             yanked: false,
+            yanked_versions: vec![], // npm doesn't expose a yank concept
+            release_dates,
+            platforms: HashMap::new(),
+            dependency_groups: HashMap::new(),
+            deprecation_messages,
         })
     }
 }
 
+/// Whether `version` is a prerelease, per SemVer precedence (a non-empty
+/// `-pre` segment) rather than sniffing for substrings like `"alpha"` or
+/// `"rc"` that can both miss a real prerelease tag and misfire on a stable
+/// version that happens to contain one.
 fn is_prerelease(version: &str) -> bool {
-    version.contains('-')
-        || version.contains("alpha")
-        || version.contains("beta")
-        || version.contains("rc")
-        || version.contains("canary")
-        || version.contains("next")
+    is_prerelease_npm(version)
 }
 
 #[cfg(test)]
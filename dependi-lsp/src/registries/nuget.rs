@@ -54,9 +54,13 @@
 //!
 //! ## Caching Strategy
 //!
-//! - **TTL**: Version data cached for 5 minutes (configurable)
+//! - **TTL**: Version data cached for 5 minutes (configurable) via
+//!   [`super::cache::ResponseCache`], wrapped with
+//!   [`super::cache::CachingRegistry`]
 //! - **Cache keys**: Package ID (lowercase)
-//! - **Invalidation**: Manual or on version mismatch
+//! - **Invalidation**: Manual via [`super::cache::clear_cache`], or on TTL
+//!   expiry, revalidated with `If-None-Match`/`If-Modified-Since` so an
+//!   unchanged package costs a `304` rather than a full re-parse
 //!
 //! ## Error Handling
 //!
@@ -78,9 +82,11 @@ use chrono::{DateTime, Utc};
 use reqwest::Client;
 use serde::Deserialize;
 
+use super::cache::{ConditionalFetch, ConditionalRegistry};
 use super::http_client::create_shared_client;
+use super::version_scheme::NuGetRange;
 use super::version_utils::is_prerelease_nuget;
-use super::{Registry, VersionInfo};
+use super::{DependencyGroup, PackageDependency, Registry, VersionInfo, VersionRange};
 
 /// Client for the NuGet registry
 pub struct NuGetRegistry {
@@ -159,6 +165,8 @@ struct NuGetCatalogEntry {
     #[serde(default)]
     deprecation: Option<NuGetDeprecation>,
     published: Option<String>,
+    #[serde(rename = "dependencyGroups", default)]
+    dependency_groups: Vec<NuGetDependencyGroupRaw>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -169,33 +177,51 @@ struct NuGetDeprecation {
     _reasons: Option<Vec<String>>,
 }
 
-impl Registry for NuGetRegistry {
-    fn http_client(&self) -> Arc<Client> {
-        Arc::clone(&self.client)
-    }
-
-    async fn get_version_info(&self, package_name: &str) -> anyhow::Result<VersionInfo> {
-        // NuGet uses lowercase package IDs in URLs
-        let package_id = package_name.to_lowercase();
-
-        // Get registration index
-        let url = format!(
-            "{}/registration5-semver1/{}/index.json",
-            self.base_url, package_id
-        );
-
-        let response = self.client.get(&url).send().await?;
+#[derive(Debug, Deserialize, Clone)]
+struct NuGetDependencyGroupRaw {
+    #[serde(rename = "targetFramework")]
+    target_framework: Option<String>,
+    #[serde(default)]
+    dependencies: Vec<NuGetDependencyRaw>,
+}
 
-        if !response.status().is_success() {
-            anyhow::bail!(
-                "Failed to fetch package info for {}: {}",
-                package_name,
-                response.status()
-            );
-        }
+#[derive(Debug, Deserialize, Clone)]
+struct NuGetDependencyRaw {
+    id: String,
+    range: Option<String>,
+}
 
-        let registration: NuGetRegistrationResponse = response.json().await?;
+/// Converts a raw `dependencyGroups` entry into the public [`DependencyGroup`]
+/// shape, parsing each dependency's NuGet interval `range` syntax into
+/// structured min/max bounds via [`NuGetRange::parse`].
+fn to_dependency_group(raw: NuGetDependencyGroupRaw) -> DependencyGroup {
+    DependencyGroup {
+        target_framework: raw.target_framework,
+        dependencies: raw
+            .dependencies
+            .into_iter()
+            .map(|dep| PackageDependency {
+                id: dep.id,
+                range: dep.range.as_deref().and_then(NuGetRange::parse).map(
+                    |range| VersionRange {
+                        min: range.min,
+                        min_inclusive: range.min_inclusive,
+                        max: range.max,
+                        max_inclusive: range.max_inclusive,
+                    },
+                ),
+            })
+            .collect(),
+    }
+}
 
+impl NuGetRegistry {
+    /// Turns a registration index response into a [`VersionInfo`], fetching
+    /// any pages whose items weren't inlined in the index.
+    async fn build_version_info(
+        &self,
+        registration: NuGetRegistrationResponse,
+    ) -> anyhow::Result<VersionInfo> {
         // Collect all versions from all pages
         let mut all_versions: Vec<NuGetCatalogEntry> = Vec::new();
 
@@ -260,6 +286,21 @@ impl Registry for NuGetRegistry {
             })
             .collect();
 
+        // Dependency groups (per target framework), keyed by version.
+        let dependency_groups: HashMap<String, Vec<DependencyGroup>> = all_versions
+            .iter()
+            .filter(|entry| !entry.dependency_groups.is_empty())
+            .map(|entry| {
+                let groups = entry
+                    .dependency_groups
+                    .iter()
+                    .cloned()
+                    .map(to_dependency_group)
+                    .collect();
+                (entry.version.clone(), groups)
+            })
+            .collect();
+
         Ok(VersionInfo {
             latest: latest_stable,
             latest_prerelease,
@@ -274,6 +315,106 @@ impl Registry for NuGetRegistry {
             yanked: false,
             yanked_versions: vec![], // Not applicable to NuGet
             release_dates,
+            platforms: HashMap::new(),
+            dependency_groups,
+            deprecation_messages: HashMap::new(),
+            requires_python: HashMap::new(),
+            rust_version: HashMap::new(),
+            yanked_reasons: HashMap::new(),
+            latest_breaking_major: None,
+            latest_compatible: None,
+            alternative_version: None,
+            version_metadata: HashMap::new(),
+        })
+    }
+}
+
+impl Registry for NuGetRegistry {
+    fn http_client(&self) -> Arc<Client> {
+        Arc::clone(&self.client)
+    }
+
+    async fn get_version_info(&self, package_name: &str) -> anyhow::Result<VersionInfo> {
+        // NuGet uses lowercase package IDs in URLs
+        let package_id = package_name.to_lowercase();
+
+        // Get registration index
+        let url = format!(
+            "{}/registration5-semver1/{}/index.json",
+            self.base_url, package_id
+        );
+
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "Failed to fetch package info for {}: {}",
+                package_name,
+                response.status()
+            );
+        }
+
+        let registration: NuGetRegistrationResponse = response.json().await?;
+        self.build_version_info(registration).await
+    }
+}
+
+impl ConditionalRegistry for NuGetRegistry {
+    /// Revalidates the registration index with `If-None-Match`/
+    /// `If-Modified-Since`, so an unchanged package (the common case, given
+    /// NuGet's CDN) costs a `304` instead of a full registration re-parse.
+    async fn get_version_info_conditional(
+        &self,
+        package_name: &str,
+        etag: Option<&str>,
+        last_modified: Option<&str>,
+    ) -> anyhow::Result<ConditionalFetch> {
+        let package_id = package_name.to_lowercase();
+        let url = format!(
+            "{}/registration5-semver1/{}/index.json",
+            self.base_url, package_id
+        );
+
+        let mut request = self.client.get(&url);
+        if let Some(etag) = etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = last_modified {
+            request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+        }
+
+        let response = request.send().await?;
+
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(ConditionalFetch::NotModified);
+        }
+
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "Failed to fetch package info for {}: {}",
+                package_name,
+                response.status()
+            );
+        }
+
+        let new_etag = response
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string());
+        let new_last_modified = response
+            .headers()
+            .get(reqwest::header::LAST_MODIFIED)
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string());
+
+        let registration: NuGetRegistrationResponse = response.json().await?;
+        let info = self.build_version_info(registration).await?;
+
+        Ok(ConditionalFetch::Modified {
+            info,
+            etag: new_etag,
+            last_modified: new_last_modified,
         })
     }
 }
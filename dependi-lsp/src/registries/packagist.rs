@@ -8,12 +8,27 @@ use reqwest::Client;
 use serde::Deserialize;
 
 use super::http_client::create_shared_client;
-use super::{Registry, VersionInfo};
+use super::{Registry, Vulnerability, VersionInfo, alternative_version, resolve_matching_version};
+use crate::vulnerabilities::cache::VulnerabilityCache;
+use crate::vulnerabilities::osv::OsvClient;
+use crate::vulnerabilities::{Ecosystem, VulnerabilityQuery, VulnerabilitySource};
+
+/// Builds an `OsvClient` sharing `client`'s connection pool, with a
+/// persistent on-disk vulnerability cache attached when one can be opened -
+/// see `npm.rs`'s and `jsr.rs`'s identical helper.
+fn default_vuln_source(client: Arc<Client>) -> Arc<dyn VulnerabilitySource> {
+    let mut osv = OsvClient::with_client(client);
+    if let Ok(cache) = VulnerabilityCache::open_default() {
+        osv = osv.with_cache(Arc::new(cache));
+    }
+    Arc::new(osv)
+}
 
 /// Client for the Packagist registry
 pub struct PackagistRegistry {
     client: Arc<Client>,
     base_url: String,
+    vuln_source: Option<Arc<dyn VulnerabilitySource>>,
 }
 
 impl PackagistRegistry {
@@ -31,10 +46,70 @@ impl PackagistRegistry {
     /// let registry = PackagistRegistry::with_client(client);
     /// ```
     pub fn with_client(client: Arc<Client>) -> Self {
+        let vuln_source = Some(default_vuln_source(Arc::clone(&client)));
+        Self {
+            client,
+            base_url: "https://repo.packagist.org".to_string(),
+            vuln_source,
+        }
+    }
+
+    /// Creates a PackagistRegistry backed by `vuln_source` instead of the
+    /// default OSV client, so tests can supply a stub and assert on the
+    /// resulting `VersionInfo.vulnerabilities` without a network round-trip.
+    #[cfg(test)]
+    pub(crate) fn with_vuln_source(
+        client: Arc<Client>,
+        vuln_source: Arc<dyn VulnerabilitySource>,
+    ) -> Self {
         Self {
             client,
             base_url: "https://repo.packagist.org".to_string(),
+            vuln_source: Some(vuln_source),
+        }
+    }
+
+    /// Queries `vuln_source` for known vulnerabilities affecting `latest`
+    /// and `latest_prerelease`, tolerating any outage by reporting no
+    /// vulnerabilities rather than failing the whole version-info fetch.
+    async fn fetch_vulnerabilities(
+        &self,
+        package_name: &str,
+        latest: Option<&str>,
+        latest_prerelease: Option<&str>,
+    ) -> Vec<Vulnerability> {
+        let Some(source) = &self.vuln_source else {
+            return vec![];
+        };
+
+        let mut versions: Vec<String> = [latest, latest_prerelease]
+            .into_iter()
+            .flatten()
+            .map(|v| v.to_string())
+            .collect();
+        versions.sort_unstable();
+        versions.dedup();
+
+        if versions.is_empty() {
+            return vec![];
         }
+
+        let queries: Vec<VulnerabilityQuery> = versions
+            .into_iter()
+            .map(|version| VulnerabilityQuery {
+                package_name: package_name.to_string(),
+                version,
+                ecosystem: Ecosystem::Packagist,
+            })
+            .collect();
+
+        source
+            .query_batch(&queries)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .collect()
     }
 }
 
@@ -159,6 +234,14 @@ impl Registry for PackagistRegistry {
             })
             .collect();
 
+        let vulnerabilities = self
+            .fetch_vulnerabilities(
+                package_name,
+                latest_stable.as_deref(),
+                latest_prerelease.as_deref(),
+            )
+            .await;
+
         Ok(VersionInfo {
             latest: latest_stable,
             latest_prerelease,
@@ -167,13 +250,38 @@ impl Registry for PackagistRegistry {
             homepage,
             repository,
             license,
-            vulnerabilities: vec![], // TODO: Check PHP Security Advisories
+            vulnerabilities,
             deprecated,
             yanked: false,
             yanked_versions: vec![], // Not applicable to Packagist
             release_dates,
+            platforms: HashMap::new(),
+            dependency_groups: HashMap::new(),
+            deprecation_messages: HashMap::new(),
+            requires_python: HashMap::new(),
+            rust_version: HashMap::new(),
+            yanked_reasons: HashMap::new(),
+            latest_breaking_major: None,
+            latest_compatible: None,
+            alternative_version: None,
+            version_metadata: HashMap::new(),
         })
     }
+
+    /// Composer constraints (`^1.2`, `~1.2.3`, `1.2.*`, `||`-combined sets,
+    /// ...) don't parse as SemVer, so this overrides the default SemVer-only
+    /// implementation with the same Composer-aware matching
+    /// [`Registry::resolve_matching`] uses.
+    async fn get_version_info_for_requirement(
+        &self,
+        package: &str,
+        requirement: &str,
+    ) -> anyhow::Result<VersionInfo> {
+        let mut info = self.get_version_info(package).await?;
+        info.latest_compatible = resolve_matching_version(&info.versions, requirement);
+        info.alternative_version = alternative_version(&info);
+        Ok(info)
+    }
 }
 
 /// Check if a version is a dev version (e.g., dev-master, dev-main)
@@ -192,7 +300,7 @@ fn is_prerelease(version: &str) -> bool {
 }
 
 /// Compare Packagist versions for sorting
-fn compare_packagist_versions(a: &str, b: &str) -> std::cmp::Ordering {
+pub(crate) fn compare_packagist_versions(a: &str, b: &str) -> std::cmp::Ordering {
     // Strip 'v' prefix if present
     let a_stripped = a.strip_prefix('v').unwrap_or(a);
     let b_stripped = b.strip_prefix('v').unwrap_or(b);
@@ -299,4 +407,60 @@ mod tests {
             "https://github.com/user/repo"
         );
     }
+
+    struct StubSource(Vec<Vulnerability>);
+
+    impl VulnerabilitySource for StubSource {
+        async fn query(
+            &self,
+            _query: &VulnerabilityQuery,
+        ) -> anyhow::Result<Vec<Vulnerability>> {
+            Ok(self.0.clone())
+        }
+
+        async fn query_batch(
+            &self,
+            queries: &[VulnerabilityQuery],
+        ) -> anyhow::Result<Vec<Vec<Vulnerability>>> {
+            Ok(queries.iter().map(|_| self.0.clone()).collect())
+        }
+    }
+
+    fn vuln(id: &str) -> Vulnerability {
+        Vulnerability {
+            id: id.to_string(),
+            severity: crate::registries::VulnerabilitySeverity::High,
+            description: String::new(),
+            url: None,
+            fixed_version: None,
+            ranges: vec![],
+            aliases: vec![],
+            related: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_vulnerabilities_queries_latest_and_prerelease() {
+        let client = Arc::new(Client::new());
+        let source: Arc<dyn VulnerabilitySource> = Arc::new(StubSource(vec![vuln("GHSA-test")]));
+        let registry = PackagistRegistry::with_vuln_source(client, source);
+
+        let vulns = registry
+            .fetch_vulnerabilities("vendor/package", Some("1.0.0"), Some("2.0.0-beta"))
+            .await;
+
+        assert_eq!(vulns.len(), 2);
+        assert!(vulns.iter().all(|v| v.id == "GHSA-test"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_vulnerabilities_empty_without_any_known_version() {
+        let client = Arc::new(Client::new());
+        let source: Arc<dyn VulnerabilitySource> = Arc::new(StubSource(vec![vuln("GHSA-test")]));
+        let registry = PackagistRegistry::with_vuln_source(client, source);
+
+        let vulns = registry.fetch_vulnerabilities("vendor/package", None, None).await;
+
+        assert!(vulns.is_empty());
+    }
 }
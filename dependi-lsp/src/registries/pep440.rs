@@ -0,0 +1,298 @@
+//! A typed PEP 440 version + specifier engine.
+//!
+//! [`version_scheme::PythonScheme`](super::version_scheme::PythonScheme) exposes
+//! PEP 440 matching as opaque strings (`is_newer`/`satisfies`) for the
+//! `VersionScheme` trait; this module gives callers that need to hold onto a
+//! parsed version or a whole constraint - rather than re-parsing a string on
+//! every comparison - typed [`Version`], [`Specifier`] and [`SpecifierSet`]
+//! values built on the same [`Pep440Version`] ordering.
+
+use super::version_utils::Pep440Version;
+
+/// A parsed PEP 440 version, ordered per PEP 440's precedence rules
+/// (`dev < pre < release < post`, local versions sorting above the release
+/// they're attached to).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    raw: String,
+    parsed: Pep440Version,
+}
+
+impl Version {
+    pub fn parse(version: &str) -> Result<Self, String> {
+        Ok(Self {
+            raw: version.trim().to_string(),
+            parsed: Pep440Version::parse(version)?,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    pub fn is_prerelease(&self) -> bool {
+        self.parsed.is_prerelease()
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    /// Delegates to the wrapped [`Pep440Version`]'s ordering - `raw` is kept
+    /// only for display/round-tripping and must never factor into ordering.
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.parsed.cmp(&other.parsed)
+    }
+}
+
+/// A single PEP 440 comparison clause, e.g. `>=1.4.2`, `~=2.2`, or
+/// `==1.0.*`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Specifier {
+    Eq(String),
+    NotEq(String),
+    Lt(String),
+    LtEq(String),
+    Gt(String),
+    GtEq(String),
+    /// `~=V.N`: "compatible release", equivalent to `>=V.N, ==V.*` with `N`
+    /// (the rightmost given component) dropped from the wildcard prefix.
+    Compatible(String),
+    /// `===V`: arbitrary equality, an exact string match with no version
+    /// parsing at all - PEP 440's escape hatch for non-conforming versions.
+    ArbitraryEq(String),
+}
+
+impl Specifier {
+    /// Parses a single clause (no commas). Returns `None` for an
+    /// unrecognized operator, so callers can fail open the way
+    /// [`super::version_scheme::PythonScheme`] does for e.g. a stray `^`.
+    pub fn parse(spec: &str) -> Option<Self> {
+        let spec = spec.trim();
+        if let Some(rest) = spec.strip_prefix("===") {
+            return Some(Specifier::ArbitraryEq(rest.trim().to_string()));
+        }
+        if let Some(rest) = spec.strip_prefix("==") {
+            return Some(Specifier::Eq(rest.trim().to_string()));
+        }
+        if let Some(rest) = spec.strip_prefix("!=") {
+            return Some(Specifier::NotEq(rest.trim().to_string()));
+        }
+        if let Some(rest) = spec.strip_prefix("~=") {
+            return Some(Specifier::Compatible(rest.trim().to_string()));
+        }
+        if let Some(rest) = spec.strip_prefix(">=") {
+            return Some(Specifier::GtEq(rest.trim().to_string()));
+        }
+        if let Some(rest) = spec.strip_prefix("<=") {
+            return Some(Specifier::LtEq(rest.trim().to_string()));
+        }
+        if let Some(rest) = spec.strip_prefix('>') {
+            return Some(Specifier::Gt(rest.trim().to_string()));
+        }
+        if let Some(rest) = spec.strip_prefix('<') {
+            return Some(Specifier::Lt(rest.trim().to_string()));
+        }
+        None
+    }
+
+    /// Whether `candidate` (a raw, unparsed version string) satisfies this
+    /// clause. Fails open - returns `true` - when `candidate` doesn't parse
+    /// as PEP 440, matching the rest of this codebase's "don't block an
+    /// update we can't classify" convention.
+    pub fn contains(&self, candidate: &str) -> bool {
+        match self {
+            Specifier::Eq(v) => eq_matches(v, candidate).unwrap_or(true),
+            Specifier::NotEq(v) => !eq_matches(v, candidate).unwrap_or(false),
+            Specifier::ArbitraryEq(v) => candidate.trim() == v.trim(),
+            Specifier::Compatible(v) => compatible_matches(v, candidate),
+            Specifier::Lt(v) => compare_or_open(candidate, v, |o| o == std::cmp::Ordering::Less),
+            Specifier::LtEq(v) => compare_or_open(candidate, v, |o| {
+                matches!(o, std::cmp::Ordering::Less | std::cmp::Ordering::Equal)
+            }),
+            Specifier::Gt(v) => compare_or_open(candidate, v, |o| o == std::cmp::Ordering::Greater),
+            Specifier::GtEq(v) => compare_or_open(candidate, v, |o| {
+                matches!(o, std::cmp::Ordering::Greater | std::cmp::Ordering::Equal)
+            }),
+        }
+    }
+}
+
+fn compare(a: &str, b: &str) -> Option<std::cmp::Ordering> {
+    Some(Version::parse(a).ok()?.cmp(&Version::parse(b).ok()?))
+}
+
+/// Runs `compare(candidate, v)` through `matches` when it parses, and fails
+/// open - returns `true` - when `candidate` doesn't parse as PEP 440, so an
+/// unclassifiable version is never silently excluded from an update range.
+fn compare_or_open(candidate: &str, v: &str, matches: impl Fn(std::cmp::Ordering) -> bool) -> bool {
+    compare(candidate, v).map(matches).unwrap_or(true)
+}
+
+/// `==`/`!=` with an optional `.*` trailing wildcard (`"1.0.*"` matches any
+/// `1.0.x`), compared component-by-component so it also works against the
+/// non-numeric segment of a partial match. Returns `None` (rather than
+/// deciding open/closed itself) when `candidate` doesn't parse as PEP 440
+/// and no wildcard lets it sidestep that parse - callers decide what "can't
+/// tell" means for their operator: [`Specifier::contains`] treats `None` as
+/// "not equal" either way, which fails both `==` and `!=` open - an
+/// unclassifiable version is never confidently equal to one specific
+/// release, so `==` doesn't block it and `!=` doesn't exclude it.
+fn eq_matches(rest: &str, candidate: &str) -> Option<bool> {
+    match rest.strip_suffix(".*") {
+        Some(prefix) => {
+            let prefix_parts: Vec<&str> = prefix.split('.').collect();
+            let candidate_parts: Vec<&str> = candidate.split(['.', '-', '_']).collect();
+            Some(
+                candidate_parts.len() >= prefix_parts.len()
+                    && prefix_parts
+                        .iter()
+                        .zip(candidate_parts.iter())
+                        .all(|(p, c)| p == c),
+            )
+        }
+        None => compare(candidate, rest).map(|o| o == std::cmp::Ordering::Equal),
+    }
+}
+
+/// `~=V.N`: `>=V.N`, restricted to the same prefix as `V` with its
+/// rightmost component dropped (`~=2.2` means `>=2.2, ==2.*`; `~=1.4.5`
+/// means `>=1.4.5, ==1.4.*`).
+fn compatible_matches(rest: &str, candidate: &str) -> bool {
+    let mut parts: Vec<&str> = rest.split('.').collect();
+    if parts.len() < 2 {
+        // A bare `~=2` isn't meaningful PEP 440 - fail open.
+        return true;
+    }
+    parts.pop();
+    let prefix_wildcard = format!("{}.*", parts.join("."));
+
+    let ge = compare_or_open(candidate, rest, |o| {
+        matches!(o, std::cmp::Ordering::Greater | std::cmp::Ordering::Equal)
+    });
+    ge && eq_matches(&prefix_wildcard, candidate).unwrap_or(true)
+}
+
+/// A comma-separated set of [`Specifier`] clauses, ANDed together - PEP 440
+/// has no OR combinator, unlike npm's `||`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SpecifierSet {
+    specifiers: Vec<Specifier>,
+}
+
+impl SpecifierSet {
+    /// Parses a comma-separated requirement string (e.g. `">=1.0,<2.0"`).
+    /// Clauses with an unrecognized operator are skipped rather than
+    /// rejecting the whole set, so one malformed clause doesn't block every
+    /// other constraint.
+    pub fn parse(requirement: &str) -> Self {
+        let specifiers = requirement
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .filter_map(Specifier::parse)
+            .collect();
+        Self { specifiers }
+    }
+
+    /// Whether `candidate` satisfies every clause in the set. An empty set
+    /// (nothing parsed) is vacuously satisfied by anything.
+    pub fn contains(&self, candidate: &str) -> bool {
+        self.specifiers.iter().all(|spec| spec.contains(candidate))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_ordering_follows_pep440_precedence() {
+        assert!(Version::parse("1.0.0a1").unwrap() < Version::parse("1.0.0").unwrap());
+        assert!(Version::parse("1.0.0").unwrap() < Version::parse("1.0.0.post1").unwrap());
+        assert!(Version::parse("1.0.0.dev1").unwrap() < Version::parse("1.0.0a1").unwrap());
+    }
+
+    #[test]
+    fn test_version_ordering_full_dev_pre_release_post_chain() {
+        let chain = [
+            "1.0.0.dev1",
+            "1.0.0a1",
+            "1.0.0rc1",
+            "1.0.0",
+            "1.0.0.post1",
+        ]
+        .map(|v| Version::parse(v).unwrap());
+        assert!(chain.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_version_is_prerelease() {
+        assert!(Version::parse("1.0.0a1").unwrap().is_prerelease());
+        assert!(!Version::parse("1.0.0").unwrap().is_prerelease());
+    }
+
+    #[test]
+    fn test_specifier_set_comparison_clauses() {
+        let set = SpecifierSet::parse(">=1.4.2");
+        assert!(set.contains("1.4.5"));
+        assert!(!set.contains("1.4.1"));
+    }
+
+    #[test]
+    fn test_specifier_set_comma_joined_and_clauses() {
+        let set = SpecifierSet::parse(">=1.2, <2.0");
+        assert!(set.contains("1.9.0"));
+        assert!(!set.contains("2.0.0"));
+        assert!(!set.contains("1.0.0"));
+    }
+
+    #[test]
+    fn test_specifier_set_compatible_release() {
+        let set = SpecifierSet::parse("~=1.4.5");
+        assert!(set.contains("1.4.9"));
+        assert!(!set.contains("1.5.0"));
+
+        let set = SpecifierSet::parse("~=2.2");
+        assert!(set.contains("2.9.0"));
+        assert!(!set.contains("3.0.0"));
+    }
+
+    #[test]
+    fn test_specifier_set_wildcard_equality() {
+        let set = SpecifierSet::parse("==1.0.*");
+        assert!(set.contains("1.0.5"));
+        assert!(!set.contains("1.1.0"));
+    }
+
+    #[test]
+    fn test_specifier_arbitrary_equality_is_exact_string_match() {
+        let set = SpecifierSet::parse("===1.0.0+local");
+        assert!(set.contains("1.0.0+local"));
+        assert!(!set.contains("1.0.0"));
+    }
+
+    #[test]
+    fn test_unparsable_candidate_fails_open_for_comparison_clauses() {
+        let candidate = "not-a-version";
+        assert!(Specifier::parse("==1.0.0").unwrap().contains(candidate));
+        assert!(Specifier::parse("<1.0.0").unwrap().contains(candidate));
+        assert!(Specifier::parse("<=1.0.0").unwrap().contains(candidate));
+        assert!(Specifier::parse(">1.0.0").unwrap().contains(candidate));
+        assert!(Specifier::parse(">=1.0.0").unwrap().contains(candidate));
+        assert!(Specifier::parse("~=1.0.0").unwrap().contains(candidate));
+    }
+
+    #[test]
+    fn test_unparsable_candidate_fails_open_for_not_equal() {
+        assert!(
+            Specifier::parse("!=1.0.0")
+                .unwrap()
+                .contains("not-a-version")
+        );
+    }
+}
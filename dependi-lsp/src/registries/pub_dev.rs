@@ -1,36 +1,129 @@
 //! Client for pub.dev registry (Dart/Flutter packages)
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
 use reqwest::Client;
 use serde::Deserialize;
 
-use super::{Registry, VersionInfo};
+use super::http_client::create_shared_client;
+use super::version_utils::is_prerelease_dart;
+use super::{Registry, Vulnerability, VersionInfo};
+use crate::vulnerabilities::cache::VulnerabilityCache;
+use crate::vulnerabilities::osv::OsvClient;
+use crate::vulnerabilities::{Ecosystem, VulnerabilityQuery, VulnerabilitySource};
+
+/// Builds an `OsvClient` sharing `client`'s connection pool, with a
+/// persistent on-disk vulnerability cache attached when one can be opened -
+/// see `npm.rs`'s and `jsr.rs`'s identical helper.
+fn default_vuln_source(client: Arc<Client>) -> Arc<dyn VulnerabilitySource> {
+    let mut osv = OsvClient::with_client(client);
+    if let Ok(cache) = VulnerabilityCache::open_default() {
+        osv = osv.with_cache(Arc::new(cache));
+    }
+    Arc::new(osv)
+}
 
 /// Client for the pub.dev registry
 pub struct PubDevRegistry {
     client: Arc<Client>,
     base_url: String,
+    vuln_source: Option<Arc<dyn VulnerabilitySource>>,
 }
 
 impl PubDevRegistry {
+    /// Creates a PubDevRegistry that uses the provided shared HTTP client.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use std::sync::Arc;
+    /// use dependi_lsp::registries::pub_dev::PubDevRegistry;
+    ///
+    /// let client = Arc::new(reqwest::Client::new());
+    /// let _registry = PubDevRegistry::with_client(client);
+    /// ```
+    pub fn with_client(client: Arc<Client>) -> Self {
+        let vuln_source = Some(default_vuln_source(Arc::clone(&client)));
+        Self {
+            client,
+            base_url: "https://pub.dev/api".to_string(),
+            vuln_source,
+        }
+    }
+
     pub fn new() -> anyhow::Result<Self> {
         let client = Client::builder()
             .user_agent("dependi-lsp (https://github.com/mathieu/zed-dependi)")
             .timeout(Duration::from_secs(10))
             .build()?;
 
-        Ok(Self {
-            client: Arc::new(client),
+        Ok(Self::with_client(Arc::new(client)))
+    }
+
+    /// Creates a PubDevRegistry backed by `vuln_source` instead of the
+    /// default OSV client, so tests can supply a stub and assert on the
+    /// resulting `VersionInfo.vulnerabilities` without a network round-trip.
+    #[cfg(test)]
+    pub(crate) fn with_vuln_source(
+        client: Arc<Client>,
+        vuln_source: Arc<dyn VulnerabilitySource>,
+    ) -> Self {
+        Self {
+            client,
             base_url: "https://pub.dev/api".to_string(),
-        })
+            vuln_source: Some(vuln_source),
+        }
+    }
+
+    /// Queries `vuln_source` for known vulnerabilities affecting `latest`
+    /// and `latest_prerelease`, tolerating any outage by reporting no
+    /// vulnerabilities rather than failing the whole version-info fetch.
+    async fn fetch_vulnerabilities(
+        &self,
+        package_name: &str,
+        latest: Option<&str>,
+        latest_prerelease: Option<&str>,
+    ) -> Vec<Vulnerability> {
+        let Some(source) = &self.vuln_source else {
+            return vec![];
+        };
+
+        let mut versions: Vec<String> = [latest, latest_prerelease]
+            .into_iter()
+            .flatten()
+            .map(|v| v.to_string())
+            .collect();
+        versions.sort_unstable();
+        versions.dedup();
+
+        if versions.is_empty() {
+            return vec![];
+        }
+
+        let queries: Vec<VulnerabilityQuery> = versions
+            .into_iter()
+            .map(|version| VulnerabilityQuery {
+                package_name: package_name.to_string(),
+                version,
+                ecosystem: Ecosystem::Pub,
+            })
+            .collect();
+
+        source
+            .query_batch(&queries)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .collect()
     }
 }
 
 impl Default for PubDevRegistry {
     fn default() -> Self {
-        Self::new().expect("Failed to create PubDevRegistry")
+        Self::with_client(create_shared_client().expect("Failed to create HTTP client"))
     }
 }
 
@@ -61,6 +154,10 @@ struct PubPubspec {
 }
 
 impl Registry for PubDevRegistry {
+    fn http_client(&self) -> Arc<Client> {
+        Arc::clone(&self.client)
+    }
+
     async fn get_version_info(&self, package_name: &str) -> anyhow::Result<VersionInfo> {
         let url = format!("{}/packages/{}", self.base_url, package_name);
 
@@ -94,6 +191,14 @@ impl Registry for PubDevRegistry {
         // Find latest prerelease
         let latest_prerelease = versions.iter().find(|v| is_prerelease(v)).cloned();
 
+        let vulnerabilities = self
+            .fetch_vulnerabilities(
+                package_name,
+                latest_stable.as_deref(),
+                latest_prerelease.as_deref(),
+            )
+            .await;
+
         Ok(VersionInfo {
             latest: latest_stable,
             latest_prerelease,
@@ -101,20 +206,32 @@ impl Registry for PubDevRegistry {
             description: pkg.latest.pubspec.description,
             homepage: pkg.latest.pubspec.homepage,
             repository: pkg.latest.pubspec.repository,
-            license: None,           // pub.dev doesn't expose license in API
-            vulnerabilities: vec![], // Will be filled by OSV
+            license: None, // pub.dev doesn't expose license in API
+            vulnerabilities,
             deprecated: pkg.latest.pubspec.discontinued,
             yanked: pkg.latest.retracted,
+            yanked_versions: vec![], // Not applicable to pub.dev
+            release_dates: HashMap::new(), // pub.dev doesn't expose per-version publish dates here
+            platforms: HashMap::new(),
+            dependency_groups: HashMap::new(),
+            deprecation_messages: HashMap::new(),
+            requires_python: HashMap::new(),
+            rust_version: HashMap::new(),
+            yanked_reasons: HashMap::new(),
+            latest_breaking_major: None,
+            latest_compatible: None,
+            alternative_version: None,
+            version_metadata: HashMap::new(),
         })
     }
 }
 
+/// Whether `version` is a prerelease, per SemVer precedence (a non-empty
+/// `-pre` segment) rather than sniffing for substrings like `"dev"` or
+/// `"rc"` that can both miss a real prerelease tag and misfire on a stable
+/// version that happens to contain one.
 fn is_prerelease(version: &str) -> bool {
-    version.contains('-')
-        || version.contains("dev")
-        || version.contains("alpha")
-        || version.contains("beta")
-        || version.contains("rc")
+    is_prerelease_dart(version)
 }
 
 #[cfg(test)]
@@ -130,4 +247,60 @@ mod tests {
         assert!(!is_prerelease("1.0.0"));
         assert!(!is_prerelease("2.0.0"));
     }
+
+    struct StubSource(Vec<Vulnerability>);
+
+    impl VulnerabilitySource for StubSource {
+        async fn query(
+            &self,
+            _query: &VulnerabilityQuery,
+        ) -> anyhow::Result<Vec<Vulnerability>> {
+            Ok(self.0.clone())
+        }
+
+        async fn query_batch(
+            &self,
+            queries: &[VulnerabilityQuery],
+        ) -> anyhow::Result<Vec<Vec<Vulnerability>>> {
+            Ok(queries.iter().map(|_| self.0.clone()).collect())
+        }
+    }
+
+    fn vuln(id: &str) -> Vulnerability {
+        Vulnerability {
+            id: id.to_string(),
+            severity: crate::registries::VulnerabilitySeverity::High,
+            description: String::new(),
+            url: None,
+            fixed_version: None,
+            ranges: vec![],
+            aliases: vec![],
+            related: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_vulnerabilities_queries_latest_and_prerelease() {
+        let client = Arc::new(Client::new());
+        let source: Arc<dyn VulnerabilitySource> = Arc::new(StubSource(vec![vuln("GHSA-test")]));
+        let registry = PubDevRegistry::with_vuln_source(client, source);
+
+        let vulns = registry
+            .fetch_vulnerabilities("http", Some("1.0.0"), Some("2.0.0-dev.1"))
+            .await;
+
+        assert_eq!(vulns.len(), 2);
+        assert!(vulns.iter().all(|v| v.id == "GHSA-test"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_vulnerabilities_empty_without_any_known_version() {
+        let client = Arc::new(Client::new());
+        let source: Arc<dyn VulnerabilitySource> = Arc::new(StubSource(vec![vuln("GHSA-test")]));
+        let registry = PubDevRegistry::with_vuln_source(client, source);
+
+        let vulns = registry.fetch_vulnerabilities("http", None, None).await;
+
+        assert!(vulns.is_empty());
+    }
 }
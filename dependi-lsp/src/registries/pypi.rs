@@ -78,14 +78,75 @@ use chrono::{DateTime, NaiveDateTime, Utc};
 use reqwest::Client;
 use serde::Deserialize;
 
+use super::cache::{CachedResponse, ResponseCache};
 use super::http_client::create_shared_client;
-use super::version_utils::is_prerelease_python;
-use super::{Registry, VersionInfo};
+use super::version_utils::{Pep440Precedence, PrecedenceOrd, is_prerelease_python};
+use super::{IndexAuth, IndexSource, Registry, RegistryConfig, VersionInfo};
+
+/// Which API surface a [`PyPiSource`] exposes. Most mirrors proxy PyPI's
+/// full JSON API, but some (devpi, Artifactory, Nexus) only implement the
+/// minimal PEP 503 "simple" HTML index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PyPiIndexKind {
+    /// `GET {base}/{package}/json` - full metadata (description, homepage,
+    /// license, per-version upload dates, ...).
+    Json,
+    /// `GET {base}/{package}/` - a PEP 503 HTML page of file links; only
+    /// the version list can be recovered from it.
+    Simple,
+}
+
+/// One upstream PyPI-compatible endpoint: its base URL, which API surface
+/// it exposes, and an optional credential applied to every request sent to
+/// it.
+#[derive(Debug, Clone)]
+pub struct PyPiSource {
+    pub source: IndexSource,
+    pub kind: PyPiIndexKind,
+}
+
+impl PyPiSource {
+    /// A source exposing PyPI's full JSON API.
+    pub fn json(base_url: impl Into<String>) -> Self {
+        Self {
+            source: IndexSource::new(base_url),
+            kind: PyPiIndexKind::Json,
+        }
+    }
+
+    /// A source exposing only the PEP 503 "simple" HTML index.
+    pub fn simple(base_url: impl Into<String>) -> Self {
+        Self {
+            source: IndexSource::new(base_url),
+            kind: PyPiIndexKind::Simple,
+        }
+    }
+
+    /// Sends `Authorization: Bearer <token>` on every request to this
+    /// source.
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.source.auth = Some(IndexAuth::Bearer(token.into()));
+        self
+    }
+
+    /// Sends HTTP Basic authentication on every request to this source.
+    pub fn with_basic_auth(mut self, username: impl Into<String>, password: impl Into<String>) -> Self {
+        self.source.auth = Some(IndexAuth::Basic {
+            username: username.into(),
+            password: password.into(),
+        });
+        self
+    }
+}
 
 /// Client for the PyPI registry
 pub struct PyPiRegistry {
     client: Arc<Client>,
-    base_url: String,
+    /// Upstream endpoints to try, in order, for every lookup - the public
+    /// PyPI JSON API unless overridden via [`PyPiRegistry::with_sources`].
+    sources: Vec<PyPiSource>,
+    timeout: Option<std::time::Duration>,
+    cache: Option<ResponseCache>,
 }
 
 impl PyPiRegistry {
@@ -108,9 +169,48 @@ impl PyPiRegistry {
     /// let registry = PyPiRegistry::with_client(client);
     /// ```
     pub fn with_client(client: Arc<Client>) -> Self {
+        Self::with_config(client, RegistryConfig::default())
+    }
+
+    /// Like [`PyPiRegistry::with_client`], additionally honoring a
+    /// [`RegistryConfig`]'s alternate base URL, per-request timeout, and
+    /// response cache TTL.
+    pub fn with_config(client: Arc<Client>, config: RegistryConfig) -> Self {
+        let base_url = config
+            .base_url
+            .clone()
+            .unwrap_or_else(|| "https://pypi.org/pypi".to_string());
+        Self::with_sources_and_config(client, vec![PyPiSource::json(base_url)], config)
+    }
+
+    /// Like [`PyPiRegistry::with_client`], but resolving every lookup
+    /// against `sources` in priority order - e.g. a private mirror before
+    /// falling back to the public PyPI index - instead of just PyPI.
+    pub fn with_sources(client: Arc<Client>, sources: Vec<PyPiSource>) -> Self {
+        Self::with_sources_and_config(client, sources, RegistryConfig::default())
+    }
+
+    /// Like [`PyPiRegistry::with_sources`], additionally honoring a
+    /// [`RegistryConfig`].
+    pub fn with_sources_and_config(
+        client: Arc<Client>,
+        sources: Vec<PyPiSource>,
+        config: RegistryConfig,
+    ) -> Self {
+        let sources = if sources.is_empty() {
+            let base_url = config
+                .base_url
+                .clone()
+                .unwrap_or_else(|| "https://pypi.org/pypi".to_string());
+            vec![PyPiSource::json(base_url)]
+        } else {
+            sources
+        };
         Self {
             client,
-            base_url: "https://pypi.org/pypi".to_string(),
+            sources,
+            timeout: config.timeout,
+            cache: config.cache_ttl.map(ResponseCache::with_ttl),
         }
     }
 }
@@ -151,14 +251,22 @@ struct PackageInfo {
     project_urls: Option<HashMap<String, String>>,
     /// Classifiers (can be used to detect deprecated packages)
     classifiers: Option<Vec<String>>,
+    /// PEP 440 specifier for supported interpreters, for the latest version
+    /// (e.g. `">=3.8,<4.0"`); per-release values live on [`ReleaseFile`].
+    requires_python: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct ReleaseFile {
     /// Whether this file has been yanked
     yanked: Option<bool>,
+    /// Why this file was yanked, when given (PyPI returns `""` rather than
+    /// omitting the field when a reason wasn't provided).
+    yanked_reason: Option<String>,
     /// Upload time for this file (ISO 8601 format without timezone)
     upload_time: Option<String>,
+    /// PEP 440 specifier for interpreters this file supports.
+    requires_python: Option<String>,
 }
 
 impl Registry for PyPiRegistry {
@@ -167,12 +275,75 @@ impl Registry for PyPiRegistry {
     }
 
     async fn get_version_info(&self, package_name: &str) -> anyhow::Result<VersionInfo> {
+        if let Some(cache) = &self.cache {
+            if let Some((cached, true)) = cache.get("pypi", package_name) {
+                return Ok(cached.info);
+            }
+        }
+
         // Normalize package name (PyPI is case-insensitive, uses lowercase)
         let normalized_name = normalize_package_name(package_name);
 
-        let url = format!("{}/{}/json", self.base_url, normalized_name);
+        let mut last_err = None;
+        for source in &self.sources {
+            let result = match source.kind {
+                PyPiIndexKind::Json => self.fetch_json(source, &normalized_name, package_name).await,
+                PyPiIndexKind::Simple => {
+                    self.fetch_simple(source, &normalized_name, package_name).await
+                }
+            };
+            match result {
+                Ok(info) => {
+                    if let Some(cache) = &self.cache {
+                        cache.insert(
+                            "pypi",
+                            package_name,
+                            CachedResponse {
+                                info: info.clone(),
+                                etag: None,
+                                last_modified: None,
+                            },
+                        );
+                    }
+                    return Ok(info);
+                }
+                Err(e) => last_err = Some(e),
+            }
+        }
+
+        Err(last_err
+            .unwrap_or_else(|| anyhow::anyhow!("no index sources configured for {}", package_name)))
+    }
+}
+
+impl PyPiRegistry {
+    /// Sends a `GET` request to `url` against `source`, applying the
+    /// configured timeout and the source's [`IndexAuth`], if any.
+    async fn get(&self, source: &PyPiSource, url: &str) -> anyhow::Result<reqwest::Response> {
+        let mut request = self.client.get(url);
+        if let Some(timeout) = self.timeout {
+            request = request.timeout(timeout);
+        }
+        if let Some(auth) = &source.source.auth {
+            request = auth.apply(request);
+        }
+        Ok(request.send().await?)
+    }
+
+    /// Fetches and parses full package metadata from `source`'s JSON API.
+    async fn fetch_json(
+        &self,
+        source: &PyPiSource,
+        normalized_name: &str,
+        package_name: &str,
+    ) -> anyhow::Result<VersionInfo> {
+        let url = format!(
+            "{}/{}/json",
+            source.source.base_url.trim_end_matches('/'),
+            normalized_name
+        );
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.get(source, &url).await?;
 
         if !response.status().is_success() {
             anyhow::bail!(
@@ -208,6 +379,34 @@ impl Registry for PyPiRegistry {
         // Find latest prerelease
         let latest_prerelease = versions.iter().find(|v| is_prerelease_python(v)).cloned();
 
+        // Versions withdrawn via PEP 592 yanking - still resolvable, but a
+        // dependent pinned to one should be warned, along with why if PyPI
+        // recorded a reason.
+        let yanked_versions: Vec<String> = pypi_response
+            .releases
+            .iter()
+            .filter(|(_, files)| files.iter().any(|f| f.yanked.unwrap_or(false)))
+            .map(|(version, _)| version.clone())
+            .collect();
+
+        let yanked_reasons: HashMap<String, String> = pypi_response
+            .releases
+            .iter()
+            .filter_map(|(version, files)| {
+                files.iter().find_map(|f| {
+                    if !f.yanked.unwrap_or(false) {
+                        return None;
+                    }
+                    f.yanked_reason
+                        .clone()
+                        .filter(|reason| !reason.is_empty())
+                        .map(|reason| (version.clone(), reason))
+                })
+            })
+            .collect();
+
+        let yanked = yanked_versions.contains(&pypi_response.info.version);
+
         // Extract repository URL from project_urls
         let repository = pypi_response.info.project_urls.as_ref().and_then(|urls| {
             urls.get("Repository")
@@ -250,6 +449,25 @@ impl Registry for PyPiRegistry {
             })
             .collect();
 
+        // Requires-Python constraint per version (use the first file's, same
+        // convention as `release_dates` above), falling back to the
+        // project-level value for whichever version it actually describes.
+        let mut requires_python: HashMap<String, String> = pypi_response
+            .releases
+            .iter()
+            .filter_map(|(version, files)| {
+                files
+                    .first()
+                    .and_then(|f| f.requires_python.clone())
+                    .map(|req| (version.clone(), req))
+            })
+            .collect();
+        if let Some(req) = pypi_response.info.requires_python.clone() {
+            requires_python
+                .entry(pypi_response.info.version.clone())
+                .or_insert(req);
+        }
+
         Ok(VersionInfo {
             latest: latest_stable,
             latest_prerelease,
@@ -260,9 +478,66 @@ impl Registry for PyPiRegistry {
             license: pypi_response.info.license,
             vulnerabilities: vec![], // TODO: Integrate Safety/OSV
             deprecated,
-            yanked: false,
-            yanked_versions: vec![], // Not applicable to PyPI
+            yanked,
+            yanked_versions,
             release_dates,
+            platforms: HashMap::new(),
+            dependency_groups: HashMap::new(),
+            deprecation_messages: HashMap::new(),
+            requires_python,
+            rust_version: HashMap::new(),
+            yanked_reasons,
+            latest_breaking_major: None,
+            latest_compatible: None,
+            alternative_version: None,
+            version_metadata: HashMap::new(),
+        })
+    }
+
+    /// Fetches a PEP 503 "simple" index page from `source` and recovers
+    /// just the version list from the linked file names - mirrors that
+    /// don't proxy PyPI's JSON API (or a private index that only ever
+    /// implements the minimal standard) still work, with no description,
+    /// homepage, license, or release dates available.
+    async fn fetch_simple(
+        &self,
+        source: &PyPiSource,
+        normalized_name: &str,
+        package_name: &str,
+    ) -> anyhow::Result<VersionInfo> {
+        let url = format!(
+            "{}/{}/",
+            source.source.base_url.trim_end_matches('/'),
+            normalized_name
+        );
+
+        let response = self.get(source, &url).await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "Failed to fetch simple index for {}: {}",
+                package_name,
+                response.status()
+            );
+        }
+
+        let html = response.text().await?;
+
+        let mut versions: Vec<String> = simple_index_filenames(&html)
+            .iter()
+            .filter_map(|filename| version_from_simple_index_filename(filename))
+            .collect();
+        versions.sort_by(|a, b| compare_python_versions(b, a));
+        versions.dedup();
+
+        let latest_stable = versions.iter().find(|v| !is_prerelease_python(v)).cloned();
+        let latest_prerelease = versions.iter().find(|v| is_prerelease_python(v)).cloned();
+
+        Ok(VersionInfo {
+            latest: latest_stable,
+            latest_prerelease,
+            versions,
+            ..Default::default()
         })
     }
 }
@@ -282,38 +557,51 @@ fn normalize_package_name(name: &str) -> String {
     name.to_lowercase().replace(['_', '.'], "-")
 }
 
-/// Compare Python versions for sorting
-/// Returns Ordering for descending sort (newer versions first)
+/// Compare Python versions for sorting, per PEP 440 precedence (epoch,
+/// then release segments, then dev/pre/post phase, then local version).
+/// Returns Ordering for descending sort (newer versions first).
 fn compare_python_versions(a: &str, b: &str) -> std::cmp::Ordering {
-    // Try parsing as semver first
-    match (semver::Version::parse(a), semver::Version::parse(b)) {
-        (Ok(va), Ok(vb)) => va.cmp(&vb),
-        _ => {
-            // Fallback to simple string comparison with version-aware logic
-            compare_version_strings(a, b)
-        }
-    }
+    Pep440Precedence.compare(a, b)
 }
 
-/// Simple version string comparison
-fn compare_version_strings(a: &str, b: &str) -> std::cmp::Ordering {
-    let parse_parts = |s: &str| -> Vec<u64> {
-        s.split(|c: char| !c.is_ascii_digit())
-            .filter_map(|p| p.parse().ok())
-            .collect()
-    };
-
-    let parts_a = parse_parts(a);
-    let parts_b = parse_parts(b);
-
-    for (pa, pb) in parts_a.iter().zip(parts_b.iter()) {
-        match pa.cmp(pb) {
-            std::cmp::Ordering::Equal => continue,
-            other => return other,
+/// Extracts every `<a href="...">` link target's filename from a PEP 503
+/// "simple" index HTML page, stripping any query/fragment (e.g. a
+/// `#sha256=...` integrity hash) and leading path.
+fn simple_index_filenames(html: &str) -> Vec<String> {
+    let mut filenames = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find("<a ") {
+        rest = &rest[start..];
+        let Some(href_start) = rest.find("href=\"").map(|i| i + 6) else {
+            break;
+        };
+        let Some(href_end) = rest[href_start..].find('"').map(|i| i + href_start) else {
+            break;
+        };
+        let href = &rest[href_start..href_end];
+        let path = href.split(['#', '?']).next().unwrap_or(href);
+        if let Some(filename) = path.rsplit('/').next() {
+            if !filename.is_empty() {
+                filenames.push(filename.to_string());
+            }
         }
+        rest = &rest[href_end..];
     }
+    filenames
+}
 
-    parts_a.len().cmp(&parts_b.len())
+/// Extracts the version encoded in a distribution filename (`{name}-{version}.tar.gz`,
+/// `{name}-{version}-py3-none-any.whl`, ...): strips the known archive/wheel
+/// extension, then takes the first `-`-separated segment that starts with a
+/// digit, which per-PEP-440 is always where the version starts (project
+/// names normalized per PEP 503 don't begin a segment with a digit).
+fn version_from_simple_index_filename(filename: &str) -> Option<String> {
+    const EXTENSIONS: &[&str] = &[".tar.gz", ".tar.bz2", ".tar.xz", ".zip", ".whl", ".egg"];
+    let stem = EXTENSIONS.iter().find_map(|ext| filename.strip_suffix(ext))?;
+
+    stem.split('-')
+        .find(|segment| segment.chars().next().is_some_and(|c| c.is_ascii_digit()))
+        .map(|segment| segment.to_string())
 }
 
 #[cfg(test)]
@@ -355,4 +643,84 @@ mod tests {
             Ordering::Greater
         );
     }
+
+    #[test]
+    fn test_with_config_overrides_base_url() {
+        let client = create_shared_client().unwrap();
+        let config = RegistryConfig::new().with_base_url("https://mirror.example.com/pypi");
+        let registry = PyPiRegistry::with_config(client, config);
+
+        assert_eq!(registry.sources.len(), 1);
+        assert_eq!(registry.sources[0].source.base_url, "https://mirror.example.com/pypi");
+        assert_eq!(registry.sources[0].kind, PyPiIndexKind::Json);
+    }
+
+    #[test]
+    fn test_with_config_defaults_to_pypi_base_url() {
+        let client = create_shared_client().unwrap();
+        let registry = PyPiRegistry::with_config(client, RegistryConfig::new());
+
+        assert_eq!(registry.sources[0].source.base_url, "https://pypi.org/pypi");
+    }
+
+    #[test]
+    fn test_with_config_cache_ttl_enables_cache() {
+        let client = create_shared_client().unwrap();
+        let config = RegistryConfig::new().with_cache_ttl(std::time::Duration::from_secs(60));
+        let registry = PyPiRegistry::with_config(client, config);
+
+        assert!(registry.cache.is_some());
+    }
+
+    #[test]
+    fn test_with_sources_tries_simple_index_after_json_mirror() {
+        let client = create_shared_client().unwrap();
+        let sources = vec![
+            PyPiSource::json("https://private.example.com/pypi").with_bearer_token("secret"),
+            PyPiSource::simple("https://pypi.org/simple"),
+        ];
+        let registry = PyPiRegistry::with_sources(client, sources);
+
+        assert_eq!(registry.sources.len(), 2);
+        assert_eq!(registry.sources[0].kind, PyPiIndexKind::Json);
+        assert!(registry.sources[0].source.auth.is_some());
+        assert_eq!(registry.sources[1].kind, PyPiIndexKind::Simple);
+    }
+
+    #[test]
+    fn test_version_from_simple_index_filename_sdist() {
+        assert_eq!(
+            version_from_simple_index_filename("flask-3.0.0.tar.gz"),
+            Some("3.0.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_version_from_simple_index_filename_wheel() {
+        assert_eq!(
+            version_from_simple_index_filename("flask-3.0.0-py3-none-any.whl"),
+            Some("3.0.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_version_from_simple_index_filename_rejects_unknown_extension() {
+        assert_eq!(version_from_simple_index_filename("flask-3.0.0.exe"), None);
+    }
+
+    #[test]
+    fn test_simple_index_filenames_extracts_hrefs() {
+        let html = r#"<!DOCTYPE html>
+<html>
+<body>
+<a href="https://files.example.com/flask-2.0.0.tar.gz#sha256=abc">flask-2.0.0.tar.gz</a>
+<a href="/whl/flask-3.0.0-py3-none-any.whl">flask-3.0.0-py3-none-any.whl</a>
+</body>
+</html>"#;
+        let filenames = simple_index_filenames(html);
+        assert_eq!(
+            filenames,
+            vec!["flask-2.0.0.tar.gz", "flask-3.0.0-py3-none-any.whl"]
+        );
+    }
 }
@@ -0,0 +1,639 @@
+//! Registry-resolution layer: discovers alternate/private registry sources
+//! from each ecosystem's own config files, so privately hosted dependencies
+//! resolve to the right endpoint instead of only the public index.
+//!
+//! This complements `Config.registries`/`Config.github_releases` (registries
+//! the user declares explicitly in LSP settings): it auto-detects sources
+//! already configured for the toolchain itself - `.cargo/config.toml`,
+//! `.npmrc`, `composer.json`, `GOPROXY`, `NuGet.config` - so most private
+//! setups need no LSP-specific configuration at all. Ecosystems not covered
+//! here keep resolving against the public registry only.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+use toml_edit::{DocumentMut, Item};
+
+use crate::auth::cargo_credentials;
+
+/// A resolved alternate registry endpoint and the credential to send with
+/// requests to it, where one was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedRegistry {
+    pub url: String,
+    pub token: Option<String>,
+    /// PASERK secret key (`credentials.toml`'s `secret-key`), set instead of
+    /// `token` when this registry is configured for
+    /// [`crate::auth::asymmetric::AsymmetricTokenProvider`]-style signed
+    /// requests rather than a bearer token. Only ever populated for Cargo
+    /// registries - no other ecosystem this module resolves supports
+    /// asymmetric auth.
+    pub secret_key: Option<String>,
+}
+
+/// Alternate registry sources discovered from the workspace's own config
+/// files. Built once via [`DiscoveredRegistries::discover`] and consulted per
+/// dependency through the `resolve_*` methods.
+#[derive(Debug, Clone, Default)]
+pub struct DiscoveredRegistries {
+    /// Cargo registries from `.cargo/config.toml`'s `[registries]`, keyed by
+    /// registry name - the same name used by `registry = "..."` in
+    /// `Cargo.toml`.
+    cargo_registries: HashMap<String, ResolvedRegistry>,
+    /// The effective replacement for crates.io itself, resolved by following
+    /// `.cargo/config.toml`'s `[source.crates-io]` `replace-with` chain to a
+    /// source with a `registry` URL - e.g. a workspace-wide mirror set up
+    /// with `[source.crates-io] replace-with = "my-mirror"` and
+    /// `[source.my-mirror] registry = "sparse+https://..."`. `None` when no
+    /// replacement is configured, or it resolves to something other than a
+    /// registry URL (a `local-registry`/`directory` source, for instance).
+    cargo_default_source: Option<ResolvedRegistry>,
+    /// npm registries from `.npmrc`: `@scope:registry=` entries, keyed by
+    /// scope (without the leading `@`).
+    npm_scoped: HashMap<String, ResolvedRegistry>,
+    /// `.npmrc`'s unscoped default `registry=` line.
+    npm_default: Option<ResolvedRegistry>,
+    /// Composer-type repositories from `composer.json`'s `repositories`
+    /// array, tried in declaration order before falling back to
+    /// packagist.org.
+    composer_repositories: Vec<ResolvedRegistry>,
+    /// `GOPROXY`'s first configured mirror, if any.
+    go_proxy: Option<String>,
+    /// NuGet sources from `NuGet.config`'s `<packageSources>`, with matching
+    /// `<packageSourceCredentials>` tokens attached where present.
+    nuget_sources: Vec<ResolvedRegistry>,
+}
+
+impl DiscoveredRegistries {
+    /// Discover every config file this module understands under
+    /// `workspace_root`. Missing or unparsable files are skipped silently -
+    /// the resolver just falls back to the public registry for that
+    /// ecosystem, same as if no config existed at all.
+    pub async fn discover(workspace_root: &Path) -> Self {
+        let secret_keys = cargo_credentials::parse_cargo_secret_keys().await;
+        Self {
+            cargo_registries: discover_cargo_registries(workspace_root, &secret_keys).await,
+            cargo_default_source: discover_cargo_default_source(workspace_root, &secret_keys).await,
+            npm_scoped: discover_npmrc_scoped(workspace_root),
+            npm_default: discover_npmrc_default(workspace_root),
+            composer_repositories: discover_composer_repositories(workspace_root),
+            go_proxy: discover_go_proxy(),
+            nuget_sources: discover_nuget_sources(workspace_root),
+        }
+    }
+
+    /// Resolve the registry a Cargo dependency should be fetched from, given
+    /// its `registry = "..."` field. `None` (no explicit `registry` field,
+    /// the default crates.io source) falls back to whatever
+    /// `[source.crates-io]` was replaced with, if anything.
+    pub fn resolve_cargo(&self, registry_name: Option<&str>) -> Option<&ResolvedRegistry> {
+        match registry_name {
+            Some(name) => self.cargo_registries.get(name),
+            None => self.cargo_default_source.as_ref(),
+        }
+    }
+
+    /// Resolve the npm registry for `package_name`, preferring its scope's
+    /// registry (`@scope/name`) over the `.npmrc` default.
+    pub fn resolve_npm(&self, package_name: &str) -> Option<&ResolvedRegistry> {
+        package_name
+            .strip_prefix('@')
+            .and_then(|rest| rest.split_once('/'))
+            .and_then(|(scope, _)| self.npm_scoped.get(scope))
+            .or(self.npm_default.as_ref())
+    }
+
+    /// Composer repositories to try before packagist.org, in declaration
+    /// order.
+    pub fn composer_repositories(&self) -> &[ResolvedRegistry] {
+        &self.composer_repositories
+    }
+
+    /// The configured `GOPROXY` mirror, if one other than the default public
+    /// proxy is set.
+    pub fn go_proxy(&self) -> Option<&str> {
+        self.go_proxy.as_deref()
+    }
+
+    /// NuGet package sources to try before nuget.org, in declaration order.
+    pub fn nuget_sources(&self) -> &[ResolvedRegistry] {
+        &self.nuget_sources
+    }
+}
+
+fn read_toml(workspace_root: &Path, candidates: &[&str]) -> Option<DocumentMut> {
+    candidates
+        .iter()
+        .find_map(|relative| fs::read_to_string(workspace_root.join(relative)).ok())
+        .and_then(|content| content.parse::<DocumentMut>().ok())
+}
+
+/// Cargo's `index`/`registry` config values name the sparse-index protocol
+/// via a `sparse+` scheme prefix (e.g. `sparse+https://.../index/`) - real
+/// per Cargo's own registry-protocols spec, not just this module's test
+/// fixtures. `CargoSparseRegistry` speaks plain HTTP(S) to that URL, so the
+/// prefix has to come off before it reaches `reqwest`, which doesn't know
+/// the `sparse+` scheme and would fail every request with it left on. A
+/// git-based index (no `sparse+` prefix at all, e.g. the crates.io git
+/// index mirror) isn't something `CargoSparseRegistry` can serve either,
+/// but there's nothing to strip in that case - it's left as-is and will
+/// simply fail to resolve packages, same as any other unsupported index.
+fn strip_sparse_scheme(index: &str) -> String {
+    index
+        .strip_prefix("sparse+")
+        .unwrap_or(index)
+        .to_string()
+}
+
+async fn discover_cargo_registries(
+    workspace_root: &Path,
+    secret_keys: &HashMap<String, String>,
+) -> HashMap<String, ResolvedRegistry> {
+    let mut registries = HashMap::new();
+
+    let Some(doc) = read_toml(workspace_root, &[".cargo/config.toml", ".cargo/config"]) else {
+        return registries;
+    };
+
+    if let Some(table) = doc.get("registries").and_then(Item::as_table) {
+        for (name, item) in table.iter() {
+            let Some(entry) = item.as_table() else {
+                continue;
+            };
+            let Some(index) = entry.get("index").and_then(Item::as_str) else {
+                continue;
+            };
+            registries.insert(
+                name.to_string(),
+                ResolvedRegistry {
+                    url: strip_sparse_scheme(index),
+                    token: cargo_credentials::cargo_registry_token(name).await,
+                    secret_key: secret_keys.get(name).cloned(),
+                },
+            );
+        }
+    }
+
+    registries
+}
+
+/// Follows `.cargo/config.toml`'s `[source.crates-io]` `replace-with` chain
+/// to the registry URL it ultimately resolves to, so a workspace-wide
+/// mirror set up the same way `cargo` itself reads it is picked up without
+/// any dependency needing an explicit `registry = "..."` field.
+///
+/// Bounded to a handful of hops so a `replace-with` cycle in a malformed
+/// config can't loop forever - real chains are one or two sources deep.
+async fn discover_cargo_default_source(
+    workspace_root: &Path,
+    secret_keys: &HashMap<String, String>,
+) -> Option<ResolvedRegistry> {
+    let doc = read_toml(workspace_root, &[".cargo/config.toml", ".cargo/config"])?;
+    let sources = doc.get("source").and_then(Item::as_table)?;
+
+    let mut name = "crates-io".to_string();
+    for _ in 0..8 {
+        let entry = sources.get(&name).and_then(Item::as_table)?;
+
+        if let Some(registry) = entry.get("registry").and_then(Item::as_str) {
+            return Some(ResolvedRegistry {
+                url: strip_sparse_scheme(registry),
+                token: cargo_credentials::cargo_registry_token(&name).await,
+                secret_key: secret_keys.get(&name).cloned(),
+            });
+        }
+
+        name = entry.get("replace-with").and_then(Item::as_str)?.to_string();
+    }
+
+    None
+}
+
+fn read_npmrc(workspace_root: &Path) -> Option<Vec<(String, String)>> {
+    let content = fs::read_to_string(workspace_root.join(".npmrc")).ok()?;
+    Some(
+        content
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                    return None;
+                }
+                let (key, value) = line.split_once('=')?;
+                Some((
+                    key.trim().to_string(),
+                    value.trim().trim_matches('"').to_string(),
+                ))
+            })
+            .collect(),
+    )
+}
+
+/// `.npmrc` credential lines (`//host/:_authToken=`/`//host/:_auth=`) are
+/// resolved separately by [`crate::auth::npmrc::parse_npmrc_providers`] into
+/// `TokenProvider`s registered with the shared `TokenProviderManager` -
+/// Bearer-only here would both duplicate that and miss Basic auth, so
+/// `token` is always `None` for npm registries discovered this way.
+fn discover_npmrc_scoped(workspace_root: &Path) -> HashMap<String, ResolvedRegistry> {
+    let mut scoped = HashMap::new();
+    let Some(lines) = read_npmrc(workspace_root) else {
+        return scoped;
+    };
+
+    for (key, value) in &lines {
+        let Some(scope) = key.strip_prefix('@').and_then(|k| k.strip_suffix(":registry")) else {
+            continue;
+        };
+        scoped.insert(
+            scope.to_string(),
+            ResolvedRegistry {
+                url: value.clone(),
+                token: None,
+                secret_key: None,
+            },
+        );
+    }
+
+    scoped
+}
+
+fn discover_npmrc_default(workspace_root: &Path) -> Option<ResolvedRegistry> {
+    let lines = read_npmrc(workspace_root)?;
+    let (_, url) = lines.iter().find(|(key, _)| key == "registry")?;
+    Some(ResolvedRegistry {
+        token: None,
+        url: url.clone(),
+        secret_key: None,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct ComposerJson {
+    #[serde(default)]
+    repositories: Vec<ComposerRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ComposerRepository {
+    #[serde(rename = "type")]
+    kind: String,
+    url: Option<String>,
+}
+
+fn discover_composer_repositories(workspace_root: &Path) -> Vec<ResolvedRegistry> {
+    let Ok(content) = fs::read_to_string(workspace_root.join("composer.json")) else {
+        return Vec::new();
+    };
+    let Ok(manifest) = serde_json::from_str::<ComposerJson>(&content) else {
+        return Vec::new();
+    };
+    let token = composer_auth_token(workspace_root);
+
+    manifest
+        .repositories
+        .into_iter()
+        .filter(|repo| repo.kind == "composer")
+        .filter_map(|repo| {
+            Some(ResolvedRegistry {
+                url: repo.url?,
+                token: token.clone(),
+                secret_key: None,
+            })
+        })
+        .collect()
+}
+
+/// Composer stores registry credentials in `auth.json`'s `bearer`/
+/// `http-basic` maps (or the `COMPOSER_AUTH` env var holding the same JSON
+/// shape), keyed by host. Callers here only need "is there a token at all",
+/// so the first one found wins.
+fn composer_auth_token(workspace_root: &Path) -> Option<String> {
+    let content = std::env::var("COMPOSER_AUTH")
+        .ok()
+        .or_else(|| fs::read_to_string(workspace_root.join("auth.json")).ok())?;
+    let auth: serde_json::Value = serde_json::from_str(&content).ok()?;
+    auth.get("bearer")
+        .or_else(|| auth.get("http-basic"))
+        .and_then(|map| map.as_object())
+        .and_then(|map| map.values().next())
+        .and_then(|entry| {
+            entry.as_str().map(str::to_string).or_else(|| {
+                entry
+                    .get("password")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+            })
+        })
+}
+
+/// The module proxy mirror list from `GOPROXY` - `""`/`"direct"`/`"off"`
+/// mean "no proxy configured", matching `go`'s own special-cased values.
+fn discover_go_proxy() -> Option<String> {
+    let value = std::env::var("GOPROXY").ok()?;
+    let first = value.split(',').next()?.trim();
+    if first.is_empty() || first == "direct" || first == "off" {
+        return None;
+    }
+    Some(first.to_string())
+}
+
+/// Bare-bones scan for a named top-level element's inner content.
+/// `NuGet.config` is simple enough (no attributes on the container elements,
+/// shallow nesting) that pulling in a full XML parser would be overkill for
+/// what this resolver needs.
+fn xml_section<'a>(content: &'a str, name: &str) -> Option<&'a str> {
+    let open = format!("<{}", name);
+    let start = content.find(&open)?;
+    let tag_end = content[start..].find('>')? + start + 1;
+    let close = format!("</{}>", name);
+    let end = content[tag_end..].find(&close)? + tag_end;
+    Some(&content[tag_end..end])
+}
+
+/// Every `<add key="..." value="..." />` inside `section` (attribute order
+/// not assumed).
+fn xml_add_entries(section: &str) -> Vec<(String, String)> {
+    let mut entries = Vec::new();
+    let mut rest = section;
+    while let Some(start) = rest.find("<add ") {
+        let Some(end) = rest[start..].find('/').map(|i| start + i) else {
+            break;
+        };
+        let tag = &rest[start..end];
+        if let (Some(key), Some(value)) = (xml_attr(tag, "key"), xml_attr(tag, "value")) {
+            entries.push((key, value));
+        }
+        rest = &rest[end..];
+    }
+    entries
+}
+
+fn xml_attr(tag: &str, name: &str) -> Option<String> {
+    let marker = format!("{}=\"", name);
+    let start = tag.find(&marker)? + marker.len();
+    let end = tag[start..].find('"')? + start;
+    Some(tag[start..end].to_string())
+}
+
+/// `<packageSourceCredentials>` nests one element per source (named after
+/// the source), each with its own `<add key="ClearTextPassword"
+/// value="..."/>` - encrypted `Password` entries aren't handled since
+/// they're only decryptable by the .NET DPAPI that wrote them.
+fn nuget_credentials(content: &str) -> HashMap<String, String> {
+    let mut credentials = HashMap::new();
+    let Some(section) = xml_section(content, "packageSourceCredentials") else {
+        return credentials;
+    };
+
+    let mut rest = section;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        if rest.starts_with('/') {
+            continue;
+        }
+        let Some(name_end) = rest.find(|c: char| c.is_whitespace() || c == '>') else {
+            break;
+        };
+        let name = rest[..name_end].to_string();
+        let close_tag = format!("</{}>", name);
+        let Some(close_start) = rest.find(&close_tag) else {
+            break;
+        };
+        let inner = &rest[..close_start];
+        if let Some(token) = xml_add_entries(inner)
+            .into_iter()
+            .find(|(key, _)| key == "ClearTextPassword")
+            .map(|(_, value)| value)
+        {
+            credentials.insert(name, token);
+        }
+        rest = &rest[close_start + close_tag.len()..];
+    }
+
+    credentials
+}
+
+fn discover_nuget_sources(workspace_root: &Path) -> Vec<ResolvedRegistry> {
+    let Ok(content) = fs::read_to_string(workspace_root.join("NuGet.config"))
+        .or_else(|_| fs::read_to_string(workspace_root.join("nuget.config")))
+    else {
+        return Vec::new();
+    };
+
+    let Some(sources_section) = xml_section(&content, "packageSources") else {
+        return Vec::new();
+    };
+    let credentials = nuget_credentials(&content);
+
+    xml_add_entries(sources_section)
+        .into_iter()
+        .map(|(key, url)| ResolvedRegistry {
+            token: credentials.get(&key).cloned(),
+            url,
+            secret_key: None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    fn temp_workspace(name: &str) -> std::path::PathBuf {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let id = COUNTER.fetch_add(1, Ordering::SeqCst);
+        let dir = std::env::temp_dir().join(format!("dependi-registry-config-test-{}-{}", name, id));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_discover_cargo_registries_from_config_toml() {
+        let dir = temp_workspace("cargo");
+        fs::create_dir_all(dir.join(".cargo")).unwrap();
+        fs::write(
+            dir.join(".cargo/config.toml"),
+            r#"
+[registries.kellnr]
+index = "sparse+https://kellnr.example.com/api/v1/crates/"
+"#,
+        )
+        .unwrap();
+
+        let config = DiscoveredRegistries::discover(&dir).await;
+        let resolved = config.resolve_cargo(Some("kellnr")).unwrap();
+        assert_eq!(resolved.url, "https://kellnr.example.com/api/v1/crates/");
+        assert!(config.resolve_cargo(Some("unknown")).is_none());
+    }
+
+    /// `index`/`registry` values always carry cargo's `sparse+` scheme
+    /// prefix in real `.cargo/config.toml` files - `reqwest` doesn't
+    /// understand that scheme, so this drives an actual HTTP request
+    /// through [`CargoSparseRegistry`](crate::registries::cargo_sparse::CargoSparseRegistry)
+    /// built from the discovered URL, to catch a regression that only a
+    /// real send (not just a string-equality assertion) would surface.
+    #[tokio::test]
+    async fn test_discovered_cargo_registry_url_is_usable_by_reqwest() {
+        use crate::registries::Registry;
+        use crate::registries::cargo_sparse::CargoSparseRegistry;
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let Ok((mut stream, _)) = listener.accept() else {
+                return;
+            };
+            let mut buf = [0u8; 1024];
+            let _ = stream.read(&mut buf);
+            let body = "{\"name\":\"foo\",\"vers\":\"1.0.0\"}\n";
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes());
+        });
+
+        let dir = temp_workspace("cargo-sparse-http");
+        fs::create_dir_all(dir.join(".cargo")).unwrap();
+        fs::write(
+            dir.join(".cargo/config.toml"),
+            format!(
+                "[registries.local]\nindex = \"sparse+http://{}/index/\"\n",
+                addr
+            ),
+        )
+        .unwrap();
+
+        let config = DiscoveredRegistries::discover(&dir).await;
+        let resolved = config.resolve_cargo(Some("local")).unwrap().clone();
+
+        let client = CargoSparseRegistry::with_client_and_config(
+            std::sync::Arc::new(reqwest::Client::new()),
+            resolved.url,
+            resolved.token,
+        );
+        let info = client.get_version_info("foo").await.unwrap();
+        assert_eq!(info.latest.as_deref(), Some("1.0.0"));
+    }
+
+    #[tokio::test]
+    async fn test_discover_cargo_default_source_replacement() {
+        let dir = temp_workspace("cargo-source");
+        fs::create_dir_all(dir.join(".cargo")).unwrap();
+        fs::write(
+            dir.join(".cargo/config.toml"),
+            r#"
+[source.crates-io]
+replace-with = "my-mirror"
+
+[source.my-mirror]
+registry = "sparse+https://mirror.example.com/index/"
+"#,
+        )
+        .unwrap();
+
+        let config = DiscoveredRegistries::discover(&dir).await;
+        let resolved = config.resolve_cargo(None).unwrap();
+        assert_eq!(resolved.url, "https://mirror.example.com/index/");
+    }
+
+    #[tokio::test]
+    async fn test_discover_cargo_default_source_absent_when_no_replacement() {
+        let dir = temp_workspace("cargo-source-none");
+        fs::create_dir_all(dir.join(".cargo")).unwrap();
+        fs::write(
+            dir.join(".cargo/config.toml"),
+            r#"
+[registries.kellnr]
+index = "sparse+https://kellnr.example.com/api/v1/crates/"
+"#,
+        )
+        .unwrap();
+
+        let config = DiscoveredRegistries::discover(&dir).await;
+        assert!(config.resolve_cargo(None).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_discover_npmrc_scoped_and_default() {
+        let dir = temp_workspace("npmrc");
+        fs::write(
+            dir.join(".npmrc"),
+            "registry=https://registry.example.com/\n@acme:registry=https://npm.acme.example.com/\n//npm.acme.example.com/:_authToken=secret-token\n",
+        )
+        .unwrap();
+
+        let config = DiscoveredRegistries::discover(&dir).await;
+
+        let scoped = config.resolve_npm("@acme/widgets").unwrap();
+        assert_eq!(scoped.url, "https://npm.acme.example.com/");
+        // `_authToken` lines are resolved into the shared TokenProviderManager
+        // (see `auth::npmrc::parse_npmrc_providers`), not this resolver.
+        assert!(scoped.token.is_none());
+
+        let default = config.resolve_npm("lodash").unwrap();
+        assert_eq!(default.url, "https://registry.example.com/");
+    }
+
+    #[tokio::test]
+    async fn test_discover_composer_repositories() {
+        let dir = temp_workspace("composer");
+        fs::write(
+            dir.join("composer.json"),
+            r#"{"repositories": [{"type": "composer", "url": "https://repo.example.com"}, {"type": "vcs", "url": "https://github.com/example/thing"}]}"#,
+        )
+        .unwrap();
+
+        let config = DiscoveredRegistries::discover(&dir).await;
+        let repos = config.composer_repositories();
+        assert_eq!(repos.len(), 1);
+        assert_eq!(repos[0].url, "https://repo.example.com");
+    }
+
+    #[tokio::test]
+    async fn test_discover_nuget_sources_with_credentials() {
+        let dir = temp_workspace("nuget");
+        fs::write(
+            dir.join("NuGet.config"),
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<configuration>
+  <packageSources>
+    <add key="internal" value="https://nuget.example.com/v3/index.json" />
+  </packageSources>
+  <packageSourceCredentials>
+    <internal>
+      <add key="Username" value="ci" />
+      <add key="ClearTextPassword" value="super-secret" />
+    </internal>
+  </packageSourceCredentials>
+</configuration>
+"#,
+        )
+        .unwrap();
+
+        let config = DiscoveredRegistries::discover(&dir).await;
+        let sources = config.nuget_sources();
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources[0].url, "https://nuget.example.com/v3/index.json");
+        assert_eq!(sources[0].token.as_deref(), Some("super-secret"));
+    }
+
+    #[tokio::test]
+    async fn test_discover_returns_defaults_when_no_config_files_exist() {
+        let dir = temp_workspace("empty");
+        let config = DiscoveredRegistries::discover(&dir).await;
+
+        assert!(config.resolve_cargo(Some("anything")).is_none());
+        assert!(config.resolve_cargo(None).is_none());
+        assert!(config.resolve_npm("lodash").is_none());
+        assert!(config.composer_repositories().is_empty());
+        assert!(config.go_proxy().is_none());
+        assert!(config.nuget_sources().is_empty());
+    }
+}
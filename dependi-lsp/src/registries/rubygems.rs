@@ -51,8 +51,11 @@
 //!
 //! - **Version ordering**: RubyGems uses its own ordering (not strictly semver)
 //! - **Yanked gems**: Available via separate endpoint (not implemented)
-//! - **Platform gems**: May have platform suffix (`-java`, `-x86_64-linux`)
-//! - **Prerelease format**: Uses `.pre.1` format (not `-pre.1`)
+//! - **Platform gems**: May have platform suffix (`-java`, `-x86_64-linux`); these
+//!   are collapsed into their base version before reaching `VersionInfo::versions`,
+//!   with the observed platforms recorded in `VersionInfo::platforms`
+//! - **Prerelease format**: Uses `.pre.1` format (not `-pre.1`); rewritten to the
+//!   hyphenated semver form so it parses and sorts correctly everywhere else
 //! - **No deprecation flag**: RubyGems API doesn't expose deprecation status
 //!
 //! ## Error Handling
@@ -75,6 +78,7 @@ use reqwest::Client;
 use serde::Deserialize;
 
 use super::http_client::create_shared_client;
+use super::version_utils::is_prerelease_ruby;
 use super::{Registry, VersionInfo};
 
 /// Client for the RubyGems.org registry
@@ -122,6 +126,22 @@ impl Default for RubyGemsRegistry {
     }
 }
 
+impl RubyGemsRegistry {
+    /// Reconstruct the version set from the compact dependencies endpoint
+    /// (`GET /api/v1/dependencies.json?gems={name}`) when `/versions` isn't
+    /// implemented by the mirror. Returns `None` on any failure so the
+    /// caller can fall back further rather than erroring the whole query.
+    async fn fetch_versions_from_dependencies(&self, package_name: &str) -> Option<Vec<String>> {
+        let url = format!("{}/dependencies.json?gems={}", self.base_url, package_name);
+        let response = self.client.get(&url).send().await.ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        let entries: Vec<DependencyResponse> = response.json().await.ok()?;
+        Some(entries.into_iter().map(|e| e.number).collect())
+    }
+}
+
 /// RubyGems API response for a gem
 #[derive(Debug, Deserialize)]
 struct GemResponse {
@@ -143,6 +163,112 @@ struct VersionResponse {
     created_at: Option<String>,
 }
 
+/// RubyGems API response for the compact dependencies endpoint
+/// (`/api/v1/dependencies.json`). Only the version number is needed here;
+/// this endpoint doesn't expose a publish date.
+#[derive(Debug, Deserialize)]
+struct DependencyResponse {
+    number: String,
+}
+
+/// A 404/400 from a RubyGems v1 endpoint means "no such gem" or "endpoint
+/// not implemented by this mirror" - not a transport failure.
+fn is_missing_or_unsupported(status: reqwest::StatusCode) -> bool {
+    status.as_u16() == 404 || status.as_u16() == 400
+}
+
+/// Fall back to just the latest version (with its date, if known) when
+/// every versions source has failed.
+fn single_version_fallback(
+    gem: &GemResponse,
+) -> (
+    Vec<String>,
+    HashMap<String, DateTime<Utc>>,
+    HashMap<String, Vec<String>>,
+) {
+    let created_at = gem
+        .version_created_at
+        .as_deref()
+        .and_then(|time_str| DateTime::parse_from_rfc3339(time_str).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+    collapse_platform_variants([(gem.version.clone(), created_at)])
+}
+
+/// Split a RubyGems version entry's `number` into its base version and an
+/// optional platform suffix (e.g. `1.16.0-x86_64-linux` -> `("1.16.0",
+/// Some("x86_64-linux"))`). RubyGems version numbers never contain a hyphen
+/// themselves - prereleases use the dotted `.pre.1` form - so the first
+/// hyphen, if any, always introduces the platform.
+fn split_platform_suffix(number: &str) -> (&str, Option<&str>) {
+    match number.split_once('-') {
+        Some((base, platform)) => (base, Some(platform)),
+        None => (number, None),
+    }
+}
+
+/// RubyGems identifies prereleases with a dotted suffix like `2.0.0.pre.1`
+/// rather than semver's hyphenated `2.0.0-pre.1`. Rewrite the leading dot of
+/// the first `pre`/`alpha`/`beta`/`rc` segment into a hyphen so the version
+/// parses as valid semver and sorts as a prerelease (lower than the final
+/// release it precedes) everywhere else in the codebase.
+fn normalize_ruby_prerelease(version: &str) -> String {
+    let segments: Vec<&str> = version.split('.').collect();
+    let marker_idx = segments.iter().position(|segment| {
+        let segment = segment.to_lowercase();
+        segment.starts_with("pre")
+            || segment.starts_with("alpha")
+            || segment.starts_with("beta")
+            || segment.starts_with("rc")
+    });
+
+    match marker_idx {
+        Some(idx) if idx > 0 => {
+            format!("{}-{}", segments[..idx].join("."), segments[idx..].join("."))
+        }
+        _ => version.to_string(),
+    }
+}
+
+/// Collapse raw RubyGems version entries into one logical, prerelease-normalized
+/// version per base, merging the platform suffix (e.g. `-java`, `-x86_64-linux`)
+/// and earliest known release date of each platform variant. Without this,
+/// every platform build of a release (`1.16.0-x86_64-linux`, `1.16.0-java`, ...)
+/// would show up as a distinct entry in `versions` and corrupt "latest version"
+/// comparisons.
+fn collapse_platform_variants(
+    entries: impl IntoIterator<Item = (String, Option<DateTime<Utc>>)>,
+) -> (
+    Vec<String>,
+    HashMap<String, DateTime<Utc>>,
+    HashMap<String, Vec<String>>,
+) {
+    let mut versions = Vec::new();
+    let mut dates = HashMap::new();
+    let mut platforms: HashMap<String, Vec<String>> = HashMap::new();
+
+    for (number, created_at) in entries {
+        let (base, platform) = split_platform_suffix(&number);
+        let base = normalize_ruby_prerelease(base);
+
+        if !versions.contains(&base) {
+            versions.push(base.clone());
+        }
+
+        if let Some(platform) = platform {
+            let variants = platforms.entry(base.clone()).or_default();
+            if !variants.iter().any(|p| p == platform) {
+                variants.push(platform.to_string());
+            }
+        }
+
+        if let Some(dt) = created_at {
+            dates.entry(base).or_insert(dt);
+        }
+    }
+
+    (versions, dates, platforms)
+}
+
 impl Registry for RubyGemsRegistry {
     fn http_client(&self) -> Arc<Client> {
         Arc::clone(&self.client)
@@ -153,49 +279,54 @@ impl Registry for RubyGemsRegistry {
         let gem_url = format!("{}/gems/{}.json", self.base_url, package_name);
         let gem_response = self.client.get(&gem_url).send().await?;
 
-        if !gem_response.status().is_success() {
-            anyhow::bail!(
-                "Failed to fetch gem info for {}: {}",
-                package_name,
-                gem_response.status()
-            );
+        let status = gem_response.status();
+        if is_missing_or_unsupported(status) {
+            // Unknown or renamed gem: report no releases rather than
+            // failing the whole document's diagnostics.
+            return Ok(VersionInfo::default());
+        }
+        if !status.is_success() {
+            anyhow::bail!("Failed to fetch gem info for {}: {}", package_name, status);
         }
 
         let gem: GemResponse = gem_response.json().await?;
 
         // Fetch all versions with dates
         let versions_url = format!("{}/versions/{}.json", self.base_url, package_name);
-        let (versions, release_dates) = match self.client.get(&versions_url).send().await {
+        let (versions, release_dates, platforms) = match self.client.get(&versions_url).send().await
+        {
             Ok(response) if response.status().is_success() => {
                 let version_list: Vec<VersionResponse> = response.json().await.unwrap_or_default();
-                let versions: Vec<String> = version_list.iter().map(|v| v.number.clone()).collect();
-                let dates: HashMap<String, DateTime<Utc>> = version_list
-                    .into_iter()
-                    .filter_map(|v| {
-                        v.created_at.as_ref().and_then(|time_str| {
-                            DateTime::parse_from_rfc3339(time_str)
-                                .ok()
-                                .map(|dt| (v.number.clone(), dt.with_timezone(&Utc)))
-                        })
-                    })
-                    .collect();
-                (versions, dates)
+                let entries = version_list.into_iter().map(|v| {
+                    let created_at = v
+                        .created_at
+                        .as_deref()
+                        .and_then(|time_str| DateTime::parse_from_rfc3339(time_str).ok())
+                        .map(|dt| dt.with_timezone(&Utc));
+                    (v.number, created_at)
+                });
+                collapse_platform_variants(entries)
             }
-            _ => {
-                // Fallback to just latest version with its date
-                let mut dates = HashMap::new();
-                if let Some(time_str) = &gem.version_created_at
-                    && let Ok(dt) = DateTime::parse_from_rfc3339(time_str)
-                {
-                    dates.insert(gem.version.clone(), dt.with_timezone(&Utc));
+            Ok(response) if is_missing_or_unsupported(response.status()) => {
+                // Some mirrors don't implement /versions - fall back to the
+                // compact dependencies endpoint to reconstruct the version set.
+                match self.fetch_versions_from_dependencies(package_name).await {
+                    Some(numbers) => {
+                        collapse_platform_variants(numbers.into_iter().map(|number| (number, None)))
+                    }
+                    None => single_version_fallback(&gem),
                 }
-                (vec![gem.version.clone()], dates)
             }
+            _ => single_version_fallback(&gem),
         };
 
         // Use the latest version from gem info
         let latest_stable = Some(gem.version.clone());
 
+        // A prerelease never shows up as `gem.version` (the latest stable
+        // release), so look for one in the collapsed version list instead.
+        let latest_prerelease = versions.iter().find(|v| is_prerelease_ruby(v)).cloned();
+
         // Get license (first one if multiple)
         let license = gem.licenses.and_then(|l| l.into_iter().next());
 
@@ -204,7 +335,7 @@ impl Registry for RubyGemsRegistry {
 
         Ok(VersionInfo {
             latest: latest_stable,
-            latest_prerelease: None,
+            latest_prerelease,
             versions,
             description: gem.info,
             homepage: gem.homepage_uri.or(gem.project_uri),
@@ -215,12 +346,24 @@ impl Registry for RubyGemsRegistry {
             yanked: false,
             yanked_versions: vec![], // Not applicable to RubyGems
             release_dates,
+            platforms,
+            dependency_groups: HashMap::new(),
+            deprecation_messages: HashMap::new(),
+            requires_python: HashMap::new(),
+            rust_version: HashMap::new(),
+            yanked_reasons: HashMap::new(),
+            latest_breaking_major: None,
+            latest_compatible: None,
+            alternative_version: None,
+            version_metadata: HashMap::new(),
         })
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn test_rubygems_url_format() {
         let base_url = "https://rubygems.org/api/v1";
@@ -228,4 +371,80 @@ mod tests {
         let url = format!("{}/gems/{}.json", base_url, name);
         assert_eq!(url, "https://rubygems.org/api/v1/gems/rails.json");
     }
+
+    #[test]
+    fn test_is_missing_or_unsupported() {
+        assert!(is_missing_or_unsupported(reqwest::StatusCode::NOT_FOUND));
+        assert!(is_missing_or_unsupported(reqwest::StatusCode::BAD_REQUEST));
+        assert!(!is_missing_or_unsupported(reqwest::StatusCode::OK));
+        assert!(!is_missing_or_unsupported(
+            reqwest::StatusCode::INTERNAL_SERVER_ERROR
+        ));
+    }
+
+    #[test]
+    fn test_single_version_fallback() {
+        let gem = GemResponse {
+            _name: "rails".to_string(),
+            version: "7.0.0".to_string(),
+            info: None,
+            licenses: None,
+            homepage_uri: None,
+            source_code_uri: None,
+            project_uri: None,
+            version_created_at: Some("2023-01-15T10:30:00.000Z".to_string()),
+        };
+
+        let (versions, dates, platforms) = single_version_fallback(&gem);
+
+        assert_eq!(versions, vec!["7.0.0".to_string()]);
+        assert!(dates.contains_key("7.0.0"));
+        assert!(platforms.is_empty());
+    }
+
+    #[test]
+    fn test_split_platform_suffix() {
+        assert_eq!(split_platform_suffix("1.16.0"), ("1.16.0", None));
+        assert_eq!(
+            split_platform_suffix("1.16.0-x86_64-linux"),
+            ("1.16.0", Some("x86_64-linux"))
+        );
+        assert_eq!(split_platform_suffix("1.16.0-java"), ("1.16.0", Some("java")));
+    }
+
+    #[test]
+    fn test_normalize_ruby_prerelease() {
+        assert_eq!(normalize_ruby_prerelease("2.0.0.pre.1"), "2.0.0-pre.1");
+        assert_eq!(normalize_ruby_prerelease("1.0.0.alpha"), "1.0.0-alpha");
+        assert_eq!(normalize_ruby_prerelease("1.0.0.rc.2"), "1.0.0-rc.2");
+        // No prerelease marker: left untouched.
+        assert_eq!(normalize_ruby_prerelease("1.0.0"), "1.0.0");
+    }
+
+    #[test]
+    fn test_collapse_platform_variants_merges_platform_builds() {
+        let entries = vec![
+            ("1.16.0".to_string(), None),
+            ("1.16.0-x86_64-linux".to_string(), None),
+            ("1.16.0-java".to_string(), None),
+        ];
+
+        let (versions, _dates, platforms) = collapse_platform_variants(entries);
+
+        assert_eq!(versions, vec!["1.16.0".to_string()]);
+        let mut variants = platforms["1.16.0"].clone();
+        variants.sort();
+        assert_eq!(variants, vec!["java".to_string(), "x86_64-linux".to_string()]);
+    }
+
+    #[test]
+    fn test_collapse_platform_variants_normalizes_prerelease() {
+        let entries = vec![("2.0.0.pre.1-java".to_string(), None)];
+
+        let (versions, _dates, platforms) = collapse_platform_variants(entries);
+
+        assert_eq!(versions, vec!["2.0.0-pre.1".to_string()]);
+        assert_eq!(platforms["2.0.0-pre.1"], vec!["java".to_string()]);
+        assert!(semver::Version::parse(&versions[0]).is_ok());
+    }
 }
@@ -0,0 +1,1086 @@
+//! Per-ecosystem version ordering and requirement matching.
+//!
+//! Different ecosystems order and match versions differently: Cargo uses
+//! strict SemVer (`1.0.0-rc < 1.0.0`, caret/tilde requirements), while NuGet
+//! allows a 4th version component (defaulting to zero when absent), treats
+//! prerelease labels case-insensitively, and expresses requirements as
+//! bracketed ranges (`[1.0,2.0)`) rather than SemVer operators. npm's ranges
+//! add `||`-combined alternatives, hyphen ranges and `x`/`*` wildcards on top
+//! of SemVer; Python's PEP 440 specifiers (`==`, `>=`, `~=`, ...) are
+//! comma-separated AND clauses; Composer's `~` differs from Cargo's; and
+//! Ruby's pessimistic `~>` operator has no SemVer equivalent. [`VersionScheme`]
+//! lets each parser advertise which rules apply to its ecosystem (see
+//! [`crate::parsers::Parser::version_scheme`]) instead of the registry/cache
+//! layer assuming SemVer - or raw string comparison - everywhere.
+
+use std::cmp::Ordering;
+
+use super::pep440::SpecifierSet;
+use super::version_utils::{Pep440Precedence, PrecedenceOrd};
+
+/// Per-ecosystem version ordering and requirement matching.
+pub trait VersionScheme: Send + Sync {
+    /// Whether `candidate` is a newer version than `current`.
+    fn is_newer(&self, current: &str, candidate: &str) -> bool;
+
+    /// Whether `candidate` satisfies a declared `requirement` (e.g. Cargo's
+    /// `^1.2`, or a NuGet bracketed range).
+    fn satisfies(&self, requirement: &str, candidate: &str) -> bool;
+
+    /// The highest version in `candidates` that satisfies `requirement`, if any.
+    fn latest_satisfying<'a>(
+        &self,
+        requirement: &str,
+        candidates: &'a [String],
+    ) -> Option<&'a str> {
+        candidates
+            .iter()
+            .filter(|candidate| self.satisfies(requirement, candidate))
+            .max_by(|a, b| {
+                if self.is_newer(a, b) {
+                    Ordering::Greater
+                } else if self.is_newer(b, a) {
+                    Ordering::Less
+                } else {
+                    Ordering::Equal
+                }
+            })
+            .map(|s| s.as_str())
+    }
+
+    /// The lowest version in `candidates` that satisfies `requirement`, if
+    /// any - the minimal-versions counterpart to [`Self::latest_satisfying`],
+    /// for callers that prefer reproducibility over freshness. A candidate
+    /// that's a pre-release of `requirement` is still excluded unless
+    /// `requirement` itself opts into that pre-release, the same rule
+    /// `satisfies` already enforces.
+    fn earliest_satisfying<'a>(
+        &self,
+        requirement: &str,
+        candidates: &'a [String],
+    ) -> Option<&'a str> {
+        candidates
+            .iter()
+            .filter(|candidate| self.satisfies(requirement, candidate))
+            .min_by(|a, b| {
+                if self.is_newer(a, b) {
+                    Ordering::Greater
+                } else if self.is_newer(b, a) {
+                    Ordering::Less
+                } else {
+                    Ordering::Equal
+                }
+            })
+            .map(|s| s.as_str())
+    }
+}
+
+/// SemVer ordering, as used by Cargo (and close enough for the other
+/// SemVer-like ecosystems this server supports).
+pub struct SemverScheme;
+
+impl SemverScheme {
+    fn parse(version: &str) -> Option<semver::Version> {
+        semver::Version::parse(&normalize_version(version)).ok()
+    }
+}
+
+impl VersionScheme for SemverScheme {
+    fn is_newer(&self, current: &str, candidate: &str) -> bool {
+        match (Self::parse(current), Self::parse(candidate)) {
+            (Some(current), Some(candidate)) => candidate > current,
+            // Can't compare as SemVer - treat any textual difference as an update.
+            _ => normalize_version(current) != normalize_version(candidate),
+        }
+    }
+
+    fn satisfies(&self, requirement: &str, candidate: &str) -> bool {
+        let Ok(req) = semver::VersionReq::parse(requirement.trim()) else {
+            // Fail open: a requirement we can't parse as SemVer shouldn't
+            // block an update we otherwise can't classify.
+            return true;
+        };
+        let Some(version) = Self::parse(candidate) else {
+            return true;
+        };
+        req.matches(&version)
+    }
+}
+
+/// Go module versions (`v1.2.3`, `v2.0.0+incompatible`) are SemVer with a
+/// mandatory leading `v` that [`SemverScheme`]'s parsing doesn't strip, so
+/// reuse its ordering logic against the unprefixed version instead of
+/// duplicating it.
+pub struct GoScheme;
+
+impl VersionScheme for GoScheme {
+    fn is_newer(&self, current: &str, candidate: &str) -> bool {
+        SemverScheme.is_newer(strip_go_prefix(current), strip_go_prefix(candidate))
+    }
+
+    fn satisfies(&self, requirement: &str, candidate: &str) -> bool {
+        // `go.mod` requirements are exact pins, not ranges - a candidate
+        // "satisfies" only by being that exact version.
+        strip_go_prefix(requirement) == strip_go_prefix(candidate)
+    }
+}
+
+fn strip_go_prefix(version: &str) -> &str {
+    version.strip_prefix(['v', 'V']).unwrap_or(version)
+}
+
+/// Normalize a version/requirement string down to the concrete version it
+/// anchors on, for ordinal comparisons (`is_newer`, cooldown candidate
+/// filtering, locked-version checks). Parses through `semver::VersionReq` so
+/// wildcards (`1.*`, `1.2.x`) and multi-comparator ranges (`>=1.2, <1.5`) are
+/// handled correctly by [`requirement_anchor`] instead of being guessed at
+/// with string surgery; only input neither `VersionReq` nor this fallback can
+/// make sense of (a bare `major`/`major.minor`) is padded out by hand.
+pub(crate) fn normalize_version(version: &str) -> String {
+    let version = version.trim();
+
+    if semver::Version::parse(version).is_ok() {
+        return version.to_string();
+    }
+
+    if let Some(anchor) = requirement_anchor(version) {
+        return anchor;
+    }
+
+    // Ensure we have at least major.minor.patch
+    let parts: Vec<&str> = version.split('.').collect();
+    match parts.len() {
+        1 => format!("{}.0.0", parts[0]),
+        2 => format!("{}.{}.0", parts[0], parts[1]),
+        _ => version.to_string(),
+    }
+}
+
+/// The lowest version a requirement's comparators anchor on. Lower-bound
+/// comparators (a bare version, `=`, `^`, `~`, `>`, `>=`, or a wildcard) are
+/// preferred and returned as soon as one is found; an upper-bound-only
+/// comparator (`<`, `<=`) is kept only as a fallback if the requirement has
+/// nothing else, since having some anchor beats having none. This is what
+/// lets `normalize_version` resolve compound ranges regardless of comparator
+/// order (`<1.5, >=1.2` anchors on `1.2.0`, not the first-listed `1.5.0`) and
+/// wildcards (`1.2.x` anchors on `1.2.0`) instead of the naive
+/// strip-operator-and-take-first-part approach this replaced.
+fn requirement_anchor(requirement: &str) -> Option<String> {
+    let req = semver::VersionReq::parse(requirement).ok()?;
+    let mut fallback = None;
+    for comparator in &req.comparators {
+        let version = format!(
+            "{}.{}.{}",
+            comparator.major,
+            comparator.minor.unwrap_or(0),
+            comparator.patch.unwrap_or(0)
+        );
+        match comparator.op {
+            semver::Op::Less | semver::Op::LessEq => fallback.get_or_insert(version),
+            _ => return Some(version),
+        };
+    }
+    fallback
+}
+
+/// NuGet version ordering: up to four numeric components (a missing one
+/// defaults to zero) and a case-insensitive, hyphen-separated prerelease
+/// label that sorts below the release it precedes.
+pub(crate) fn parse_nuget(version: &str) -> Option<([u64; 4], Option<String>)> {
+    let version = version.trim();
+    let (numeric, prerelease) = match version.split_once('-') {
+        Some((numeric, label)) => (numeric, Some(label.to_lowercase())),
+        None => (version, None),
+    };
+
+    let mut components = [0u64; 4];
+    let mut seen = false;
+    for (i, part) in numeric.split('.').take(4).enumerate() {
+        components[i] = part.parse().ok()?;
+        seen = true;
+    }
+    seen.then_some((components, prerelease))
+}
+
+pub(crate) fn cmp_nuget(a: &str, b: &str) -> Option<Ordering> {
+    let (a_components, a_pre) = parse_nuget(a)?;
+    let (b_components, b_pre) = parse_nuget(b)?;
+
+    match a_components.cmp(&b_components) {
+        Ordering::Equal => {}
+        order => return Some(order),
+    }
+
+    Some(match (&a_pre, &b_pre) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater, // a release outranks any prerelease
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => a.cmp(b),
+    })
+}
+
+/// A NuGet version range, e.g. `[1.0,2.0)` (inclusive min, exclusive max) or
+/// `[1.0,)` (minimum only). Either bound may be omitted.
+pub(crate) struct NuGetRange {
+    pub(crate) min: Option<String>,
+    pub(crate) min_inclusive: bool,
+    pub(crate) max: Option<String>,
+    pub(crate) max_inclusive: bool,
+}
+
+impl NuGetRange {
+    pub(crate) fn parse(requirement: &str) -> Option<Self> {
+        let requirement = requirement.trim();
+        let min_inclusive = requirement.starts_with('[');
+        if !min_inclusive && !requirement.starts_with('(') {
+            return None;
+        }
+        let max_inclusive = requirement.ends_with(']');
+        if !max_inclusive && !requirement.ends_with(')') {
+            return None;
+        }
+
+        let inner = &requirement[1..requirement.len() - 1];
+        let mut parts = inner.splitn(2, ',');
+        let min = parts.next().unwrap_or("").trim();
+        let max = parts.next().unwrap_or("").trim();
+
+        Some(Self {
+            min: (!min.is_empty()).then(|| min.to_string()),
+            min_inclusive,
+            max: (!max.is_empty()).then(|| max.to_string()),
+            max_inclusive,
+        })
+    }
+
+    fn contains(&self, candidate: &str) -> bool {
+        if let Some(min) = &self.min {
+            let within = match cmp_nuget(candidate, min) {
+                Some(Ordering::Greater) => true,
+                Some(Ordering::Equal) => self.min_inclusive,
+                _ => false,
+            };
+            if !within {
+                return false;
+            }
+        }
+        if let Some(max) = &self.max {
+            let within = match cmp_nuget(candidate, max) {
+                Some(Ordering::Less) => true,
+                Some(Ordering::Equal) => self.max_inclusive,
+                _ => false,
+            };
+            if !within {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// NuGet's version ordering and range semantics, used by PackageReference
+/// and Central Package Management versions.
+pub struct NuGetScheme;
+
+impl VersionScheme for NuGetScheme {
+    fn is_newer(&self, current: &str, candidate: &str) -> bool {
+        match cmp_nuget(current, candidate) {
+            Some(order) => order == Ordering::Less,
+            None => current.trim() != candidate.trim(),
+        }
+    }
+
+    fn satisfies(&self, requirement: &str, candidate: &str) -> bool {
+        if let Some(range) = NuGetRange::parse(requirement) {
+            return range.contains(candidate);
+        }
+        // A bare version acts as a minimum-inclusive bound (NuGet's
+        // "floating" shorthand), rather than an exact pin, matching how
+        // `PackageReference` entries are actually resolved.
+        matches!(
+            cmp_nuget(candidate, requirement),
+            Some(Ordering::Greater) | Some(Ordering::Equal) | None
+        )
+    }
+}
+
+/// Parse the leading dot-separated numeric components of a version string,
+/// stopping at the first component that isn't a bare integer (a wildcard
+/// like `x`/`X`/`*`, a prerelease suffix, or simply absent). Used to read
+/// the explicit prefix out of partial/wildcard version specifiers (`1.2`,
+/// `1.2.x`) across npm, Composer and Ruby.
+fn parse_numeric_prefix(s: &str) -> Vec<u64> {
+    let mut out = Vec::new();
+    for part in s.split('.').take(3) {
+        match part.parse::<u64>() {
+            Ok(n) => out.push(n),
+            Err(_) => break,
+        }
+    }
+    out
+}
+
+/// Pad an explicit-prefix component list out to `major.minor.patch`,
+/// defaulting missing trailing components to zero.
+fn pad_version(nums: &[u64]) -> String {
+    let mut padded = [0u64; 3];
+    for (slot, n) in padded.iter_mut().zip(nums.iter()) {
+        *slot = *n;
+    }
+    format!("{}.{}.{}", padded[0], padded[1], padded[2])
+}
+
+/// Whether `current` falls within the range implied by an explicit-prefix
+/// component list: unconstrained components are free, matching how a
+/// partial version (`1.2`, bare `1`) behaves as a range in npm, Composer
+/// and Ruby alike.
+fn partial_matches(nums: &[u64], current: (u64, u64, u64)) -> bool {
+    match nums {
+        [] => true,
+        [major] => current.0 == *major,
+        [major, minor] => current.0 == *major && current.1 == *minor,
+        [major, minor, patch] => current == (*major, *minor, *patch),
+        _ => true,
+    }
+}
+
+/// Strip a leading `>=`/`<=`/`^`/`~`/`>`/`<`/`=` operator shared by npm and
+/// Composer's requirement syntax, returning `("", token)` when none matches.
+fn split_generic_version_operator(token: &str) -> (&str, &str) {
+    for op in [">=", "<=", "^", "~", ">", "<", "="] {
+        if let Some(rest) = token.strip_prefix(op) {
+            return (op, rest.trim());
+        }
+    }
+    ("", token)
+}
+
+/// npm's semver range syntax: `^`/`~` caret-tilde ranges (delegated to the
+/// `semver` crate for fully-specified versions), `x`/`*`/partial-version
+/// wildcards, hyphen ranges (`1.2.3 - 2.3.4`), and `||`-combined
+/// alternatives. Unlike Cargo, a bare fully-specified version with no
+/// operator is an exact pin, not an implicit caret range.
+pub struct NpmScheme;
+
+impl VersionScheme for NpmScheme {
+    fn is_newer(&self, current: &str, candidate: &str) -> bool {
+        SemverScheme.is_newer(current, candidate)
+    }
+
+    fn satisfies(&self, requirement: &str, candidate: &str) -> bool {
+        let Some(version) = semver::Version::parse(&normalize_version(candidate)).ok() else {
+            // Can't parse the candidate as SemVer - fail open like SemverScheme.
+            return true;
+        };
+        requirement.split("||").any(|clause| {
+            let clause = clause.trim();
+            match clause.split_once(" - ") {
+                Some((lo, hi)) => npm_hyphen_matches(lo.trim(), hi.trim(), &version),
+                None => clause
+                    .split_whitespace()
+                    .all(|token| npm_token_matches(token, &version)),
+            }
+        })
+    }
+}
+
+fn npm_token_matches(token: &str, candidate: &semver::Version) -> bool {
+    let token = token.trim();
+    if token.is_empty() {
+        return true;
+    }
+
+    let (op, rest) = split_generic_version_operator(token);
+    let nums = parse_numeric_prefix(rest);
+    let current = (candidate.major, candidate.minor, candidate.patch);
+
+    if nums.len() == 3 && rest.split('.').count() == 3 {
+        // Fully specified version - delegate to the real semver crate for
+        // correct ^/~ caret-tilde precedence rather than re-deriving it.
+        let translated = match op {
+            "" => format!("={}", rest),
+            other => format!("{}{}", other, rest),
+        };
+        return semver::VersionReq::parse(&translated)
+            .map(|req| req.matches(candidate))
+            .unwrap_or(true);
+    }
+
+    // Partial or wildcard version (`1.2`, `1.2.x`, `*`) - a missing or
+    // wildcard trailing component ranges over that component regardless of
+    // any ^/~ prefix.
+    partial_matches(&nums, current)
+}
+
+fn npm_hyphen_matches(lo: &str, hi: &str, candidate: &semver::Version) -> bool {
+    let lo_nums = parse_numeric_prefix(lo);
+    let hi_nums = parse_numeric_prefix(hi);
+    let current = (candidate.major, candidate.minor, candidate.patch);
+
+    let above_lo = match lo_nums.as_slice() {
+        [] => true,
+        [major] => current.0 >= *major,
+        [major, minor] => current >= (*major, *minor, 0),
+        [major, minor, patch] => current >= (*major, *minor, *patch),
+        _ => true,
+    };
+    if !above_lo {
+        return false;
+    }
+
+    match hi_nums.as_slice() {
+        [] => true,
+        [major] => current.0 <= *major,
+        // A partial upper bound covers every version under that prefix.
+        [major, minor] => current.0 < *major || (current.0 == *major && current.1 <= *minor),
+        [major, minor, patch] => current <= (*major, *minor, *patch),
+        _ => true,
+    }
+}
+
+/// Composer's requirement syntax: `^`/`~` ranges (Composer's `~` bumps at
+/// the rightmost given component, unlike Cargo's tilde), comparator
+/// operators, `*` wildcards, hyphen ranges (`1.0 - 2.0`), and
+/// `||`-combined alternatives.
+pub struct ComposerScheme;
+
+impl VersionScheme for ComposerScheme {
+    fn is_newer(&self, current: &str, candidate: &str) -> bool {
+        SemverScheme.is_newer(current, candidate)
+    }
+
+    fn satisfies(&self, requirement: &str, candidate: &str) -> bool {
+        let Some(version) = semver::Version::parse(&normalize_version(candidate)).ok() else {
+            return true;
+        };
+        requirement
+            .split("||")
+            .any(|clause| composer_clause_matches(clause, &version))
+    }
+}
+
+/// A single (comma/whitespace-joined, AND'd) Composer constraint clause - one
+/// side of a top-level `||` alternative. Handled as a hyphen range
+/// (`1.0 - 2.0`) as a whole when present, since splitting that on whitespace
+/// like every other clause would tear the two bounds apart.
+fn composer_clause_matches(clause: &str, candidate: &semver::Version) -> bool {
+    if let Some((lo, hi)) = clause.split_once(" - ") {
+        return composer_hyphen_range_matches(lo.trim(), hi.trim(), candidate);
+    }
+    clause
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .all(|token| composer_token_matches(token, candidate))
+}
+
+/// Composer's inclusive hyphen range: `1.0 - 2.0` allows `>=1.0.0`, and - like
+/// npm - treats a partial upper bound as a wildcard over its missing
+/// components rather than a precise ceiling, so the upper bound only
+/// excludes versions past the next unit beyond whatever was written
+/// (`1.0 - 2.0` excludes `2.1.0`, but `1.0 - 2.0.0` excludes only `>2.0.0`).
+fn composer_hyphen_range_matches(lo: &str, hi: &str, candidate: &semver::Version) -> bool {
+    let current = (candidate.major, candidate.minor, candidate.patch);
+    if current < nums_floor(&parse_numeric_prefix(lo)) {
+        return false;
+    }
+
+    let hi_nums = parse_numeric_prefix(hi);
+    match hi_nums.as_slice() {
+        [major, minor, patch] => current <= (*major, *minor, *patch),
+        [major, minor] => current < (*major, minor + 1, 0),
+        [major] => current < (major + 1, 0, 0),
+        _ => true,
+    }
+}
+
+/// An explicit-prefix component list as a `(major, minor, patch)` floor,
+/// defaulting missing trailing components to zero.
+fn nums_floor(nums: &[u64]) -> (u64, u64, u64) {
+    (
+        nums.first().copied().unwrap_or(0),
+        nums.get(1).copied().unwrap_or(0),
+        nums.get(2).copied().unwrap_or(0),
+    )
+}
+
+fn composer_token_matches(token: &str, candidate: &semver::Version) -> bool {
+    if token == "*" {
+        return true;
+    }
+
+    let (op, rest) = split_generic_version_operator(token);
+    let nums = parse_numeric_prefix(rest);
+    let current = (candidate.major, candidate.minor, candidate.patch);
+
+    match op {
+        "^" => {
+            let translated = format!("^{}", pad_version(&nums));
+            semver::VersionReq::parse(&translated)
+                .map(|req| req.matches(candidate))
+                .unwrap_or(true)
+        }
+        "~" => composer_tilde_matches(&nums, current),
+        ">=" | "<=" | ">" | "<" => {
+            let translated = format!("{}{}", op, pad_version(&nums));
+            semver::VersionReq::parse(&translated)
+                .map(|req| req.matches(candidate))
+                .unwrap_or(true)
+        }
+        _ => partial_matches(&nums, current),
+    }
+}
+
+/// Composer's `~`: bumps at the rightmost given component (`~1.2` allows
+/// `>=1.2 <2.0`; `~1.2.3` allows `>=1.2.3 <1.3.0`), unlike Cargo's tilde
+/// which always bumps the minor regardless of how many components are given.
+fn composer_tilde_matches(nums: &[u64], current: (u64, u64, u64)) -> bool {
+    match nums {
+        [] => true,
+        [major] => current.0 == *major,
+        [major, minor] => current.0 == *major && current >= (*major, *minor, 0),
+        [major, minor, patch] => {
+            current.0 == *major && current.1 == *minor && current >= (*major, *minor, *patch)
+        }
+        _ => true,
+    }
+}
+
+/// Ruby's Gemfile/gemspec requirement syntax: the pessimistic `~>` operator
+/// (bumps at the rightmost given component, like Composer's `~`), standard
+/// comparators, and comma-separated AND clauses (`">= 1.2, < 2.0"`).
+pub struct RubyScheme;
+
+impl VersionScheme for RubyScheme {
+    fn is_newer(&self, current: &str, candidate: &str) -> bool {
+        SemverScheme.is_newer(current, candidate)
+    }
+
+    fn satisfies(&self, requirement: &str, candidate: &str) -> bool {
+        let Some(version) = semver::Version::parse(&normalize_version(candidate)).ok() else {
+            return true;
+        };
+        requirement
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .all(|clause| ruby_clause_matches(clause, &version))
+    }
+}
+
+fn ruby_clause_matches(clause: &str, candidate: &semver::Version) -> bool {
+    let (op, rest) = split_ruby_operator(clause);
+    let nums = parse_numeric_prefix(rest);
+    let current = (candidate.major, candidate.minor, candidate.patch);
+
+    match op {
+        "~>" => composer_tilde_matches(&nums, current),
+        ">=" | "<=" | ">" | "<" => {
+            let translated = format!("{}{}", op, pad_version(&nums));
+            semver::VersionReq::parse(&translated)
+                .map(|req| req.matches(candidate))
+                .unwrap_or(true)
+        }
+        "!=" => nums.len() != 3 || current != (nums[0], nums[1], nums[2]),
+        _ => partial_matches(&nums, current),
+    }
+}
+
+fn split_ruby_operator(clause: &str) -> (&str, &str) {
+    for op in ["~>", ">=", "<=", "!=", ">", "<", "="] {
+        if let Some(rest) = clause.strip_prefix(op) {
+            return (op, rest.trim());
+        }
+    }
+    ("", clause.trim())
+}
+
+/// Python's PEP 440 version specifiers: `==`/`!=`/`<=`/`>=`/`<`/`>`/`~=`/`===`,
+/// comma-separated AND clauses (PEP 440 has no OR), and `.*` trailing
+/// wildcards on `==`/`!=`. Delegates the actual clause matching to
+/// [`super::pep440::SpecifierSet`], keeping a single implementation of PEP 440
+/// constraint evaluation; ordering is delegated to [`Pep440Precedence`].
+pub struct PythonScheme;
+
+impl VersionScheme for PythonScheme {
+    fn is_newer(&self, current: &str, candidate: &str) -> bool {
+        Pep440Precedence.compare(candidate, current) == Ordering::Greater
+    }
+
+    fn satisfies(&self, requirement: &str, candidate: &str) -> bool {
+        SpecifierSet::parse(requirement).contains(candidate)
+    }
+}
+
+/// Whether `toolchain` (e.g. `"1.75.0"`) satisfies a crate's declared
+/// `rust-version` (Cargo's MSRV field, e.g. `"1.70"`) - a bare minimum
+/// version with no operator, compared as a partial version the way cargo's
+/// own MSRV resolution does (a missing minor/patch component is treated as
+/// zero, so `"1.70"` means `"1.70.0"`).
+pub fn satisfies_rust_version(rust_version: &str, toolchain: &str) -> bool {
+    fn parse(version: &str) -> Option<(u64, u64, u64)> {
+        let mut components = version.trim().split('.');
+        let major = components.next()?.parse().ok()?;
+        let minor = components.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+        let patch = components.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+        Some((major, minor, patch))
+    }
+
+    match (parse(rust_version), parse(toolchain)) {
+        (Some(min), Some(actual)) => actual >= min,
+        // Fail open: an unparsable MSRV or toolchain shouldn't block a
+        // version we can't classify either way.
+        _ => true,
+    }
+}
+
+/// Coarse SemVer bump classification between a declared requirement string
+/// (e.g. `"^1.2.0"`, used as-is since this codebase stores the requirement
+/// and the "current version" as the same string) and a candidate version.
+/// `None` when either side doesn't parse as SemVer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BumpClass {
+    Patch,
+    Minor,
+    Major,
+}
+
+pub fn bump_class(current: &str, candidate: &str) -> Option<BumpClass> {
+    let current = semver::Version::parse(&normalize_version(current)).ok()?;
+    let candidate = semver::Version::parse(&normalize_version(candidate)).ok()?;
+    Some(if candidate.major != current.major {
+        BumpClass::Major
+    } else if candidate.minor != current.minor {
+        BumpClass::Minor
+    } else {
+        BumpClass::Patch
+    })
+}
+
+/// Result of resolving a declared requirement against a registry's known
+/// versions: the highest version that's still compatible with the
+/// requirement (safe to bump in place) vs. the highest version overall
+/// (which may require editing the requirement itself), plus how big a jump
+/// the latter would be.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpdateResolution {
+    /// Highest known version that still satisfies the declared requirement.
+    pub compatible_latest: Option<String>,
+    /// Highest known version overall, compatible with the requirement or not.
+    pub latest_overall: Option<String>,
+    /// SemVer bump class between the requirement and `latest_overall`, when
+    /// both sides parse as SemVer.
+    pub bump: Option<BumpClass>,
+}
+
+/// Resolve a declared requirement against a registry's known versions under
+/// `scheme`, distinguishing "the highest version still compatible with the
+/// requirement" from "the highest version overall" so callers (e.g. inlay
+/// hints, code actions) can tell an in-range bump from one that needs the
+/// requirement itself edited.
+pub fn resolve_update(
+    req: &str,
+    info: &super::VersionInfo,
+    scheme: &dyn VersionScheme,
+) -> UpdateResolution {
+    let compatible_latest = scheme
+        .latest_satisfying(req, &info.versions)
+        .map(str::to_string);
+
+    let latest_overall = info.latest.clone().or_else(|| {
+        info.versions
+            .iter()
+            .max_by(|a, b| {
+                if scheme.is_newer(a, b) {
+                    Ordering::Greater
+                } else if scheme.is_newer(b, a) {
+                    Ordering::Less
+                } else {
+                    Ordering::Equal
+                }
+            })
+            .cloned()
+    });
+
+    let bump = latest_overall
+        .as_deref()
+        .and_then(|latest| bump_class(req, latest));
+
+    UpdateResolution {
+        compatible_latest,
+        latest_overall,
+        bump,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_version() {
+        assert_eq!(normalize_version("1.0.0"), "1.0.0");
+        assert_eq!(normalize_version("^1.0"), "1.0.0");
+        assert_eq!(normalize_version("~1.0.0"), "1.0.0");
+        assert_eq!(normalize_version(">=1.0, <2.0"), "1.0.0");
+        assert_eq!(normalize_version("1"), "1.0.0");
+        assert_eq!(normalize_version("1.2"), "1.2.0");
+    }
+
+    #[test]
+    fn test_normalize_version_wildcards() {
+        assert_eq!(normalize_version("1.2.x"), "1.2.0");
+        assert_eq!(normalize_version("1.2.*"), "1.2.0");
+        assert_eq!(normalize_version("1.*"), "1.0.0");
+    }
+
+    #[test]
+    fn test_normalize_version_compound_range_anchors_on_lower_bound() {
+        // Reversed comparator order - the naive "take the first comma part"
+        // approach this replaced would have anchored on 1.5.0 instead.
+        assert_eq!(normalize_version("<1.5, >=1.2"), "1.2.0");
+        assert_eq!(normalize_version(">=1.2, <1.5"), "1.2.0");
+    }
+
+    #[test]
+    fn test_normalize_version_upper_bound_only_falls_back() {
+        assert_eq!(normalize_version("<2.0"), "2.0.0");
+    }
+
+    #[test]
+    fn test_semver_satisfies_wildcard_and_compound_range() {
+        let scheme = SemverScheme;
+        assert!(scheme.satisfies("1.2.x", "1.2.9"));
+        assert!(!scheme.satisfies("1.2.x", "1.3.0"));
+        assert!(scheme.satisfies(">=1.2, <1.5", "1.4.9"));
+        assert!(!scheme.satisfies(">=1.2, <1.5", "1.5.0"));
+    }
+
+    #[test]
+    fn test_semver_is_newer() {
+        let scheme = SemverScheme;
+        assert!(scheme.is_newer("1.0.0", "1.1.0"));
+        assert!(!scheme.is_newer("1.1.0", "1.0.0"));
+        assert!(!scheme.is_newer("1.0.0", "1.0.0"));
+    }
+
+    #[test]
+    fn test_semver_prerelease_sorts_below_release() {
+        let scheme = SemverScheme;
+        assert!(scheme.is_newer("1.0.0-rc", "1.0.0"));
+        assert!(!scheme.is_newer("1.0.0", "1.0.0-rc"));
+    }
+
+    #[test]
+    fn test_go_scheme_strips_v_prefix_for_ordering() {
+        let scheme = GoScheme;
+        assert!(scheme.is_newer("v1.0.0", "v1.1.0"));
+        assert!(!scheme.is_newer("v1.1.0", "v1.0.0"));
+        assert!(scheme.is_newer("1.0.0", "v1.1.0"));
+    }
+
+    #[test]
+    fn test_go_scheme_satisfies_only_exact_pin() {
+        let scheme = GoScheme;
+        assert!(scheme.satisfies("v1.2.3", "v1.2.3"));
+        assert!(!scheme.satisfies("v1.2.3", "v1.2.4"));
+    }
+
+    #[test]
+    fn test_semver_caret_requirement() {
+        let scheme = SemverScheme;
+        assert!(scheme.satisfies("^1.2", "1.5.0"));
+        assert!(!scheme.satisfies("^1.2", "2.0.0"));
+        // ^0.2 is narrower: only patch bumps are compatible pre-1.0.
+        assert!(scheme.satisfies("^0.2", "0.2.5"));
+        assert!(!scheme.satisfies("^0.2", "0.3.0"));
+    }
+
+    #[test]
+    fn test_semver_partial_version_requirement() {
+        // A bare `1`/`1.2` requirement has no explicit operator, so the
+        // `semver` crate treats the missing components as caret-style
+        // wildcards - `"1"` means `>=1.0.0, <2.0.0` and `"1.2"` means
+        // `>=1.2.0, <1.3.0` - matching how Cargo itself resolves manifests
+        // like `anyhow = "1"` or `thiserror = "2"`.
+        let scheme = SemverScheme;
+        assert!(scheme.satisfies("1", "1.9.9"));
+        assert!(!scheme.satisfies("1", "2.0.0"));
+        assert!(scheme.satisfies("2", "2.0.0"));
+        assert!(scheme.satisfies("1.2", "1.2.9"));
+        assert!(!scheme.satisfies("1.2", "1.3.0"));
+    }
+
+    #[test]
+    fn test_semver_latest_satisfying() {
+        let scheme = SemverScheme;
+        let candidates = vec![
+            "1.0.0".to_string(),
+            "1.5.0".to_string(),
+            "2.0.0".to_string(),
+            "1.6.0-rc".to_string(),
+        ];
+        assert_eq!(
+            scheme.latest_satisfying("^1.0", &candidates),
+            Some("1.5.0")
+        );
+    }
+
+    #[test]
+    fn test_semver_earliest_satisfying() {
+        let scheme = SemverScheme;
+        let candidates = vec![
+            "1.0.0".to_string(),
+            "1.5.0".to_string(),
+            "2.0.0".to_string(),
+        ];
+        assert_eq!(
+            scheme.earliest_satisfying("^1.0", &candidates),
+            Some("1.0.0")
+        );
+        // A pre-release in the numeric range is still excluded unless the
+        // requirement itself opts into it, same as `latest_satisfying`.
+        assert_eq!(
+            scheme.earliest_satisfying("^1.0", &["1.2.0-rc".to_string(), "1.5.0".to_string()]),
+            Some("1.5.0")
+        );
+    }
+
+    #[test]
+    fn test_satisfies_rust_version_pads_missing_components() {
+        assert!(satisfies_rust_version("1.70", "1.70.0"));
+        assert!(satisfies_rust_version("1.70", "1.75.0"));
+        assert!(!satisfies_rust_version("1.70", "1.69.0"));
+    }
+
+    #[test]
+    fn test_satisfies_rust_version_compares_patch() {
+        assert!(satisfies_rust_version("1.70.1", "1.70.1"));
+        assert!(!satisfies_rust_version("1.70.1", "1.70.0"));
+    }
+
+    #[test]
+    fn test_satisfies_rust_version_fails_open_on_unparsable_input() {
+        assert!(satisfies_rust_version("not-a-version", "1.70.0"));
+    }
+
+    #[test]
+    fn test_nuget_is_newer() {
+        let scheme = NuGetScheme;
+        assert!(scheme.is_newer("1.0.0", "1.0.1"));
+        assert!(!scheme.is_newer("1.0.1", "1.0.0"));
+    }
+
+    #[test]
+    fn test_nuget_missing_fourth_component_defaults_to_zero() {
+        let scheme = NuGetScheme;
+        assert!(!scheme.is_newer("1.2.3", "1.2.3.0"));
+        assert!(scheme.is_newer("1.2.3", "1.2.3.1"));
+    }
+
+    #[test]
+    fn test_nuget_prerelease_case_insensitive_and_sorts_below_release() {
+        let scheme = NuGetScheme;
+        assert!(scheme.is_newer("1.0.0-Beta", "1.0.0-beta"));
+        assert!(!scheme.is_newer("1.0.0-beta", "1.0.0-Beta"));
+        assert!(scheme.is_newer("1.0.0-rc", "1.0.0"));
+    }
+
+    #[test]
+    fn test_nuget_bracket_range() {
+        let scheme = NuGetScheme;
+        assert!(scheme.satisfies("[1.0,2.0)", "1.5.0"));
+        assert!(!scheme.satisfies("[1.0,2.0)", "2.0.0"));
+        assert!(scheme.satisfies("[1.0,2.0]", "2.0.0"));
+        assert!(!scheme.satisfies("(1.0,2.0)", "1.0.0"));
+    }
+
+    #[test]
+    fn test_nuget_open_ended_minimum_range() {
+        let scheme = NuGetScheme;
+        assert!(scheme.satisfies("[1.0,)", "5.0.0"));
+        assert!(!scheme.satisfies("[1.0,)", "0.9.0"));
+    }
+
+    #[test]
+    fn test_nuget_bare_version_is_minimum_inclusive() {
+        let scheme = NuGetScheme;
+        assert!(scheme.satisfies("1.0.0", "1.0.0"));
+        assert!(scheme.satisfies("1.0.0", "1.5.0"));
+        assert!(!scheme.satisfies("1.0.0", "0.9.0"));
+    }
+
+    #[test]
+    fn test_npm_exact_pin_is_not_a_caret_range() {
+        let scheme = NpmScheme;
+        assert!(scheme.satisfies("1.2.3", "1.2.3"));
+        assert!(!scheme.satisfies("1.2.3", "1.2.4"));
+    }
+
+    #[test]
+    fn test_npm_caret_and_tilde() {
+        let scheme = NpmScheme;
+        assert!(scheme.satisfies("^1.2.3", "1.9.0"));
+        assert!(!scheme.satisfies("^1.2.3", "2.0.0"));
+        assert!(scheme.satisfies("~1.2.3", "1.2.9"));
+        assert!(!scheme.satisfies("~1.2.3", "1.3.0"));
+    }
+
+    #[test]
+    fn test_npm_partial_and_wildcard_versions() {
+        let scheme = NpmScheme;
+        assert!(scheme.satisfies("1.2.x", "1.2.9"));
+        assert!(!scheme.satisfies("1.2.x", "1.3.0"));
+        assert!(scheme.satisfies("1", "1.9.9"));
+        assert!(scheme.satisfies("*", "9.9.9"));
+    }
+
+    #[test]
+    fn test_npm_hyphen_range() {
+        let scheme = NpmScheme;
+        assert!(scheme.satisfies("1.2.3 - 2.3.4", "2.0.0"));
+        assert!(!scheme.satisfies("1.2.3 - 2.3.4", "2.3.5"));
+        assert!(scheme.satisfies("1.2 - 2.3", "2.3.9"));
+    }
+
+    #[test]
+    fn test_npm_or_combined_ranges() {
+        let scheme = NpmScheme;
+        assert!(scheme.satisfies("^1.0.0 || ^2.0.0", "2.5.0"));
+        assert!(!scheme.satisfies("^1.0.0 || ^2.0.0", "3.0.0"));
+    }
+
+    #[test]
+    fn test_composer_tilde_bumps_rightmost_component() {
+        let scheme = ComposerScheme;
+        assert!(scheme.satisfies("~1.2", "1.9.9"));
+        assert!(!scheme.satisfies("~1.2", "2.0.0"));
+        assert!(scheme.satisfies("~1.2.3", "1.2.9"));
+        assert!(!scheme.satisfies("~1.2.3", "1.3.0"));
+    }
+
+    #[test]
+    fn test_composer_caret_and_wildcard() {
+        let scheme = ComposerScheme;
+        assert!(scheme.satisfies("^1.2.3", "1.9.0"));
+        assert!(!scheme.satisfies("^1.2.3", "2.0.0"));
+        assert!(scheme.satisfies("*", "4.5.6"));
+    }
+
+    #[test]
+    fn test_composer_hyphen_range_partial_upper_bound() {
+        let scheme = ComposerScheme;
+        assert!(scheme.satisfies("1.0 - 2.0", "1.0.0"));
+        assert!(scheme.satisfies("1.0 - 2.0", "2.0.9"));
+        assert!(!scheme.satisfies("1.0 - 2.0", "2.1.0"));
+        assert!(!scheme.satisfies("1.0 - 2.0", "0.9.9"));
+    }
+
+    #[test]
+    fn test_composer_hyphen_range_full_upper_bound() {
+        let scheme = ComposerScheme;
+        assert!(scheme.satisfies("1.0.0 - 2.0.0", "2.0.0"));
+        assert!(!scheme.satisfies("1.0.0 - 2.0.0", "2.0.1"));
+    }
+
+    #[test]
+    fn test_ruby_pessimistic_operator() {
+        let scheme = RubyScheme;
+        assert!(scheme.satisfies("~> 2.2", "2.9.0"));
+        assert!(!scheme.satisfies("~> 2.2", "3.0.0"));
+        assert!(scheme.satisfies("~> 2.2.3", "2.2.9"));
+        assert!(!scheme.satisfies("~> 2.2.3", "2.3.0"));
+    }
+
+    #[test]
+    fn test_ruby_comma_separated_and_clauses() {
+        let scheme = RubyScheme;
+        assert!(scheme.satisfies(">= 1.2, < 2.0", "1.9.0"));
+        assert!(!scheme.satisfies(">= 1.2, < 2.0", "2.0.0"));
+    }
+
+    #[test]
+    fn test_python_comparison_specifiers() {
+        let scheme = PythonScheme;
+        assert!(scheme.satisfies(">=1.4.2", "1.4.5"));
+        assert!(!scheme.satisfies(">=1.4.2", "1.4.1"));
+        assert!(scheme.satisfies(">=1.2, <2.0", "1.9.0"));
+        assert!(!scheme.satisfies(">=1.2, <2.0", "2.0.0"));
+    }
+
+    #[test]
+    fn test_python_compatible_release_operator() {
+        let scheme = PythonScheme;
+        assert!(scheme.satisfies("~=1.4.5", "1.4.9"));
+        assert!(!scheme.satisfies("~=1.4.5", "1.5.0"));
+        assert!(scheme.satisfies("~=2.2", "2.9.0"));
+        assert!(!scheme.satisfies("~=2.2", "3.0.0"));
+    }
+
+    #[test]
+    fn test_python_wildcard_equality() {
+        let scheme = PythonScheme;
+        assert!(scheme.satisfies("==1.0.*", "1.0.5"));
+        assert!(!scheme.satisfies("==1.0.*", "1.1.0"));
+    }
+
+    #[test]
+    fn test_python_arbitrary_equality() {
+        let scheme = PythonScheme;
+        assert!(scheme.satisfies("===1.0.0+local", "1.0.0+local"));
+        assert!(!scheme.satisfies("===1.0.0+local", "1.0.0"));
+    }
+
+    #[test]
+    fn test_python_is_newer_uses_pep440_precedence() {
+        let scheme = PythonScheme;
+        assert!(scheme.is_newer("1.0.0a1", "1.0.0"));
+        assert!(scheme.is_newer("1.0.0", "1.0.0.post1"));
+        assert!(!scheme.is_newer("1.0.0", "1.0.0a1"));
+    }
+
+    #[test]
+    fn test_bump_class() {
+        assert_eq!(bump_class("1.2.3", "2.0.0"), Some(BumpClass::Major));
+        assert_eq!(bump_class("1.2.3", "1.3.0"), Some(BumpClass::Minor));
+        assert_eq!(bump_class("1.2.3", "1.2.4"), Some(BumpClass::Patch));
+        assert_eq!(bump_class("not-a-version", "1.2.4"), None);
+    }
+
+    #[test]
+    fn test_resolve_update_distinguishes_compatible_from_overall_latest() {
+        let info = super::super::VersionInfo {
+            latest: Some("3.0.0".to_string()),
+            versions: vec![
+                "1.0.0".to_string(),
+                "1.5.0".to_string(),
+                "2.0.0".to_string(),
+                "3.0.0".to_string(),
+            ],
+            ..Default::default()
+        };
+        let resolution = resolve_update("^1.0.0", &info, &SemverScheme);
+        assert_eq!(resolution.compatible_latest, Some("1.5.0".to_string()));
+        assert_eq!(resolution.latest_overall, Some("3.0.0".to_string()));
+        assert_eq!(resolution.bump, Some(BumpClass::Major));
+    }
+
+    #[test]
+    fn test_resolve_update_with_bare_partial_requirement() {
+        // `"1"` (no operator) is how manifests like `anyhow = "1"` declare a
+        // requirement - resolve_update should treat it the same as `^1`.
+        let info = super::super::VersionInfo {
+            latest: Some("2.0.0".to_string()),
+            versions: vec![
+                "1.0.0".to_string(),
+                "1.9.0".to_string(),
+                "2.0.0".to_string(),
+            ],
+            ..Default::default()
+        };
+        let resolution = resolve_update("1", &info, &SemverScheme);
+        assert_eq!(resolution.compatible_latest, Some("1.9.0".to_string()));
+        assert_eq!(resolution.latest_overall, Some("2.0.0".to_string()));
+        assert_eq!(resolution.bump, Some(BumpClass::Major));
+    }
+}
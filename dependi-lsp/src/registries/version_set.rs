@@ -0,0 +1,537 @@
+//! Half-open `[lo, hi)` SemVer interval sets and the nearest-safe-version
+//! resolver built on them.
+//!
+//! Pointing vulnerability quick-fixes at "latest" forces a major upgrade even
+//! when a small patch would clear an advisory. [`nearest_safe_version`] picks
+//! the lowest published version that both satisfies the user's declared
+//! constraint and falls outside every known-vulnerable range, modeled
+//! PubGrub-style: the constraint and each vulnerable range are half-open
+//! intervals, combined via [`VersionSet`] union/intersection/complement
+//! instead of ad hoc comparisons.
+
+use semver::Version;
+
+use super::VulnerableRange;
+use super::version_scheme::normalize_version;
+
+/// A half-open version interval `[lower, upper)`. A missing bound is
+/// unbounded in that direction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Interval {
+    lower: Option<Version>,
+    upper: Option<Version>,
+}
+
+impl Interval {
+    fn contains(&self, v: &Version) -> bool {
+        self.lower.as_ref().is_none_or(|lo| v >= lo) && self.upper.as_ref().is_none_or(|hi| v < hi)
+    }
+
+    /// Whether `self` and `other` overlap or abut closely enough that their
+    /// union is itself a single contiguous interval.
+    fn overlaps_or_touches(&self, other: &Interval) -> bool {
+        let self_reaches = self.upper.is_none() || other.lower.as_ref().is_none_or(|lo| &self.upper.clone().unwrap() >= lo);
+        let other_reaches = other.upper.is_none() || self.lower.as_ref().is_none_or(|lo| &other.upper.clone().unwrap() >= lo);
+        self_reaches && other_reaches
+    }
+}
+
+/// A set of versions, represented internally as a sorted, disjoint list of
+/// half-open [`Interval`]s. This is the PubGrub-style building block behind
+/// [`nearest_safe_version`]: a declared constraint and the union of a
+/// crate's vulnerable ranges are both modeled as `VersionSet`s, so "versions
+/// that satisfy the constraint and avoid every advisory" is just an
+/// intersection with a complement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionSet(Vec<Interval>);
+
+impl VersionSet {
+    /// The set containing no versions.
+    pub fn empty() -> Self {
+        Self(Vec::new())
+    }
+
+    /// The set containing every version.
+    pub fn full() -> Self {
+        Self(vec![Interval {
+            lower: None,
+            upper: None,
+        }])
+    }
+
+    /// The set `[lower, upper)`. Empty if the bounds are inverted or equal.
+    pub fn range(lower: Option<Version>, upper: Option<Version>) -> Self {
+        if let (Some(lo), Some(hi)) = (&lower, &upper) {
+            if lo >= hi {
+                return Self::empty();
+            }
+        }
+        Self(vec![Interval { lower, upper }])
+    }
+
+    pub fn contains(&self, v: &Version) -> bool {
+        self.0.iter().any(|i| i.contains(v))
+    }
+
+    /// The set of versions in either `self` or `other`, merging any
+    /// overlapping or touching intervals back into one.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut intervals: Vec<Interval> = self.0.iter().chain(other.0.iter()).cloned().collect();
+        intervals.sort_by(|a, b| cmp_lower(&a.lower, &b.lower));
+
+        let mut merged: Vec<Interval> = Vec::new();
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if last.overlaps_or_touches(&interval) => {
+                    last.upper = max_upper(&last.upper, &interval.upper);
+                }
+                _ => merged.push(interval),
+            }
+        }
+        Self(merged)
+    }
+
+    /// The set of versions in both `self` and `other`.
+    pub fn intersect(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        for a in &self.0 {
+            for b in &other.0 {
+                let lower = max_lower(&a.lower, &b.lower);
+                let upper = min_upper(&a.upper, &b.upper);
+                if is_non_empty(&lower, &upper) {
+                    intervals.push(Interval { lower, upper });
+                }
+            }
+        }
+        Self(intervals)
+    }
+
+    /// The set of versions in neither this set nor outside its span - i.e.
+    /// everything this set doesn't cover.
+    pub fn complement(&self) -> Self {
+        if self.0.is_empty() {
+            return Self::full();
+        }
+
+        let mut result = Vec::new();
+        let mut prev_upper: Option<Version> = None;
+        let mut started = false;
+
+        for interval in &self.0 {
+            let gap_lower = if started { prev_upper.clone() } else { None };
+            if gap_lower != interval.lower {
+                result.push(Interval {
+                    lower: gap_lower,
+                    upper: interval.lower.clone(),
+                });
+            }
+            started = true;
+            if interval.upper.is_none() {
+                return Self(result);
+            }
+            prev_upper = interval.upper.clone();
+        }
+
+        result.push(Interval {
+            lower: prev_upper,
+            upper: None,
+        });
+        Self(result)
+    }
+}
+
+fn cmp_lower(a: &Option<Version>, b: &Option<Version>) -> std::cmp::Ordering {
+    match (a, b) {
+        (None, None) => std::cmp::Ordering::Equal,
+        (None, Some(_)) => std::cmp::Ordering::Less,
+        (Some(_), None) => std::cmp::Ordering::Greater,
+        (Some(a), Some(b)) => a.cmp(b),
+    }
+}
+
+fn max_upper(a: &Option<Version>, b: &Option<Version>) -> Option<Version> {
+    match (a, b) {
+        (None, _) | (_, None) => None,
+        (Some(a), Some(b)) => Some(a.max(b).clone()),
+    }
+}
+
+fn max_lower(a: &Option<Version>, b: &Option<Version>) -> Option<Version> {
+    match (a, b) {
+        (None, other) | (other, None) => other.clone(),
+        (Some(a), Some(b)) => Some(a.max(b).clone()),
+    }
+}
+
+fn min_upper(a: &Option<Version>, b: &Option<Version>) -> Option<Version> {
+    match (a, b) {
+        (None, other) | (other, None) => other.clone(),
+        (Some(a), Some(b)) => Some(a.min(b).clone()),
+    }
+}
+
+fn is_non_empty(lower: &Option<Version>, upper: &Option<Version>) -> bool {
+    match (lower, upper) {
+        (Some(lo), Some(hi)) => lo < hi,
+        _ => true,
+    }
+}
+
+/// The `VersionSet` of versions a single advisory range excludes: `[0,
+/// fixed)` when `introduced` is absent or `"0"` (OSV's convention for "from
+/// the start"), otherwise `[introduced, fixed)`. Unparsable bounds fail open
+/// (full() for an unparsable `introduced`, unbounded for an unparsable
+/// `fixed`) rather than silently excluding a range we can't actually read.
+fn vulnerable_set(range: &VulnerableRange) -> VersionSet {
+    let lower = range
+        .introduced
+        .as_deref()
+        .filter(|v| *v != "0")
+        .and_then(|v| Version::parse(&normalize_version(v)).ok());
+    let upper = range
+        .fixed
+        .as_deref()
+        .and_then(|v| Version::parse(&normalize_version(v)).ok());
+    VersionSet::range(lower, upper)
+}
+
+/// The allowed set for a SemVer requirement, approximated comparator by
+/// comparator as half-open intervals (`^`/`~`/`=`/`>`/`>=`/`<`/`<=`). Falls
+/// back to [`VersionSet::full`] when the requirement doesn't parse as
+/// SemVer, matching this codebase's existing fail-open convention for
+/// requirements it can't classify (see [`super::version_scheme::SemverScheme`]).
+fn constraint_set(requirement: &str) -> VersionSet {
+    let Ok(req) = semver::VersionReq::parse(requirement.trim()) else {
+        return VersionSet::full();
+    };
+
+    req.comparators.iter().fold(VersionSet::full(), |set, comparator| {
+        let major = comparator.major;
+        let minor = comparator.minor.unwrap_or(0);
+        let patch = comparator.patch.unwrap_or(0);
+        let Ok(base) = Version::parse(&format!("{major}.{minor}.{patch}")) else {
+            return set;
+        };
+
+        let comparator_set = match comparator.op {
+            semver::Op::Exact => {
+                VersionSet::range(Some(base.clone()), Some(bump(&base, 0, 0, 1)))
+            }
+            semver::Op::Greater => VersionSet::range(Some(bump(&base, 0, 0, 1)), None),
+            semver::Op::GreaterEq => VersionSet::range(Some(base), None),
+            semver::Op::Less => VersionSet::range(None, Some(base)),
+            semver::Op::LessEq => VersionSet::range(None, Some(bump(&base, 0, 0, 1))),
+            semver::Op::Tilde => VersionSet::range(Some(base.clone()), Some(bump(&base, 0, 1, 0))),
+            semver::Op::Caret => {
+                let upper = caret_upper(&base, comparator.minor.is_some(), comparator.patch.is_some());
+                VersionSet::range(Some(base), Some(upper))
+            }
+            // Wildcard and any future comparator kinds aren't narrowed here -
+            // fail open rather than wrongly excluding versions.
+            _ => return set,
+        };
+        set.intersect(&comparator_set)
+    })
+}
+
+/// One past `version` in the given component, matching SemVer's rule that
+/// `(major, minor, patch) + 1` increments resets the components to its
+/// right to zero.
+fn bump(version: &Version, major: u64, minor: u64, patch: u64) -> Version {
+    if major > 0 {
+        Version::new(version.major + major, 0, 0)
+    } else if minor > 0 {
+        Version::new(version.major, version.minor + minor, 0)
+    } else {
+        Version::new(version.major, version.minor, version.patch + patch)
+    }
+}
+
+/// Cargo's caret-requirement upper bound: bumps the first nonzero component
+/// from the left among the ones the user actually wrote, so `^1.2.3` allows
+/// up to (not including) `2.0.0`, `^0.2.3` allows up to `0.3.0`, and `^0.0.3`
+/// allows up to `0.0.4`.
+fn caret_upper(base: &Version, has_minor: bool, has_patch: bool) -> Version {
+    if base.major > 0 {
+        bump(base, 1, 0, 0)
+    } else if has_minor && base.minor > 0 {
+        bump(base, 0, 1, 0)
+    } else if has_patch {
+        bump(base, 0, 0, 1)
+    } else if has_minor {
+        bump(base, 0, 1, 0)
+    } else {
+        bump(base, 1, 0, 0)
+    }
+}
+
+/// Whether `version` (an exact, resolved version - not a requirement) falls
+/// within any range `vulnerability` reports as affected. Advisories with no
+/// ranges at all (OSV's convention for "every version is affected") and
+/// versions that don't parse as SemVer both fail open (treated as
+/// affected), matching this module's existing fail-open convention rather
+/// than silently hiding a report we can't actually evaluate.
+pub fn version_is_affected(version: &str, vulnerability: &super::Vulnerability) -> bool {
+    if vulnerability.ranges.is_empty() {
+        return true;
+    }
+    let Ok(parsed) = Version::parse(&normalize_version(version)) else {
+        return true;
+    };
+    vulnerability
+        .ranges
+        .iter()
+        .map(vulnerable_set)
+        .fold(VersionSet::empty(), |acc, set| acc.union(&set))
+        .contains(&parsed)
+}
+
+/// Whether `version` is vulnerable given a RustSec-style advisory's
+/// `patched`/`unaffected` requirement lists - the inverse framing from
+/// [`version_is_affected`]'s OSV-style `introduced`/`fixed` ranges, since
+/// that's the safe-version shape RustSec advisories actually publish.
+/// `version` is safe (not vulnerable) if it satisfies any `patched` or
+/// `unaffected` requirement; advisories with no requirements at all fail
+/// open (treated as affecting every version), and a version that doesn't
+/// parse as SemVer also fails open, matching [`version_is_affected`]'s
+/// convention.
+pub fn is_version_vulnerable(version: &str, patched: &[String], unaffected: &[String]) -> bool {
+    if patched.is_empty() && unaffected.is_empty() {
+        return true;
+    }
+    let Ok(parsed) = Version::parse(&normalize_version(version)) else {
+        return true;
+    };
+    !patched
+        .iter()
+        .chain(unaffected)
+        .any(|requirement| constraint_set(requirement).contains(&parsed))
+}
+
+/// The lowest version in `versions` that both satisfies `requirement` and
+/// avoids every range in `vulnerabilities`, or the highest version still
+/// satisfying `requirement` if no published version clears every advisory
+/// (matching the "fall back to latest compatible" behavior of the ordinary
+/// update actions when the safe set is empty).
+pub fn nearest_safe_version(
+    requirement: &str,
+    versions: &[String],
+    vulnerabilities: &[super::Vulnerability],
+) -> Option<String> {
+    let vulnerable = vulnerabilities
+        .iter()
+        .flat_map(|vuln| &vuln.ranges)
+        .map(vulnerable_set)
+        .fold(VersionSet::empty(), |acc, set| acc.union(&set));
+
+    let safe = constraint_set(requirement).intersect(&vulnerable.complement());
+
+    let mut parsed: Vec<(Version, &str)> = versions
+        .iter()
+        .filter_map(|v| {
+            Version::parse(&normalize_version(v))
+                .ok()
+                .map(|parsed| (parsed, v.as_str()))
+        })
+        .collect();
+    parsed.sort_by(|a, b| a.0.cmp(&b.0));
+
+    parsed
+        .iter()
+        .find(|(v, _)| safe.contains(v))
+        .or_else(|| parsed.iter().rev().find(|(v, _)| constraint_set(requirement).contains(v)))
+        .map(|(_, raw)| raw.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registries::{Vulnerability, VulnerabilitySeverity};
+
+    fn v(s: &str) -> Version {
+        Version::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_range_rejects_inverted_bounds() {
+        let set = VersionSet::range(Some(v("2.0.0")), Some(v("1.0.0")));
+        assert!(!set.contains(&v("1.5.0")));
+    }
+
+    #[test]
+    fn test_union_merges_overlapping_intervals() {
+        let a = VersionSet::range(Some(v("1.0.0")), Some(v("1.5.0")));
+        let b = VersionSet::range(Some(v("1.2.0")), Some(v("2.0.0")));
+        let merged = a.union(&b);
+        assert!(merged.contains(&v("1.3.0")));
+        assert!(merged.contains(&v("1.9.0")));
+        assert!(!merged.contains(&v("2.0.0")));
+    }
+
+    #[test]
+    fn test_union_keeps_disjoint_intervals_separate() {
+        let a = VersionSet::range(Some(v("1.0.0")), Some(v("1.1.0")));
+        let b = VersionSet::range(Some(v("2.0.0")), Some(v("2.1.0")));
+        let merged = a.union(&b);
+        assert!(!merged.contains(&v("1.5.0")));
+        assert!(merged.contains(&v("1.0.5")));
+        assert!(merged.contains(&v("2.0.5")));
+    }
+
+    #[test]
+    fn test_intersect() {
+        let a = VersionSet::range(Some(v("1.0.0")), Some(v("2.0.0")));
+        let b = VersionSet::range(Some(v("1.5.0")), None);
+        let intersected = a.intersect(&b);
+        assert!(!intersected.contains(&v("1.4.0")));
+        assert!(intersected.contains(&v("1.5.0")));
+        assert!(intersected.contains(&v("1.9.9")));
+        assert!(!intersected.contains(&v("2.0.0")));
+    }
+
+    #[test]
+    fn test_complement_of_bounded_range() {
+        let set = VersionSet::range(Some(v("1.0.0")), Some(v("2.0.0")));
+        let complement = set.complement();
+        assert!(complement.contains(&v("0.5.0")));
+        assert!(!complement.contains(&v("1.5.0")));
+        assert!(complement.contains(&v("2.0.0")));
+    }
+
+    #[test]
+    fn test_complement_of_unbounded_lower() {
+        let set = VersionSet::range(None, Some(v("1.0.0")));
+        let complement = set.complement();
+        assert!(!complement.contains(&v("0.5.0")));
+        assert!(complement.contains(&v("1.0.0")));
+    }
+
+    #[test]
+    fn test_complement_of_empty_is_full() {
+        let complement = VersionSet::empty().complement();
+        assert!(complement.contains(&v("0.0.1")));
+        assert!(complement.contains(&v("999.0.0")));
+    }
+
+    fn vuln_fixed_at(fixed: &str) -> Vulnerability {
+        Vulnerability {
+            id: "GHSA-test".to_string(),
+            severity: VulnerabilitySeverity::High,
+            description: "test".to_string(),
+            url: None,
+            fixed_version: Some(fixed.to_string()),
+            ranges: vec![VulnerableRange {
+                introduced: None,
+                fixed: Some(fixed.to_string()),
+            }],
+            aliases: vec![],
+            related: vec![],
+        }
+    }
+
+    #[test]
+    fn test_nearest_safe_version_picks_minimal_patch_over_latest() {
+        let versions = vec![
+            "1.0.0".to_string(),
+            "1.2.0".to_string(),
+            "1.2.5".to_string(),
+            "1.9.0".to_string(),
+        ];
+        let vulnerabilities = vec![vuln_fixed_at("1.2.5")];
+        assert_eq!(
+            nearest_safe_version("^1.0", &versions, &vulnerabilities),
+            Some("1.2.5".to_string())
+        );
+    }
+
+    #[test]
+    fn test_nearest_safe_version_respects_constraint() {
+        let versions = vec!["1.5.0".to_string(), "2.0.0".to_string()];
+        // The fix ships in 2.0.0, outside the ^1 constraint - no safe
+        // version exists within range, so fall back to latest compatible.
+        let vulnerabilities = vec![vuln_fixed_at("2.0.0")];
+        assert_eq!(
+            nearest_safe_version("^1.0", &versions, &vulnerabilities),
+            Some("1.5.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_nearest_safe_version_no_vulnerabilities_returns_lowest_satisfying() {
+        let versions = vec!["1.0.0".to_string(), "1.5.0".to_string()];
+        assert_eq!(
+            nearest_safe_version("^1.0", &versions, &[]),
+            Some("1.0.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_nearest_safe_version_union_of_multiple_advisories() {
+        let versions = vec![
+            "1.0.0".to_string(),
+            "1.1.0".to_string(),
+            "1.2.0".to_string(),
+            "1.3.0".to_string(),
+        ];
+        // One advisory fixed at 1.2.0, another (overlapping) fixed at 1.3.0 -
+        // the floor is the union of both, not just the first found.
+        let vulnerabilities = vec![vuln_fixed_at("1.2.0"), vuln_fixed_at("1.3.0")];
+        assert_eq!(
+            nearest_safe_version("^1.0", &versions, &vulnerabilities),
+            Some("1.3.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_version_is_affected_inside_range() {
+        let vuln = vuln_fixed_at("1.2.0");
+        assert!(version_is_affected("1.0.0", &vuln));
+        assert!(!version_is_affected("1.2.0", &vuln));
+    }
+
+    #[test]
+    fn test_version_is_affected_fails_open_with_no_ranges() {
+        let vuln = Vulnerability {
+            id: "GHSA-test".to_string(),
+            severity: VulnerabilitySeverity::High,
+            description: "test".to_string(),
+            url: None,
+            fixed_version: None,
+            ranges: vec![],
+            aliases: vec![],
+            related: vec![],
+        };
+        assert!(version_is_affected("1.0.0", &vuln));
+    }
+
+    #[test]
+    fn test_version_is_affected_fails_open_on_unparsable_version() {
+        let vuln = vuln_fixed_at("1.2.0");
+        assert!(version_is_affected("not-a-version", &vuln));
+    }
+
+    #[test]
+    fn test_is_version_vulnerable_below_patched_requirement() {
+        let patched = vec![">=1.2.3".to_string()];
+        assert!(is_version_vulnerable("1.0.0", &patched, &[]));
+        assert!(!is_version_vulnerable("1.2.3", &patched, &[]));
+    }
+
+    #[test]
+    fn test_is_version_vulnerable_checks_unaffected_too() {
+        let unaffected = vec!["<1.0.0".to_string()];
+        assert!(!is_version_vulnerable("0.5.0", &[], &unaffected));
+        assert!(is_version_vulnerable("1.0.0", &[], &unaffected));
+    }
+
+    #[test]
+    fn test_is_version_vulnerable_fails_open_with_no_requirements() {
+        assert!(is_version_vulnerable("1.0.0", &[], &[]));
+    }
+
+    #[test]
+    fn test_is_version_vulnerable_fails_open_on_unparsable_version() {
+        let patched = vec![">=1.2.3".to_string()];
+        assert!(is_version_vulnerable("not-a-version", &patched, &[]));
+    }
+}
@@ -4,58 +4,394 @@
 //! different package registries, with support for registry-specific
 //! behavior where needed.
 
-/// Checks if a Rust crate version is a prerelease.
+use std::cmp::Ordering;
+
+use super::version_scheme::{cmp_nuget, parse_nuget};
+
+/// Total version ordering for an ecosystem: detects prereleases and ranks a
+/// version list, rather than the substring sniffing the `is_prerelease_*`
+/// functions below used to do. Unlike [`super::version_scheme::VersionScheme`]
+/// (which matches a declared requirement like `^1.2`), this only orders
+/// versions against each other - there's no requirement syntax involved.
 ///
-/// Uses semver-compatible prerelease detection. A version is considered
-/// a prerelease if it contains a hyphen (prerelease separator) or common
-/// prerelease identifiers.
-pub fn is_prerelease_rust(version: &str) -> bool {
-    let v = version.to_lowercase();
-    v.contains('-') || v.contains("alpha") || v.contains("beta") || v.contains("rc")
+/// A version this ecosystem's parser rejects sorts below every version that
+/// does parse, and equal to every other unparsable version, so a garbled
+/// entry can never be picked as "latest" but also never poisons the rest of
+/// the ordering.
+pub trait PrecedenceOrd: Send + Sync {
+    /// Whether `version` is a prerelease under this ecosystem's rules.
+    fn is_prerelease(&self, version: &str) -> bool;
+
+    /// Total ordering between two versions of this ecosystem.
+    fn compare(&self, a: &str, b: &str) -> Ordering;
+
+    /// The highest version in `versions`, optionally excluding prereleases.
+    fn highest<'a>(&self, versions: &'a [String], include_prerelease: bool) -> Option<&'a str> {
+        versions
+            .iter()
+            .filter(|v| include_prerelease || !self.is_prerelease(v))
+            .max_by(|a, b| self.compare(a, b))
+            .map(String::as_str)
+    }
 }
 
-/// Checks if an npm package version is a prerelease.
+/// Parse `version` as SemVer, tolerating Go's conventional leading `v`
+/// (`v1.2.3`) that the `semver` crate itself doesn't accept.
 ///
-/// npm-specific prerelease identifiers include `canary` and `next` tags
-/// in addition to common patterns.
+/// `semver::Error` isn't `Clone`, so callers that need to hold onto a
+/// failure (rather than immediately discard it) get an owned `String`
+/// instead of the error type itself.
+fn parse_semver(version: &str) -> Result<semver::Version, String> {
+    let trimmed = version.trim();
+    let trimmed = trimmed.strip_prefix(['v', 'V']).unwrap_or(trimmed);
+    semver::Version::parse(trimmed).map_err(|e| e.to_string())
+}
+
+/// SemVer precedence for Rust, npm, Dart, and Go - all SemVer or
+/// SemVer-compatible ecosystems. A version is a prerelease exactly when its
+/// SemVer prerelease segment (the part after `-`) is non-empty; ordering is
+/// `semver::Version`'s own `Ord`, which already compares numeric identifiers
+/// numerically, alphanumeric identifiers lexically, and ranks any
+/// prerelease below the release it precedes.
+pub struct SemverPrecedence;
+
+impl PrecedenceOrd for SemverPrecedence {
+    fn is_prerelease(&self, version: &str) -> bool {
+        parse_semver(version)
+            .map(|v| !v.pre.is_empty())
+            .unwrap_or(false)
+    }
+
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        match (parse_semver(a), parse_semver(b)) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            (Ok(_), Err(_)) => Ordering::Greater,
+            (Err(_), Ok(_)) => Ordering::Less,
+            (Err(_), Err(_)) => Ordering::Equal,
+        }
+    }
+}
+
+/// A PEP 440 version's non-release phase, ordered `Dev < Pre < Release <
+/// Post` per spec. Declaration order matters here: `derive(Ord)` ranks
+/// variants by declaration order before looking at their payloads, which is
+/// exactly this ordering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Pep440Phase {
+    Dev(u64),
+    /// `0` = alpha, `1` = beta, `2` = release candidate.
+    Pre(u8, u64),
+    Release,
+    Post(u64),
+}
+
+/// One segment of a PEP 440 local version identifier (the part after `+`).
+/// Declaration order matters for the derived `Ord`: a numeric segment
+/// outranks an alphanumeric one at the same position, per spec.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum LocalSegment {
+    Alpha(String),
+    Numeric(u64),
+}
+
+fn parse_local_segments(local: &str) -> Vec<LocalSegment> {
+    local
+        .split(['.', '-', '_'])
+        .map(|seg| match seg.parse::<u64>() {
+            Ok(n) => LocalSegment::Numeric(n),
+            Err(_) => LocalSegment::Alpha(seg.to_lowercase()),
+        })
+        .collect()
+}
+
+fn compare_local(a: Option<&str>, b: Option<&str>) -> Ordering {
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        // A local version is "more specific" than the plain release it's
+        // attached to, so it sorts above the same version with no local
+        // segment.
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+        (Some(a), Some(b)) => {
+            let (sa, sb) = (parse_local_segments(a), parse_local_segments(b));
+            for i in 0..sa.len().max(sb.len()) {
+                match (sa.get(i), sb.get(i)) {
+                    (Some(x), Some(y)) => match x.cmp(y) {
+                        Ordering::Equal => continue,
+                        order => return order,
+                    },
+                    (Some(_), None) => return Ordering::Greater,
+                    (None, Some(_)) => return Ordering::Less,
+                    (None, None) => return Ordering::Equal,
+                }
+            }
+            Ordering::Equal
+        }
+    }
+}
+
+/// A fully parsed PEP 440 version: `[N!]N(.N)*[{a|b|rc}N][.postN][.devN]
+/// [+local]`. Exposed so other modules that need PEP 440-aware comparisons
+/// (not just the [`PrecedenceOrd`] "rank a version list" view) can parse and
+/// compare versions directly instead of re-implementing this grammar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pep440Version {
+    epoch: u64,
+    release: Vec<u64>,
+    phase: Pep440Phase,
+    local: Option<String>,
+}
+
+impl Pep440Version {
+    /// Parses a PEP 440 version string. Handles the no-separator shorthand
+    /// (`1.0.0a1`, `2.0.0beta`) alongside the dotted/hyphenated forms
+    /// (`1.0.0.dev1`, `1.0.0-rc1`), an explicit epoch (`1!2.0.0`), and a
+    /// local version segment (`1.0.0+abc.1`).
+    pub fn parse(version: &str) -> Result<Self, String> {
+        let v = version.trim();
+
+        let (epoch, rest) = match v.split_once('!') {
+            Some((epoch_str, rest)) => {
+                let epoch = epoch_str
+                    .parse::<u64>()
+                    .map_err(|_| format!("bad epoch in {version:?}"))?;
+                (epoch, rest)
+            }
+            None => (0, v),
+        };
+
+        let (rest, local) = match rest.split_once('+') {
+            Some((base, local)) => (base, Some(local.to_string())),
+            None => (rest, None),
+        };
+
+        let release_end = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(rest.len());
+        let release_str = rest[..release_end].trim_end_matches('.');
+        if release_str.is_empty() {
+            return Err(format!("no release segment in {version:?}"));
+        }
+        let release = release_str
+            .split('.')
+            .map(|part| {
+                part.parse::<u64>()
+                    .map_err(|_| format!("bad release component in {version:?}"))
+            })
+            .collect::<Result<Vec<u64>, String>>()?;
+
+        let phase = parse_pep440_phase(&rest[release_end..], version)?;
+
+        Ok(Self {
+            epoch,
+            release,
+            phase,
+            local,
+        })
+    }
+
+    /// Whether this version is a `.dev` or pre-release (`a`/`b`/`rc`)
+    /// build. Post-releases and local versions of an otherwise-stable
+    /// release are not prereleases.
+    pub fn is_prerelease(&self) -> bool {
+        matches!(self.phase, Pep440Phase::Dev(_) | Pep440Phase::Pre(_, _))
+    }
+
+    /// Renders the canonical `[N!]N(.N)*[{a|b|rc}N][.postN][.devN][+local]`
+    /// form, e.g. normalizing `"1.0.0beta2"` to `"1.0.0b2"`. Unlike the
+    /// original parsed string, this is stable across equivalent spellings,
+    /// which matters for OSV affected-range matching - the epoch and local
+    /// segment are never dropped, since `1.2.3` and `1.2.3+cu118` are
+    /// distinct versions for vulnerability purposes.
+    pub fn canonical(&self) -> String {
+        let mut out = String::new();
+        if self.epoch != 0 {
+            out.push_str(&format!("{}!", self.epoch));
+        }
+        out.push_str(
+            &self
+                .release
+                .iter()
+                .map(u64::to_string)
+                .collect::<Vec<_>>()
+                .join("."),
+        );
+        match self.phase {
+            Pep440Phase::Dev(n) => out.push_str(&format!(".dev{n}")),
+            Pep440Phase::Pre(rank, n) => {
+                let label = match rank {
+                    0 => "a",
+                    1 => "b",
+                    _ => "rc",
+                };
+                out.push_str(&format!("{label}{n}"));
+            }
+            Pep440Phase::Release => {}
+            Pep440Phase::Post(n) => out.push_str(&format!(".post{n}")),
+        }
+        if let Some(local) = &self.local {
+            out.push('+');
+            out.push_str(local);
+        }
+        out
+    }
+}
+
+impl PartialOrd for Pep440Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Pep440Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.epoch
+            .cmp(&other.epoch)
+            .then_with(|| compare_release_segments(&self.release, &other.release))
+            .then_with(|| self.phase.cmp(&other.phase))
+            .then_with(|| compare_local(self.local.as_deref(), other.local.as_deref()))
+    }
+}
+
+fn parse_pep440_phase(rest: &str, original: &str) -> Result<Pep440Phase, String> {
+    let normalized = rest.to_lowercase().replace(['-', '_'], ".");
+    let normalized = normalized.trim_start_matches('.');
+    if normalized.is_empty() {
+        return Ok(Pep440Phase::Release);
+    }
+
+    if let Some(n) = strip_segment_number(normalized, "dev") {
+        return Ok(Pep440Phase::Dev(n));
+    }
+    // `postN` and its `revN`/`rN` synonyms all denote a post-release.
+    // Checked in this order so `"rc1"` (a pre-release) isn't swallowed by
+    // the bare `"r"` synonym: `strip_segment_number` only matches when the
+    // remainder after the label parses as a number, and `"c1"` doesn't.
+    for label in ["post", "rev", "r"] {
+        if let Some(n) = strip_segment_number(normalized, label) {
+            return Ok(Pep440Phase::Post(n));
+        }
+    }
+    for (label, rank) in [
+        ("alpha", 0),
+        ("a", 0),
+        ("beta", 1),
+        ("b", 1),
+        ("rc", 2),
+        ("c", 2),
+        ("preview", 2),
+        ("pre", 2),
+    ] {
+        if let Some(n) = strip_segment_number(normalized, label) {
+            return Ok(Pep440Phase::Pre(rank, n));
+        }
+    }
+
+    Err(format!("unrecognized version suffix {rest:?} in {original:?}"))
+}
+
+/// Strip `label` from the front of `s` and parse whatever's left as the
+/// segment's number (an empty remainder, e.g. bare `"alpha"`, is segment 0).
+fn strip_segment_number(s: &str, label: &str) -> Option<u64> {
+    let rest = s.strip_prefix(label)?;
+    if rest.is_empty() {
+        Some(0)
+    } else {
+        rest.parse().ok()
+    }
+}
+
+fn compare_release_segments(a: &[u64], b: &[u64]) -> Ordering {
+    for i in 0..a.len().max(b.len()) {
+        match a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0)) {
+            Ordering::Equal => continue,
+            order => return order,
+        }
+    }
+    Ordering::Equal
+}
+
+/// PEP 440 precedence for Python. `a`/`b`/`rc` (and their `alpha`/`beta`
+/// spellings) are prereleases; `.dev` sorts below even those; `.post`
+/// (`.post`/`.rev`/`.r`) is a stable release that sorts above the release it
+/// follows; an epoch (`1!...`) outranks every version of a lower epoch
+/// regardless of release/phase; a local version (`+abc`) sorts above the
+/// same version without one.
+pub struct Pep440Precedence;
+
+impl PrecedenceOrd for Pep440Precedence {
+    fn is_prerelease(&self, version: &str) -> bool {
+        Pep440Version::parse(version)
+            .map(|v| v.is_prerelease())
+            .unwrap_or(false)
+    }
+
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        match (Pep440Version::parse(a), Pep440Version::parse(b)) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            (Ok(_), Err(_)) => Ordering::Greater,
+            (Err(_), Ok(_)) => Ordering::Less,
+            (Err(_), Err(_)) => Ordering::Equal,
+        }
+    }
+}
+
+/// NuGet precedence, delegating to the same parser
+/// [`super::version_scheme::NuGetScheme`] uses for requirement matching, so
+/// the two stay consistent.
+pub struct NuGetPrecedence;
+
+impl PrecedenceOrd for NuGetPrecedence {
+    fn is_prerelease(&self, version: &str) -> bool {
+        parse_nuget(version)
+            .map(|(_, pre)| pre.is_some())
+            .unwrap_or(false)
+    }
+
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        match (parse_nuget(a), parse_nuget(b)) {
+            (Some(_), Some(_)) => cmp_nuget(a, b).unwrap_or(Ordering::Equal),
+            (Some(_), None) => Ordering::Greater,
+            (None, Some(_)) => Ordering::Less,
+            (None, None) => Ordering::Equal,
+        }
+    }
+}
+
+/// Checks if a Rust crate version is a prerelease, per SemVer precedence
+/// (a non-empty `-pre` segment).
+pub fn is_prerelease_rust(version: &str) -> bool {
+    SemverPrecedence.is_prerelease(version)
+}
+
+/// Checks if an npm package version is a prerelease, per SemVer precedence.
 pub fn is_prerelease_npm(version: &str) -> bool {
-    let v = version.to_lowercase();
-    v.contains('-')
-        || v.contains("alpha")
-        || v.contains("beta")
-        || v.contains("rc")
-        || v.contains("canary")
-        || v.contains("next")
+    SemverPrecedence.is_prerelease(version)
 }
 
-/// Checks if a PyPI package version is a prerelease.
-///
-/// Python-specific prerelease identifiers per PEP 440, including
-/// shorthand notation like `a1` for alpha and `b2` for beta.
-/// Note: Post-releases (`.postN`) are stable releases per PEP 440.
+/// Checks if a JSR package version is a prerelease, per SemVer precedence.
+pub fn is_prerelease_jsr(version: &str) -> bool {
+    SemverPrecedence.is_prerelease(version)
+}
+
+/// Checks if a PyPI package version is a prerelease, per PEP 440 (`dev`,
+/// `a`/`alpha`, `b`/`beta`, `rc`/`c` segments; `.post` is a stable release).
 pub fn is_prerelease_python(version: &str) -> bool {
-    let v = version.to_lowercase();
-    v.contains("dev")
-        || v.contains("alpha")
-        || v.contains("beta")
-        || v.contains("rc")
-        || (v.contains('a') && v.chars().last().is_some_and(|c| c.is_ascii_digit()))
-        || (v.contains('b') && v.chars().last().is_some_and(|c| c.is_ascii_digit()))
-        || v.contains(".dev")
+    Pep440Precedence.is_prerelease(version)
 }
 
-/// Checks if a Go module version is a prerelease.
-///
-/// Go uses semver-like versions with hyphenated prerelease suffixes.
+/// Checks if a Go module version is a prerelease, per SemVer precedence
+/// (after stripping the conventional `v` prefix).
 pub fn is_prerelease_go(version: &str) -> bool {
-    let v = version.to_lowercase();
-    v.contains("-rc") || v.contains("-alpha") || v.contains("-beta") || v.contains("-pre")
+    SemverPrecedence.is_prerelease(version)
 }
 
 /// Checks if a PHP Composer package version is a prerelease.
 ///
 /// Composer-specific stability flags including `dev` and common
-/// prerelease identifiers.
+/// prerelease identifiers. Composer's branch aliases and stability flags
+/// (`dev-master`, `2.x-dev`) don't fit SemVer or PEP 440 precedence, so this
+/// stays a heuristic rather than moving onto [`PrecedenceOrd`].
 pub fn is_prerelease_php(version: &str) -> bool {
     let v = version.to_lowercase();
     v.contains("alpha")
@@ -65,30 +401,27 @@ pub fn is_prerelease_php(version: &str) -> bool {
         || v.contains("dev")
 }
 
-/// Checks if a Dart/Flutter package version is a prerelease.
-///
-/// Dart uses semver with hyphenated prerelease suffixes and common
-/// prerelease identifiers.
+/// Checks if a Dart/Flutter package version is a prerelease, per SemVer
+/// precedence.
 pub fn is_prerelease_dart(version: &str) -> bool {
-    let v = version.to_lowercase();
-    v.contains('-')
-        || v.contains("dev")
-        || v.contains("alpha")
-        || v.contains("beta")
-        || v.contains("rc")
+    SemverPrecedence.is_prerelease(version)
 }
 
-/// Checks if a NuGet package version is a prerelease.
+/// Checks if a RubyGems package version is a prerelease.
 ///
-/// NuGet-specific prerelease identifiers include `preview` in addition
-/// to common patterns.
-pub fn is_prerelease_nuget(version: &str) -> bool {
+/// RubyGems marks prereleases with a dotted segment (`.pre.1`, `.alpha`,
+/// `.beta`, `.rc1`) rather than semver's hyphenated form, which doesn't fit
+/// [`PrecedenceOrd`]'s SemVer/PEP 440/NuGet comparators, so this stays a
+/// heuristic.
+pub fn is_prerelease_ruby(version: &str) -> bool {
     let v = version.to_lowercase();
-    v.contains('-')
-        || v.contains("alpha")
-        || v.contains("beta")
-        || v.contains("preview")
-        || v.contains("rc")
+    v.contains("pre") || v.contains("alpha") || v.contains("beta") || v.contains("rc")
+}
+
+/// Checks if a NuGet package version is a prerelease, per NuGet precedence
+/// (a hyphenated prerelease label).
+pub fn is_prerelease_nuget(version: &str) -> bool {
+    NuGetPrecedence.is_prerelease(version)
 }
 
 #[cfg(test)]
@@ -116,6 +449,14 @@ mod tests {
         assert!(!is_prerelease_npm("2.3.4"));
     }
 
+    #[test]
+    fn test_is_prerelease_jsr() {
+        assert!(is_prerelease_jsr("1.0.0-alpha"));
+        assert!(is_prerelease_jsr("1.0.0-rc.1"));
+        assert!(!is_prerelease_jsr("1.0.0"));
+        assert!(!is_prerelease_jsr("2.3.4"));
+    }
+
     #[test]
     fn test_is_prerelease_python() {
         assert!(is_prerelease_python("1.0.0a1"));
@@ -129,6 +470,72 @@ mod tests {
         assert!(!is_prerelease_python("2.3.4"));
     }
 
+    #[test]
+    fn test_pep440_ordering() {
+        let ord = Pep440Precedence;
+        assert_eq!(ord.compare("1.0.0.dev1", "1.0.0a1"), Ordering::Less);
+        assert_eq!(ord.compare("1.0.0a1", "1.0.0b1"), Ordering::Less);
+        assert_eq!(ord.compare("1.0.0b1", "1.0.0rc1"), Ordering::Less);
+        assert_eq!(ord.compare("1.0.0rc1", "1.0.0"), Ordering::Less);
+        assert_eq!(ord.compare("1.0.0", "1.0.0.post1"), Ordering::Less);
+        assert_eq!(ord.compare("1.0", "1.0.0"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_pep440_epoch_outranks_release() {
+        let ord = Pep440Precedence;
+        assert_eq!(ord.compare("1!1.0.0", "9.0.0"), Ordering::Greater);
+        assert_eq!(ord.compare("2.0.0", "1!0.0.1"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_pep440_rev_and_r_are_post_releases() {
+        let ord = Pep440Precedence;
+        assert_eq!(ord.compare("1.0.0", "1.0.0.rev1"), Ordering::Less);
+        assert_eq!(ord.compare("1.0.0", "1.0.0-r1"), Ordering::Less);
+        assert!(!is_prerelease_python("1.0.0.rev1"));
+    }
+
+    #[test]
+    fn test_pep440_local_version_outranks_plain() {
+        let ord = Pep440Precedence;
+        assert_eq!(ord.compare("1.0.0", "1.0.0+abc"), Ordering::Less);
+        assert_eq!(ord.compare("1.0.0+abc", "1.0.0+abc"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_pep440_version_alternate_spellings_normalize() {
+        assert_eq!(
+            Pep440Version::parse("1.0.0alpha1").unwrap(),
+            Pep440Version::parse("1.0.0a1").unwrap()
+        );
+        assert_eq!(
+            Pep440Version::parse("1.0.0c1").unwrap(),
+            Pep440Version::parse("1.0.0rc1").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_pep440_canonical_normalizes_alternate_spellings() {
+        assert_eq!(Pep440Version::parse("1.0.0beta2").unwrap().canonical(), "1.0.0b2");
+        assert_eq!(Pep440Version::parse("1.0.0c1").unwrap().canonical(), "1.0.0rc1");
+    }
+
+    #[test]
+    fn test_pep440_canonical_keeps_epoch_and_local() {
+        assert_eq!(Pep440Version::parse("1!1.2.3").unwrap().canonical(), "1!1.2.3");
+        assert_eq!(
+            Pep440Version::parse("1.2.3+cu118").unwrap().canonical(),
+            "1.2.3+cu118"
+        );
+    }
+
+    #[test]
+    fn test_pep440_canonical_round_trips_post_and_dev() {
+        assert_eq!(Pep440Version::parse("1.2.3.post2").unwrap().canonical(), "1.2.3.post2");
+        assert_eq!(Pep440Version::parse("1.2.3.dev0").unwrap().canonical(), "1.2.3.dev0");
+    }
+
     #[test]
     fn test_is_prerelease_go() {
         assert!(is_prerelease_go("v1.0.0-rc1"));
@@ -139,6 +546,21 @@ mod tests {
         assert!(!is_prerelease_go("v2.3.4"));
     }
 
+    #[test]
+    fn test_semver_precedence_ordering() {
+        let ord = SemverPrecedence;
+        assert_eq!(ord.compare("v1.0.0-rc1", "v1.0.0"), Ordering::Less);
+        assert_eq!(ord.compare("1.2.0", "1.10.0"), Ordering::Less);
+        assert_eq!(ord.highest(
+            &["1.0.0".to_string(), "1.5.0-rc.1".to_string(), "1.4.0".to_string()],
+            false,
+        ), Some("1.4.0"));
+        assert_eq!(ord.highest(
+            &["1.0.0".to_string(), "1.5.0-rc.1".to_string(), "1.4.0".to_string()],
+            true,
+        ), Some("1.5.0-rc.1"));
+    }
+
     #[test]
     fn test_is_prerelease_php() {
         assert!(is_prerelease_php("1.0.0-alpha"));
@@ -159,6 +581,16 @@ mod tests {
         assert!(!is_prerelease_dart("2.0.0"));
     }
 
+    #[test]
+    fn test_is_prerelease_ruby() {
+        assert!(is_prerelease_ruby("2.0.0.pre.1"));
+        assert!(is_prerelease_ruby("1.0.0.alpha"));
+        assert!(is_prerelease_ruby("1.0.0.beta.2"));
+        assert!(is_prerelease_ruby("1.0.0.rc1"));
+        assert!(!is_prerelease_ruby("1.0.0"));
+        assert!(!is_prerelease_ruby("2.3.4"));
+    }
+
     #[test]
     fn test_is_prerelease_nuget() {
         assert!(is_prerelease_nuget("1.0.0-alpha"));
@@ -169,4 +601,11 @@ mod tests {
         assert!(!is_prerelease_nuget("1.0.0"));
         assert!(!is_prerelease_nuget("2.0.0"));
     }
+
+    #[test]
+    fn test_nuget_precedence_ordering() {
+        let ord = NuGetPrecedence;
+        assert_eq!(ord.compare("1.0.0-beta", "1.0.0"), Ordering::Less);
+        assert_eq!(ord.compare("1.2.3", "1.2.3.1"), Ordering::Less);
+    }
 }
@@ -1,9 +1,10 @@
 //! Vulnerability report generation
 //!
 //! This module handles the generation of vulnerability reports
-//! in various formats (JSON, Markdown).
+//! in various formats (JSON, Markdown, SARIF).
 
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use tower_lsp::lsp_types::Url;
 
 /// Summary of vulnerabilities grouped by severity level.
@@ -40,6 +41,13 @@ pub struct VulnerabilityReportEntry {
     pub description: String,
     /// URL for more information about the vulnerability.
     pub url: Option<String>,
+    /// Line the affected dependency is declared on (0-indexed), for SARIF's
+    /// `physicalLocation`. `None` when the entry isn't tied to a manifest
+    /// line (e.g. a report assembled outside an open document).
+    pub line: Option<u32>,
+    /// Column range of the declared version string on `line`.
+    pub version_start: Option<u32>,
+    pub version_end: Option<u32>,
 }
 
 /// Generate a Markdown-formatted vulnerability report.
@@ -117,6 +125,85 @@ pub fn generate_markdown_report(
     lines.join("\n")
 }
 
+/// Map a report entry's severity string to a SARIF `level`
+/// ("error"/"warning"/"note"), matching how `create_vulnerability_diagnostic`
+/// maps the same severities to LSP diagnostic severities.
+fn sarif_level(severity: &str) -> &'static str {
+    match severity {
+        "critical" | "high" => "error",
+        "medium" => "warning",
+        _ => "note",
+    }
+}
+
+/// Generate a SARIF 2.1.0 vulnerability report, for CI pipelines that
+/// consume this server's findings with standard SARIF tooling (the same
+/// role `cargo-audit --format sarif` plays for a plain `cargo audit` run).
+///
+/// Each entry becomes a `result` referencing a `rule` (the advisory ID) in
+/// the run's `driver.rules`, with a `physicalLocation` pointing at `uri` and
+/// the dependency's declared line/column range when known.
+pub fn generate_sarif_report(uri: &Url, vulnerabilities: &[VulnerabilityReportEntry]) -> String {
+    let mut rule_ids: Vec<&str> = vulnerabilities.iter().map(|v| v.id.as_str()).collect();
+    rule_ids.sort_unstable();
+    rule_ids.dedup();
+
+    let rules: Vec<_> = rule_ids
+        .iter()
+        .map(|id| {
+            json!({
+                "id": id,
+                "shortDescription": { "text": *id },
+            })
+        })
+        .collect();
+
+    let results: Vec<_> = vulnerabilities
+        .iter()
+        .map(|vuln| {
+            let region = match (vuln.line, vuln.version_start, vuln.version_end) {
+                (Some(line), Some(start), Some(end)) => json!({
+                    "startLine": line + 1,
+                    "startColumn": start + 1,
+                    "endLine": line + 1,
+                    "endColumn": end + 1,
+                }),
+                _ => json!({ "startLine": 1 }),
+            };
+
+            json!({
+                "ruleId": vuln.id,
+                "level": sarif_level(&vuln.severity),
+                "message": { "text": format!("{}@{}: {}", vuln.package, vuln.version, vuln.description) },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": uri.to_string() },
+                        "region": region,
+                    },
+                }],
+            })
+        })
+        .collect();
+
+    let sarif = json!({
+        "version": "2.1.0",
+        "$schema": "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "dependi",
+                    "informationUri": "https://github.com/mpiton/zed-dependi",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    });
+
+    serde_json::to_string_pretty(&sarif).expect("SARIF report is always valid JSON")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -139,6 +226,9 @@ mod tests {
                 severity: "critical".to_string(),
                 description: "Critical vulnerability".to_string(),
                 url: Some("https://example.com/cve".to_string()),
+                line: None,
+                version_start: None,
+                version_end: None,
             },
             VulnerabilityReportEntry {
                 package: "tokio".to_string(),
@@ -147,6 +237,9 @@ mod tests {
                 severity: "high".to_string(),
                 description: "High vulnerability".to_string(),
                 url: None,
+                line: None,
+                version_start: None,
+                version_end: None,
             },
         ];
 
@@ -180,6 +273,9 @@ mod tests {
                 severity: "critical".to_string(),
                 description: "Old version vulnerability".to_string(),
                 url: None,
+                line: None,
+                version_start: None,
+                version_end: None,
             },
             VulnerabilityReportEntry {
                 package: "serde".to_string(),
@@ -188,6 +284,9 @@ mod tests {
                 severity: "high".to_string(),
                 description: "New version vulnerability".to_string(),
                 url: None,
+                line: None,
+                version_start: None,
+                version_end: None,
             },
         ];
 
@@ -211,4 +310,91 @@ mod tests {
         assert!(report.contains("## No vulnerabilities found"));
         assert!(report.contains("✅ All dependencies are free of known security vulnerabilities."));
     }
+
+    #[test]
+    fn test_generate_sarif_report_maps_severity_to_level() {
+        let uri = Url::parse("file:///project/Cargo.toml").unwrap();
+        let vulnerabilities = vec![
+            VulnerabilityReportEntry {
+                package: "serde".to_string(),
+                version: "1.0.0".to_string(),
+                id: "CVE-2021-1234".to_string(),
+                severity: "critical".to_string(),
+                description: "Critical vulnerability".to_string(),
+                url: None,
+                line: Some(4),
+                version_start: Some(10),
+                version_end: Some(17),
+            },
+            VulnerabilityReportEntry {
+                package: "tokio".to_string(),
+                version: "1.0.0".to_string(),
+                id: "CVE-2021-5678".to_string(),
+                severity: "medium".to_string(),
+                description: "Medium vulnerability".to_string(),
+                url: None,
+                line: None,
+                version_start: None,
+                version_end: None,
+            },
+        ];
+
+        let report = generate_sarif_report(&uri, &vulnerabilities);
+        let parsed: serde_json::Value = serde_json::from_str(&report).unwrap();
+
+        assert_eq!(parsed["version"], "2.1.0");
+        assert_eq!(parsed["runs"][0]["tool"]["driver"]["name"], "dependi");
+
+        let rules = parsed["runs"][0]["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 2);
+
+        let results = parsed["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["ruleId"], "CVE-2021-1234");
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(results[1]["level"], "warning");
+
+        let region = &results[0]["locations"][0]["physicalLocation"]["region"];
+        // 0-indexed dependency columns become 1-indexed SARIF columns.
+        assert_eq!(region["startLine"], 5);
+        assert_eq!(region["startColumn"], 11);
+        assert_eq!(region["endColumn"], 18);
+    }
+
+    #[test]
+    fn test_generate_sarif_report_dedupes_rules_for_repeated_advisory() {
+        let uri = Url::parse("file:///project/Cargo.toml").unwrap();
+        let vulnerabilities = vec![
+            VulnerabilityReportEntry {
+                package: "serde".to_string(),
+                version: "1.0.0".to_string(),
+                id: "CVE-2021-1111".to_string(),
+                severity: "high".to_string(),
+                description: "example".to_string(),
+                url: None,
+                line: None,
+                version_start: None,
+                version_end: None,
+            },
+            VulnerabilityReportEntry {
+                package: "serde".to_string(),
+                version: "2.0.0".to_string(),
+                id: "CVE-2021-1111".to_string(),
+                severity: "high".to_string(),
+                description: "example".to_string(),
+                url: None,
+                line: None,
+                version_start: None,
+                version_end: None,
+            },
+        ];
+
+        let report = generate_sarif_report(&uri, &vulnerabilities);
+        let parsed: serde_json::Value = serde_json::from_str(&report).unwrap();
+
+        let rules = parsed["runs"][0]["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 1);
+        let results = parsed["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+    }
 }
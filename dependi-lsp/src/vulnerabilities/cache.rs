@@ -1,11 +1,15 @@
 //! Vulnerability cache with configurable TTL
 //!
 //! Provides in-memory caching for vulnerability data with a default
-//! 6-hour TTL to reduce API calls.
+//! 6-hour TTL to reduce API calls. Optionally backed by a SQLite file so
+//! entries survive an editor restart instead of re-hitting OSV cold.
 
-use std::time::{Duration, Instant};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use dashmap::DashMap;
+use rusqlite::{Connection, params};
 
 use super::Ecosystem;
 use crate::registries::Vulnerability;
@@ -53,12 +57,178 @@ struct VulnCacheEntry {
     inserted_at: Instant,
 }
 
-/// In-memory vulnerability cache with TTL
+/// On-disk persistence for the vulnerability cache, so a cold start can
+/// skip re-querying OSV for rows that haven't expired yet.
+struct PersistentStore {
+    conn: Mutex<Connection>,
+}
+
+impl PersistentStore {
+    fn open(path: &Path) -> anyhow::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(path)?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS vulnerabilities (
+                key TEXT PRIMARY KEY,
+                ecosystem TEXT NOT NULL,
+                package_name TEXT NOT NULL,
+                version TEXT NOT NULL,
+                data TEXT NOT NULL,
+                inserted_at INTEGER NOT NULL,
+                ttl_secs INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Load every non-expired row, for repopulating the in-memory map on
+    /// construction.
+    fn load_fresh(&self, now: i64) -> Vec<(VulnCacheKey, Vec<Vulnerability>, Instant, Duration)> {
+        let Ok(conn) = self.conn.lock() else {
+            return Vec::new();
+        };
+        let Ok(mut stmt) = conn.prepare(
+            "SELECT ecosystem, package_name, version, data, inserted_at, ttl_secs
+             FROM vulnerabilities WHERE inserted_at + ttl_secs >= ?",
+        ) else {
+            return Vec::new();
+        };
+        let Ok(rows) = stmt.query_map(params![now], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, i64>(4)?,
+                row.get::<_, i64>(5)?,
+            ))
+        }) else {
+            return Vec::new();
+        };
+
+        rows.filter_map(|row| row.ok())
+            .filter_map(|(ecosystem, package_name, version, data, inserted_at, ttl_secs)| {
+                let ecosystem = Ecosystem::from_osv_str(&ecosystem)?;
+                let vulnerabilities: Vec<Vulnerability> = serde_json::from_str(&data).ok()?;
+                let key = VulnCacheKey::new(ecosystem, &package_name, &version);
+                // Reconstruct an Instant-relative age from the stored Unix
+                // timestamp so the in-memory TTL check behaves as if the
+                // entry had been inserted `age` ago.
+                let age = Duration::from_secs((now - inserted_at).max(0) as u64);
+                let inserted_at = Instant::now().checked_sub(age).unwrap_or_else(Instant::now);
+                Some((
+                    key,
+                    vulnerabilities,
+                    inserted_at,
+                    Duration::from_secs(ttl_secs.max(0) as u64),
+                ))
+            })
+            .collect()
+    }
+
+    /// Look up a single row, regardless of expiry - the caller applies the
+    /// TTL check, since it may differ from the row's stored `ttl_secs` if
+    /// the cache was reconfigured between runs.
+    fn get(&self, key: &VulnCacheKey) -> Option<(Vec<Vulnerability>, i64)> {
+        let conn = self.conn.lock().ok()?;
+        let (data, inserted_at): (String, i64) = conn
+            .query_row(
+                "SELECT data, inserted_at FROM vulnerabilities WHERE key = ?",
+                params![key.to_sqlite_key()],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .ok()?;
+        let vulnerabilities = serde_json::from_str(&data).ok()?;
+        Some((vulnerabilities, inserted_at))
+    }
+
+    fn insert(&self, key: &VulnCacheKey, vulnerabilities: &[Vulnerability], ttl_secs: u64) {
+        let Ok(conn) = self.conn.lock() else {
+            return;
+        };
+        let Ok(data) = serde_json::to_string(vulnerabilities) else {
+            return;
+        };
+        let _ = conn.execute(
+            "INSERT OR REPLACE INTO vulnerabilities
+             (key, ecosystem, package_name, version, data, inserted_at, ttl_secs)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+            params![
+                key.to_sqlite_key(),
+                key.ecosystem.as_osv_str(),
+                key.package_name,
+                key.version,
+                data,
+                current_timestamp(),
+                ttl_secs as i64,
+            ],
+        );
+    }
+
+    fn delete_expired(&self) {
+        let Ok(conn) = self.conn.lock() else {
+            return;
+        };
+        let _ = conn.execute(
+            "DELETE FROM vulnerabilities WHERE inserted_at + ttl_secs < ?",
+            params![current_timestamp()],
+        );
+    }
+
+    fn clear(&self) {
+        if let Ok(conn) = self.conn.lock() {
+            let _ = conn.execute("DELETE FROM vulnerabilities", []);
+        }
+    }
+}
+
+fn current_timestamp() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+/// Default location for the vulnerability cache database
+/// (`~/.cache/dependi/vulnerability-cache.db`), mirroring
+/// [`crate::registries::cache::default_cache_path`]'s cache directory.
+pub fn default_cache_path() -> anyhow::Result<std::path::PathBuf> {
+    let cache_dir = dirs::cache_dir()
+        .ok_or_else(|| anyhow::anyhow!("Could not determine user cache directory"))?
+        .join("dependi");
+    std::fs::create_dir_all(&cache_dir)?;
+    Ok(cache_dir.join("vulnerability-cache.db"))
+}
+
+/// Removes all entries from the on-disk vulnerability cache at
+/// [`default_cache_path`], so a user can manually purge stale data.
+pub fn clear_cache() -> anyhow::Result<()> {
+    let path = default_cache_path()?;
+    if path.exists() {
+        PersistentStore::open(&path)?.clear();
+    }
+    Ok(())
+}
+
+/// Vulnerability cache with TTL, optionally backed by a SQLite file.
+///
+/// Entries live in memory via `DashMap` for fast lookups; when a
+/// persistent store is configured (`with_persistent_store`), every insert
+/// is also written through to disk, and a `get` miss in memory falls back
+/// to the disk row (repopulating memory) before giving up.
 pub struct VulnerabilityCache {
     /// Cache entries
     entries: DashMap<VulnCacheKey, VulnCacheEntry>,
     /// Cache TTL
     ttl: Duration,
+    /// On-disk store, present only when constructed with
+    /// `with_persistent_store`
+    store: Option<PersistentStore>,
 }
 
 impl VulnerabilityCache {
@@ -67,6 +237,7 @@ impl VulnerabilityCache {
         Self {
             entries: DashMap::new(),
             ttl: DEFAULT_VULN_CACHE_TTL,
+            store: None,
         }
     }
 
@@ -75,22 +246,78 @@ impl VulnerabilityCache {
         Self {
             entries: DashMap::new(),
             ttl: Duration::from_secs(ttl_secs),
+            store: None,
+        }
+    }
+
+    /// Create a cache backed by a SQLite file at `path`, loading any
+    /// non-expired rows into memory immediately so a restarted LSP doesn't
+    /// start cold.
+    pub fn with_persistent_store(path: impl AsRef<Path>, ttl_secs: u64) -> anyhow::Result<Self> {
+        let store = PersistentStore::open(path.as_ref())?;
+        let entries = DashMap::new();
+        for (key, vulnerabilities, inserted_at, _row_ttl) in store.load_fresh(current_timestamp())
+        {
+            entries.insert(
+                key,
+                VulnCacheEntry {
+                    vulnerabilities,
+                    inserted_at,
+                },
+            );
         }
+
+        Ok(Self {
+            entries,
+            ttl: Duration::from_secs(ttl_secs),
+            store: Some(store),
+        })
+    }
+
+    /// Create a cache backed by the default on-disk location.
+    pub fn with_default_store(ttl_secs: u64) -> anyhow::Result<Self> {
+        Self::with_persistent_store(default_cache_path()?, ttl_secs)
     }
 
-    /// Get vulnerabilities from cache if present and not expired
+    /// Create a cache backed by the default on-disk location, using the
+    /// default TTL (6 hours).
+    pub fn open_default() -> anyhow::Result<Self> {
+        Self::with_default_store(DEFAULT_VULN_CACHE_TTL.as_secs())
+    }
+
+    /// Get vulnerabilities from cache if present and not expired, falling
+    /// back to the on-disk store (when configured) and repopulating
+    /// memory on a disk hit.
     pub fn get(&self, key: &VulnCacheKey) -> Option<Vec<Vulnerability>> {
-        self.entries.get(key).and_then(|entry| {
+        if let Some(entry) = self.entries.get(key) {
             if entry.inserted_at.elapsed() < self.ttl {
-                Some(entry.vulnerabilities.clone())
-            } else {
-                None
+                return Some(entry.vulnerabilities.clone());
             }
-        })
+        }
+
+        let store = self.store.as_ref()?;
+        let (vulnerabilities, inserted_at) = store.get(key)?;
+        let age = Duration::from_secs((current_timestamp() - inserted_at).max(0) as u64);
+        if age >= self.ttl {
+            return None;
+        }
+
+        self.entries.insert(
+            key.clone(),
+            VulnCacheEntry {
+                vulnerabilities: vulnerabilities.clone(),
+                inserted_at: Instant::now().checked_sub(age).unwrap_or_else(Instant::now),
+            },
+        );
+        Some(vulnerabilities)
     }
 
-    /// Insert vulnerabilities into cache
+    /// Insert vulnerabilities into cache, writing through to the on-disk
+    /// store when configured
     pub fn insert(&self, key: VulnCacheKey, vulnerabilities: Vec<Vulnerability>) {
+        if let Some(store) = &self.store {
+            store.insert(&key, &vulnerabilities, self.ttl.as_secs());
+        }
         self.entries.insert(
             key,
             VulnCacheEntry {
@@ -105,10 +332,14 @@ impl VulnerabilityCache {
         self.entries.get(key).is_some_and(|entry| entry.inserted_at.elapsed() < self.ttl)
     }
 
-    /// Remove expired entries from cache
+    /// Remove expired entries from cache, and from the on-disk store when
+    /// configured
     pub fn cleanup(&self) {
         self.entries
             .retain(|_, entry| entry.inserted_at.elapsed() < self.ttl);
+        if let Some(store) = &self.store {
+            store.delete_expired();
+        }
     }
 
     /// Clear all entries from cache
@@ -148,6 +379,10 @@ mod tests {
             severity: VulnerabilitySeverity::High,
             description: "Prototype pollution".to_string(),
             url: Some("https://nvd.nist.gov/vuln/detail/CVE-2021-23337".to_string()),
+            fixed_version: None,
+            ranges: vec![],
+            aliases: vec![],
+            related: vec![],
         }];
 
         cache.insert(key.clone(), vulns.clone());
@@ -192,4 +427,80 @@ mod tests {
         let cache = VulnerabilityCache::with_ttl(3600); // 1 hour
         assert_eq!(cache.ttl, Duration::from_secs(3600));
     }
+
+    fn temp_db_path(name: &str) -> std::path::PathBuf {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let id = COUNTER.fetch_add(1, Ordering::SeqCst);
+        std::env::temp_dir().join(format!("dependi-vuln-cache-test-{}-{}.db", name, id))
+    }
+
+    #[test]
+    fn test_persistent_store_survives_reload() {
+        let path = temp_db_path("reload");
+        let key = VulnCacheKey::new(Ecosystem::Npm, "lodash", "4.17.0");
+        let vulns = vec![Vulnerability {
+            id: "CVE-2021-23337".to_string(),
+            severity: VulnerabilitySeverity::High,
+            description: "Prototype pollution".to_string(),
+            url: None,
+            fixed_version: None,
+            ranges: vec![],
+            aliases: vec![],
+            related: vec![],
+        }];
+
+        {
+            let cache = VulnerabilityCache::with_persistent_store(&path, 3600).unwrap();
+            cache.insert(key.clone(), vulns.clone());
+        }
+
+        // A fresh cache (simulating an LSP restart) should load the row
+        // straight from disk without needing a `get` fallback.
+        let reloaded = VulnerabilityCache::with_persistent_store(&path, 3600).unwrap();
+        assert!(reloaded.contains(&key));
+        let retrieved = reloaded.get(&key).unwrap();
+        assert_eq!(retrieved[0].id, "CVE-2021-23337");
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_persistent_store_falls_back_to_disk_on_memory_miss() {
+        let path = temp_db_path("fallback");
+        let key = VulnCacheKey::new(Ecosystem::CratesIo, "serde", "1.0.0");
+
+        let writer = VulnerabilityCache::with_persistent_store(&path, 3600).unwrap();
+        writer.insert(key.clone(), vec![]);
+
+        // A second handle with nothing in its own in-memory map should
+        // still find the row on disk and repopulate memory from it.
+        let reader = VulnerabilityCache::with_persistent_store(temp_db_path("unused"), 3600)
+            .unwrap();
+        assert!(reader.get(&key).is_none());
+
+        let same_file_reader = PersistentStore::open(&path).unwrap();
+        assert!(same_file_reader.get(&key).is_some());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_cleanup_deletes_expired_rows_from_disk() {
+        let path = temp_db_path("cleanup");
+        let key = VulnCacheKey::new(Ecosystem::Go, "example.com/pkg", "1.0.0");
+
+        let cache = VulnerabilityCache::with_persistent_store(&path, 0).unwrap();
+        cache.insert(key.clone(), vec![]);
+
+        // ttl_secs = 0, so the row is expired as soon as the stored
+        // second-granularity timestamp ticks over.
+        std::thread::sleep(Duration::from_millis(1100));
+        cache.cleanup();
+
+        let store = PersistentStore::open(&path).unwrap();
+        assert!(store.get(&key).is_none());
+
+        let _ = std::fs::remove_file(&path);
+    }
 }
@@ -0,0 +1,162 @@
+//! A [`VulnerabilitySource`] that merges results from two other sources.
+//!
+//! OSV and the offline RustSec mirror (see [`super::rustsec_client`]) both
+//! surface RustSec advisories for the crates.io ecosystem - OSV aggregates
+//! them, so querying both naively would double-report the same CVE.
+//! [`CompositeSource`] queries both and deduplicates by advisory ID or
+//! shared alias, keeping whichever copy it saw first (primary wins).
+
+use super::{VulnerabilityQuery, VulnerabilitySource};
+use crate::registries::Vulnerability;
+
+/// Merges `primary` and `secondary`, preferring `primary`'s copy of any
+/// advisory ID both report. A query error from either side is logged and
+/// treated as "no results from that side" rather than failing the whole
+/// query - this is meant to let `secondary` (e.g. an offline mirror) keep
+/// serving results when `primary` (e.g. OSV) is unreachable, and vice versa.
+pub struct CompositeSource<A: VulnerabilitySource, B: VulnerabilitySource> {
+    primary: A,
+    secondary: B,
+}
+
+impl<A: VulnerabilitySource, B: VulnerabilitySource> CompositeSource<A, B> {
+    pub fn new(primary: A, secondary: B) -> Self {
+        Self { primary, secondary }
+    }
+
+    fn merge(primary: Vec<Vulnerability>, secondary: Vec<Vulnerability>) -> Vec<Vulnerability> {
+        let mut merged = primary;
+        for vuln in secondary {
+            if !merged.iter().any(|existing| shares_identity(existing, &vuln)) {
+                merged.push(vuln);
+            }
+        }
+        merged
+    }
+}
+
+/// Whether `a` and `b` identify the same advisory, either by ID or because
+/// one's `id`/`aliases` overlaps the other's - e.g. OSV reporting a RustSec
+/// advisory under its GHSA ID while the offline mirror uses the RustSec ID
+/// itself, with each listing the other as an alias.
+fn shares_identity(a: &Vulnerability, b: &Vulnerability) -> bool {
+    a.id == b.id
+        || a.aliases.iter().any(|alias| *alias == b.id || b.aliases.contains(alias))
+}
+
+impl<A: VulnerabilitySource, B: VulnerabilitySource> VulnerabilitySource for CompositeSource<A, B> {
+    async fn query(&self, query: &VulnerabilityQuery) -> anyhow::Result<Vec<Vulnerability>> {
+        let primary = self.primary.query(query).await.unwrap_or_else(|err| {
+            tracing::warn!("primary vulnerability source failed, falling back to secondary: {err}");
+            Vec::new()
+        });
+        let secondary = self.secondary.query(query).await.unwrap_or_default();
+        Ok(Self::merge(primary, secondary))
+    }
+
+    async fn query_batch(&self, queries: &[VulnerabilityQuery]) -> anyhow::Result<Vec<Vec<Vulnerability>>> {
+        let primary = self.primary.query_batch(queries).await.unwrap_or_else(|err| {
+            tracing::warn!("primary vulnerability source failed, falling back to secondary: {err}");
+            vec![Vec::new(); queries.len()]
+        });
+        let secondary = self.secondary.query_batch(queries).await.unwrap_or_else(|_| vec![Vec::new(); queries.len()]);
+        Ok(primary.into_iter().zip(secondary).map(|(p, s)| Self::merge(p, s)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registries::VulnerabilitySeverity;
+    use crate::vulnerabilities::Ecosystem;
+
+    fn vuln(id: &str) -> Vulnerability {
+        vuln_with_aliases(id, &[])
+    }
+
+    fn vuln_with_aliases(id: &str, aliases: &[&str]) -> Vulnerability {
+        Vulnerability {
+            id: id.to_string(),
+            severity: VulnerabilitySeverity::High,
+            description: String::new(),
+            url: None,
+            fixed_version: None,
+            ranges: Vec::new(),
+            aliases: aliases.iter().map(|a| a.to_string()).collect(),
+            related: Vec::new(),
+        }
+    }
+
+    struct StubSource {
+        result: anyhow::Result<Vec<Vulnerability>>,
+    }
+
+    impl Clone for StubSource {
+        fn clone(&self) -> Self {
+            Self {
+                result: match &self.result {
+                    Ok(v) => Ok(v.clone()),
+                    Err(_) => Err(anyhow::anyhow!("stub error")),
+                },
+            }
+        }
+    }
+
+    impl VulnerabilitySource for StubSource {
+        async fn query(&self, _query: &VulnerabilityQuery) -> anyhow::Result<Vec<Vulnerability>> {
+            match &self.result {
+                Ok(v) => Ok(v.clone()),
+                Err(_) => Err(anyhow::anyhow!("stub error")),
+            }
+        }
+
+        async fn query_batch(&self, queries: &[VulnerabilityQuery]) -> anyhow::Result<Vec<Vec<Vulnerability>>> {
+            let one = self.query(&queries[0]).await?;
+            Ok(queries.iter().map(|_| one.clone()).collect())
+        }
+    }
+
+    fn query() -> VulnerabilityQuery {
+        VulnerabilityQuery {
+            package_name: "foo".to_string(),
+            version: "1.0.0".to_string(),
+            ecosystem: Ecosystem::CratesIo,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_composite_source_dedupes_by_id_preferring_primary() {
+        let primary = StubSource { result: Ok(vec![vuln("RUSTSEC-2024-0001")]) };
+        let secondary = StubSource {
+            result: Ok(vec![vuln("RUSTSEC-2024-0001"), vuln("RUSTSEC-2024-0002")]),
+        };
+        let composite = CompositeSource::new(primary, secondary);
+        let result = composite.query(&query()).await.unwrap();
+        let ids: Vec<_> = result.iter().map(|v| v.id.as_str()).collect();
+        assert_eq!(ids, vec!["RUSTSEC-2024-0001", "RUSTSEC-2024-0002"]);
+    }
+
+    #[tokio::test]
+    async fn test_composite_source_dedupes_by_shared_alias() {
+        let primary = StubSource {
+            result: Ok(vec![vuln_with_aliases("GHSA-xxxx", &["RUSTSEC-2024-0001"])]),
+        };
+        let secondary = StubSource {
+            result: Ok(vec![vuln("RUSTSEC-2024-0001")]),
+        };
+        let composite = CompositeSource::new(primary, secondary);
+        let result = composite.query(&query()).await.unwrap();
+        let ids: Vec<_> = result.iter().map(|v| v.id.as_str()).collect();
+        assert_eq!(ids, vec!["GHSA-xxxx"]);
+    }
+
+    #[tokio::test]
+    async fn test_composite_source_falls_back_when_primary_errors() {
+        let primary = StubSource { result: Err(anyhow::anyhow!("network down")) };
+        let secondary = StubSource { result: Ok(vec![vuln("RUSTSEC-2024-0003")]) };
+        let composite = CompositeSource::new(primary, secondary);
+        let result = composite.query(&query()).await.unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].id, "RUSTSEC-2024-0003");
+    }
+}
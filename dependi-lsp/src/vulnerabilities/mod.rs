@@ -7,8 +7,11 @@
 use crate::registries::Vulnerability;
 
 pub mod cache;
+pub mod composite;
+pub mod normalize;
 pub mod osv;
 pub mod rustsec_client;
+pub mod severity;
 
 /// Ecosystem identifiers for vulnerability sources
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -27,6 +30,8 @@ pub enum Ecosystem {
     Pub,
     /// .NET packages (NuGet)
     NuGet,
+    /// JavaScript/TypeScript packages on the JSR registry (jsr.io)
+    Jsr,
 }
 
 impl Ecosystem {
@@ -40,8 +45,26 @@ impl Ecosystem {
             Ecosystem::Packagist => "Packagist",
             Ecosystem::Pub => "Pub",
             Ecosystem::NuGet => "NuGet",
+            Ecosystem::Jsr => "JSR",
         }
     }
+
+    /// Parse an OSV.dev ecosystem string back into an `Ecosystem`, the
+    /// inverse of `as_osv_str`. Used to reconstruct cache keys loaded from
+    /// persistent storage.
+    pub fn from_osv_str(s: &str) -> Option<Self> {
+        Some(match s {
+            "crates.io" => Ecosystem::CratesIo,
+            "npm" => Ecosystem::Npm,
+            "PyPI" => Ecosystem::PyPI,
+            "Go" => Ecosystem::Go,
+            "Packagist" => Ecosystem::Packagist,
+            "Pub" => Ecosystem::Pub,
+            "NuGet" => Ecosystem::NuGet,
+            "JSR" => Ecosystem::Jsr,
+            _ => return None,
+        })
+    }
 }
 
 /// Query for vulnerability lookup
@@ -0,0 +1,187 @@
+//! Per-[`Ecosystem`] normalization of a manifest-declared version or
+//! requirement down to the version an OSV query should actually be run
+//! against.
+//!
+//! PyPI's PEP 440 specifiers and npm/crates.io's SemVer ranges both admit a
+//! whole family of concrete versions from one declared string, and OSV
+//! affected-range matching needs a single concrete version, not the
+//! requirement itself. [`normalize_for_query`] picks the version to query -
+//! the exact pin itself when the declared string names exactly one, or the
+//! requirement's lower anchor otherwise - and reports which case it was, so
+//! callers know whether the resulting OSV lookup is an exact match or only
+//! an approximation of "the range's floor".
+
+use super::Ecosystem;
+use crate::registries::pep440::Specifier;
+use crate::registries::version_scheme::normalize_version;
+use crate::registries::version_utils::Pep440Version;
+
+/// The result of normalizing a declared version/requirement for an OSV
+/// query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizedQueryVersion {
+    /// The concrete version to query OSV with.
+    pub version: String,
+    /// Whether `version` is only the requirement's anchor rather than an
+    /// exact pin - the caller can't assume a hit/miss against this version
+    /// speaks for every version the requirement actually admits.
+    pub is_range: bool,
+}
+
+/// Normalize `raw` for `ecosystem`. PyPI versions are parsed as PEP 440
+/// directly, since the epoch and any `+local` tag must survive normalization
+/// verbatim - `1.2.3` and `1.2.3+cu118` are distinct versions for
+/// vulnerability-matching purposes, even though they'd collide under naive
+/// string stripping. Every other ecosystem goes through
+/// [`normalize_version`]'s existing SemVer range-anchor stripping.
+pub fn normalize_for_query(raw: &str, ecosystem: Ecosystem) -> NormalizedQueryVersion {
+    match ecosystem {
+        Ecosystem::PyPI => normalize_pep440(raw),
+        _ => normalize_semver_like(raw),
+    }
+}
+
+/// PEP 440 side of [`normalize_for_query`]: a single `==`/`===` clause (or a
+/// bare version with no operator at all) is an exact pin; anything else -
+/// `>=`, `~=`, a `.*` wildcard, or a comma-separated multi-clause
+/// requirement - is a range, normalized down to its first clause's anchor so
+/// there's still something to query.
+fn normalize_pep440(raw: &str) -> NormalizedQueryVersion {
+    let trimmed = raw.trim();
+
+    if !trimmed.contains(',') {
+        if let Some(exact) = exact_pep440_version(trimmed) {
+            if let Ok(parsed) = Pep440Version::parse(&exact) {
+                return NormalizedQueryVersion { version: parsed.canonical(), is_range: false };
+            }
+        }
+    }
+
+    let first_clause = trimmed.split(',').next().unwrap_or(trimmed).trim();
+    let anchor = strip_pep440_operator(first_clause);
+    match Pep440Version::parse(&anchor) {
+        Ok(parsed) => NormalizedQueryVersion { version: parsed.canonical(), is_range: true },
+        // Can't parse even the stripped anchor as PEP 440 - pass the raw
+        // string through rather than discarding it, matching this module's
+        // fail-open convention elsewhere.
+        Err(_) => NormalizedQueryVersion { version: trimmed.to_string(), is_range: true },
+    }
+}
+
+/// The bare version named by `clause`, if it admits exactly one version: a
+/// plain version with no operator, `===` (arbitrary equality), or `==`
+/// without a `.*` wildcard. `None` for anything that admits a range
+/// (`>=`, `~=`, `==1.0.*`, ...).
+fn exact_pep440_version(clause: &str) -> Option<String> {
+    match Specifier::parse(clause) {
+        None => Some(clause.to_string()),
+        Some(Specifier::Eq(v)) if !v.ends_with(".*") => Some(v),
+        Some(Specifier::ArbitraryEq(v)) => Some(v),
+        _ => None,
+    }
+}
+
+/// Strips a PEP 440 comparison operator (and any trailing `.*` wildcard),
+/// leaving the version a range clause anchors on.
+fn strip_pep440_operator(clause: &str) -> String {
+    let value = match Specifier::parse(clause) {
+        None => return clause.to_string(),
+        Some(
+            Specifier::Eq(v)
+            | Specifier::NotEq(v)
+            | Specifier::Lt(v)
+            | Specifier::LtEq(v)
+            | Specifier::Gt(v)
+            | Specifier::GtEq(v)
+            | Specifier::Compatible(v)
+            | Specifier::ArbitraryEq(v),
+        ) => v,
+    };
+    value.trim_end_matches(".*").to_string()
+}
+
+/// SemVer-like side of [`normalize_for_query`] (npm, crates.io, and the other
+/// ecosystems [`normalize_version`] already handles): a lone, fully concrete
+/// SemVer version is exact; everything `normalize_version` has to anchor
+/// instead (a caret/tilde range, a wildcard, a multi-comparator requirement)
+/// is a range.
+fn normalize_semver_like(raw: &str) -> NormalizedQueryVersion {
+    let trimmed = raw.trim();
+    let is_range = semver::Version::parse(trimmed).is_err();
+    NormalizedQueryVersion { version: normalize_version(trimmed), is_range }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pypi_bare_version_is_exact() {
+        let result = normalize_for_query("1.2.3", Ecosystem::PyPI);
+        assert_eq!(result.version, "1.2.3");
+        assert!(!result.is_range);
+    }
+
+    #[test]
+    fn test_pypi_double_equals_is_exact() {
+        let result = normalize_for_query("==1.2.3", Ecosystem::PyPI);
+        assert_eq!(result.version, "1.2.3");
+        assert!(!result.is_range);
+    }
+
+    #[test]
+    fn test_pypi_triple_equals_is_exact() {
+        let result = normalize_for_query("===1.2.3+local", Ecosystem::PyPI);
+        assert_eq!(result.version, "1.2.3+local");
+        assert!(!result.is_range);
+    }
+
+    #[test]
+    fn test_pypi_compatible_release_is_a_range() {
+        let result = normalize_for_query("~=1.4.2", Ecosystem::PyPI);
+        assert_eq!(result.version, "1.4.2");
+        assert!(result.is_range);
+    }
+
+    #[test]
+    fn test_pypi_comma_separated_requirement_anchors_on_first_clause() {
+        let result = normalize_for_query(">=1.2,<2.0", Ecosystem::PyPI);
+        assert_eq!(result.version, "1.2");
+        assert!(result.is_range);
+    }
+
+    #[test]
+    fn test_pypi_preserves_epoch_and_local() {
+        let result = normalize_for_query("1!2.0.0+cu118", Ecosystem::PyPI);
+        assert_eq!(result.version, "1!2.0.0+cu118");
+        assert!(!result.is_range);
+    }
+
+    #[test]
+    fn test_pypi_wildcard_is_a_range() {
+        let result = normalize_for_query("==1.0.*", Ecosystem::PyPI);
+        assert_eq!(result.version, "1.0");
+        assert!(result.is_range);
+    }
+
+    #[test]
+    fn test_npm_exact_version_is_exact() {
+        let result = normalize_for_query("1.2.3", Ecosystem::Npm);
+        assert_eq!(result.version, "1.2.3");
+        assert!(!result.is_range);
+    }
+
+    #[test]
+    fn test_npm_caret_range_anchors_to_floor() {
+        let result = normalize_for_query("^1.2.3", Ecosystem::Npm);
+        assert_eq!(result.version, "1.2.3");
+        assert!(result.is_range);
+    }
+
+    #[test]
+    fn test_crates_io_tilde_range_anchors_to_floor() {
+        let result = normalize_for_query("~1.2", Ecosystem::CratesIo);
+        assert_eq!(result.version, "1.2.0");
+        assert!(result.is_range);
+    }
+}
@@ -3,14 +3,18 @@
 //! OSV (Open Source Vulnerabilities) provides a unified API for querying
 //! vulnerability data across multiple ecosystems.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
-use super::{VulnerabilityQuery, VulnerabilitySource};
-use crate::registries::{Vulnerability, VulnerabilitySeverity};
+use super::cache::{VulnCacheKey, VulnerabilityCache};
+use super::severity::{cvss_vector_base_score, severity_from_score};
+use super::{Ecosystem, VulnerabilityQuery, VulnerabilitySource};
+use crate::registries::packagist::compare_packagist_versions;
+use crate::registries::{Vulnerability, VulnerabilitySeverity, VulnerableRange};
 
 const OSV_API_BASE: &str = "https://api.osv.dev/v1";
 
@@ -18,6 +22,10 @@ const OSV_API_BASE: &str = "https://api.osv.dev/v1";
 pub struct OsvClient {
     client: Arc<Client>,
     base_url: String,
+    /// Optional persistent, TTL'd cache for per-(package, version) lookups -
+    /// see [`OsvClient::with_cache`]. `None` means every call hits the
+    /// network, same as before this cache existed.
+    cache: Option<Arc<VulnerabilityCache>>,
 }
 
 impl OsvClient {
@@ -31,9 +39,29 @@ impl OsvClient {
         Ok(Self {
             client: Arc::new(client),
             base_url: OSV_API_BASE.to_string(),
+            cache: None,
         })
     }
 
+    /// Create a new OSV client that reuses an existing shared HTTP client,
+    /// so registry callers (e.g. npm, JSR) don't pay for a second
+    /// connection pool just to query vulnerabilities.
+    pub fn with_client(client: Arc<Client>) -> Self {
+        Self {
+            client,
+            base_url: OSV_API_BASE.to_string(),
+            cache: None,
+        }
+    }
+
+    /// Attaches a persistent [`VulnerabilityCache`] so repeated
+    /// `query_batch_hydrated` calls for the same `(ecosystem, package,
+    /// version)` within the cache's TTL are served without re-hitting OSV.
+    pub fn with_cache(mut self, cache: Arc<VulnerabilityCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
     /// Create with custom base URL (for testing)
     #[cfg(test)]
     pub fn with_base_url(base_url: String) -> anyhow::Result<Self> {
@@ -45,6 +73,7 @@ impl OsvClient {
         Ok(Self {
             client: Arc::new(client),
             base_url,
+            cache: None,
         })
     }
 
@@ -85,8 +114,220 @@ impl OsvClient {
             severity,
             description,
             url,
+            fixed_version: extract_fixed_version(&osv.affected),
+            ranges: extract_ranges(&osv.affected),
+            aliases: osv.aliases.clone().unwrap_or_default(),
+            related: osv.related.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// The first `fixed` version event recorded across an advisory's affected
+/// ranges, if any - the boundary a "nearest non-vulnerable version" quick
+/// fix should land on or above.
+fn extract_fixed_version(affected: &Option<Vec<OsvAffected>>) -> Option<String> {
+    affected
+        .as_ref()?
+        .iter()
+        .filter_map(|a| a.ranges.as_ref())
+        .flatten()
+        .flat_map(|range| &range.events)
+        .find_map(|event| event.fixed.clone())
+}
+
+/// Every affected range an advisory reports, as `introduced`/`fixed` pairs -
+/// the input [`crate::registries::version_set::nearest_safe_version`] turns
+/// into `VersionSet`s. OSV's `"0"` sentinel for "introduced from the start"
+/// is normalized to `None` here so downstream range handling only has to
+/// reason about "unbounded" once.
+fn extract_ranges(affected: &Option<Vec<OsvAffected>>) -> Vec<VulnerableRange> {
+    let Some(affected) = affected else {
+        return Vec::new();
+    };
+
+    affected
+        .iter()
+        .filter_map(|a| a.ranges.as_ref())
+        .flatten()
+        .map(|range| {
+            let introduced = range
+                .events
+                .iter()
+                .find_map(|event| event.introduced.clone())
+                .filter(|v| v != "0");
+            let fixed = range.events.iter().find_map(|event| event.fixed.clone());
+            VulnerableRange { introduced, fixed }
+        })
+        .collect()
+}
+
+/// Builds a canonical Package URL for `package_name` in `ecosystem`, for
+/// advisories that only key on purl rather than ecosystem+name (see
+/// [`OsvClient::query_by_purl`]). Packagist names are `vendor/package` and
+/// split into the purl namespace/name pair; every other ecosystem here is
+/// a flat name. Returns `None` for ecosystems OSV has no purl type for
+/// (currently just JSR).
+fn build_purl(ecosystem: Ecosystem, package_name: &str) -> Option<String> {
+    let purl_type = match ecosystem {
+        Ecosystem::CratesIo => "cargo",
+        Ecosystem::Npm => "npm",
+        Ecosystem::PyPI => "pypi",
+        Ecosystem::Go => "golang",
+        Ecosystem::Packagist => "composer",
+        Ecosystem::Pub => "pub",
+        Ecosystem::NuGet => "nuget",
+        Ecosystem::Jsr => return None,
+    };
+
+    Some(match package_name.split_once('/') {
+        Some((namespace, name)) => format!(
+            "pkg:{}/{}/{}",
+            purl_type,
+            percent_encode_purl_segment(namespace),
+            percent_encode_purl_segment(name)
+        ),
+        None => format!(
+            "pkg:{}/{}",
+            purl_type,
+            percent_encode_purl_segment(package_name)
+        ),
+    })
+}
+
+/// Percent-encodes a single purl path segment, leaving the characters the
+/// spec treats as always-safe (alphanumerics and `-_.~`) untouched - the
+/// same hand-rolled approach `npm.rs` uses for scoped package names, since
+/// no URL-encoding crate is vendored here.
+fn percent_encode_purl_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char);
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// Compares two version strings the way a range of `range_type` expects:
+/// `SEMVER` ranges are guaranteed valid semver by the OSV schema, so parse
+/// strictly; any other type (`ECOSYSTEM`, `GIT`) falls back to
+/// [`compare_packagist_versions`]'s semver-then-numeric-segments heuristic,
+/// since there's no single correct parser for every ecosystem's own scheme.
+fn compare_range_versions(range_type: &str, a: &str, b: &str) -> std::cmp::Ordering {
+    if range_type == "SEMVER" {
+        if let (Ok(va), Ok(vb)) = (semver::Version::parse(a), semver::Version::parse(b)) {
+            return va.cmp(&vb);
+        }
+    }
+    compare_packagist_versions(a, b)
+}
+
+/// The upper bound of a vulnerable interval opened by an `introduced` event.
+enum IntervalEnd {
+    /// No closing event - every version from the interval's start onward is
+    /// affected.
+    Unbounded,
+    /// Closed by a `fixed` event: affected up to, but excluding, this version.
+    Exclusive(String),
+    /// Closed by a `last_affected` event: affected up to and including this
+    /// version.
+    Inclusive(String),
+}
+
+/// One OSV range's events, turned into `[start, end)`-style intervals. A
+/// range normally has a single `introduced`/`fixed` (or `last_affected`)
+/// pair, but OSV allows several such pairs per range to describe
+/// non-contiguous vulnerable windows (e.g. fixed in 1.5, reintroduced in
+/// 2.0, fixed again in 2.5).
+fn vulnerable_intervals(range: &OsvRange) -> Vec<(String, IntervalEnd)> {
+    let mut sorted: Vec<&OsvRangeEvent> = range.events.iter().collect();
+    sorted.sort_by(|a, b| {
+        compare_range_versions(
+            &range.range_type,
+            event_version(a),
+            event_version(b),
+        )
+    });
+
+    let mut intervals = Vec::new();
+    let mut open_at: Option<String> = None;
+    for event in sorted {
+        if let Some(v) = &event.introduced {
+            if let Some(start) = open_at.replace(v.clone()) {
+                // An unclosed interval followed by another `introduced` -
+                // treat the first as unbounded rather than dropping it.
+                intervals.push((start, IntervalEnd::Unbounded));
+            }
+        } else if let Some(v) = &event.fixed {
+            // A `fixed`/`last_affected` with no preceding `introduced` in
+            // this range means the range was affected from the start - the
+            // "0" sentinel OSV itself uses for an unbounded lower bound.
+            let start = open_at.take().unwrap_or_else(|| "0".to_string());
+            intervals.push((start, IntervalEnd::Exclusive(v.clone())));
+        } else if let Some(v) = &event.last_affected {
+            let start = open_at.take().unwrap_or_else(|| "0".to_string());
+            intervals.push((start, IntervalEnd::Inclusive(v.clone())));
         }
     }
+    if let Some(start) = open_at.take() {
+        intervals.push((start, IntervalEnd::Unbounded));
+    }
+    intervals
+}
+
+/// An event's own position for sorting purposes - whichever field is set,
+/// with a missing `introduced` treated as `"0"` (OSV's own sentinel for
+/// "affected from the start").
+fn event_version(event: &OsvRangeEvent) -> &str {
+    event
+        .introduced
+        .as_deref()
+        .or(event.fixed.as_deref())
+        .or(event.last_affected.as_deref())
+        .unwrap_or("0")
+}
+
+/// Whether `version` falls inside any interval of any range on `affected`,
+/// or is named explicitly in `affected.versions`.
+fn affected_entry_covers(affected: &OsvAffected, version: &str) -> bool {
+    let explicit = affected
+        .versions
+        .as_ref()
+        .is_some_and(|versions| versions.iter().any(|v| v == version));
+    if explicit {
+        return true;
+    }
+
+    affected.ranges.as_ref().is_some_and(|ranges| {
+        ranges.iter().any(|range| {
+            vulnerable_intervals(range).iter().any(|(start, end)| {
+                let at_or_after_start =
+                    compare_range_versions(&range.range_type, version, start) != std::cmp::Ordering::Less;
+                if !at_or_after_start {
+                    return false;
+                }
+                match end {
+                    IntervalEnd::Unbounded => true,
+                    IntervalEnd::Exclusive(bound) => {
+                        compare_range_versions(&range.range_type, version, bound) == std::cmp::Ordering::Less
+                    }
+                    IntervalEnd::Inclusive(bound) => {
+                        compare_range_versions(&range.range_type, version, bound) != std::cmp::Ordering::Greater
+                    }
+                }
+            })
+        })
+    })
+}
+
+/// Whether any of `osv`'s affected entries cover `version`.
+fn osv_affects_version(osv: &OsvVulnerability, version: &str) -> bool {
+    osv.affected
+        .as_ref()
+        .is_some_and(|affected| affected.iter().any(|entry| affected_entry_covers(entry, version)))
 }
 
 impl Default for OsvClient {
@@ -95,22 +336,22 @@ impl Default for OsvClient {
     }
 }
 
-/// Parse CVSS score to severity level
+/// Parses OSV's `severity.score`, which is either a bare CVSS base score
+/// (e.g. `"7.5"`) or a full `CVSS:3.x/...` vector with no score attached -
+/// in the vector case this computes the real base score via
+/// [`cvss_vector_base_score`] rather than guessing, falling back to
+/// `Medium` only if the vector itself fails to parse.
 fn parse_cvss_severity(score: &str) -> VulnerabilitySeverity {
-    // Try to parse as CVSS score (float) first
-    // CVSS v3 score ranges: 0-3.9 Low, 4-6.9 Medium, 7-8.9 High, 9-10 Critical
+    // Bare numeric CVSS base score - fast path, no vector to parse.
     if let Ok(score) = score.parse::<f64>() {
-        return match score {
-            s if s >= 9.0 => VulnerabilitySeverity::Critical,
-            s if s >= 7.0 => VulnerabilitySeverity::High,
-            s if s >= 4.0 => VulnerabilitySeverity::Medium,
-            _ => VulnerabilitySeverity::Low,
-        };
+        return severity_from_score(score as f32);
     }
 
-    // Try to extract score from CVSS vector string (e.g., "CVSS:3.1/AV:N/AC:L/...")
+    // Full CVSS vector string (e.g., "CVSS:3.1/AV:N/AC:L/...").
     if score.starts_with("CVSS:") {
-        // The score isn't directly in the vector, default to Medium
+        if let Some(base_score) = cvss_vector_base_score(score) {
+            return severity_from_score(base_score);
+        }
         return VulnerabilitySeverity::Medium;
     }
 
@@ -121,10 +362,10 @@ fn parse_cvss_severity(score: &str) -> VulnerabilitySeverity {
 impl VulnerabilitySource for OsvClient {
     async fn query(&self, query: &VulnerabilityQuery) -> anyhow::Result<Vec<Vulnerability>> {
         let request = OsvQueryRequest {
-            package: OsvPackage {
-                name: query.package_name.clone(),
-                ecosystem: query.ecosystem.as_osv_str().to_string(),
-            },
+            package: OsvPackage::by_name(
+                query.package_name.clone(),
+                query.ecosystem.as_osv_str(),
+            ),
             version: Some(query.version.clone()),
         };
 
@@ -141,6 +382,7 @@ impl VulnerabilitySource for OsvClient {
             .vulns
             .unwrap_or_default()
             .iter()
+            .filter(|osv| osv.withdrawn.is_none())
             .map(Self::convert_vulnerability)
             .collect();
 
@@ -159,10 +401,7 @@ impl VulnerabilitySource for OsvClient {
             queries: queries
                 .iter()
                 .map(|q| OsvQueryRequest {
-                    package: OsvPackage {
-                        name: q.package_name.clone(),
-                        ecosystem: q.ecosystem.as_osv_str().to_string(),
-                    },
+                    package: OsvPackage::by_name(q.package_name.clone(), q.ecosystem.as_osv_str()),
                     version: Some(q.version.clone()),
                 })
                 .collect(),
@@ -185,6 +424,7 @@ impl VulnerabilitySource for OsvClient {
                     .as_ref()
                     .unwrap_or(&vec![])
                     .iter()
+                    .filter(|osv| osv.withdrawn.is_none())
                     .map(Self::convert_vulnerability)
                     .collect()
             })
@@ -194,6 +434,213 @@ impl VulnerabilitySource for OsvClient {
     }
 }
 
+impl OsvClient {
+    /// Issues a single `/querybatch` call across all `queries`, then
+    /// hydrates each distinct vulnerability ID the batch returns via
+    /// `/vulns/<id>` - the batch endpoint only echoes IDs, not full
+    /// records. An ID that fails to hydrate is simply dropped rather than
+    /// failing the whole call, so a vuln-service outage never blocks
+    /// version info.
+    ///
+    /// When a [`VulnerabilityCache`] is attached (see [`OsvClient::with_cache`]),
+    /// each `(ecosystem, package, version)` is served from cache when fresh;
+    /// only the cache misses are sent to OSV, and fresh results are written
+    /// back before returning.
+    pub async fn query_batch_hydrated(
+        &self,
+        queries: &[VulnerabilityQuery],
+    ) -> anyhow::Result<Vec<Vec<Vulnerability>>> {
+        let Some(cache) = &self.cache else {
+            return self.query_batch_hydrated_uncached(queries).await;
+        };
+
+        let cache_keys: Vec<VulnCacheKey> = queries
+            .iter()
+            .map(|q| VulnCacheKey::new(q.ecosystem, &q.package_name, &q.version))
+            .collect();
+
+        let mut results: Vec<Option<Vec<Vulnerability>>> =
+            cache_keys.iter().map(|key| cache.get(key)).collect();
+
+        let misses: Vec<VulnerabilityQuery> = queries
+            .iter()
+            .zip(&results)
+            .filter(|(_, cached)| cached.is_none())
+            .map(|(q, _)| q.clone())
+            .collect();
+
+        if !misses.is_empty() {
+            let mut fetched = self.query_batch_hydrated_uncached(&misses).await?.into_iter();
+            for (slot, key) in results.iter_mut().zip(cache_keys.iter()) {
+                if slot.is_none() {
+                    let vulns = fetched.next().unwrap_or_default();
+                    cache.insert(key.clone(), vulns.clone());
+                    *slot = Some(vulns);
+                }
+            }
+        }
+
+        Ok(results.into_iter().map(Option::unwrap_or_default).collect())
+    }
+
+    /// The uncached `/querybatch` + hydrate implementation - see
+    /// [`OsvClient::query_batch_hydrated`].
+    async fn query_batch_hydrated_uncached(
+        &self,
+        queries: &[VulnerabilityQuery],
+    ) -> anyhow::Result<Vec<Vec<Vulnerability>>> {
+        if queries.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let request = OsvBatchRequest {
+            queries: queries
+                .iter()
+                .map(|q| OsvQueryRequest {
+                    package: OsvPackage::by_name(q.package_name.clone(), q.ecosystem.as_osv_str()),
+                    version: Some(q.version.clone()),
+                })
+                .collect(),
+        };
+
+        let url = format!("{}/querybatch", self.base_url);
+        let response = self.client.post(&url).json(&request).send().await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("OSV API batch error: {}", response.status());
+        }
+
+        let batch_response: OsvBatchRefResponse = response.json().await?;
+
+        let id_lists: Vec<Vec<String>> = batch_response
+            .results
+            .iter()
+            .map(|r| {
+                r.vulns
+                    .as_ref()
+                    .unwrap_or(&vec![])
+                    .iter()
+                    .map(|v| v.id.clone())
+                    .collect()
+            })
+            .collect();
+
+        let mut unique_ids: Vec<String> = id_lists.iter().flatten().cloned().collect();
+        unique_ids.sort_unstable();
+        unique_ids.dedup();
+
+        let hydrated = self.hydrate(&unique_ids).await;
+
+        Ok(id_lists
+            .into_iter()
+            .map(|ids| ids.iter().filter_map(|id| hydrated.get(id).cloned()).collect())
+            .collect())
+    }
+
+    /// Issues a single `/query` call for `package_name` with no version
+    /// pinned, then evaluates every advisory's affected ranges locally
+    /// against each entry of `versions` - the full list a manifest might
+    /// resolve to, e.g. `VersionInfo.versions` - instead of one query per
+    /// version. Versions with no advisories applying to them are omitted
+    /// from the result rather than mapped to an empty `Vec`.
+    pub async fn query_versions(
+        &self,
+        ecosystem: Ecosystem,
+        package_name: &str,
+        versions: &[String],
+    ) -> anyhow::Result<HashMap<String, Vec<Vulnerability>>> {
+        let request = OsvQueryRequest {
+            package: OsvPackage::by_name(package_name.to_string(), ecosystem.as_osv_str()),
+            version: None,
+        };
+
+        let url = format!("{}/query", self.base_url);
+        let response = self.client.post(&url).json(&request).send().await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("OSV API error: {}", response.status());
+        }
+
+        let osv_response: OsvQueryResponse = response.json().await?;
+        let advisories = osv_response.vulns.unwrap_or_default();
+
+        let mut by_version = HashMap::new();
+        for version in versions {
+            let vulns: Vec<Vulnerability> = advisories
+                .iter()
+                .filter(|osv| osv.withdrawn.is_none() && osv_affects_version(osv, version))
+                .map(Self::convert_vulnerability)
+                .collect();
+            if !vulns.is_empty() {
+                by_version.insert(version.clone(), vulns);
+            }
+        }
+
+        Ok(by_version)
+    }
+
+    /// Issues a single `/query` call using OSV's purl form instead of
+    /// ecosystem+name, for advisories (chiefly GHSA entries) that only key
+    /// on a Package URL. Returns `Ok(vec![])` rather than erroring when
+    /// `ecosystem` has no purl mapping (see [`build_purl`]) - that's
+    /// "nothing to query", not a failure.
+    pub async fn query_by_purl(
+        &self,
+        ecosystem: Ecosystem,
+        package_name: &str,
+        version: &str,
+    ) -> anyhow::Result<Vec<Vulnerability>> {
+        let Some(purl) = build_purl(ecosystem, package_name) else {
+            return Ok(vec![]);
+        };
+
+        let request = OsvQueryRequest {
+            package: OsvPackage::by_purl(purl),
+            version: Some(version.to_string()),
+        };
+
+        let url = format!("{}/query", self.base_url);
+        let response = self.client.post(&url).json(&request).send().await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("OSV API error: {}", response.status());
+        }
+
+        let osv_response: OsvQueryResponse = response.json().await?;
+
+        Ok(osv_response
+            .vulns
+            .unwrap_or_default()
+            .iter()
+            .filter(|osv| osv.withdrawn.is_none())
+            .map(Self::convert_vulnerability)
+            .collect())
+    }
+
+    /// Fetches a full vulnerability record for each of `ids` via
+    /// `/vulns/<id>`, dropping any that fail rather than erroring out.
+    async fn hydrate(&self, ids: &[String]) -> HashMap<String, Vulnerability> {
+        let mut hydrated = HashMap::new();
+        for id in ids {
+            let url = format!("{}/vulns/{}", self.base_url, id);
+            let Ok(response) = self.client.post(&url).send().await else {
+                continue;
+            };
+            if !response.status().is_success() {
+                continue;
+            }
+            let Ok(osv) = response.json::<OsvVulnerability>().await else {
+                continue;
+            };
+            if osv.withdrawn.is_some() {
+                continue;
+            }
+            hydrated.insert(id.clone(), Self::convert_vulnerability(&osv));
+        }
+        hydrated
+    }
+}
+
 // OSV API Request/Response structures
 
 #[derive(Debug, Serialize)]
@@ -205,8 +652,32 @@ struct OsvQueryRequest {
 
 #[derive(Debug, Serialize)]
 struct OsvPackage {
-    name: String,
-    ecosystem: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ecosystem: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    purl: Option<String>,
+}
+
+impl OsvPackage {
+    /// The ecosystem+name form most queries use.
+    fn by_name(name: String, ecosystem: &str) -> Self {
+        Self {
+            name: Some(name),
+            ecosystem: Some(ecosystem.to_string()),
+            purl: None,
+        }
+    }
+
+    /// The purl form - see [`OsvClient::query_by_purl`].
+    fn by_purl(purl: String) -> Self {
+        Self {
+            name: None,
+            ecosystem: None,
+            purl: Some(purl),
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -224,6 +695,23 @@ struct OsvBatchResponse {
     results: Vec<OsvQueryResponse>,
 }
 
+/// A minimal vulnerability reference as returned by `/querybatch` - just
+/// enough to know what to hydrate via `/vulns/<id>`.
+#[derive(Debug, Deserialize)]
+struct OsvVulnRef {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvBatchRefQueryResponse {
+    vulns: Option<Vec<OsvVulnRef>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvBatchRefResponse {
+    results: Vec<OsvBatchRefQueryResponse>,
+}
+
 #[derive(Debug, Deserialize)]
 struct OsvVulnerability {
     id: String,
@@ -234,6 +722,8 @@ struct OsvVulnerability {
     #[allow(dead_code)]
     affected: Option<Vec<OsvAffected>>,
     aliases: Option<Vec<String>>,
+    related: Option<Vec<String>>,
+    withdrawn: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -255,9 +745,7 @@ struct OsvReference {
 struct OsvAffected {
     #[allow(dead_code)]
     package: Option<OsvAffectedPackage>,
-    #[allow(dead_code)]
     ranges: Option<Vec<OsvRange>>,
-    #[allow(dead_code)]
     versions: Option<Vec<String>>,
 }
 
@@ -271,20 +759,15 @@ struct OsvAffectedPackage {
 
 #[derive(Debug, Deserialize)]
 struct OsvRange {
-    #[allow(dead_code)]
     #[serde(rename = "type")]
     range_type: String,
-    #[allow(dead_code)]
     events: Vec<OsvRangeEvent>,
 }
 
 #[derive(Debug, Deserialize)]
 struct OsvRangeEvent {
-    #[allow(dead_code)]
     introduced: Option<String>,
-    #[allow(dead_code)]
     fixed: Option<String>,
-    #[allow(dead_code)]
     last_affected: Option<String>,
 }
 
@@ -301,8 +784,19 @@ mod tests {
         assert_eq!(parse_cvss_severity("5.0"), VulnerabilitySeverity::Medium);
         assert_eq!(parse_cvss_severity("3.0"), VulnerabilitySeverity::Low);
         assert_eq!(parse_cvss_severity("0.0"), VulnerabilitySeverity::Low);
+        // A full CVSS vector has no score of its own - the vector *is* the
+        // score, computed by `cvss_vector_base_score`. This one works out to
+        // 9.8 (Critical), not the old unconditional `Medium` fallback.
         assert_eq!(
             parse_cvss_severity("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"),
+            VulnerabilitySeverity::Critical
+        );
+    }
+
+    #[test]
+    fn test_parse_cvss_severity_falls_back_to_medium_for_unparsable_vector() {
+        assert_eq!(
+            parse_cvss_severity("CVSS:2.0/AV:N/AC:L/Au:N/C:C/I:C/A:C"),
             VulnerabilitySeverity::Medium
         );
     }
@@ -316,6 +810,7 @@ mod tests {
         assert_eq!(Ecosystem::Packagist.as_osv_str(), "Packagist");
         assert_eq!(Ecosystem::Pub.as_osv_str(), "Pub");
         assert_eq!(Ecosystem::NuGet.as_osv_str(), "NuGet");
+        assert_eq!(Ecosystem::Jsr.as_osv_str(), "JSR");
     }
 
     #[test]
@@ -334,6 +829,8 @@ mod tests {
             }]),
             affected: None,
             aliases: Some(vec!["CVE-2021-12345".to_string()]),
+            related: Some(vec!["GHSA-yyyy-yyyy-yyyy".to_string()]),
+            withdrawn: None,
         };
 
         let vuln = OsvClient::convert_vulnerability(&osv);
@@ -342,5 +839,338 @@ mod tests {
         assert_eq!(vuln.severity, VulnerabilitySeverity::High);
         assert_eq!(vuln.description, "Test vulnerability");
         assert_eq!(vuln.url, Some("https://example.com/advisory".to_string()));
+        assert_eq!(vuln.fixed_version, None);
+        assert_eq!(vuln.aliases, vec!["CVE-2021-12345".to_string()]);
+        assert_eq!(vuln.related, vec!["GHSA-yyyy-yyyy-yyyy".to_string()]);
+    }
+
+    #[test]
+    fn test_convert_vulnerability_extracts_fixed_version() {
+        let osv = OsvVulnerability {
+            id: "GHSA-yyyy-yyyy-yyyy".to_string(),
+            summary: Some("Test vulnerability".to_string()),
+            details: None,
+            severity: None,
+            references: None,
+            affected: Some(vec![OsvAffected {
+                package: None,
+                ranges: Some(vec![OsvRange {
+                    range_type: "SEMVER".to_string(),
+                    events: vec![
+                        OsvRangeEvent {
+                            introduced: Some("0.0.0".to_string()),
+                            fixed: None,
+                            last_affected: None,
+                        },
+                        OsvRangeEvent {
+                            introduced: None,
+                            fixed: Some("1.2.3".to_string()),
+                            last_affected: None,
+                        },
+                    ],
+                }]),
+                versions: None,
+            }]),
+            aliases: None,
+            related: None,
+            withdrawn: None,
+        };
+
+        let vuln = OsvClient::convert_vulnerability(&osv);
+        assert_eq!(vuln.fixed_version, Some("1.2.3".to_string()));
+        assert_eq!(
+            vuln.ranges,
+            vec![VulnerableRange {
+                introduced: None,
+                fixed: Some("1.2.3".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_convert_vulnerability_range_introduced_nonzero() {
+        let osv = OsvVulnerability {
+            id: "GHSA-zzzz-zzzz-zzzz".to_string(),
+            summary: Some("Test vulnerability".to_string()),
+            details: None,
+            severity: None,
+            references: None,
+            affected: Some(vec![OsvAffected {
+                package: None,
+                ranges: Some(vec![OsvRange {
+                    range_type: "SEMVER".to_string(),
+                    events: vec![
+                        OsvRangeEvent {
+                            introduced: Some("1.0.0".to_string()),
+                            fixed: None,
+                            last_affected: None,
+                        },
+                        OsvRangeEvent {
+                            introduced: None,
+                            fixed: Some("1.5.0".to_string()),
+                            last_affected: None,
+                        },
+                    ],
+                }]),
+                versions: None,
+            }]),
+            aliases: None,
+            related: None,
+            withdrawn: None,
+        };
+
+        let vuln = OsvClient::convert_vulnerability(&osv);
+        assert_eq!(
+            vuln.ranges,
+            vec![VulnerableRange {
+                introduced: Some("1.0.0".to_string()),
+                fixed: Some("1.5.0".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_convert_vulnerability_carries_aliases_and_related() {
+        let osv = OsvVulnerability {
+            id: "GHSA-cccc-cccc-cccc".to_string(),
+            summary: Some("Test vulnerability".to_string()),
+            details: None,
+            severity: None,
+            references: None,
+            affected: None,
+            aliases: Some(vec!["CVE-2022-00001".to_string()]),
+            related: Some(vec!["RUSTSEC-2022-0001".to_string()]),
+            withdrawn: None,
+        };
+
+        let vuln = OsvClient::convert_vulnerability(&osv);
+        assert_eq!(vuln.aliases, vec!["CVE-2022-00001".to_string()]);
+        assert_eq!(vuln.related, vec!["RUSTSEC-2022-0001".to_string()]);
+    }
+
+    #[test]
+    fn test_build_purl_cargo() {
+        assert_eq!(
+            build_purl(Ecosystem::CratesIo, "serde"),
+            Some("pkg:cargo/serde".to_string())
+        );
+    }
+
+    #[test]
+    fn test_build_purl_npm() {
+        assert_eq!(
+            build_purl(Ecosystem::Npm, "lodash"),
+            Some("pkg:npm/lodash".to_string())
+        );
+    }
+
+    #[test]
+    fn test_build_purl_composer_splits_vendor_package() {
+        assert_eq!(
+            build_purl(Ecosystem::Packagist, "symfony/http-kernel"),
+            Some("pkg:composer/symfony/http-kernel".to_string())
+        );
+    }
+
+    #[test]
+    fn test_build_purl_percent_encodes_namespace() {
+        assert_eq!(
+            build_purl(Ecosystem::Packagist, "my vendor/pkg"),
+            Some("pkg:composer/my%20vendor/pkg".to_string())
+        );
+    }
+
+    #[test]
+    fn test_build_purl_pub_and_pypi_and_nuget_and_golang() {
+        assert_eq!(build_purl(Ecosystem::Pub, "http"), Some("pkg:pub/http".to_string()));
+        assert_eq!(
+            build_purl(Ecosystem::PyPI, "requests"),
+            Some("pkg:pypi/requests".to_string())
+        );
+        assert_eq!(
+            build_purl(Ecosystem::NuGet, "Newtonsoft.Json"),
+            Some("pkg:nuget/Newtonsoft.Json".to_string())
+        );
+        assert_eq!(
+            build_purl(Ecosystem::Go, "rsc.io/quote"),
+            Some("pkg:golang/rsc.io/quote".to_string())
+        );
+    }
+
+    #[test]
+    fn test_build_purl_jsr_unsupported() {
+        assert_eq!(build_purl(Ecosystem::Jsr, "@std/assert"), None);
+    }
+
+    fn osv_with_range(range_type: &str, events: Vec<OsvRangeEvent>) -> OsvVulnerability {
+        OsvVulnerability {
+            id: "GHSA-aaaa-aaaa-aaaa".to_string(),
+            summary: None,
+            details: None,
+            severity: None,
+            references: None,
+            affected: Some(vec![OsvAffected {
+                package: None,
+                ranges: Some(vec![OsvRange {
+                    range_type: range_type.to_string(),
+                    events,
+                }]),
+                versions: None,
+            }]),
+            aliases: None,
+            related: None,
+            withdrawn: None,
+        }
+    }
+
+    #[test]
+    fn test_osv_affects_version_open_ended_range_covers_everything_from_introduced() {
+        let osv = osv_with_range(
+            "SEMVER",
+            vec![OsvRangeEvent {
+                introduced: Some("1.0.0".to_string()),
+                fixed: None,
+                last_affected: None,
+            }],
+        );
+
+        assert!(!osv_affects_version(&osv, "0.9.0"));
+        assert!(osv_affects_version(&osv, "1.0.0"));
+        assert!(osv_affects_version(&osv, "99.0.0"));
+    }
+
+    #[test]
+    fn test_osv_affects_version_fixed_bound_is_exclusive() {
+        let osv = osv_with_range(
+            "SEMVER",
+            vec![
+                OsvRangeEvent {
+                    introduced: Some("1.0.0".to_string()),
+                    fixed: None,
+                    last_affected: None,
+                },
+                OsvRangeEvent {
+                    introduced: None,
+                    fixed: Some("1.5.0".to_string()),
+                    last_affected: None,
+                },
+            ],
+        );
+
+        assert!(osv_affects_version(&osv, "1.4.9"));
+        assert!(!osv_affects_version(&osv, "1.5.0"));
+    }
+
+    #[test]
+    fn test_osv_affects_version_last_affected_bound_is_inclusive() {
+        let osv = osv_with_range(
+            "SEMVER",
+            vec![
+                OsvRangeEvent {
+                    introduced: Some("1.0.0".to_string()),
+                    fixed: None,
+                    last_affected: None,
+                },
+                OsvRangeEvent {
+                    introduced: None,
+                    fixed: None,
+                    last_affected: Some("1.5.0".to_string()),
+                },
+            ],
+        );
+
+        assert!(osv_affects_version(&osv, "1.5.0"));
+        assert!(!osv_affects_version(&osv, "1.5.1"));
+    }
+
+    #[test]
+    fn test_osv_affects_version_missing_introduced_treated_as_zero() {
+        let osv = osv_with_range(
+            "SEMVER",
+            vec![OsvRangeEvent {
+                introduced: None,
+                fixed: Some("1.0.0".to_string()),
+                last_affected: None,
+            }],
+        );
+
+        assert!(osv_affects_version(&osv, "0.1.0"));
+        assert!(!osv_affects_version(&osv, "1.0.0"));
+    }
+
+    #[test]
+    fn test_osv_affects_version_multiple_disjoint_intervals() {
+        // Fixed in 1.5, then reintroduced in 2.0 with no further fix.
+        let osv = osv_with_range(
+            "SEMVER",
+            vec![
+                OsvRangeEvent {
+                    introduced: Some("1.0.0".to_string()),
+                    fixed: None,
+                    last_affected: None,
+                },
+                OsvRangeEvent {
+                    introduced: None,
+                    fixed: Some("1.5.0".to_string()),
+                    last_affected: None,
+                },
+                OsvRangeEvent {
+                    introduced: Some("2.0.0".to_string()),
+                    fixed: None,
+                    last_affected: None,
+                },
+            ],
+        );
+
+        assert!(osv_affects_version(&osv, "1.2.0"));
+        assert!(!osv_affects_version(&osv, "1.6.0"));
+        assert!(osv_affects_version(&osv, "2.1.0"));
+    }
+
+    #[test]
+    fn test_osv_affects_version_explicit_versions_list() {
+        let osv = OsvVulnerability {
+            id: "GHSA-bbbb-bbbb-bbbb".to_string(),
+            summary: None,
+            details: None,
+            severity: None,
+            references: None,
+            affected: Some(vec![OsvAffected {
+                package: None,
+                ranges: None,
+                versions: Some(vec!["1.0.0".to_string(), "1.0.1".to_string()]),
+            }]),
+            aliases: None,
+            related: None,
+            withdrawn: None,
+        };
+
+        assert!(osv_affects_version(&osv, "1.0.1"));
+        assert!(!osv_affects_version(&osv, "1.0.2"));
+    }
+
+    #[test]
+    fn test_osv_affects_version_ecosystem_range_falls_back_to_packagist_style_compare() {
+        // Packagist-flavored versions aren't valid semver, so an ECOSYSTEM
+        // range must fall back to `compare_packagist_versions` rather than
+        // failing to parse.
+        let osv = osv_with_range(
+            "ECOSYSTEM",
+            vec![
+                OsvRangeEvent {
+                    introduced: Some("v1.0".to_string()),
+                    fixed: None,
+                    last_affected: None,
+                },
+                OsvRangeEvent {
+                    introduced: None,
+                    fixed: Some("v1.5".to_string()),
+                    last_affected: None,
+                },
+            ],
+        );
+
+        assert!(osv_affects_version(&osv, "v1.2"));
+        assert!(!osv_affects_version(&osv, "v1.5"));
     }
 }
@@ -1,36 +1,104 @@
-//! RustSec Advisory Database client for Rust-specific vulnerability data
+//! Offline RustSec Advisory Database client for Rust-specific vulnerability
+//! data.
 //!
-//! This module provides integration with the RustSec advisory database
-//! for more detailed Rust-specific vulnerability information.
-//!
-//! NOTE: Currently disabled due to rustsec crate API changes.
-//! OSV.dev already aggregates RustSec data, so this is optional.
-//! TODO: Update to use the new rustsec API.
+//! OSV.dev aggregates RustSec advisories, but it requires network access.
+//! This client keeps a local checkout of the [RustSec advisory-db
+//! repository](https://github.com/RustSec/advisory-db) - cloned on first
+//! use and fast-forwarded on later ones - and reads advisories straight off
+//! disk, so crates.io vulnerability scanning keeps working with no network
+//! at all once the checkout exists. Each advisory file is Markdown with a
+//! fenced TOML front-matter block; only that front-matter is parsed.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use serde::Deserialize;
 
-use crate::registries::Vulnerability;
+use crate::registries::version_set::is_version_vulnerable;
+use crate::registries::{Vulnerability, VulnerabilitySeverity};
 
-/// RustSec advisory database client (currently a stub)
-///
-/// The rustsec crate API has changed significantly. For now, we rely on
-/// OSV.dev which aggregates RustSec advisories. This client can be
-/// implemented later for additional Rust-specific details like affected functions.
+use super::severity::{cvss_vector_base_score, severity_from_score};
+use super::{Ecosystem, VulnerabilityQuery, VulnerabilitySource};
+
+const ADVISORY_DB_URL: &str = "https://github.com/RustSec/advisory-db.git";
+
+/// RustSec advisory database client, backed by a local git checkout.
 pub struct RustSecClient {
-    _enabled: bool,
+    /// Path to the advisory-db checkout, e.g.
+    /// `~/.cache/dependi/advisory-db`.
+    repo_path: PathBuf,
 }
 
 impl RustSecClient {
-    /// Create a new RustSec client
+    /// Create a client backed by the default cache location.
     pub fn new() -> Self {
-        Self { _enabled: false }
+        Self {
+            repo_path: default_repo_path().unwrap_or_else(|_| PathBuf::from("advisory-db")),
+        }
+    }
+
+    /// Create a client backed by an existing advisory-db checkout (or a
+    /// path to clone one into), for tests and for callers that want to
+    /// share a checkout across instances.
+    pub fn with_repo_path(repo_path: impl Into<PathBuf>) -> Self {
+        Self { repo_path: repo_path.into() }
+    }
+
+    /// Clones the advisory-db repo if it isn't present yet, or
+    /// fast-forwards it if it is. Both are best-effort: a failure (no
+    /// network, no `git` binary, a dirty checkout) just means `query` keeps
+    /// serving whatever's already on disk rather than failing outright.
+    fn ensure_repo(&self) {
+        if self.repo_path.join(".git").is_dir() {
+            let _ = Command::new("git")
+                .args(["-C", &self.repo_path.to_string_lossy(), "pull", "--ff-only", "--quiet"])
+                .output();
+            return;
+        }
+
+        if let Some(parent) = self.repo_path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let _ = Command::new("git")
+            .args([
+                "clone",
+                "--depth",
+                "1",
+                "--quiet",
+                ADVISORY_DB_URL,
+                &self.repo_path.to_string_lossy(),
+            ])
+            .output();
     }
 
-    /// Query vulnerabilities for a Rust crate
-    ///
-    /// Currently returns empty - use OSV.dev for Rust vulnerability data.
-    pub async fn query(&self, _crate_name: &str, _version: &str) -> anyhow::Result<Vec<Vulnerability>> {
-        // TODO: Implement using rustsec crate when API is stabilized
-        // For now, OSV.dev covers RustSec advisories
-        Ok(vec![])
+    /// Query vulnerabilities for a Rust crate, entirely from the local
+    /// checkout. Refreshes the checkout first (see [`Self::ensure_repo`]);
+    /// if that's not possible, falls back to whatever's already on disk.
+    pub async fn query(&self, crate_name: &str, version: &str) -> anyhow::Result<Vec<Vulnerability>> {
+        self.ensure_repo();
+
+        let crate_dir = self.repo_path.join("crates").join(crate_name);
+        let Ok(entries) = std::fs::read_dir(&crate_dir) else {
+            return Ok(Vec::new());
+        };
+
+        let mut vulnerabilities = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("md") {
+                continue;
+            }
+            let Ok(content) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            let Some(advisory) = parse_advisory(&content) else {
+                continue;
+            };
+            if is_version_vulnerable(version, &advisory.patched, &advisory.unaffected) {
+                vulnerabilities.push(advisory.into());
+            }
+        }
+        Ok(vulnerabilities)
     }
 }
 
@@ -40,14 +108,180 @@ impl Default for RustSecClient {
     }
 }
 
+impl VulnerabilitySource for RustSecClient {
+    /// Only the crates.io ecosystem has advisories in advisory-db; every
+    /// other ecosystem reports no vulnerabilities rather than an error, so
+    /// a [`super::composite::CompositeSource`] wrapping this alongside OSV
+    /// doesn't have to special-case ecosystems itself.
+    async fn query(&self, query: &VulnerabilityQuery) -> anyhow::Result<Vec<Vulnerability>> {
+        if query.ecosystem != Ecosystem::CratesIo {
+            return Ok(Vec::new());
+        }
+        self.query(&query.package_name, &query.version).await
+    }
+
+    async fn query_batch(&self, queries: &[VulnerabilityQuery]) -> anyhow::Result<Vec<Vec<Vulnerability>>> {
+        let mut results = Vec::with_capacity(queries.len());
+        for query in queries {
+            results.push(VulnerabilitySource::query(self, query).await?);
+        }
+        Ok(results)
+    }
+}
+
+/// `~/.cache/dependi/advisory-db`, mirroring
+/// [`crate::registries::cache::default_cache_path`]'s cache directory.
+fn default_repo_path() -> anyhow::Result<PathBuf> {
+    let cache_dir = dirs::cache_dir()
+        .ok_or_else(|| anyhow::anyhow!("Could not determine user cache directory"))?
+        .join("dependi");
+    std::fs::create_dir_all(&cache_dir)?;
+    Ok(cache_dir.join("advisory-db"))
+}
+
+/// A parsed advisory, before it's mapped into the shared [`Vulnerability`]
+/// shape.
+struct ParsedAdvisory {
+    id: String,
+    description: String,
+    url: Option<String>,
+    severity: Option<String>,
+    fixed_version: Option<String>,
+    /// Requirements the advisory's `[versions]` table lists as patched /
+    /// unaffected - the safe-version shape [`is_version_vulnerable`] checks
+    /// a queried version against.
+    patched: Vec<String>,
+    unaffected: Vec<String>,
+}
+
+impl From<ParsedAdvisory> for Vulnerability {
+    fn from(advisory: ParsedAdvisory) -> Self {
+        let severity = advisory
+            .severity
+            .as_deref()
+            .and_then(cvss_vector_base_score)
+            .map(severity_from_score)
+            .unwrap_or(VulnerabilitySeverity::Medium);
+
+        Vulnerability {
+            id: advisory.id,
+            severity,
+            description: advisory.description,
+            url: advisory.url,
+            fixed_version: advisory.fixed_version,
+            // RustSec's `patched`/`unaffected` requirements describe the
+            // SAFE versions rather than OSV's affected `introduced`/`fixed`
+            // ranges, so there's nothing to put here without inverting
+            // them into ranges the rest of the codebase can't otherwise
+            // use (e.g. `nearest_safe_version`'s range-based floor search).
+            ranges: Vec::new(),
+            aliases: Vec::new(),
+            related: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AdvisoryFrontMatter {
+    advisory: AdvisoryMeta,
+    versions: Option<AdvisoryVersions>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AdvisoryMeta {
+    id: String,
+    url: Option<String>,
+    cvss: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AdvisoryVersions {
+    patched: Option<Vec<String>>,
+    unaffected: Option<Vec<String>>,
+}
+
+/// Extracts the fenced ```toml front-matter block from a RustSec advisory
+/// Markdown file, deserializes it, and pulls the Markdown title (the first
+/// `# ...` line after the front-matter) out as the description.
+fn parse_advisory(content: &str) -> Option<ParsedAdvisory> {
+    let after_open = content.split_once("```toml")?.1;
+    let (frontmatter, body) = after_open.split_once("```")?;
+    let parsed: AdvisoryFrontMatter = toml::from_str(frontmatter).ok()?;
+
+    let description = body
+        .lines()
+        .find_map(|line| line.strip_prefix("# "))
+        .map(str::trim)
+        .unwrap_or(&parsed.advisory.id)
+        .to_string();
+
+    let patched = parsed.versions.as_ref().and_then(|v| v.patched.clone()).unwrap_or_default();
+    let unaffected = parsed.versions.as_ref().and_then(|v| v.unaffected.clone()).unwrap_or_default();
+    let fixed_version = patched
+        .first()
+        .and_then(|req| req.trim_start_matches(">=").split(',').next())
+        .map(str::trim)
+        .map(str::to_string);
+
+    Some(ParsedAdvisory {
+        id: parsed.advisory.id,
+        description,
+        url: parsed.advisory.url,
+        severity: parsed.advisory.cvss,
+        fixed_version,
+        patched,
+        unaffected,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const ADVISORY_MD: &str = r#"```toml
+[advisory]
+id = "RUSTSEC-2020-0001"
+package = "example"
+date = "2020-01-01"
+url = "https://rustsec.org/advisories/RUSTSEC-2020-0001.html"
+cvss = "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"
+
+[versions]
+patched = [">=1.2.3"]
+unaffected = ["<1.0.0"]
+```
+
+# Example crate has a buffer overflow
+
+Full description of the vulnerability goes here.
+"#;
+
+    #[test]
+    fn test_parse_advisory_extracts_front_matter_and_title() {
+        let advisory = parse_advisory(ADVISORY_MD).unwrap();
+        assert_eq!(advisory.id, "RUSTSEC-2020-0001");
+        assert_eq!(advisory.description, "Example crate has a buffer overflow");
+        assert_eq!(advisory.url.as_deref(), Some("https://rustsec.org/advisories/RUSTSEC-2020-0001.html"));
+        assert_eq!(advisory.fixed_version.as_deref(), Some("1.2.3"));
+    }
+
+    #[test]
+    fn test_parse_advisory_maps_cvss_vector_to_severity() {
+        let advisory = parse_advisory(ADVISORY_MD).unwrap();
+        let vuln: Vulnerability = advisory.into();
+        assert_eq!(vuln.severity, VulnerabilitySeverity::Critical);
+    }
+
+    #[test]
+    fn test_parse_advisory_rejects_content_without_front_matter() {
+        assert!(parse_advisory("# Just a title\n\nNo front-matter here.").is_none());
+    }
+
     #[tokio::test]
-    async fn test_rustsec_client_returns_empty() {
-        let client = RustSecClient::new();
-        let result = client.query("serde", "1.0.0").await.unwrap();
+    async fn test_rustsec_client_returns_empty_for_unknown_crate() {
+        let dir = std::env::temp_dir().join("dependi-rustsec-client-test-empty");
+        let client = RustSecClient::with_repo_path(&dir);
+        let result = client.query("definitely-not-a-real-crate", "1.0.0").await.unwrap();
         assert!(result.is_empty());
     }
 }
@@ -0,0 +1,265 @@
+//! CVSS v3 base-score computation and a severity-threshold filter over a
+//! [`VulnerabilitySource`].
+//!
+//! OSV reports a `severity` field that's sometimes a bare numeric score and
+//! sometimes a full CVSS vector string (e.g.
+//! `"CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"`) with no score attached
+//! at all - the vector *is* the score, computed from its metrics. This
+//! module implements that computation so callers aren't stuck defaulting
+//! every vector-only advisory to the same severity, and [`SeverityFilter`]
+//! lets a caller discard everything below a CVSS threshold once a numeric
+//! score is available.
+
+use super::{VulnerabilityQuery, VulnerabilitySource};
+use crate::registries::{Vulnerability, VulnerabilitySeverity};
+
+/// Computes the CVSS v3.0/3.1 base score from a vector string such as
+/// `"CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"`. Returns `None` if the
+/// vector is missing any of the six base metrics or uses a value this
+/// parser doesn't recognize - the caller should fail open rather than
+/// invent a score.
+pub fn cvss_vector_base_score(vector: &str) -> Option<f32> {
+    let vector = vector.strip_prefix("CVSS:3.1/").or_else(|| vector.strip_prefix("CVSS:3.0/"))?;
+
+    let mut av = None;
+    let mut ac = None;
+    let mut pr = None;
+    let mut ui = None;
+    let mut scope_changed = None;
+    let mut c = None;
+    let mut i = None;
+    let mut a = None;
+
+    for metric in vector.split('/') {
+        let (key, value) = metric.split_once(':')?;
+        match key {
+            "AV" => av = Some(attack_vector_weight(value)?),
+            "AC" => ac = Some(attack_complexity_weight(value)?),
+            "PR" => pr = Some(value),
+            "UI" => ui = Some(user_interaction_weight(value)?),
+            "S" => scope_changed = Some(scope_is_changed(value)?),
+            "C" => c = Some(impact_weight(value)?),
+            "I" => i = Some(impact_weight(value)?),
+            "A" => a = Some(impact_weight(value)?),
+            // CVSS carries optional temporal/environmental metrics after the
+            // base ones - irrelevant to the base score, so skip them.
+            _ => continue,
+        }
+    }
+
+    let scope_changed = scope_changed?;
+    let pr = privileges_required_weight(pr?, scope_changed)?;
+
+    let iss = 1.0 - ((1.0 - c?) * (1.0 - i?) * (1.0 - a?));
+    let impact = if scope_changed {
+        7.52 * (iss - 0.029) - 3.25 * (iss - 0.02).powf(15.0)
+    } else {
+        6.42 * iss
+    };
+    if impact <= 0.0 {
+        return Some(0.0);
+    }
+
+    let exploitability = 8.22 * av? * ac? * pr * ui?;
+    let score = if scope_changed {
+        1.08 * (impact + exploitability)
+    } else {
+        impact + exploitability
+    };
+
+    Some(round_up_to_tenth(score.min(10.0)))
+}
+
+fn attack_vector_weight(value: &str) -> Option<f32> {
+    Some(match value {
+        "N" => 0.85,
+        "A" => 0.62,
+        "L" => 0.55,
+        "P" => 0.2,
+        _ => return None,
+    })
+}
+
+fn attack_complexity_weight(value: &str) -> Option<f32> {
+    Some(match value {
+        "L" => 0.77,
+        "H" => 0.44,
+        _ => return None,
+    })
+}
+
+fn privileges_required_weight(value: &str, scope_changed: bool) -> Option<f32> {
+    Some(match (value, scope_changed) {
+        ("N", _) => 0.85,
+        ("L", false) => 0.62,
+        ("L", true) => 0.68,
+        ("H", false) => 0.27,
+        ("H", true) => 0.5,
+        _ => return None,
+    })
+}
+
+fn user_interaction_weight(value: &str) -> Option<f32> {
+    Some(match value {
+        "N" => 0.85,
+        "R" => 0.62,
+        _ => return None,
+    })
+}
+
+fn scope_is_changed(value: &str) -> Option<bool> {
+    match value {
+        "U" => Some(false),
+        "C" => Some(true),
+        _ => None,
+    }
+}
+
+fn impact_weight(value: &str) -> Option<f32> {
+    Some(match value {
+        "H" => 0.56,
+        "L" => 0.22,
+        "N" => 0.0,
+        _ => return None,
+    })
+}
+
+/// CVSS scores round up to the nearest 0.1 rather than using ordinary
+/// rounding, per the published base-score algorithm.
+fn round_up_to_tenth(score: f32) -> f32 {
+    (score * 10.0).ceil() / 10.0
+}
+
+/// Maps a numeric CVSS base score to one of this codebase's coarser
+/// [`VulnerabilitySeverity`] buckets, per CVSS v3's documented ranges.
+pub fn severity_from_score(score: f32) -> VulnerabilitySeverity {
+    match score {
+        s if s >= 9.0 => VulnerabilitySeverity::Critical,
+        s if s >= 7.0 => VulnerabilitySeverity::High,
+        s if s >= 4.0 => VulnerabilitySeverity::Medium,
+        _ => VulnerabilitySeverity::Low,
+    }
+}
+
+/// Wraps a [`VulnerabilitySource`], discarding any vulnerability whose
+/// severity falls below `threshold` so callers that only care about, say,
+/// `High` and above don't have to filter every result themselves.
+pub struct SeverityFilter<S: VulnerabilitySource> {
+    inner: S,
+    threshold: VulnerabilitySeverity,
+}
+
+impl<S: VulnerabilitySource> SeverityFilter<S> {
+    pub fn new(inner: S, threshold: VulnerabilitySeverity) -> Self {
+        Self { inner, threshold }
+    }
+
+    fn keep(&self, vulnerabilities: Vec<Vulnerability>) -> Vec<Vulnerability> {
+        vulnerabilities.into_iter().filter(|v| v.severity >= self.threshold).collect()
+    }
+}
+
+impl<S: VulnerabilitySource> VulnerabilitySource for SeverityFilter<S> {
+    async fn query(&self, query: &VulnerabilityQuery) -> anyhow::Result<Vec<Vulnerability>> {
+        Ok(self.keep(self.inner.query(query).await?))
+    }
+
+    async fn query_batch(&self, queries: &[VulnerabilityQuery]) -> anyhow::Result<Vec<Vec<Vulnerability>>> {
+        Ok(self
+            .inner
+            .query_batch(queries)
+            .await?
+            .into_iter()
+            .map(|vulnerabilities| self.keep(vulnerabilities))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cvss_vector_base_score_critical() {
+        let score = cvss_vector_base_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H").unwrap();
+        assert_eq!(score, 9.8);
+    }
+
+    #[test]
+    fn test_cvss_vector_base_score_with_scope_change() {
+        let score = cvss_vector_base_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:R/S:C/C:H/I:H/A:H").unwrap();
+        assert_eq!(score, 9.6);
+    }
+
+    #[test]
+    fn test_cvss_vector_base_score_low() {
+        let score = cvss_vector_base_score("CVSS:3.1/AV:L/AC:H/PR:H/UI:R/S:U/C:L/I:N/A:N").unwrap();
+        assert_eq!(score, 1.8);
+    }
+
+    #[test]
+    fn test_cvss_vector_base_score_no_impact_is_zero() {
+        let score = cvss_vector_base_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:N").unwrap();
+        assert_eq!(score, 0.0);
+    }
+
+    #[test]
+    fn test_cvss_vector_base_score_rejects_unknown_metric_value() {
+        assert!(cvss_vector_base_score("CVSS:3.1/AV:X/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H").is_none());
+    }
+
+    #[test]
+    fn test_cvss_vector_base_score_rejects_non_cvss3_vector() {
+        assert!(cvss_vector_base_score("AV:N/AC:L/Au:N/C:C/I:C/A:C").is_none());
+    }
+
+    #[test]
+    fn test_severity_from_score_buckets() {
+        assert_eq!(severity_from_score(9.8), VulnerabilitySeverity::Critical);
+        assert_eq!(severity_from_score(7.5), VulnerabilitySeverity::High);
+        assert_eq!(severity_from_score(5.0), VulnerabilitySeverity::Medium);
+        assert_eq!(severity_from_score(0.0), VulnerabilitySeverity::Low);
+    }
+
+    struct StubSource(Vec<Vulnerability>);
+
+    impl VulnerabilitySource for StubSource {
+        async fn query(&self, _query: &VulnerabilityQuery) -> anyhow::Result<Vec<Vulnerability>> {
+            Ok(self.0.clone())
+        }
+
+        async fn query_batch(&self, queries: &[VulnerabilityQuery]) -> anyhow::Result<Vec<Vec<Vulnerability>>> {
+            Ok(queries.iter().map(|_| self.0.clone()).collect())
+        }
+    }
+
+    fn vuln(id: &str, severity: VulnerabilitySeverity) -> Vulnerability {
+        Vulnerability {
+            id: id.to_string(),
+            severity,
+            description: String::new(),
+            url: None,
+            fixed_version: None,
+            ranges: Vec::new(),
+            aliases: Vec::new(),
+            related: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_severity_filter_drops_below_threshold() {
+        let source = StubSource(vec![
+            vuln("RUSTSEC-1", VulnerabilitySeverity::Low),
+            vuln("RUSTSEC-2", VulnerabilitySeverity::High),
+        ]);
+        let filtered = SeverityFilter::new(source, VulnerabilitySeverity::High);
+        let query = VulnerabilityQuery {
+            package_name: "foo".to_string(),
+            version: "1.0.0".to_string(),
+            ecosystem: super::super::Ecosystem::CratesIo,
+        };
+        let result = filtered.query(&query).await.unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].id, "RUSTSEC-2");
+    }
+}